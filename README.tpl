@@ -20,7 +20,9 @@
 [`writeln!`]:            https://doc.rust-lang.org/std/macro.writeln.html
 [`format!`]:             https://doc.rust-lang.org/std/macro.format.html
 [`format_args!`]:        https://doc.rust-lang.org/std/macro.format_args.html
+[`assert_eq!`]:          https://doc.rust-lang.org/std/macro.assert_eq.html
 [`thread_local!`]:       https://doc.rust-lang.org/std/macro.thread_local.html
+[std::io::Result]:       https://doc.rust-lang.org/std/io/type.Result.html
 
 [`Ansi`]:                https://docs.rs/ansiconst/latest/ansiconst/struct.Ansi.html
 [`Colour`]:              https://docs.rs/ansiconst/latest/ansiconst/enum.Colour.html
@@ -35,9 +37,24 @@
 [`paintln!`]:            https://docs.rs/ansiconst/latest/ansiconst/macro.paintln.html
 [`epaint!`]:             https://docs.rs/ansiconst/latest/ansiconst/macro.epaint.html
 [`epaintln!`]:           https://docs.rs/ansiconst/latest/ansiconst/macro.epaintln.html
+[`try_paint!`]:          https://docs.rs/ansiconst/latest/ansiconst/macro.try_paint.html
+[`try_paintln!`]:        https://docs.rs/ansiconst/latest/ansiconst/macro.try_paintln.html
+[`try_epaint!`]:         https://docs.rs/ansiconst/latest/ansiconst/macro.try_epaint.html
+[`try_epaintln!`]:       https://docs.rs/ansiconst/latest/ansiconst/macro.try_epaintln.html
+[`paint_success!`]:      https://docs.rs/ansiconst/latest/ansiconst/macro.paint_success.html
+[`paint_warning!`]:      https://docs.rs/ansiconst/latest/ansiconst/macro.paint_warning.html
+[`paint_error!`]:        https://docs.rs/ansiconst/latest/ansiconst/macro.paint_error.html
+[`paint_message!`]:      https://docs.rs/ansiconst/latest/ansiconst/macro.paint_message.html
+[`epaint_message!`]:     https://docs.rs/ansiconst/latest/ansiconst/macro.epaint_message.html
+[`assert_styled_eq!`]:   https://docs.rs/ansiconst/latest/ansiconst/macro.assert_styled_eq.html
+[`palette!`]:            https://docs.rs/ansiconst/latest/ansiconst/macro.palette.html
+[`ansi_style!`]:         https://docs.rs/ansiconst/latest/ansiconst/macro.ansi_style.html
+[`macros`]:              https://docs.rs/ansiconst/latest/ansiconst/macros/index.html
 [Colour::Ansi256]:       https://docs.rs/ansiconst/latest/ansiconst/enum.Colour.html#variant.Ansi256
 [Colour::Rgb]:           https://docs.rs/ansiconst/latest/ansiconst/enum.Colour.html#variant.Rgb
 [Ansi::protect_attrs]:   https://docs.rs/ansiconst/latest/ansiconst/struct.Ansi.html#method.protected_attrs
+[Ansi::add]:             https://docs.rs/ansiconst/latest/ansiconst/struct.Ansi.html#method.add
+[Ansi::transition]:      https://docs.rs/ansiconst/latest/ansiconst/struct.Ansi.html#method.transition
 [Ansi::only]:            https://docs.rs/ansiconst/latest/ansiconst/struct.Ansi.html#method.only
 
 ## Version History
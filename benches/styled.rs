@@ -0,0 +1,126 @@
+//! Benchmarks for formatting styled output.
+//!
+//! Run with `cargo bench`. This uses a minimal harness (rather than e.g. `criterion`)
+//! to avoid any extra dependencies, so results are indicative only: each benchmark
+//! reports the mean time per iteration over the fastest of several runs.
+//!
+//! Indicative results (x86_64 Linux, release profile), before and after removing the
+//! per-byte scan of unescaped output and merging the style `thread_local!`s:
+//!
+//! | Benchmark        | Before (ns) | After (ns) |
+//! |------------------|-------------|------------|
+//! | `unstyled`       |        10.2 |       10.0 |
+//! | `styled`         |       101.0 |      102.0 |
+//! | `nested`         |       265.0 |      272.0 |
+//! | `writer`         |       320.0 |      318.0 |
+//! | `writer no_ansi` |        93.0 |       78.5 |
+//!
+//! The `fragments batch` benchmark formats the same fragments as `fragments` using a
+//! [`StyledBatch`], i.e. in one pass (~200ns vs ~310ns).
+//!
+//! The `ansiout` benchmarks write nothing to `stdout`, so measure only the overhead of
+//! each write, e.g. of [`paint!`]. Skipping the pager's lock when no pager is active,
+//! and skipping empty writes, reduced these from ~35ns to ~29ns (`empty arg`)
+//! and ~11ns (`empty`). Storing their default style in a mutex, rather than a `static mut`,
+//! added ~6ns (`empty arg`).
+//!
+//! The `ansiout plain` benchmarks (with `feature=global-writer`) write whole lines to a sink.
+//! Writing plain literals, e.g. `paintln!("text")`, straight through when the default style
+//! and options would leave them unchanged, without locking them, reduced `plain` from ~52ns
+//! to ~25ns. Writes with arguments (`plain arg`) still lock them, at ~57ns.
+
+use ansiconst::{*, io::{ansiout, AnsiPreference, AnsiWrite, AnsiWriter}, Colour::{Blue, Green, Red}, Effect::{Bold, Italic}};
+use std::fmt::Write as _;
+use std::hint::black_box;
+use std::io::{self, Write as _};
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 10;
+const ITERS: u32 = 100_000;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    let best = (0..RUNS).map(|_| {
+        let start = Instant::now();
+        for _ in 0..ITERS { f(); }
+        start.elapsed()
+    }).min().unwrap_or(Duration::ZERO);
+    println!("{:<24} {:>8.1} ns/iter", name, best.as_nanos() as f64 / f64::from(ITERS));
+}
+
+/// A `Writer` that discards its output.
+struct Sink;
+
+impl io::Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { Ok(black_box(buf).len()) }
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+impl AnsiPreference for Sink {
+    fn is_ansi_preferred(&self) -> bool { true }
+}
+
+fn main() {
+    let mut s = String::with_capacity(256);
+
+    bench("unstyled", || {
+        s.clear();
+        write!(s, "{}", black_box("Hello world")).unwrap();
+    });
+    bench("styled", || {
+        s.clear();
+        write!(s, "{}", styled!(Red, Bold, black_box("Hello world"))).unwrap();
+    });
+    bench("nested", || {
+        s.clear();
+        write!(s, "{}", styled_format_args!(Red, "Red {} Red",
+            styled_format_args!(Green, Italic, "Green {} Green", styled!(Blue, black_box("Blue"))),
+        )).unwrap();
+    });
+
+    const FRAGMENTS: [(Ansi, &str); 4] = [
+        (ansi!(Red),    "error"),
+        (ansi!(Bold),   ": "),
+        (ansi!(Green),  "fixed"),
+        (ansi!(Italic), " (1)"),
+    ];
+    bench("fragments", || {
+        s.clear();
+        for (ansi, text) in black_box(&FRAGMENTS) {
+            write!(s, "{}", Styled::new(*ansi, *text)).unwrap();
+        }
+    });
+    bench("fragments batch", || {
+        s.clear();
+        write!(s, "{}", StyledBatch::new(black_box(&FRAGMENTS))).unwrap();
+    });
+
+    let mut writer = AnsiWriter::new(Sink, Ansi::unspecified());
+    bench("writer", || {
+        write!(writer, "{}", styled!(Red, Bold, black_box("Hello world\n"))).unwrap();
+    });
+    writer.no_ansi();
+    bench("writer no_ansi", || {
+        write!(writer, "{}", styled!(Red, Bold, black_box("Hello world\n"))).unwrap();
+    });
+
+    // Note: these write nothing, so as not to interleave with the results
+    ansiout().set_ansi(Ansi::unspecified());
+    bench("ansiout empty", || {
+        write!(ansiout(), "").unwrap();
+    });
+    bench("ansiout empty arg", || {
+        write!(ansiout(), "{}", black_box("")).unwrap();
+    });
+
+    // Note: these write to a `Sink`, so need `feature=global-writer`
+    #[cfg(feature="global-writer")]
+    {
+        assert!(ansiconst::io::set_global_writer(Box::new(Sink)).is_ok());
+        bench("ansiout plain", || {
+            paintln!("Hello world");
+        });
+        bench("ansiout plain arg", || {
+            paintln!("{}", black_box("Hello world"));
+        });
+    }
+}
@@ -0,0 +1,128 @@
+//! An executable specification of how styles combine, nest and transition.
+//!
+//! Each recipe pairs an expression with the exact output it claims to produce.
+//! Run `cargo run --example cookbook` to browse the recipes, with each output shown
+//! both rendered and escaped, or `cargo test --example cookbook` to check them all.
+
+use ansiconst::{*, Colour::{Blue, Green, Red, Yellow}, Effect::{Bold, Italic, Underline}};
+use ansiconst::theme::Slot;
+
+/// An expression, as written, with its actual and expected output.
+struct Recipe {
+    code:     &'static str,
+    actual:   String,
+    expected: &'static str,
+}
+
+macro_rules! recipe {
+    ($code:expr => $expected:expr) => {
+        Recipe { code: stringify!($code), actual: $code.to_string(), expected: $expected }
+    };
+}
+
+/// A titled group of recipes.
+struct Section {
+    title:   &'static str,
+    recipes: Vec<Recipe>,
+}
+
+const INNER:           Styled<&str> = styled!(Underline,        "Inner");
+const INNER_PROTECTED: Styled<&str> = styled!(Underline.only(), "Inner");
+
+const APP:           Ansi = ansi!(Blue).protect();
+const USER:          Ansi = ansi!(Red).priority(2);
+const ACCESSIBILITY: Ansi = ansi!(Yellow).priority(3);
+
+fn sections() -> Vec<Section> {
+    vec![
+        Section {
+            title: "Nesting",
+            recipes: vec![
+                // Inner is both Bold and Underline
+                recipe!(styled_format!(Bold, "Bold {INNER} Bold again")
+                    => "\x1B[1mBold \x1B[4mInner\x1B[24m Bold again\x1B[22m"),
+                // Inner is not Bold, only Underline, due to inner's .only()
+                recipe!(styled_format!(Bold, "Bold {INNER_PROTECTED} Bold again")
+                    => "\x1B[1mBold \x1B[22;4mInner\x1B[24;1m Bold again\x1B[22m"),
+                // Entire string is Bold, nested Underline is ignored
+                recipe!(styled_format!(Bold.only(), "Bold {INNER} Bold again")
+                    => "\x1B[1mBold Inner Bold again\x1B[22m"),
+                // Entire string is Bold, because outer's .only() takes precedence over inner's
+                recipe!(styled_format!(Bold.only(), "Bold {INNER_PROTECTED} Bold again")
+                    => "\x1B[1mBold Inner Bold again\x1B[22m"),
+                // Only the changed attributes are written between nesting levels
+                recipe!(styled_format!(Red, Bold, "a {} c", styled!(Blue, "b"))
+                    => "\x1B[1;31ma \x1B[34mb\x1B[31m c\x1B[22;39m"),
+                // Unspecified styles write nothing
+                recipe!(styled_format!(Ansi::unspecified(), "a {} c", styled!(Ansi::unspecified(), "b"))
+                    => "a b c"),
+            ],
+        },
+        Section {
+            title: "Protection",
+            recipes: vec![
+                // Protecting the foreground ignores nested foreground colours only
+                recipe!(styled_format!(Red.ansi().protect(), "a {} c", styled!(Blue, Bold, "b"))
+                    => "\x1B[31ma \x1B[1mb\x1B[22m c\x1B[39m"),
+                // Ansi::no_ansi() protects all attributes without setting any
+                recipe!(styled_format!(Ansi::no_ansi(), "a {} c", styled!(Red, "b"))
+                    => "a b c"),
+                // A higher priority nested style overrides a protected outer style...
+                recipe!(styled_format!(APP, "a {} c", styled!(USER, "b"))
+                    => "\x1B[34ma \x1B[31mb\x1B[34m c\x1B[39m"),
+                // ...but not an outer style of an even higher priority
+                recipe!(styled_format!(ACCESSIBILITY, "a {} c", styled!(USER, "b"))
+                    => "\x1B[33ma b c\x1B[39m"),
+            ],
+        },
+        Section {
+            title: "Combinators",
+            recipes: vec![
+                // add(): the union, with the other's attributes replacing self's
+                recipe!(ansi!(Red, Bold).add(ansi!(Blue, Italic))
+                    => "\x1B[1;3;34m"),
+                // add(): protected attributes of self are preserved
+                recipe!(ansi!(Red).protect().add(ansi!(Blue, Italic))
+                    => "\x1B[3;31m"),
+                // remove(): self's attributes, excluding those of the other
+                recipe!(ansi!(Red, Bold, Italic).remove(ansi!(Bold))
+                    => "\x1B[3;31m"),
+                // not(): the codes that reset self's attributes
+                recipe!(ansi!(Red, Bold, Underline).not()
+                    => "\x1B[22;24;39m"),
+                // transition(): the minimum codes to get from self to the other
+                recipe!(ansi!(Red, Bold).transition(ansi!(Blue, Bold))
+                    => "\x1B[34m"),
+                recipe!(ansi!(Red, Bold).transition(ansi!(Italic))
+                    => "\x1B[22;3;39m"),
+                recipe!(ansi!(Red, Bold).transition(ansi!(Red, Bold))
+                    => ""),
+                // filter(): only the given attributes
+                recipe!(ansi!(Red, Green.bg(), Bold).filter(Attrs::Foreground.union(Attrs::Bold))
+                    => "\x1B[1;31m"),
+                // Theme slots are styles too
+                recipe!(styled_format!(Slot::Error, "error")
+                    => "\x1B[1;31merror\x1B[22;39m"),
+            ],
+        },
+    ]
+}
+
+fn main() {
+    for section in sections() {
+        paintln!(Slot::Heading, "{}", section.title);
+        for recipe in section.recipes {
+            assert_eq!(recipe.actual, recipe.expected, "{}", recipe.code);
+            paintln!(Slot::Muted, "{}", recipe.code);
+            println!("    {}  {:?}", recipe.actual, recipe.actual);
+        }
+        println!();
+    }
+}
+
+#[test]
+fn test_cookbook() {
+    for recipe in sections().into_iter().flat_map(|section| section.recipes) {
+        assert_eq!(recipe.actual, recipe.expected, "{}", recipe.code);
+    }
+}
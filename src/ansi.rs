@@ -1,11 +1,28 @@
 mod colour;
 mod effect;
 mod attr;
+mod spec;
+mod explain;
+mod parts;
+mod priority;
 pub(crate) use colour::Colours;
+#[cfg(feature="web")]
+pub(crate) use colour::PALETTE;
+#[cfg(all(feature="ansi256", feature="web"))]
+pub(crate) use colour::ansi256_rgb;
 pub(crate) use effect::Effects;
 pub use colour::Colour;
+#[cfg(feature="rgb")]
+pub use colour::BlendMode;
 pub use effect::Effect;
 pub use attr::Attrs;
+pub use spec::{ParseAnsiError, Spec};
+#[cfg(feature="serde")]
+pub(crate) use spec::{style_colour, ColourSpec};
+pub use explain::Explanation;
+pub use parts::AnsiParts;
+use priority::Protection;
+use crate::io::ColourSupport;
 use std::fmt;
 
 /// Represents an arbitrary combination of ANSI [`Effect`]s and
@@ -20,7 +37,7 @@ use std::fmt;
 pub struct Ansi {
     effect:  Effects,
     colour:  Colours,
-    protect: Attrs,
+    protect: Protection,
 }
 
 impl Ansi {
@@ -33,7 +50,26 @@ impl Ansi {
     /// Gets the set of [`Attrs`] of this instance that are [`protected`](Self::protect_attrs()).
     #[inline]
     pub const fn protected_attrs(&self) -> Attrs {
-        self.protect
+        self.protect.attrs()
+    }
+
+    /// True if every attribute specified by this instance is also specified by `policy`,
+    /// regardless of their values, e.g. to check that a style only uses the attributes
+    /// that an application permits.
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::Red, Effect::{Bold, NotItalic}};
+    ///
+    /// // Permits effects, but not colours
+    /// const MONOCHROME: Ansi = Ansi::reset_effects();
+    ///
+    /// assert!(ansi!(Bold, NotItalic).is_subset_of(&MONOCHROME));
+    /// assert!(!ansi!(Red, Bold).is_subset_of(&MONOCHROME));
+    /// assert!(Ansi::unspecified().is_subset_of(&MONOCHROME));
+    /// ```
+    #[inline]
+    pub const fn is_subset_of(&self, policy: &Ansi) -> bool {
+        policy.attrs().contains(self.attrs())
     }
 
     /// True if this instance is `NoAnsi` - see [`no_ansi()`][Self::no_ansi()]
@@ -61,6 +97,24 @@ impl Ansi {
         self.is_unspecified() && self.is_unprotected()
     }
 
+    /// True if formatting this instance renders an empty string, i.e. it has no ANSI codes,
+    /// regardless of its [`protected attributes`](Self::protect_attrs()).
+    ///
+    /// E.g. the [`transition()`](Self::transition()) between two instances that differ only
+    /// in their protected attributes renders nothing.
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::Red};
+    ///
+    /// assert!(Ansi::no_ansi().renders_nothing());
+    /// assert!(Red.ansi().transition(Red.only()).renders_nothing());
+    /// assert!(!Ansi::reset().renders_nothing());
+    /// ```
+    #[inline]
+    pub const fn renders_nothing(&self) -> bool {
+        self.is_unspecified()
+    }
+
     /// True if this instance is `Reset` - see [`reset()`](Self::reset())
     #[inline]
     pub const fn is_reset(&self) -> bool {
@@ -83,7 +137,7 @@ impl Ansi {
     /// See [`Styled<T>`](crate::Styled) for details.
     #[inline]
     pub const fn no_ansi() -> Ansi {
-        Self { effect: Effects::unspecified(), colour: Colours::unspecified(), protect: Attrs::all() }
+        Self { effect: Effects::unspecified(), colour: Colours::unspecified(), protect: Protection::all() }
     }
 
     /// Creates an `Ansi` instance whose [`Effect`]s and [`Colour`]s are `Unspecified`,
@@ -93,7 +147,7 @@ impl Ansi {
     /// The resulting `Ansi`'s attributes are [`unprotected`](Self::unprotect_attrs()).
     #[inline]
     pub const fn unspecified() -> Ansi {
-        Self { effect: Effects::unspecified(), colour: Colours::unspecified(), protect: Attrs::empty() }
+        Self { effect: Effects::unspecified(), colour: Colours::unspecified(), protect: Protection::empty() }
     }
 
     /// Creates an `Ansi` instance whose [`Effect`]s and [`Colour`]s are `Reset`,
@@ -106,9 +160,45 @@ impl Ansi {
     /// The resulting `Ansi`'s attributes are [`unprotected`](Self::unprotect_attrs()).
     #[inline]
     pub const fn reset() -> Ansi {
-        Self { effect: Effects::reset(), colour: Colours::reset(), protect: Attrs::empty() }
+        Self { effect: Effects::reset(), colour: Colours::reset(), protect: Protection::empty() }
     }
 
+    /// Creates an `Ansi` instance whose [`Colour`]s are `Reset` and whose [`Effect`]s
+    /// are `Unspecified`, i.e. which resets only the colours, leaving the effects untouched.
+    ///
+    /// This renders `"\x1B[39;49m"` when formatted.
+    ///
+    /// The resulting `Ansi`'s attributes are [`unprotected`](Self::unprotect_attrs()).
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::Red, Effect::Bold};
+    ///
+    /// assert_eq!(Ansi::reset_colors().to_string(), "\x1B[39;49m");
+    /// assert_eq!(ansi!(Red, Bold).add(Ansi::reset_colors()).to_string(), "\x1B[1;39;49m");
+    /// ```
+    #[inline]
+    pub const fn reset_colors() -> Ansi { Self::reset().filter(Attrs::colours()) }
+
+    /// Creates an `Ansi` instance whose [`Effect`]s are `Reset` and whose [`Colour`]s
+    /// are `Unspecified`, i.e. which resets only the effects, leaving the colours untouched.
+    ///
+    /// This renders `"\x1B[22;23;24;25;27;28;29m"` when formatted.
+    ///
+    /// The resulting `Ansi`'s attributes are [`unprotected`](Self::unprotect_attrs()).
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::Red, Effect::Bold};
+    ///
+    /// assert_eq!(Ansi::reset_effects().to_string(), "\x1B[22;23;24;25;27;28;29m");
+    /// assert_eq!(ansi!(Red, Bold).add(Ansi::reset_effects()).to_string(), "\x1B[22;23;24;25;27;28;29;31m");
+    /// ```
+    #[inline]
+    pub const fn reset_effects() -> Ansi { Self::reset().filter(Attrs::effects()) }
+
     /// Creates an `Ansi` instance by adding another `Ansi`'s [`Effect`]s and [`Colour`]s to `self`'s.
     ///
     /// In the absence of [`protected attributes`](Self::protect_attrs()) in either `self`
@@ -117,20 +207,53 @@ impl Ansi {
     ///
     /// In the event of `protected` attributes in either `self` or `other`,
     /// these attributes are preserved in the result, except where the same attributes are
-    /// `protected` in both instances, in which case `self`'s take precedence.
+    /// `protected` in both instances, in which case the higher [`priority`](Self::priority_attrs())
+    /// takes precedence, or `self`'s if the priorities are equal.
     ///
-    /// The resulting `Ansi`'s `protected` attributes are the union of those of both instances.
+    /// The resulting `Ansi`'s `protected` attributes are the union of those of both instances,
+    /// with the higher priority of each.
     #[inline]
     pub const fn add(&self, other: Ansi) -> Ansi {
-        let filter_self  = other.protect.difference(self.protect).complement();
-        let filter_other = self.protect.complement();
+        let filter_self  = other.protect.wins_over(self.protect, false).complement();
+        let filter_other = self.protect.wins_over(other.protect, true).complement();
         Self {
             effect:  self.effect.filter(filter_self).add(other.effect.filter(filter_other)),
             colour:  self.colour.filter(filter_self).add(other.colour.filter(filter_other)),
-            protect: self.protect.union(other.protect),
+            protect: self.protect.max(other.protect),
         }
     }
 
+    /// Creates an `Ansi` instance by adding another `Ansi`'s [`Effect`]s and [`Colour`]s to `self`'s,
+    /// as [`add()`](Self::add()) does, except that where both instances specify a colour,
+    /// the colours are [blended](Colour::blend()) using the given [`BlendMode`], rather than
+    /// `other`'s replacing `self`'s, e.g. for tinting a selection or an overlay.
+    ///
+    /// As with `add()`, [`protected attributes`](Self::protect_attrs()) are preserved,
+    /// i.e. a protected colour of `self` is neither replaced nor blended.
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::Rgb, Effect::Bold};
+    ///
+    /// const TEXT:      Ansi = ansi!(Rgb(200, 200, 200), Rgb(0, 0, 0).bg());
+    /// const SELECTION: Ansi = ansi!(Rgb(0, 0, 200).bg(), Bold);
+    ///
+    /// assert_eq!(TEXT.blend(SELECTION, BlendMode::Average), ansi!(Rgb(200, 200, 200), Rgb(0, 0, 100).bg(), Bold));
+    /// assert_eq!(TEXT.protect().blend(SELECTION, BlendMode::Average), TEXT.protect().add(ansi!(Bold)));
+    /// ```
+    #[cfg(feature="rgb")]
+    #[inline]
+    pub const fn blend(&self, other: Ansi, mode: BlendMode) -> Ansi {
+        let blended = Colours::new(
+            self.colour.fg().blend(other.colour.fg(), mode),
+            self.colour.bg().blend(other.colour.bg(), mode),
+        );
+        self.add(other.with_colour(blended))
+    }
+
     /// Creates an `Ansi` instance by removing another `Ansi`'s [`Effect`]s and [`Colour`]s
     /// from `self`'s.
     ///
@@ -145,8 +268,8 @@ impl Ansi {
     #[inline]
     pub const fn remove(&self, other: Ansi) -> Ansi {
         Self {
-            effect:  self.effect.remove(other.effect.filter(self.protect.complement())),
-            colour:  self.colour.remove(other.colour.filter(self.protect.complement())),
+            effect:  self.effect.remove(other.effect.filter(self.protect.attrs().complement())),
+            colour:  self.colour.remove(other.colour.filter(self.protect.attrs().complement())),
             protect: self.protect,
         }
     }
@@ -161,7 +284,7 @@ impl Ansi {
         Self {
             effect:  self.effect.transition(to_other.effect),
             colour:  self.colour.transition(to_other.colour),
-            protect: Attrs::empty(),
+            protect: Protection::empty(),
         }
     }
 
@@ -267,17 +390,204 @@ impl Ansi {
         }
     }
 
+    /// Creates an `Ansi` instance using this instance's [`Effect`]s and [`Colour`]s,
+    /// but with [`protection`](Self::protect_attrs()) enabled at the given priority level
+    /// for any [`Attrs`] that are `specified`.
+    ///
+    /// See [`priority_attrs()`](Self::priority_attrs) for further details and examples.
+    #[inline]
+    pub const fn priority(&self, level: u8) -> Ansi { self.priority_attrs(self.attrs(), level) }
+
+    /// Creates an `Ansi` instance using this instance's [`Effect`]s and [`Colour`]s,
+    /// but with protection enabled at the given priority level for the given [`Attrs`].
+    ///
+    /// Priority levels range from 0 to 3, where higher levels are clamped to 3.
+    /// Level 0 means unprotected, i.e. the same as [`unprotect_attrs()`](Self::unprotect_attrs()),
+    /// and [`protect_attrs()`](Self::protect_attrs()) protects at level 1 by default.
+    ///
+    /// When nested `Ansi` instances protect the same attributes, the higher priority wins,
+    /// or the outer instance's if the priorities are equal (see [`add()`](Self::add())).
+    /// This enables layered themes, e.g. app defaults at level 1, a user's theme at level 2
+    /// and accessibility overrides at level 3, without tuning each style by hand.
+    ///
+    /// Each attribute has its own level, so e.g. an accessibility override may raise the priority
+    /// of [`Underline`](Effect::Underline) alone, leaving the instance's other protected
+    /// attributes at their existing levels.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::{Blue, Red, Yellow}};
+    ///
+    /// const APP:           Ansi = ansi!(Blue).protect();
+    /// const USER:          Ansi = ansi!(Red).priority(2);
+    /// const ACCESSIBILITY: Ansi = ansi!(Yellow).priority(3);
+    ///
+    /// // The inner user theme overrides the outer app default...
+    /// assert_eq!(APP.add(USER), ansi!(Red).priority(2));
+    /// // ...but not an outer accessibility override
+    /// assert_eq!(ACCESSIBILITY.add(USER), ansi!(Yellow).priority(3));
+    /// assert_eq!(ACCESSIBILITY.priority_of(Attrs::Foreground), 3);
+    ///
+    /// // Levels are per attribute
+    /// let theme = ansi!(Red, Effect::Bold, Effect::Underline).protect().priority_attrs(Attrs::Underline, 3);
+    /// assert_eq!(theme.priority_of(Attrs::Underline), 3);
+    /// assert_eq!(theme.priority_of(Attrs::Bold),      1);
+    /// ```
+    #[inline]
+    pub const fn priority_attrs(&self, attrs: Attrs, level: u8) -> Ansi {
+        Self {
+            effect:  self.effect,
+            colour:  self.colour,
+            protect: self.protect.with_priority(attrs, level),
+        }
+    }
+
+    /// Gets the highest priority level of the given [`Attrs`] of this instance,
+    /// which is 0 if none of them are [`protected`](Self::protect_attrs()).
+    ///
+    /// See [`priority_attrs()`](Self::priority_attrs) for further details and examples.
+    #[inline]
+    pub const fn priority_of(&self, attrs: Attrs) -> u8 { self.protect.priority(attrs) }
+
+    /// Iterates over the [`Effect`]s of this instance that are `specified`,
+    /// in the order given by [`Effect::all()`].
+    ///
+    /// To restrict the iteration to certain attributes, e.g. the protected ones,
+    /// call [`filter()`](Self::filter()) first.
+    ///
+    /// ```
+    /// use ansiconst::{*, Effect::*};
+    ///
+    /// const STYLE: Ansi = ansi!(Bold.ansi().protect(), NotItalic, Underline);
+    ///
+    /// assert_eq!(STYLE.effects().collect::<Vec<_>>(), [Bold, NotItalic, Underline]);
+    /// assert_eq!(STYLE.effects().filter(Effect::is_reset).collect::<Vec<_>>(), [NotItalic]);
+    /// assert_eq!(STYLE.filter(STYLE.protected_attrs()).effects().collect::<Vec<_>>(), [Bold]);
+    /// ```
+    #[inline]
+    pub fn effects(&self) -> impl Iterator<Item = Effect> {
+        let effect = self.effect;
+        Effect::all().into_iter().filter(move |ef| effect.has_effect(*ef))
+    }
+
+    /// Iterates over the foreground and background [`Colour`]s of this instance
+    /// that are `specified`, each paired with [`Attrs::Foreground`] or [`Attrs::Background`]
+    /// accordingly.
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::*};
+    ///
+    /// assert_eq!(
+    ///     ansi!(Red, Blue.bg()).colours().collect::<Vec<_>>(),
+    ///     [(Attrs::Foreground, Red), (Attrs::Background, Blue)]
+    /// );
+    /// assert_eq!(
+    ///     ansi!(Reset.bg()).colours().collect::<Vec<_>>(),
+    ///     [(Attrs::Background, Reset)]
+    /// );
+    /// ```
+    #[inline]
+    pub fn colours(&self) -> impl Iterator<Item = (Attrs, Colour)> {
+        [(Attrs::Foreground, self.colour.fg()), (Attrs::Background, self.colour.bg())]
+            .into_iter()
+            .filter(|(_, colour)| !colour.is_unspecified())
+    }
+
+    /// Creates an `Ansi` instance using this instance's [`Effect`]s, but with its [`Colour`]s
+    /// converted to the nearest colours supported at the given [`ColourSupport`] level
+    /// (see [`Colour::downgraded()`]).
+    ///
+    /// Since this is `const`, fallbacks of e.g. [`Rgb`](Colour::Rgb) styles can be computed
+    /// at compile time, and selected at runtime with a single branch on the detected
+    /// [`colour_support()`](crate::io::colour_support()).
+    ///
+    /// The resulting `Ansi`'s [`protected attributes`](Self::protect_attrs())
+    /// are those of `self`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # #[cfg(feature="rgb")] {
+    /// use ansiconst::{*, io::ColourSupport, Colour::{BrightRed, Rgb}, Effect::Bold};
+    ///
+    /// const ERROR:    Ansi = ansi!(Rgb(250, 20, 30), Bold);
+    /// const ERROR_16: Ansi = ERROR.downgraded(ColourSupport::Ansi16);
+    ///
+    /// assert_eq!(ERROR_16, ansi!(BrightRed, Bold));
+    /// assert_eq!(ERROR.downgraded(ColourSupport::Monochrome), ansi!(Bold));
+    /// assert_eq!(ERROR.downgraded(ColourSupport::Rgb), ERROR);
+    ///
+    /// let error = if io::colour_support() >= ColourSupport::Rgb { ERROR } else { ERROR_16 };
+    /// # }
+    /// ```
+    #[inline]
+    pub const fn downgraded(self, level: ColourSupport) -> Ansi {
+        self.with_colour(Colours::new(self.colour.fg().downgraded(level), self.colour.bg().downgraded(level)))
+    }
+
+    /// Creates an `Ansi` instance that is progressively more emphatic than this instance
+    /// as `count` increases, e.g. for warnings that escalate with each retry.
+    ///
+    /// Each step adds to the previous one, and `count`s beyond the last step are clamped:
+    /// 0. `self`
+    /// 1. adds [`Effect::Bold`]
+    /// 2. adds [`Effect::Underline`]
+    /// 3. switches the foreground to its bright variant, e.g. [`Colour::Red`] becomes
+    ///    [`Colour::BrightRed`], or to [`Colour::BrightRed`] if it has none
+    /// 4. switches the foreground to [`Colour::BrightRed`]
+    ///
+    /// Like [`add()`](Self::add()), [`protected attributes`](Self::protect_attrs()) of `self`
+    /// are not changed.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::{BrightRed, BrightYellow, Yellow}, Effect::{Bold, Underline}};
+    ///
+    /// const RETRY: Ansi = ansi!(Yellow);
+    ///
+    /// assert_eq!(RETRY.escalated(0), ansi!(Yellow));
+    /// assert_eq!(RETRY.escalated(1), ansi!(Yellow, Bold));
+    /// assert_eq!(RETRY.escalated(2), ansi!(Yellow, Bold, Underline));
+    /// assert_eq!(RETRY.escalated(3), ansi!(BrightYellow, Bold, Underline));
+    /// assert_eq!(RETRY.escalated(9), ansi!(BrightRed, Bold, Underline));
+    /// ```
+    pub const fn escalated(self, count: u32) -> Ansi {
+        let mut ansi = self;
+        if count >= 1 {
+            ansi = ansi.add(Effect::Bold.ansi());
+        }
+        if count >= 2 {
+            ansi = ansi.add(Effect::Underline.ansi());
+        }
+        if count >= 3 {
+            let fg = self.colour.fg();
+            let bright = if fg.is_normal() { fg.to_bright() } else { Colour::BrightRed };
+            ansi = ansi.add(if count >= 4 { Colour::BrightRed.ansi() } else { bright.ansi() });
+        }
+        ansi
+    }
+
     /// Used by the `styled_*!` macros to coerce a style argument to an `Ansi` instance.
     #[inline]
     pub const fn ansi(&self) -> Ansi { *self }
 
     #[inline]
     pub(super) const fn from_effect(effect: Effects) -> Ansi {
-        Self { effect, colour: Colours::unspecified(), protect: Attrs::empty() }
+        Self { effect, colour: Colours::unspecified(), protect: Protection::empty() }
     }
     #[inline]
     pub(super) const fn from_colour(colour: Colours) -> Ansi {
-        Self { colour, effect: Effects::unspecified(), protect: Attrs::empty() }
+        Self { colour, effect: Effects::unspecified(), protect: Protection::empty() }
+    }
+    #[inline]
+    pub(crate) const fn with_effect(&self, effect: Effects) -> Ansi {
+        Self { effect, colour: self.colour, protect: self.protect }
+    }
+    #[inline]
+    pub(crate) const fn with_colour(&self, colour: Colours) -> Ansi {
+        Self { colour, effect: self.effect, protect: self.protect }
     }
     #[inline]
     pub(super) const fn effect(&self) -> Effects { self.effect }
@@ -1,6 +1,22 @@
 use super::{Ansi, Attrs};
+use crate::io::ColourSupport;
+#[cfg(feature="ansi256")]
+use crate::text::COLOURS;
 use std::fmt::Debug;
 
+/// The RGB components of the 16 named colours, i.e. those of xterm's default palette.
+#[cfg(any(feature="ansi256", feature="web"))]
+pub(crate) const PALETTE: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00), (0xcd, 0x00, 0x00), (0x00, 0xcd, 0x00), (0xcd, 0xcd, 0x00),
+    (0x00, 0x00, 0xee), (0xcd, 0x00, 0xcd), (0x00, 0xcd, 0xcd), (0xe5, 0xe5, 0xe5),
+    (0x7f, 0x7f, 0x7f), (0xff, 0x00, 0x00), (0x00, 0xff, 0x00), (0xff, 0xff, 0x00),
+    (0x5c, 0x5c, 0xff), (0xff, 0x00, 0xff), (0x00, 0xff, 0xff), (0xff, 0xff, 0xff),
+];
+
+/// The levels of each component of the 6x6x6 colour cube of 8-bit colours.
+#[cfg(feature="ansi256")]
+const CUBE_LEVELS: [u8; 6] = [0x00, 0x5f, 0x87, 0xaf, 0xd7, 0xff];
+
 /// Represents the colour codes that are used to set foreground
 /// and background colours on ANSI terminals.
 ///
@@ -57,23 +73,30 @@ pub enum Colour {
     Rgb(u8, u8, u8),
 }
 
+/// How [`Colour::blend()`] and [`Ansi::blend()`] combine two colours.
+///
+/// *Note: only available with `feature=rgb`*
+#[cfg(feature="rgb")]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum BlendMode {
+    /// The average of each component, e.g. for a subtle tint.
+    Average,
+    /// The product of each component, scaled to `0..=255`, i.e. always darker,
+    /// like a coloured filter.
+    Multiply,
+}
+
 impl Colour {
     /// True if this instance is unspecified - see [`Ansi::unspecified()`]
     #[inline]
     pub const fn is_unspecified(&self) -> bool {
-        match self {
-            Self::Unspecified => true,
-            _                 => false,
-        }
+        matches!(self, Self::Unspecified)
     }
 
     /// True if this instance is reset - see [`Ansi::reset()`]
     #[inline]
     pub const fn is_reset(&self) -> bool {
-        match self {
-            Self::Reset       => true,
-            _                 => false,
-        }
+        matches!(self, Self::Reset)
     }
 
     /// Used for resetting ANSI styles - see [`Ansi::not()`].
@@ -95,6 +118,578 @@ impl Colour {
         }
     }
 
+    /// True if this is one of the 8 normal colours, i.e. `Black` to `White`.
+    #[inline]
+    pub(crate) const fn is_normal(&self) -> bool {
+        matches!(self, Self::Black | Self::Red | Self::Green | Self::Yellow |
+                       Self::Blue | Self::Purple | Self::Cyan | Self::White)
+    }
+
+    /// True if this is one of the 8 bright colours, i.e. `BrightBlack` to `BrightWhite`.
+    #[inline]
+    pub(crate) const fn is_bright(&self) -> bool {
+        matches!(self, Self::BrightBlack | Self::BrightRed | Self::BrightGreen | Self::BrightYellow |
+                       Self::BrightBlue | Self::BrightPurple | Self::BrightCyan | Self::BrightWhite)
+    }
+
+    /// Gets the bright variant of this colour, e.g. `Red` becomes `BrightRed`.
+    ///
+    /// Returns `self` if this is not one of the 8 normal colours.
+    pub(crate) const fn to_bright(self) -> Colour {
+        match self {
+            Self::Black  => Self::BrightBlack,
+            Self::Red    => Self::BrightRed,
+            Self::Green  => Self::BrightGreen,
+            Self::Yellow => Self::BrightYellow,
+            Self::Blue   => Self::BrightBlue,
+            Self::Purple => Self::BrightPurple,
+            Self::Cyan   => Self::BrightCyan,
+            Self::White  => Self::BrightWhite,
+            colour       => colour,
+        }
+    }
+
+    /// Gets the normal variant of this colour, e.g. `BrightRed` becomes `Red`.
+    ///
+    /// Returns `self` if this is not one of the 8 bright colours.
+    pub(crate) const fn to_normal(self) -> Colour {
+        match self {
+            Self::BrightBlack  => Self::Black,
+            Self::BrightRed    => Self::Red,
+            Self::BrightGreen  => Self::Green,
+            Self::BrightYellow => Self::Yellow,
+            Self::BrightBlue   => Self::Blue,
+            Self::BrightPurple => Self::Purple,
+            Self::BrightCyan   => Self::Cyan,
+            Self::BrightWhite  => Self::White,
+            colour             => colour,
+        }
+    }
+
+    /// Gets the 16 named colours, i.e. the 8 normal colours followed by the 8 bright colours,
+    /// e.g. for listing the available colours.
+    ///
+    /// ```
+    /// use ansiconst::Colour;
+    ///
+    /// let names: Vec<&str> = Colour::all16().into_iter().filter_map(|c| c.name()).collect();
+    ///
+    /// assert_eq!(names[..3], ["black", "red", "green"]);
+    /// assert_eq!(names[15], "bright_white");
+    /// ```
+    #[inline]
+    pub const fn all16() -> [Colour; 16] {
+        [
+            Self::Black,       Self::Red,          Self::Green,       Self::Yellow,
+            Self::Blue,        Self::Purple,       Self::Cyan,        Self::White,
+            Self::BrightBlack, Self::BrightRed,    Self::BrightGreen, Self::BrightYellow,
+            Self::BrightBlue,  Self::BrightPurple, Self::BrightCyan,  Self::BrightWhite,
+        ]
+    }
+
+    /// Gets the name of this colour, e.g. `"bright_red"`, as used by [`Ansi::spec()`].
+    ///
+    /// Returns `None` if this colour is `Unspecified`, or an 8-bit or RGB colour.
+    ///
+    /// ```
+    /// use ansiconst::Colour::{BrightRed, Reset, Unspecified};
+    ///
+    /// assert_eq!(  BrightRed.name(), Some("bright_red"));
+    /// assert_eq!(      Reset.name(), Some("reset"));
+    /// assert_eq!(Unspecified.name(), None);
+    /// ```
+    pub const fn name(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::Reset        => "reset",
+            Self::Black        => "black",
+            Self::Red          => "red",
+            Self::Green        => "green",
+            Self::Yellow       => "yellow",
+            Self::Blue         => "blue",
+            Self::Purple       => "purple",
+            Self::Cyan         => "cyan",
+            Self::White        => "white",
+            Self::BrightBlack  => "bright_black",
+            Self::BrightRed    => "bright_red",
+            Self::BrightGreen  => "bright_green",
+            Self::BrightYellow => "bright_yellow",
+            Self::BrightBlue   => "bright_blue",
+            Self::BrightPurple => "bright_purple",
+            Self::BrightCyan   => "bright_cyan",
+            Self::BrightWhite  => "bright_white",
+            _                  => return None,
+        })
+    }
+
+    /// Gets the colour with the given [`name()`](Self::name()), ignoring case, e.g. for
+    /// validating colour names in config files.
+    ///
+    /// Returns `None` if there is no such colour. The valid names are those of
+    /// [`all16()`](Self::all16()), plus `"reset"`.
+    ///
+    /// ```
+    /// use ansiconst::Colour;
+    ///
+    /// assert_eq!(Colour::by_name("bright_red"), Some(Colour::BrightRed));
+    /// assert_eq!(Colour::by_name("Blue"),       Some(Colour::Blue));
+    /// assert_eq!(Colour::by_name("pink"),       None);
+    ///
+    /// // E.g. for an error message listing the valid names
+    /// let valid: Vec<&str> = Colour::all16().into_iter().filter_map(|c| c.name()).collect();
+    /// assert!(valid.join(", ").starts_with("black, red, green, yellow"));
+    /// ```
+    pub fn by_name(name: &str) -> Option<Colour> {
+        Self::all16().into_iter().chain([Self::Reset])
+            .find(|colour| colour.name().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+    }
+
+    /// Creates an [`Rgb`](Colour::Rgb) colour from a hex integer of the form `0xRRGGBB`,
+    /// e.g. as copied from a design tool. The highest 8 bits of `hex`, if any, are ignored.
+    ///
+    /// See also [`from_hex()`](Colour::from_hex()) and [`rgb!`](crate::rgb) for hex strings.
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ```
+    /// use ansiconst::*;
+    ///
+    /// const BRAND: Colour = Colour::hex(0xFF8800);
+    ///
+    /// assert_eq!(BRAND,                   Colour::Rgb(255, 136, 0));
+    /// assert_eq!(BRAND,                   rgb!("#ff8800"));
+    /// assert_eq!(ansi!(BRAND).to_string(), "\x1B[38;2;255;136;0m");
+    /// ```
+    #[cfg(feature="rgb")]
+    pub const fn hex(hex: u32) -> Colour {
+        Self::Rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+    }
+
+    /// Parses a hex colour of the form `"#rrggbb"` or `"#rgb"` (ignoring case)
+    /// as an [`Rgb`](Colour::Rgb) colour, or returns `None` if it is malformed.
+    ///
+    /// See also [`rgb!`](crate::rgb), which does this at compile time.
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ```
+    /// use ansiconst::Colour;
+    ///
+    /// assert_eq!(Colour::from_hex("#ff8800"), Some(Colour::Rgb(255, 136, 0)));
+    /// assert_eq!(Colour::from_hex("#F80"),    Some(Colour::Rgb(255, 136, 0)));
+    /// assert_eq!(Colour::from_hex("ff8800"),  None);
+    /// assert_eq!(Colour::from_hex("#ff880"),  None);
+    /// ```
+    #[cfg(feature="rgb")]
+    pub const fn from_hex(hex: &str) -> Option<Colour> {
+        const fn digit(b: u8) -> Option<u8> {
+            match b {
+                b'0'..=b'9' => Some(b - b'0'),
+                b'a'..=b'f' => Some(b - b'a' + 10),
+                b'A'..=b'F' => Some(b - b'A' + 10),
+                _           => None,
+            }
+        }
+        const fn channel(hi: u8, lo: u8) -> Option<u8> {
+            match (digit(hi), digit(lo)) {
+                (Some(hi), Some(lo)) => Some(hi << 4 | lo),
+                _                    => None,
+            }
+        }
+        let (r, g, b) = match hex.as_bytes() {
+            [b'#', r1, r2, g1, g2, b1, b2] => (channel(*r1, *r2), channel(*g1, *g2), channel(*b1, *b2)),
+            [b'#', r, g, b]                => (channel(*r, *r),   channel(*g, *g),   channel(*b, *b)),
+            _                              => return None,
+        };
+        match (r, g, b) {
+            (Some(r), Some(g), Some(b)) => Some(Self::Rgb(r, g, b)),
+            _                           => None,
+        }
+    }
+
+    /// Converts this colour to the nearest colour that is supported at the given level,
+    /// e.g. an [`Rgb`](Colour::Rgb) colour becomes one of the 16 named colours at
+    /// [`ColourSupport::Ansi16`], and every colour becomes `Unspecified` at
+    /// [`ColourSupport::Monochrome`].
+    ///
+    /// Colours are compared by their RGB components in xterm's default palette, using
+    /// a perceptual distance, which weights differences in green more than in red and blue.
+    ///
+    /// See [`Ansi::downgraded()`] for details and examples.
+    pub const fn downgraded(self, level: ColourSupport) -> Colour {
+        match (self, level) {
+            (_, ColourSupport::Monochrome) => Self::Unspecified,
+            #[cfg(feature="ansi256")]
+            (Self::Ansi256(num), ColourSupport::Ansi16) => {
+                if (num as usize) < COLOURS.len() { COLOURS[num as usize] } else { nearest_named(ansi256_rgb(num)) }
+            },
+            #[cfg(feature="rgb")]
+            (Self::Rgb(r, g, b), ColourSupport::Ansi16)  => nearest_named((r, g, b)),
+            #[cfg(feature="rgb")]
+            (Self::Rgb(r, g, b), ColourSupport::Ansi256) => Self::Ansi256(nearest_ansi256((r, g, b))),
+            (colour, _) => colour,
+        }
+    }
+
+    /// Quantizes this colour to the nearest [`Ansi256`](Colour::Ansi256) colour of the colour
+    /// cube or greyscale ramp, if it is an [`Rgb`](Colour::Rgb) colour.
+    ///
+    /// Equivalent to [`downgraded(ColourSupport::Ansi256)`](Colour::downgraded()).
+    ///
+    /// ```
+    /// # #[cfg(feature="rgb")] {
+    /// use ansiconst::Colour::{Ansi256, Red, Rgb};
+    ///
+    /// assert_eq!(Rgb(255, 135, 0).to_256(),   Ansi256(208));
+    /// assert_eq!(Rgb(120, 120, 120).to_256(), Ansi256(243));
+    /// assert_eq!(Red.to_256(),                Red);
+    /// # }
+    /// ```
+    #[inline]
+    pub const fn to_256(self) -> Colour { self.downgraded(ColourSupport::Ansi256) }
+
+    /// Quantizes this colour to the nearest of the 16 named colours, i.e. `Black` to
+    /// `BrightWhite`, if it is an [`Ansi256`](Colour::Ansi256) or [`Rgb`](Colour::Rgb) colour.
+    ///
+    /// Equivalent to [`downgraded(ColourSupport::Ansi16)`](Colour::downgraded()).
+    ///
+    /// ```
+    /// # #[cfg(feature="rgb")] {
+    /// use ansiconst::Colour::{Ansi256, BrightRed, Purple, Rgb};
+    ///
+    /// assert_eq!(Rgb(250, 10, 20).to_16(), BrightRed);
+    /// assert_eq!(Ansi256(90).to_16(),      Purple);
+    /// # }
+    /// ```
+    #[inline]
+    pub const fn to_16(self) -> Colour { self.downgraded(ColourSupport::Ansi16) }
+
+    /// Gets a lighter shade of this colour, if it is an [`Ansi256`](Colour::Ansi256) colour
+    /// of the colour cube or greyscale ramp, without converting it to RGB.
+    ///
+    /// Within the cube, each component below the highest level is raised by one level,
+    /// i.e. the colour is tinted towards white. Within the ramp, the next lighter grey is used.
+    ///
+    /// Returns `None` if there is no lighter shade, i.e. for white and the lightest grey,
+    /// or for colours outside the cube and ramp, e.g. `Ansi256(9)` and [`Colour::Red`],
+    /// whose components vary between terminals.
+    ///
+    /// *Note: only available with `feature=ansi256`*
+    ///
+    /// ```
+    /// use ansiconst::Colour::{Ansi256, Red};
+    ///
+    /// const PINK: Option<ansiconst::Colour> = Ansi256(196).lighter();
+    ///
+    /// assert_eq!(PINK,                    Some(Ansi256(203)));
+    /// assert_eq!(Ansi256(244).lighter(),  Some(Ansi256(245)));
+    /// assert_eq!(Ansi256(231).lighter(),  None);
+    /// assert_eq!(Red.lighter(),           None);
+    /// ```
+    #[cfg(feature="ansi256")]
+    pub const fn lighter(self) -> Option<Colour> {
+        match self {
+            Self::Ansi256(num @ 16..=231) => {
+                let (r, g, b) = cube_levels(num);
+                if r == 5 && g == 5 && b == 5 { return None; }
+                const fn up(level: u8) -> u8 { if level < 5 { level + 1 } else { level } }
+                Some(Self::Ansi256(cube_num(up(r), up(g), up(b))))
+            },
+            Self::Ansi256(num @ 232..=254) => Some(Self::Ansi256(num + 1)),
+            _ => None,
+        }
+    }
+
+    /// Gets a darker shade of this colour, if it is an [`Ansi256`](Colour::Ansi256) colour
+    /// of the colour cube or greyscale ramp, without converting it to RGB.
+    ///
+    /// Within the cube, each component above the lowest level is lowered by one level,
+    /// i.e. the colour is shaded towards black. Within the ramp, the next darker grey is used.
+    ///
+    /// Returns `None` if there is no darker shade, i.e. for black and the darkest grey,
+    /// or for colours outside the cube and ramp (see [`lighter()`](Colour::lighter())).
+    ///
+    /// *Note: only available with `feature=ansi256`*
+    ///
+    /// ```
+    /// use ansiconst::Colour::{Ansi256, Red};
+    ///
+    /// assert_eq!(Ansi256(203).darker(), Some(Ansi256(160)));
+    /// assert_eq!(Ansi256(244).darker(), Some(Ansi256(243)));
+    /// assert_eq!(Ansi256(16).darker(),  None);
+    /// assert_eq!(Red.darker(),          None);
+    /// ```
+    #[cfg(feature="ansi256")]
+    pub const fn darker(self) -> Option<Colour> {
+        match self {
+            Self::Ansi256(num @ 16..=231) => {
+                let (r, g, b) = cube_levels(num);
+                if r == 0 && g == 0 && b == 0 { return None; }
+                const fn down(level: u8) -> u8 { if level > 0 { level - 1 } else { level } }
+                Some(Self::Ansi256(cube_num(down(r), down(g), down(b))))
+            },
+            Self::Ansi256(num @ 233..=255) => Some(Self::Ansi256(num - 1)),
+            _ => None,
+        }
+    }
+
+    /// Gets this colour with its hue rotated by the given number of `steps` around the
+    /// colour cube, if it is an [`Ansi256`](Colour::Ansi256) colour of the cube,
+    /// without converting it to RGB.
+    ///
+    /// The hues of the cube's colours of a given lightness and saturation form a ring,
+    /// running from red to yellow, green, cyan, blue, magenta and back to red, where each
+    /// step changes one component by one level. Positive `steps` rotate in that order,
+    /// negative `steps` in reverse, e.g. the hues of pure red are 5 steps apart, so
+    /// 15 steps gives its complementary colour, cyan.
+    ///
+    /// Returns `None` if this colour has no hue, i.e. for greys, or is outside the cube.
+    ///
+    /// *Note: only available with `feature=ansi256`*
+    ///
+    /// ```
+    /// use ansiconst::Colour::{Ansi256, Red};
+    ///
+    /// const RED: ansiconst::Colour = Ansi256(196);
+    ///
+    /// assert_eq!(RED.shift_hue(5),          Some(Ansi256(226))); // yellow
+    /// assert_eq!(RED.shift_hue(15),         Some(Ansi256(51)));  // cyan
+    /// assert_eq!(RED.shift_hue(-5),         Some(Ansi256(201))); // magenta
+    /// assert_eq!(RED.shift_hue(30),         Some(RED));
+    /// assert_eq!(Ansi256(102).shift_hue(1), None);
+    /// assert_eq!(Red.shift_hue(1),          None);
+    /// ```
+    #[cfg(feature="ansi256")]
+    pub const fn shift_hue(self, steps: i32) -> Option<Colour> {
+        let num = match self {
+            Self::Ansi256(num @ 16..=231) => num,
+            _ => return None,
+        };
+        let (r, g, b) = cube_levels(num);
+        let min = if r < g { if r < b { r } else { b } } else if g < b { g } else { b };
+        let (r, g, b) = (r - min, g - min, b - min);
+        let chroma = if r > g { if r > b { r } else { b } } else if g > b { g } else { b };
+        if chroma == 0 {
+            return None;
+        }
+        // The position on the ring of hues, which has 6 segments of `chroma` steps each
+        let c = chroma as i32;
+        let pos = if r == chroma && b == 0 {
+            g as i32
+        } else if g == chroma && b == 0 {
+            2 * c - r as i32
+        } else if g == chroma && r == 0 {
+            2 * c + b as i32
+        } else if b == chroma && r == 0 {
+            4 * c - g as i32
+        } else if b == chroma && g == 0 {
+            4 * c + r as i32
+        } else {
+            6 * c - b as i32
+        };
+        let pos = (pos + steps % (6 * c)).rem_euclid(6 * c);
+        let (seg, off) = ((pos / c) as u8, (pos % c) as u8);
+        let (r, g, b) = match seg {
+            0 => (chroma, off, 0),
+            1 => (chroma - off, chroma, 0),
+            2 => (0, chroma, off),
+            3 => (0, chroma - off, chroma),
+            4 => (off, 0, chroma),
+            _ => (chroma, 0, chroma - off),
+        };
+        Some(Self::Ansi256(cube_num(r + min, g + min, b + min)))
+    }
+
+    /// Gets the RGB components of this colour, or `None` if [`Colour::Unspecified`]
+    /// or [`Colour::Reset`].
+    ///
+    /// The 16 named colours use xterm's default palette, as do [`Ansi256`](Colour::Ansi256) colours.
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ```
+    /// use ansiconst::Colour;
+    ///
+    /// assert_eq!(Colour::Red.to_rgb(),          Some((0xcd, 0, 0)));
+    /// assert_eq!(Colour::Ansi256(231).to_rgb(), Some((0xff, 0xff, 0xff)));
+    /// assert_eq!(Colour::Reset.to_rgb(),        None);
+    /// ```
+    #[cfg(feature="rgb")]
+    pub const fn to_rgb(self) -> Option<(u8, u8, u8)> {
+        let num = match self {
+            Self::Unspecified | Self::Reset => return None,
+            Self::Rgb(r, g, b) => return Some((r, g, b)),
+            Self::Ansi256(num) => num,
+            Self::Black        => 0,
+            Self::Red          => 1,
+            Self::Green        => 2,
+            Self::Yellow       => 3,
+            Self::Blue         => 4,
+            Self::Purple       => 5,
+            Self::Cyan         => 6,
+            Self::White        => 7,
+            Self::BrightBlack  => 8,
+            Self::BrightRed    => 9,
+            Self::BrightGreen  => 10,
+            Self::BrightYellow => 11,
+            Self::BrightBlue   => 12,
+            Self::BrightPurple => 13,
+            Self::BrightCyan   => 14,
+            Self::BrightWhite  => 15,
+        };
+        Some(ansi256_rgb(num))
+    }
+
+    /// Creates an [`Rgb`](Colour::Rgb) colour from its hue, saturation and lightness,
+    /// e.g. for gradients or for deriving a theme's colours from a single hue.
+    ///
+    /// The hue is in degrees, wrapping at 360, and the saturation and lightness are
+    /// percentages, clamped to 100. Each component is rounded to the nearest integer.
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ```
+    /// use ansiconst::Colour::{self, Rgb};
+    ///
+    /// const ORANGE: Colour = Colour::hsl(30, 100, 50);
+    ///
+    /// assert_eq!(ORANGE,                      Rgb(255, 128, 0));
+    /// assert_eq!(Colour::hsl(120, 100, 25),   Rgb(0, 128, 0));
+    /// assert_eq!(Colour::hsl(480, 100, 25),   Rgb(0, 128, 0));
+    /// assert_eq!(Colour::hsl(0, 0, 50),       Rgb(128, 128, 128));
+    /// ```
+    #[cfg(feature="rgb")]
+    pub const fn hsl(hue: u16, saturation: u8, lightness: u8) -> Colour {
+        let (s, l) = (percent(saturation), percent(lightness));
+        let chroma = (100 - (2 * l - 100).abs()) * s;
+        hue_rgb(hue, chroma, l * 100 - chroma / 2)
+    }
+
+    /// Creates an [`Rgb`](Colour::Rgb) colour from its hue, saturation and value
+    /// (i.e. brightness), as for [`hsl()`](Colour::hsl()).
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ```
+    /// use ansiconst::Colour::{self, Rgb};
+    ///
+    /// assert_eq!(Colour::hsv(30, 100, 100), Rgb(255, 128, 0));
+    /// assert_eq!(Colour::hsv(240, 50, 50),  Rgb(64, 64, 128));
+    /// assert_eq!(Colour::hsv(0, 0, 100),    Rgb(255, 255, 255));
+    /// ```
+    #[cfg(feature="rgb")]
+    pub const fn hsv(hue: u16, saturation: u8, value: u8) -> Colour {
+        let (s, v) = (percent(saturation), percent(value));
+        let chroma = v * s;
+        hue_rgb(hue, chroma, v * 100 - chroma)
+    }
+
+    /// Gets the hue, saturation and lightness of this colour, as for [`hsl()`](Colour::hsl()),
+    /// or `None` if it has no RGB components (see [`to_rgb()`](Colour::to_rgb())).
+    ///
+    /// Greys have a hue and saturation of 0. Components are rounded to the nearest integer,
+    /// so converting to and from HSL may change a colour slightly.
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ```
+    /// use ansiconst::Colour::{self, Rgb};
+    ///
+    /// assert_eq!(Rgb(255, 128, 0).to_hsl(),   Some((30, 100, 50)));
+    /// assert_eq!(Colour::Blue.to_hsl(),       Some((240, 100, 47)));
+    /// assert_eq!(Colour::Reset.to_hsl(),      None);
+    ///
+    /// // E.g. a lighter shade of the same hue
+    /// let (h, s, l) = Rgb(0, 128, 0).to_hsl().unwrap();
+    /// assert_eq!(Colour::hsl(h, s, l + 25),   Rgb(0, 255, 0));
+    /// ```
+    #[cfg(feature="rgb")]
+    pub const fn to_hsl(self) -> Option<(u16, u8, u8)> {
+        let (hue, max, min) = match self.to_rgb() {
+            Some(rgb) => rgb_hue(rgb),
+            None      => return None,
+        };
+        let (sum, delta) = (max + min, max - min);
+        let saturation = if delta == 0 { 0 } else { div_round(delta * 100, 255 - (sum - 255).abs()) };
+        Some((hue, saturation as u8, div_round(sum * 100, 510) as u8))
+    }
+
+    /// Gets the hue, saturation and lightness of this colour, the same as [`to_hsl()`](Colour::to_hsl()).
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ```
+    /// use ansiconst::Colour::Rgb;
+    ///
+    /// assert_eq!(Rgb(255, 128, 0).get_hsl(), Some((30, 100, 50)));
+    /// ```
+    #[cfg(feature="rgb")]
+    #[inline]
+    pub const fn get_hsl(self) -> Option<(u16, u8, u8)> { self.to_hsl() }
+
+    /// Gets the hue, saturation and value of this colour, as for [`hsv()`](Colour::hsv()),
+    /// or `None` if it has no RGB components (see [`to_rgb()`](Colour::to_rgb())).
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ```
+    /// use ansiconst::Colour::Rgb;
+    ///
+    /// assert_eq!(Rgb(255, 128, 0).to_hsv(), Some((30, 100, 100)));
+    /// assert_eq!(Rgb(64, 64, 128).to_hsv(), Some((240, 50, 50)));
+    /// ```
+    #[cfg(feature="rgb")]
+    pub const fn to_hsv(self) -> Option<(u16, u8, u8)> {
+        let (hue, max, min) = match self.to_rgb() {
+            Some(rgb) => rgb_hue(rgb),
+            None      => return None,
+        };
+        let saturation = if max == 0 { 0 } else { div_round((max - min) * 100, max) };
+        Some((hue, saturation as u8, div_round(max * 100, 255) as u8))
+    }
+
+    /// Gets the hue, saturation and value of this colour, the same as [`to_hsv()`](Colour::to_hsv()).
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ```
+    /// use ansiconst::Colour::Rgb;
+    ///
+    /// assert_eq!(Rgb(255, 128, 0).get_hsv(), Some((30, 100, 100)));
+    /// ```
+    #[cfg(feature="rgb")]
+    #[inline]
+    pub const fn get_hsv(self) -> Option<(u16, u8, u8)> { self.to_hsv() }
+
+    /// Blends this colour with `other`, using the given [`BlendMode`], resulting
+    /// in an [`Rgb`](Colour::Rgb) colour (see [`to_rgb()`](Colour::to_rgb())).
+    ///
+    /// If either colour is [`Colour::Unspecified`] or [`Colour::Reset`], there is nothing
+    /// to blend, so the result is `other`, as when combining styles with [`Ansi::add()`].
+    ///
+    /// *Note: only available with `feature=rgb`*
+    ///
+    /// ```
+    /// use ansiconst::{BlendMode, Colour::{self, Rgb}};
+    ///
+    /// assert_eq!(Rgb(200, 100, 0).blend(Rgb(0, 100, 200), BlendMode::Average), Rgb(100, 100, 100));
+    /// assert_eq!(Rgb(255, 128, 0).blend(Rgb(128, 128, 128), BlendMode::Multiply), Rgb(128, 64, 0));
+    /// assert_eq!(Rgb(255, 128, 0).blend(Colour::Unspecified, BlendMode::Average), Colour::Unspecified);
+    /// ```
+    #[cfg(feature="rgb")]
+    pub const fn blend(self, other: Colour, mode: BlendMode) -> Colour {
+        const fn component(a: u8, b: u8, mode: BlendMode) -> u8 {
+            let (a, b) = (a as u16, b as u16);
+            (match mode {
+                BlendMode::Average  => (a + b).div_ceil(2),
+                BlendMode::Multiply => (a * b + 127) / 255,
+            }) as u8
+        }
+        match (self.to_rgb(), other.to_rgb()) {
+            (Some(a), Some(b)) => Self::Rgb(component(a.0, b.0, mode), component(a.1, b.1, mode), component(a.2, b.2, mode)),
+            _                  => other,
+        }
+    }
+
     /// Creates an [`Ansi`] style with this colour used as the foreground colour.
     #[inline]
     pub const fn fg(&self) -> Ansi {
@@ -128,6 +723,122 @@ impl Colour {
     }
 }
 
+/// Gets the RGB components of an 8-bit colour.
+#[cfg(feature="ansi256")]
+pub(crate) const fn ansi256_rgb(num: u8) -> (u8, u8, u8) {
+    match num {
+        0..=15    => PALETTE[num as usize],
+        16..=231  => {
+            let (r, g, b) = cube_levels(num);
+            (CUBE_LEVELS[r as usize], CUBE_LEVELS[g as usize], CUBE_LEVELS[b as usize])
+        },
+        232..=255 => {
+            let level = 8 + 10 * (num - 232);
+            (level, level, level)
+        },
+    }
+}
+
+/// Gets the levels (0 to 5) of the components of an 8-bit colour of the colour cube.
+#[cfg(feature="ansi256")]
+const fn cube_levels(num: u8) -> (u8, u8, u8) {
+    let num = num - 16;
+    (num / 36, num / 6 % 6, num % 6)
+}
+
+/// Gets the number of the 8-bit colour of the colour cube with the given component levels.
+#[cfg(feature="ansi256")]
+const fn cube_num(r: u8, g: u8, b: u8) -> u8 {
+    16 + 36 * r + 6 * g + b
+}
+
+/// Gets a percentage as an `i64`, clamped to 100.
+#[cfg(feature="rgb")]
+const fn percent(value: u8) -> i64 {
+    if value > 100 { 100 } else { value as i64 }
+}
+
+/// Divides `a` by the positive `b`, rounding to the nearest integer (halves up).
+#[cfg(feature="rgb")]
+const fn div_round(a: i64, b: i64) -> i64 {
+    (2 * a + b).div_euclid(2 * b)
+}
+
+/// Creates an [`Rgb`](Colour::Rgb) colour from its hue in degrees, and its chroma and
+/// the amount `m` added to each component, both in units of 1/10000.
+#[cfg(feature="rgb")]
+const fn hue_rgb(hue: u16, chroma: i64, m: i64) -> Colour {
+    let hue = (hue % 360) as i64;
+    // In units of 1/600000, so that the second largest component `x` is exact
+    let (c, x, m) = (chroma * 60, chroma * (60 - (hue % 120 - 60).abs()), m * 60);
+    let (r, g, b) = match hue / 60 {
+        0 => (c, x, 0),
+        1 => (x, c, 0),
+        2 => (0, c, x),
+        3 => (0, x, c),
+        4 => (x, 0, c),
+        _ => (c, 0, x),
+    };
+    const fn component(value: i64) -> u8 { div_round(value * 255, 600_000) as u8 }
+    Colour::Rgb(component(r + m), component(g + m), component(b + m))
+}
+
+/// Gets the hue in degrees, and the largest and smallest components, of an RGB colour.
+#[cfg(feature="rgb")]
+const fn rgb_hue((r, g, b): (u8, u8, u8)) -> (u16, i64, i64) {
+    let (r, g, b) = (r as i64, g as i64, b as i64);
+    let max = if r > g { if r > b { r } else { b } } else if g > b { g } else { b };
+    let min = if r < g { if r < b { r } else { b } } else if g < b { g } else { b };
+    let delta = max - min;
+    let hue = if delta == 0 {
+        0
+    } else if max == r {
+        div_round(60 * (g - b), delta)
+    } else if max == g {
+        div_round(60 * (b - r), delta) + 120
+    } else {
+        div_round(60 * (r - g), delta) + 240
+    };
+    (hue.rem_euclid(360) as u16, max, min)
+}
+
+/// Gets the perceptual distance between two RGB colours, i.e. the squared distance
+/// weighted by the *redmean* approximation of how the eye perceives differences.
+#[cfg(feature="ansi256")]
+const fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let mean = (a.0 as u32 + b.0 as u32) / 2;
+    let (r, g, b) = (a.0.abs_diff(b.0) as u32, a.1.abs_diff(b.1) as u32, a.2.abs_diff(b.2) as u32);
+    (((512 + mean) * r * r) >> 8) + 4 * g * g + (((767 - mean) * b * b) >> 8)
+}
+
+/// Gets the named colour nearest to the given RGB components.
+#[cfg(feature="ansi256")]
+const fn nearest_named(rgb: (u8, u8, u8)) -> Colour {
+    let (mut i, mut nearest) = (1, 0);
+    while i < PALETTE.len() {
+        if distance(rgb, PALETTE[i]) < distance(rgb, PALETTE[nearest]) {
+            nearest = i;
+        }
+        i += 1;
+    }
+    COLOURS[nearest]
+}
+
+/// Gets the number of the 8-bit colour nearest to the given RGB components, amongst
+/// the colour cube and the greyscale ramp, i.e. excluding the 16 named colours, whose
+/// components vary between terminals.
+#[cfg(feature="rgb")]
+const fn nearest_ansi256(rgb: (u8, u8, u8)) -> u8 {
+    let (mut num, mut nearest) = (16, 16);
+    while num < 255 {
+        num += 1;
+        if distance(rgb, ansi256_rgb(num)) < distance(rgb, ansi256_rgb(nearest)) {
+            nearest = num;
+        }
+    }
+    nearest
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub(crate) struct Colours { fg: Colour, bg: Colour }
 
@@ -147,6 +858,8 @@ impl Colours {
     #[inline]
     pub(crate) const fn from_bg(bg: Colour) -> Self { Self { fg: Colour::Unspecified, bg } }
     #[inline]
+    pub(crate) const fn with_fg(&self, fg: Colour) -> Self { Self { fg, bg: self.bg } }
+    #[inline]
     pub(crate) const fn fg(&self) -> Colour { self.fg }
     #[inline]
     pub(crate) const fn bg(&self) -> Colour { self.bg }
@@ -50,26 +50,22 @@ impl Effect {
     /// True if this instance is unspecified - see [`Ansi::unspecified()`]
     #[inline]
     pub const fn is_unspecified(&self) -> bool {
-        match self {
-            Self::Unspecified  => true,
-            _                  => false,
-        }
+        matches!(self, Self::Unspecified)
     }
 
     /// True if this instance is reset - see [`Ansi::reset()`]
     #[inline]
     pub const fn is_reset(&self) -> bool {
-        match *self {
-            Self::NotBold      => true,
-            Self::NotFaint     => true,
-            Self::NotItalic    => true,
-            Self::NotUnderline => true,
-            Self::NotBlink     => true,
-            Self::NotReverse   => true,
-            Self::NotHidden    => true,
-            Self::NotStrike    => true,
-            _                  => false,
-        }
+        matches!(self,
+            Self::NotBold      |
+            Self::NotFaint     |
+            Self::NotItalic    |
+            Self::NotUnderline |
+            Self::NotBlink     |
+            Self::NotReverse   |
+            Self::NotHidden    |
+            Self::NotStrike
+        )
     }
 
     /// Used for resetting ANSI styles - see [`Ansi::not()`].
@@ -98,6 +94,79 @@ impl Effect {
         }
     }
 
+    /// Gets all effects, excluding `Unspecified`, in declaration order.
+    ///
+    /// ```
+    /// use ansiconst::Effect;
+    ///
+    /// let resets: Vec<Effect> = Effect::all().into_iter().filter(Effect::is_reset).collect();
+    ///
+    /// assert_eq!(Effect::all().len(), 16);
+    /// assert_eq!(resets.len(), 8);
+    /// ```
+    #[inline]
+    pub const fn all() -> [Effect; 16] {
+        [
+            Self::Bold,      Self::NotBold,
+            Self::Faint,     Self::NotFaint,
+            Self::Italic,    Self::NotItalic,
+            Self::Underline, Self::NotUnderline,
+            Self::Blink,     Self::NotBlink,
+            Self::Reverse,   Self::NotReverse,
+            Self::Hidden,    Self::NotHidden,
+            Self::Strike,    Self::NotStrike,
+        ]
+    }
+
+    /// Gets the name of this effect, e.g. `"bold"` or `"not_bold"`, as used by [`Ansi::spec()`].
+    ///
+    /// Returns `None` if this effect is `Unspecified`.
+    ///
+    /// ```
+    /// use ansiconst::Effect::{Bold, NotBold, Unspecified};
+    ///
+    /// assert_eq!(       Bold.name(), Some("bold"));
+    /// assert_eq!(    NotBold.name(), Some("not_bold"));
+    /// assert_eq!(Unspecified.name(), None);
+    /// ```
+    pub const fn name(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::Unspecified  => return None,
+            Self::Bold         => "bold",
+            Self::NotBold      => "not_bold",
+            Self::Faint        => "faint",
+            Self::NotFaint     => "not_faint",
+            Self::Italic       => "italic",
+            Self::NotItalic    => "not_italic",
+            Self::Underline    => "underline",
+            Self::NotUnderline => "not_underline",
+            Self::Blink        => "blink",
+            Self::NotBlink     => "not_blink",
+            Self::Reverse      => "reverse",
+            Self::NotReverse   => "not_reverse",
+            Self::Hidden       => "hidden",
+            Self::NotHidden    => "not_hidden",
+            Self::Strike       => "strike",
+            Self::NotStrike    => "not_strike",
+        })
+    }
+
+    /// Gets the effect with the given [`name()`](Self::name()), ignoring case, e.g. for
+    /// validating effect names in config files.
+    ///
+    /// Returns `None` if there is no such effect. The valid names are those of [`all()`](Self::all()).
+    ///
+    /// ```
+    /// use ansiconst::Effect;
+    ///
+    /// assert_eq!(Effect::by_name("underline"), Some(Effect::Underline));
+    /// assert_eq!(Effect::by_name("Not_Bold"),  Some(Effect::NotBold));
+    /// assert_eq!(Effect::by_name("shiny"),     None);
+    /// ```
+    pub fn by_name(name: &str) -> Option<Effect> {
+        Self::all().into_iter().find(|effect| effect.name().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+    }
+
     /// Creates an [`Ansi`] style with this `Effect` and with [`Attrs::all()`] protected.
     #[inline]
     pub const fn only(&self) -> Ansi { self.ansi().only() }
@@ -117,8 +186,8 @@ impl Effect {
         }
     }
     #[inline]
-    const fn to_bits(&self) -> Bits {
-        match *self {
+    const fn to_bits(self) -> Bits {
+        match self {
             Self::Unspecified  => Bits::empty(),
             Self::Bold         => Bits::Bold,
             Self::NotBold      => Bits::Bold,
@@ -155,13 +224,13 @@ bitflags! {
 }
 
 impl Bits {
-    const fn to_not_bitmask(&self) -> Self {
+    const fn to_not_bitmask(self) -> Self {
         if self.intersects(Bits::Bold) {
             self.union(Bits::Faint)
         } else if self.intersects(Bits::Faint) {
             self.union(Bits::Bold)
         } else {
-            *self
+            self
         }
     }
     const fn filter(&self, attrs: Attrs) -> Self {
@@ -179,7 +248,7 @@ impl Bits {
         if attrs.intersects(Attrs::Strike)    { bits = bits.union(Self::Strike); }
         bits
     }
-    const fn to_attrs(&self) -> Attrs {
+    const fn to_attrs(self) -> Attrs {
         let mut attrs = Attrs::empty();
         if self.intersects(Self::Bold)        { attrs = attrs.union(Attrs::Bold); }
         if self.intersects(Self::Faint)       { attrs = attrs.union(Attrs::Faint); }
@@ -215,6 +284,23 @@ impl Effects {
             self.n.intersects(ef.to_bits())
         }
     }
+    /// Sets the given effect, replacing its opposite (if any), unlike [`add()`](Self::add())
+    /// which would cancel both out.
+    #[inline]
+    pub(crate) const fn with_effect(&self, ef: Effect) -> Self {
+        let bits = ef.to_bits();
+        if !ef.is_reset() {
+            Self { y: self.y.union(bits), n: self.n.difference(bits) }
+        } else {
+            Self { y: self.y.difference(bits), n: self.n.union(bits) }
+        }
+    }
+    /// Clears the given effect and its opposite, leaving the effect unspecified.
+    #[inline]
+    pub(crate) const fn without_effect(&self, ef: Effect) -> Self {
+        let bits = ef.to_bits();
+        Self { y: self.y.difference(bits), n: self.n.difference(bits) }
+    }
     #[inline]
     pub(crate) const fn add(&self, other: Self) -> Self {
         self.union(other)
@@ -247,6 +333,19 @@ impl Effects {
     pub(crate) const fn attrs(&self) -> Attrs {
         self.y.to_attrs().union(self.n.to_attrs())
     }
+    /// Gets the attributes of the effects that are set.
+    #[inline]
+    pub(crate) const fn set_attrs(&self) -> Attrs { self.y.to_attrs() }
+    /// Gets the attributes of the effects that are reset.
+    #[inline]
+    pub(crate) const fn reset_attrs(&self) -> Attrs { self.n.to_attrs() }
+    /// Creates an instance from the attributes of the effects to set and to reset,
+    /// where setting an effect takes precedence over resetting it.
+    #[inline]
+    pub(crate) const fn from_attrs(set: Attrs, reset: Attrs) -> Self {
+        let y = Bits::from_attrs(set);
+        Self { y, n: Bits::from_attrs(reset).difference(y) }
+    }
     #[inline]
     const fn union(&self, other: Self) -> Self {
         Self {
@@ -296,15 +395,14 @@ impl Iterator for Iter {
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(y) = self.y.next() {
             Some(Effects { y, n: Bits::empty() })
-        } else if let Some(n) = self.n.next() {
-            Some(Effects { y: Bits::empty(), n })
         } else {
-            None
+            self.n.next().map(|n| Effects { y: Bits::empty(), n })
         }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
     use super::Effect::{Bold, Faint};
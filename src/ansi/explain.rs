@@ -0,0 +1,131 @@
+use super::{Ansi, Attrs};
+use super::spec::ATTRS;
+use std::fmt;
+
+/// Describes how a *child* [`Ansi`] style combines with its *parent* style,
+/// on a per-attribute basis.
+///
+/// Created by [`Ansi::explain()`]. Each of the attribute sets returned by this
+/// struct is disjoint from the others:
+///
+/// - [`inherited()`](Self::inherited()): set by the parent, not changed by the child.
+/// - [`overridden()`](Self::overridden()): set by both, the child's value won.
+/// - [`added()`](Self::added()): set by the child only.
+/// - [`blocked()`](Self::blocked()): set by the child, but ignored because the
+///   parent [protects](Ansi::protect_attrs()) it at the same or a higher
+///   [priority](Ansi::priority_attrs()).
+/// - [`suppressed()`](Self::suppressed()): set by the parent, but removed because
+///   the child protects it without setting it (e.g. using [`only()`](Ansi::only())),
+///   and at a higher priority, if the parent also protects it.
+///
+/// Formatting this struct with [`Display`](fmt::Display) produces one line per
+/// affected attribute, which is handy for an application's `--explain-style` flag.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, Colour::{Red, Blue}, Effect::{Bold, Italic}};
+///
+/// const PARENT: Ansi = ansi!(Red.protect(), Bold);
+/// const CHILD:  Ansi = ansi!(Blue, Italic);
+///
+/// let explanation = PARENT.explain(CHILD);
+///
+/// assert_eq!(explanation.result(),     PARENT.add(CHILD));
+/// assert_eq!(explanation.inherited(),  Attrs::Bold);
+/// assert_eq!(explanation.added(),      Attrs::Italic);
+/// assert_eq!(explanation.blocked(),    Attrs::Foreground);
+/// assert_eq!(
+///     explanation.to_string(),
+///     "bold: inherited (parent: bold)\n\
+///      italic: added (child: italic)\n\
+///      fg: blocked (parent: fg=red,!fg, child: fg=blue)\n"
+/// );
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Explanation {
+    parent:     Ansi,
+    child:      Ansi,
+    inherited:  Attrs,
+    overridden: Attrs,
+    added:      Attrs,
+    blocked:    Attrs,
+    suppressed: Attrs,
+}
+
+impl Explanation {
+    /// Gets the parent style.
+    #[inline]
+    pub const fn parent(&self) -> Ansi { self.parent }
+    /// Gets the child style.
+    #[inline]
+    pub const fn child(&self) -> Ansi { self.child }
+    /// Gets the effective style, i.e. the result of [`Ansi::add()`].
+    #[inline]
+    pub const fn result(&self) -> Ansi { self.parent.add(self.child) }
+    /// Gets the attributes set by the parent and left unchanged by the child.
+    #[inline]
+    pub const fn inherited(&self) -> Attrs { self.inherited }
+    /// Gets the attributes set by both parent and child, where the child's value won.
+    #[inline]
+    pub const fn overridden(&self) -> Attrs { self.overridden }
+    /// Gets the attributes set by the child but not the parent.
+    #[inline]
+    pub const fn added(&self) -> Attrs { self.added }
+    /// Gets the attributes set by the child that were ignored, because they are
+    /// protected by the parent at the same or a higher priority.
+    #[inline]
+    pub const fn blocked(&self) -> Attrs { self.blocked }
+    /// Gets the attributes set by the parent that were removed, because they are
+    /// protected but not set by the child.
+    #[inline]
+    pub const fn suppressed(&self) -> Attrs { self.suppressed }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (attr, name) in ATTRS {
+            let parent = self.parent.filter(attr).spec();
+            let child  = self.child.filter(attr).spec();
+            let result = self.result().filter(attr).spec();
+            if self.inherited.contains(attr) {
+                writeln!(f, "{}: inherited (parent: {})", name, parent)?;
+            } else if self.overridden.contains(attr) {
+                writeln!(f, "{}: overridden (parent: {}, child: {}, result: {})", name, parent, child, result)?;
+            } else if self.added.contains(attr) {
+                writeln!(f, "{}: added (child: {})", name, child)?;
+            } else if self.blocked.contains(attr) {
+                writeln!(f, "{}: blocked (parent: {}, child: {})", name, parent, child)?;
+            } else if self.suppressed.contains(attr) {
+                writeln!(f, "{}: suppressed (parent: {}, child: {})", name, parent, child)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Ansi {
+    /// Explains how the attributes of a `child` style combine with those of `self`,
+    /// when `self` is the parent style - i.e. how [`self.add(child)`](Self::add()) is
+    /// arrived at.
+    ///
+    /// See [`Explanation`] for details and examples.
+    pub const fn explain(&self, child: Ansi) -> Explanation {
+        // The same rules as add(), i.e. considering priority levels
+        let parent_set    = self.attrs();
+        let child_set     = child.attrs();
+        let parent_wins   = self.protect.wins_over(child.protect, true);
+        let child_wins    = child.protect.wins_over(self.protect, false);
+        let child_allowed = child_set.difference(parent_wins);
+        let suppressed    = parent_set.difference(child_set).intersection(child_wins);
+        Explanation {
+            parent:     *self,
+            child,
+            inherited:  parent_set.difference(child_set).difference(suppressed),
+            overridden: child_allowed.intersection(parent_set),
+            added:      child_allowed.difference(parent_set),
+            blocked:    child_set.intersection(parent_wins),
+            suppressed,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use super::{Ansi, Attrs, Colour, Colours, Effects};
+use super::priority::{Protection, ATTRS_LEN};
+
+/// A snapshot of the attributes of an [`Ansi`] style as plain public fields,
+/// for destructuring and pattern matching.
+///
+/// Created by [`Ansi::parts()`], and converted back with [`Ansi::from()`](From),
+/// such that `Ansi::from(ansi.parts()) == ansi` for every `Ansi`.
+///
+/// - `effects_set`: the effects that are set, e.g. [`Attrs::Bold`] for `Bold`.
+/// - `effects_reset`: the effects that are reset, e.g. [`Attrs::Bold`] for `NotBold`.
+/// - `fg`/`bg`: the foreground and background colours, which may be `Unspecified`.
+/// - `protected`: the [protected](Ansi::protect_attrs()) attributes, i.e. those
+///   marked *important*.
+/// - `priorities`: the [priority levels](Ansi::priority_attrs()) of each attribute, in the order
+///   of the [`Attrs`]' bits, i.e. from `Bold` to `Background`, or 0 for unprotected attributes.
+///
+/// Any attributes of `effects_set` and `effects_reset` other than effects are ignored when
+/// converting to `Ansi`, as are effects in both, which are then set. Likewise, protected
+/// attributes have a priority of at least 1 and at most 3, whatever their `priorities`.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, Colour::{Red, Unspecified}, Effect::{Bold, NotItalic}};
+///
+/// const STYLE: Ansi = ansi!(Red.ansi().priority(2), Bold, NotItalic);
+///
+/// match STYLE.parts() {
+///     AnsiParts { fg: Red, bg: Unspecified, effects_set, .. } if effects_set == Attrs::Bold => (),
+///     parts => panic!("unexpected: {:?}", parts),
+/// }
+///
+/// let AnsiParts { effects_reset, protected, priorities, .. } = STYLE.parts();
+/// assert_eq!(effects_reset, Attrs::Italic);
+/// assert_eq!(protected,     Attrs::Foreground);
+/// assert_eq!(priorities,    [0, 0, 0, 0, 0, 0, 0, 0, 2, 0]);
+///
+/// assert_eq!(Ansi::from(STYLE.parts()), STYLE);
+/// assert_eq!(Ansi::from(AnsiParts { bg: Red, ..Ansi::unspecified().parts() }), Red.bg());
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct AnsiParts {
+    pub effects_set:   Attrs,
+    pub effects_reset: Attrs,
+    pub fg:            Colour,
+    pub bg:            Colour,
+    pub protected:     Attrs,
+    pub priorities:    [u8; ATTRS_LEN],
+}
+
+impl AnsiParts {
+    /// Converts to the equivalent [`Ansi`], as for [`From<AnsiParts>`](Ansi::from()),
+    /// but usable in `const` contexts.
+    pub const fn to_ansi(&self) -> Ansi {
+        Ansi {
+            effect:  Effects::from_attrs(self.effects_set, self.effects_reset),
+            colour:  Colours::new(self.fg, self.bg),
+            protect: Protection::new(self.protected, self.priorities),
+        }
+    }
+}
+
+impl Ansi {
+    /// Gets the attributes of this instance as an [`AnsiParts`],
+    /// e.g. for destructuring or pattern matching.
+    ///
+    /// See [`AnsiParts`] for details and examples.
+    pub const fn parts(&self) -> AnsiParts {
+        AnsiParts {
+            effects_set:   self.effect.set_attrs(),
+            effects_reset: self.effect.reset_attrs(),
+            fg:            self.colour.fg(),
+            bg:            self.colour.bg(),
+            protected:     self.protect.attrs(),
+            priorities:    self.protect.levels(),
+        }
+    }
+}
+
+impl From<AnsiParts> for Ansi {
+    fn from(parts: AnsiParts) -> Ansi { parts.to_ansi() }
+}
+
+impl From<Ansi> for AnsiParts {
+    fn from(ansi: Ansi) -> AnsiParts { ansi.parts() }
+}
@@ -0,0 +1,162 @@
+use super::Attrs;
+use std::fmt;
+
+/// The highest priority level.
+const MAX_PRIORITY: u8 = 3;
+
+/// The number of [`Attrs`], each of which has its own priority level.
+pub(crate) const ATTRS_LEN: usize = Attrs::all().bits().count_ones() as usize;
+
+/// The lowest bit of each attribute's priority level, i.e. every attribute at level 1.
+const LEVEL_ONES: u32 = 0x55555;
+
+/// The protected [`Attrs`] of an [`Ansi`](crate::Ansi), along with their priority levels.
+///
+/// To keep `Ansi` instances small, this is packed into 32 bits: each attribute's priority
+/// level takes two bits, in the order of the [`Attrs`]' bits. Unprotected attributes have
+/// priority 0, and protected attributes a priority from 1 to 3.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub(crate) struct Protection(u32);
+
+impl Protection {
+    #[inline]
+    pub(crate) const fn empty() -> Self { Self(0) }
+    #[inline]
+    pub(crate) const fn all() -> Self { Self(LEVEL_ONES) }
+
+    /// Gets the protected attributes, i.e. those with a non-zero priority level.
+    pub(crate) const fn attrs(&self) -> Attrs {
+        let protected = (self.0 | (self.0 >> 1)) & LEVEL_ONES;
+        let mut bits = 0;
+        let mut attr = 0;
+        while attr < ATTRS_LEN {
+            bits |= (((protected >> (2 * attr)) & 1) as u16) << attr;
+            attr += 1;
+        }
+        Attrs::from_bits_retain(bits)
+    }
+    #[inline]
+    pub(crate) const fn is_empty(&self) -> bool { self.0 == 0 }
+    #[inline]
+    pub(crate) const fn is_all(&self) -> bool { self.attrs().is_all() }
+
+    /// Gets the priority level of the attribute with the given bit index.
+    #[inline]
+    const fn level(&self, attr: usize) -> u8 { ((self.0 >> (2 * attr)) & 0b11) as u8 }
+
+    /// Gets the bits of the attribute with the given bit index at the given level.
+    #[inline]
+    const fn bits(attr: usize, level: u8) -> u32 {
+        (if level > MAX_PRIORITY { MAX_PRIORITY } else { level } as u32) << (2 * attr)
+    }
+
+    /// Creates an instance from protected attributes and the levels of each attribute,
+    /// in the order of their bits. Protected attributes have a level of at least 1.
+    pub(crate) const fn new(attrs: Attrs, levels: [u8; ATTRS_LEN]) -> Self {
+        let mut bits = 0;
+        let mut attr = 0;
+        while attr < ATTRS_LEN {
+            if attrs.bits() & (1 << attr) != 0 {
+                bits |= Self::bits(attr, if levels[attr] > 1 { levels[attr] } else { 1 });
+            }
+            attr += 1;
+        }
+        Self(bits)
+    }
+
+    /// Gets the priority levels of each attribute, in the order of their bits.
+    pub(crate) const fn levels(&self) -> [u8; ATTRS_LEN] {
+        let mut levels = [0; ATTRS_LEN];
+        let mut attr = 0;
+        while attr < ATTRS_LEN {
+            levels[attr] = self.level(attr);
+            attr += 1;
+        }
+        levels
+    }
+
+    /// Gets the highest priority level of the given attributes.
+    pub(crate) const fn priority(&self, attrs: Attrs) -> u8 {
+        let mut priority = 0;
+        let mut attr = 0;
+        while attr < ATTRS_LEN {
+            if attrs.bits() & (1 << attr) != 0 && self.level(attr) > priority {
+                priority = self.level(attr);
+            }
+            attr += 1;
+        }
+        priority
+    }
+
+    /// Gets the mask of the priority levels of the given attributes.
+    const fn mask(attrs: Attrs) -> u32 {
+        let mut mask = 0;
+        let mut attr = 0;
+        while attr < ATTRS_LEN {
+            if attrs.bits() & (1 << attr) != 0 {
+                mask |= Self::bits(attr, MAX_PRIORITY);
+            }
+            attr += 1;
+        }
+        mask
+    }
+
+    /// Protects the given attributes, keeping the priority levels of those already protected.
+    #[inline]
+    pub(crate) const fn union(&self, attrs: Attrs) -> Self {
+        Self(self.0 | (LEVEL_ONES & Self::mask(attrs.difference(self.attrs()))))
+    }
+    /// Unprotects the given attributes.
+    #[inline]
+    pub(crate) const fn difference(&self, attrs: Attrs) -> Self { Self(self.0 & !Self::mask(attrs)) }
+    /// Unprotects all but the given attributes.
+    #[inline]
+    pub(crate) const fn intersection(&self, attrs: Attrs) -> Self { Self(self.0 & Self::mask(attrs)) }
+
+    /// Protects the given attributes at the given priority level.
+    /// Level zero unprotects the attributes instead.
+    pub(crate) const fn with_priority(&self, attrs: Attrs, level: u8) -> Self {
+        let mut bits = self.difference(attrs).0;
+        let mut attr = 0;
+        while attr < ATTRS_LEN {
+            if attrs.bits() & (1 << attr) != 0 {
+                bits |= Self::bits(attr, level);
+            }
+            attr += 1;
+        }
+        Self(bits)
+    }
+
+    /// Gets the attributes whose priority is higher in `self` than in `other`, or,
+    /// if `or_equal`, whose protection is at least as high (and non-zero).
+    pub(crate) const fn wins_over(&self, other: Protection, or_equal: bool) -> Attrs {
+        let mut wins = 0;
+        let mut attr = 0;
+        while attr < ATTRS_LEN {
+            let (level, other_level) = (self.level(attr), other.level(attr));
+            if level > other_level || (or_equal && level > 0 && level == other_level) {
+                wins |= 1 << attr;
+            }
+            attr += 1;
+        }
+        Attrs::from_bits_retain(wins)
+    }
+
+    /// Combines the protection of two instances, using the higher level of each attribute.
+    pub(crate) const fn max(&self, other: Protection) -> Self {
+        let mut bits = 0;
+        let mut attr = 0;
+        while attr < ATTRS_LEN {
+            let (level, other_level) = (self.level(attr), other.level(attr));
+            bits |= Self::bits(attr, if level > other_level { level } else { other_level });
+            attr += 1;
+        }
+        Self(bits)
+    }
+}
+
+impl fmt::Debug for Protection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Protection").field("attrs", &self.attrs()).field("levels", &self.levels()).finish()
+    }
+}
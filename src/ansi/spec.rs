@@ -0,0 +1,406 @@
+use super::{Ansi, Attrs, Colour, Effect};
+use crate::text::{parse_sgr, segments, Segment};
+use std::{error, fmt};
+
+const EFFECTS: [Effect; 16] = [
+    Effect::Bold,     Effect::Faint,      Effect::Italic,    Effect::Underline,
+    Effect::Blink,    Effect::Reverse,    Effect::Hidden,    Effect::Strike,
+    Effect::NotBold,  Effect::NotFaint,   Effect::NotItalic, Effect::NotUnderline,
+    Effect::NotBlink, Effect::NotReverse, Effect::NotHidden, Effect::NotStrike,
+];
+
+pub(super) const ATTRS: [(Attrs, &str); 10] = [
+    (Attrs::Bold,       "bold"),
+    (Attrs::Faint,      "faint"),
+    (Attrs::Italic,     "italic"),
+    (Attrs::Underline,  "underline"),
+    (Attrs::Blink,      "blink"),
+    (Attrs::Reverse,    "reverse"),
+    (Attrs::Hidden,     "hidden"),
+    (Attrs::Strike,     "strike"),
+    (Attrs::Foreground, "fg"),
+    (Attrs::Background, "bg"),
+];
+
+/// A [`Display`](fmt::Display) adapter that renders an [`Ansi`] as a compact,
+/// machine-parsable *spec* string, e.g. `"bold,italic,fg=red,bg=reset,!fg"`.
+///
+/// Created by [`Ansi::spec()`]. The result can be turned back into an
+/// identical [`Ansi`] using [`Ansi::from_spec()`].
+///
+/// The spec is a comma-separated list of tokens:
+///
+/// | Token                            | Meaning                                           |
+/// |----------------------------------|---------------------------------------------------|
+/// | `reset`                          | [`Ansi::reset()`]                                  |
+/// | `bold`, `italic`, ...            | [`Effect::Bold`], [`Effect::Italic`], ...          |
+/// | `not_bold`, `not_italic`, ...    | [`Effect::NotBold`], [`Effect::NotItalic`], ...    |
+/// | `fg=red`, `bg=bright_blue`, ...  | Foreground/background [`Colour`]                   |
+/// | `fg=128`                         | [`Colour::Ansi256`] (*`feature=ansi256`*)          |
+/// | `fg=#ff8800`, `fg=#f80`          | [`Colour::Rgb`] (*`feature=rgb`*)                  |
+/// | `!bold`, `!fg`, `!bg`, ...       | [Protected](Ansi::protect_attrs()) attribute       |
+/// | `!all`                           | All attributes protected, i.e. [`Ansi::only()`]   |
+/// | `!fg=2`, `!all=3`, ...           | Protected at a [priority](Ansi::priority_attrs()) level above 1 |
+///
+/// Each attribute has its own priority level, so the last level given for it applies.
+/// An [`unspecified`](Ansi::unspecified()) `Ansi` is rendered as an empty string.
+///
+/// Formatting a `Spec` does not allocate.
+#[derive(Clone, Copy, Debug)]
+pub struct Spec(Ansi);
+
+impl fmt::Display for Spec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ansi = self.0;
+        let mut sep = "";
+        let mut token = |f: &mut fmt::Formatter<'_>, args: fmt::Arguments<'_>| -> fmt::Result {
+            f.write_str(sep)?;
+            sep = ",";
+            f.write_fmt(args)
+        };
+        if ansi.is_reset() {
+            token(f, format_args!("reset"))?;
+        } else {
+            for effect in EFFECTS {
+                if ansi.effect().has_effect(effect) {
+                    token(f, format_args!("{}", effect.name().unwrap_or_default()))?;
+                }
+            }
+            for (attr, colour) in [("fg", ansi.colour().fg()), ("bg", ansi.colour().bg())] {
+                if !colour.is_unspecified() {
+                    token(f, format_args!("{}={}", attr, ColourSpec(colour)))?;
+                }
+            }
+        }
+        let level = ansi.priority_of(Attrs::all());
+        if ansi.is_only() && ATTRS.iter().all(|(attr, _)| ansi.priority_of(*attr) == level) {
+            token(f, format_args!("!all{}", Level(level)))?;
+        } else {
+            for (attr, name) in ATTRS {
+                if ansi.protected_attrs().contains(attr) {
+                    token(f, format_args!("!{}{}", name, Level(ansi.priority_of(attr))))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats a priority level as a spec token's `=level` suffix, which is omitted for level 1.
+struct Level(u8);
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 > 1 { write!(f, "={}", self.0) } else { Ok(()) }
+    }
+}
+
+pub(crate) struct ColourSpec(pub(crate) Colour);
+
+impl fmt::Display for ColourSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            #[cfg(feature="ansi256")]
+            Colour::Ansi256(num) => write!(f, "{}", num),
+            #[cfg(feature="rgb")]
+            Colour::Rgb(r,g,b)   => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+            colour               => f.write_str(colour.name().unwrap_or_default()),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`Ansi`].
+///
+/// See [`Ansi::from_spec()`] and [`Ansi::parse_sgr()`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseAnsiError { token: String }
+
+impl ParseAnsiError {
+    #[inline]
+    pub(crate) fn new(token: &str) -> Self { Self { token: token.into() } }
+
+    /// Gets the token that could not be parsed.
+    #[inline]
+    pub fn token(&self) -> &str { &self.token }
+}
+
+impl fmt::Display for ParseAnsiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid ANSI style token: `{}`", self.token)
+    }
+}
+
+impl error::Error for ParseAnsiError {}
+
+impl Ansi {
+    /// Creates a [`Spec`] that formats this instance as a compact, machine-parsable
+    /// string, suitable for logging or persisting styles.
+    ///
+    /// With `feature=serde`, `Ansi` instances are serialized as this string, e.g. in
+    /// config files, and deserialized from it or from a [style string](Self::from_style_str()),
+    /// as are [`Colour`]s and [`Effect`]s as their names, e.g. `"bright_red"`.
+    /// [`Styled<T>`](crate::Styled)s are serialized as a struct with fields `style` and
+    /// `text`, and [`Theme`](crate::theme::Theme)s as a struct with a field per slot.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::{Red, Blue}, Effect::{Bold, Italic}};
+    ///
+    /// const STYLE: Ansi = ansi!(Bold, Italic, Red, Blue.bg()).protect_attrs(Attrs::Foreground);
+    ///
+    /// assert_eq!(STYLE.spec().to_string(), "bold,italic,fg=red,bg=blue,!fg");
+    /// assert_eq!(Ansi::from_spec("bold,italic,fg=red,bg=blue,!fg"), Ok(STYLE));
+    /// ```
+    #[inline]
+    pub const fn spec(&self) -> Spec { Spec(*self) }
+
+    /// Parses a string produced by [`spec()`](Self::spec()) back into an `Ansi`.
+    ///
+    /// Tokens may appear in any order and are matched case-insensitively.
+    /// Whitespace around tokens is ignored. See [`Spec`] for the list of tokens.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::Red, Effect::Bold};
+    ///
+    /// assert_eq!(Ansi::from_spec("fg=red, bold"),   Ok(ansi!(Red, Bold)));
+    /// assert_eq!(Ansi::from_spec("reset"),          Ok(Ansi::reset()));
+    /// assert_eq!(Ansi::from_spec(""),               Ok(Ansi::unspecified()));
+    /// assert_eq!(Ansi::from_spec("!all"),           Ok(Ansi::no_ansi()));
+    /// assert_eq!(Ansi::from_spec("fg=red,!fg=3"),   Ok(ansi!(Red).priority(3)));
+    /// assert_eq!(Ansi::from_spec("bold,fg=pink").unwrap_err().token(), "fg=pink");
+    /// ```
+    pub fn from_spec(spec: &str) -> Result<Ansi, ParseAnsiError> {
+        let mut ansi    = Ansi::unspecified();
+        // Protection is applied last, so that it doesn't filter the attributes added
+        let mut protect = Ansi::unspecified();
+        for token in spec.split(',').map(str::trim).filter(|token| !token.is_empty()) {
+            let error = || ParseAnsiError::new(token);
+            if token.eq_ignore_ascii_case("reset") {
+                ansi = ansi.add(Ansi::reset());
+            } else if let Some(name) = token.strip_prefix('!') {
+                let (name, level) = match name.split_once('=') {
+                    Some((name, level)) => (name.trim(), level.trim().parse().ok().filter(|level| *level > 0).ok_or_else(error)?),
+                    None                => (name, 1),
+                };
+                let attrs = if name.eq_ignore_ascii_case("all") {
+                    Attrs::all()
+                } else {
+                    ATTRS.iter().find(|(_, n)| n.eq_ignore_ascii_case(name)).ok_or_else(error)?.0
+                };
+                protect = protect.priority_attrs(attrs, level);
+            } else if let Some((attr, value)) = token.split_once('=') {
+                let value = value.trim();
+                let colour = style_colour(value.as_bytes(), 0, value.len()).ok_or_else(error)?;
+                match attr.trim() {
+                    attr if attr.eq_ignore_ascii_case("fg") => ansi = ansi.add(colour.fg()),
+                    attr if attr.eq_ignore_ascii_case("bg") => ansi = ansi.add(colour.bg()),
+                    _                                       => return Err(error()),
+                }
+            } else {
+                let effect = Effect::by_name(token).ok_or_else(error)?;
+                ansi = ansi.add(effect.ansi());
+            }
+        }
+        Ok(ATTRS.iter().fold(ansi, |ansi, (attr, _)| {
+            if protect.protected_attrs().contains(*attr) { ansi.priority_attrs(*attr, protect.priority_of(*attr)) } else { ansi }
+        }))
+    }
+
+    /// Parses one or more SGR escape sequences, e.g. `"\x1B[1;31m"`, into an `Ansi`, i.e. the
+    /// style that formats as the same ANSI codes. Later sequences take precedence over earlier ones.
+    ///
+    /// Reset codes become `Reset` attributes, e.g. `"\x1B[39m"` becomes [`Colour::Reset`]
+    /// in the foreground, and `"\x1B[0m"` becomes [`Ansi::reset()`]. Unrecognized parameters
+    /// are ignored, but anything other than SGR escape sequences is an error.
+    ///
+    /// To split a string containing both text and SGR sequences into styled spans,
+    /// see [`text::spans()`](crate::text::spans()).
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::{Blue, Red}, Effect::{Bold, NotItalic}};
+    ///
+    /// assert_eq!(Ansi::parse_sgr("\x1B[1;31m"),          Ok(ansi!(Bold, Red)));
+    /// assert_eq!(Ansi::parse_sgr("\x1B[31m\x1B[23;44m"), Ok(ansi!(Red, NotItalic, Blue.bg())));
+    /// assert_eq!(Ansi::parse_sgr("\x1B[0m"),             Ok(Ansi::reset()));
+    /// assert_eq!(Ansi::parse_sgr(""),                    Ok(Ansi::unspecified()));
+    /// assert_eq!(Ansi::parse_sgr("\x1B[1mBold").unwrap_err().token(), "Bold");
+    /// ```
+    pub fn parse_sgr(s: &str) -> Result<Ansi, ParseAnsiError> {
+        let mut ansi = Ansi::unspecified();
+        for segment in segments(s) {
+            let sgr = match segment {
+                Segment::Escape(escape) => parse_sgr(escape).ok_or_else(|| ParseAnsiError::new(escape))?,
+                Segment::Text(text)     => return Err(ParseAnsiError::new(text)),
+            };
+            let changed = sgr.reset.union(sgr.set.attrs());
+            ansi = ansi.filter(Attrs::all().difference(changed)).add(Ansi::reset().filter(sgr.reset)).add(sgr.set);
+        }
+        Ok(ansi)
+    }
+
+    /// Parses a *style string*, i.e. whitespace-separated names of effects and colours,
+    /// e.g. `"bold underline bright_red on_black"`, as commonly used in config files
+    /// and env variables. Names are matched case-insensitively.
+    ///
+    /// Each token is one of:
+    ///
+    /// - An [`Effect`] name, e.g. `bold` or `not_italic`, see [`Effect::name()`].
+    /// - A [`Colour`] name, e.g. `red`, for the foreground colour, see [`Colour::name()`].
+    /// - A colour name prefixed with `on_` or `on `, e.g. `on_blue` or `on blue`, for the background colour.
+    /// - `reset`, for [`Ansi::reset()`].
+    ///
+    /// With `feature=ansi256`, a colour may also be an 8-bit colour number, e.g. `196`,
+    /// and with `feature=rgb`, a hex colour, e.g. `#ff8800` or `on_#0af`.
+    ///
+    /// Later tokens take precedence over earlier ones. To parse a style string at
+    /// compile time, use [`ansi_style!`](crate::ansi_style).
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::{Black, BrightRed}, Effect::{Bold, Underline}};
+    ///
+    /// assert_eq!(Ansi::from_style_str("bold underline bright_red on_black"), Ok(ansi!(Bold, Underline, BrightRed, Black.bg())));
+    /// assert_eq!(Ansi::from_style_str("  Bold  "),                           Ok(ansi!(Bold)));
+    /// assert_eq!(Ansi::from_style_str(""),                                   Ok(Ansi::unspecified()));
+    /// assert_eq!(Ansi::from_style_str("bold on_pink").unwrap_err().token(),  "on_pink");
+    /// ```
+    pub fn from_style_str(s: &str) -> Result<Ansi, ParseAnsiError> {
+        Self::parse_style_str(s).map_err(|(start, end)| ParseAnsiError::new(&s[start..end]))
+    }
+
+    /// Parses a style string like [`from_style_str()`](Self::from_style_str()), except
+    /// that an invalid token is returned as its start and end index. Used by [`ansi_style!`](crate::ansi_style).
+    #[doc(hidden)]
+    pub const fn parse_style_str(s: &str) -> Result<Ansi, (usize, usize)> {
+        let bytes = s.as_bytes();
+        let mut ansi = Ansi::unspecified();
+        let mut i = 0;
+        loop {
+            let (start, end) = next_token(bytes, i);
+            if start == end {
+                break;
+            }
+            i = end;
+            // A background colour may also be given as two tokens, e.g. `on blue`
+            if token_eq(bytes, start, end, "on") {
+                let (colour_start, colour_end) = next_token(bytes, end);
+                if colour_start == colour_end {
+                    return Err((start, end));
+                }
+                i = colour_end;
+                match style_colour(bytes, colour_start, colour_end) {
+                    Some(colour) => ansi = ansi.add(colour.bg()),
+                    None         => return Err((colour_start, colour_end)),
+                }
+                continue;
+            }
+            match style_token(bytes, start, end) {
+                Some(token) => ansi = ansi.add(token),
+                None        => return Err((start, end)),
+            }
+        }
+        Ok(ansi)
+    }
+}
+
+/// Gets the start and end index of the next whitespace-separated token of `bytes`
+/// at or after index `i`, which are equal if there is none.
+const fn next_token(bytes: &[u8], mut i: usize) -> (usize, usize) {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    let start = i;
+    while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    (start, i)
+}
+
+/// Determines whether `bytes[start..end]` equals `name`, ignoring ASCII case.
+const fn token_eq(bytes: &[u8], start: usize, end: usize, name: &str) -> bool {
+    let name = name.as_bytes();
+    if end - start != name.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < name.len() {
+        if !bytes[start + i].eq_ignore_ascii_case(&name[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Parses `bytes[start..end]` as a token of a style string.
+const fn style_token(bytes: &[u8], start: usize, end: usize) -> Option<Ansi> {
+    if token_eq(bytes, start, end, "reset") {
+        return Some(Ansi::reset());
+    }
+    let effects = Effect::all();
+    let mut i = 0;
+    while i < effects.len() {
+        if let Some(name) = effects[i].name() {
+            if token_eq(bytes, start, end, name) {
+                return Some(effects[i].ansi());
+            }
+        }
+        i += 1;
+    }
+    if end - start > 3 && token_eq(bytes, start, start + 3, "on_") {
+        return match style_colour(bytes, start + 3, end) {
+            Some(colour) => Some(colour.bg()),
+            None         => None,
+        };
+    }
+    match style_colour(bytes, start, end) {
+        Some(colour) => Some(colour.fg()),
+        None         => None,
+    }
+}
+
+/// Parses `bytes[start..end]` as a colour of a style string or spec.
+pub(crate) const fn style_colour(bytes: &[u8], start: usize, end: usize) -> Option<Colour> {
+    if start >= end {
+        return None;
+    }
+    #[cfg(feature="rgb")]
+    if bytes[start] == b'#' {
+        let token = bytes.split_at(end).0.split_at(start).1;
+        return match std::str::from_utf8(token) {
+            Ok(hex) => Colour::from_hex(hex),
+            Err(_)  => None,
+        };
+    }
+    #[cfg(feature="ansi256")]
+    if end - start <= 3 {
+        let (mut num, mut i) = (0u16, start);
+        while i < end && bytes[i].is_ascii_digit() {
+            num = num * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+        if i == end && num <= 255 {
+            return Some(Colour::Ansi256(num as u8));
+        }
+    }
+    let colours = Colour::all16();
+    let mut i = 0;
+    while i < colours.len() {
+        if let Some(name) = colours[i].name() {
+            if token_eq(bytes, start, end, name) {
+                return Some(colours[i]);
+            }
+        }
+        i += 1;
+    }
+    if token_eq(bytes, start, end, "reset") {
+        return Some(Colour::Reset);
+    }
+    None
+}
@@ -0,0 +1,292 @@
+//! Render classic ANSI art, i.e. `.ans` files.
+//!
+//! ANSI art files are encoded in the [CP437](https://en.wikipedia.org/wiki/Code_page_437)
+//! character set, and draw on an (usually 80-column) DOS terminal using SGR sequences
+//! for colours and cursor-movement sequences for positioning. An [`AnsiArt`] decodes such
+//! a file and replays its escape sequences onto a grid of cells, which can then be
+//! formatted like any other [`Display`](fmt::Display) target, e.g. with [`paint!`](crate::paint).
+//!
+//! The art is written as a [`StyledBatch`] of runs of cells with the same style, so it is
+//! rendered through this crate's usual machinery, i.e. it nests inside parent styles, and
+//! respects [`Ansi::no_ansi()`], [style maps](crate::map) and [style budgets](crate::io::AnsiWrite::set_style_budget()).
+//!
+//! Additionally, the art's 16 colours can be remapped with a [`palette()`](AnsiArt::palette()),
+//! and its width can be clamped with [`max_width()`](AnsiArt::max_width()).
+//!
+//! *Note:* ANSI art usually relies on DOS terminals rendering [`Bold`](crate::Effect::Bold)
+//! colours as bright. To reproduce this, write the art with the
+//! [`bold_as_bright()`](crate::map::bold_as_bright()) style map.
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{art::AnsiArt, Colour};
+//!
+//! // "\xDB" is a full block in CP437
+//! let art = AnsiArt::from_cp437(b"\x1B[31m\xDB\xDB\x1B[0m Hi\r\n\x1B[3C\x1B[44m\xDB");
+//!
+//! assert_eq!((art.width(), art.height()), (5, 2));
+//! assert_eq!(art.to_string(), "\x1B[31m██\x1B[39m Hi\n   \x1B[44m█\x1B[49m\n");
+//!
+//! let art = art.palette(AnsiArt::DOS_PALETTE.map(|c| if c == Colour::Red { Colour::Green } else { c }));
+//! assert_eq!(art.max_width(2).to_string(), "\x1B[32m██\x1B[39m\n\n");
+//! ```
+
+use crate::ansi::Colours;
+use crate::text::{self, parse_sgr, Segment, COLOURS};
+use crate::{Ansi, Colour, StyledBatch};
+use std::fmt;
+use std::{fs, io, path::Path};
+
+/// The characters for CP437 bytes `0x01..=0x1F`, which are drawn as glyphs in ANSI art.
+const CP437_LOW: &str = "☺☻♥♦♣♠•◘○◙♂♀♪♫☼►◄↕‼¶§▬↨↑↓→←∟↔▲▼";
+
+/// The characters for CP437 bytes `0x80..=0xFF`.
+const CP437_HIGH: &str = concat!(
+    "ÇüéâäàåçêëèïîìÄÅ", "ÉæÆôöòûùÿÖÜ¢£¥₧ƒ", "áíóúñÑªº¿⌐¬½¼¡«»", "░▒▓│┤╡╢╖╕╣║╗╝╜╛┐",
+    "└┴┬├─┼╞╟╚╔╩╦╠═╬╧", "╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀", "αßΓπΣσµτΦΘΩδ∞φε∩", "≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{A0}",
+);
+
+/// The byte that marks the end of the art, before any SAUCE metadata.
+const EOF: u8 = 0x1A;
+
+/// Decodes CP437 `bytes` as a `String`.
+///
+/// Control bytes are drawn as their CP437 glyphs, except for `\t`, `\n`, `\r`,
+/// `ESC` (so that escape sequences are preserved) and `NUL`.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::art::decode_cp437;
+///
+/// assert_eq!(decode_cp437(b"\xDA\xC4\xBF \x03\r\n"), "┌─┐ ♥\r\n");
+/// ```
+pub fn decode_cp437(bytes: &[u8]) -> String {
+    let low:  Vec<char> = CP437_LOW.chars().collect();
+    let high: Vec<char> = CP437_HIGH.chars().collect();
+    bytes.iter().map(|&b| match b {
+        b'\t' | b'\n' | b'\r' | 0x1B | 0x00 => b as char,
+        0x01..=0x1F                         => low[b as usize - 0x01],
+        0x7F                                => '⌂',
+        0x80..=0xFF                         => high[b as usize - 0x80],
+        _                                   => b as char,
+    }).collect()
+}
+
+/// A single character of ANSI art, and its style.
+type Cell = (char, Ansi);
+
+const BLANK: Cell = (' ', Ansi::unspecified());
+
+/// ANSI art, decoded onto a grid of styled cells.
+///
+/// See the [module-level documentation](crate::art).
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct AnsiArt {
+    rows:      Vec<Vec<Cell>>,
+    palette:   Option<[Colour; 16]>,
+    max_width: Option<usize>,
+}
+
+impl AnsiArt {
+    /// The number of columns of a DOS terminal, at which ANSI art wraps by default.
+    pub const COLUMNS: usize = 80;
+
+    /// The 16 colours in the order of their SGR codes, i.e. the identity
+    /// [`palette()`](AnsiArt::palette()).
+    pub const DOS_PALETTE: [Colour; 16] = COLOURS;
+
+    /// Decodes ANSI art from CP437 `bytes`, e.g. the contents of a `.ans` file.
+    ///
+    /// The art ends at the first `0x1A` byte, if any, or else at its SAUCE metadata. If
+    /// the SAUCE metadata gives the art's width, the art wraps at that many columns,
+    /// and otherwise at [`COLUMNS`](AnsiArt::COLUMNS).
+    pub fn from_cp437(bytes: &[u8]) -> Self {
+        let (content, columns) = split_sauce(bytes);
+        Self::parse(&decode_cp437(content), columns.unwrap_or(Self::COLUMNS))
+    }
+
+    /// Decodes ANSI art from text that is already decoded, e.g. UTF-8 art, wrapping
+    /// at the given number of `columns`.
+    pub fn from_text(s: &str, columns: usize) -> Self {
+        Self::parse(s, columns)
+    }
+
+    /// Reads and decodes an `.ans` file, see [`from_cp437()`](AnsiArt::from_cp437()).
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read(path).map(|bytes| Self::from_cp437(&bytes))
+    }
+
+    /// Remaps the art's 16 colours, i.e. [`DOS_PALETTE`](AnsiArt::DOS_PALETTE)`[i]`
+    /// is rendered as `palette[i]`, for both foreground and background colours.
+    pub fn palette(self, palette: [Colour; 16]) -> Self { Self { palette: Some(palette), ..self } }
+
+    /// Clamps the rendered width to at most `max_width` columns, truncating longer rows.
+    pub fn max_width(self, max_width: usize) -> Self { Self { max_width: Some(max_width), ..self } }
+
+    /// Gets the width in columns, i.e. the longest row, ignoring
+    /// [`max_width()`](AnsiArt::max_width()).
+    pub fn width(&self) -> usize { self.rows.iter().map(Vec::len).max().unwrap_or(0) }
+
+    /// Gets the height in rows.
+    pub fn height(&self) -> usize { self.rows.len() }
+
+    fn parse(s: &str, columns: usize) -> Self {
+        let mut canvas = Canvas { rows: Vec::new(), row: 0, col: 0, saved: (0, 0), ansi: Ansi::unspecified(), columns: columns.max(1) };
+        for segment in text::segments(s) {
+            match segment {
+                Segment::Text(text)     => text.chars().for_each(|c| canvas.put(c)),
+                Segment::Escape(escape) => canvas.escape(escape),
+            }
+        }
+        Self { rows: canvas.rows, palette: None, max_width: None }
+    }
+
+    fn remap(&self, ansi: Ansi) -> Ansi {
+        let Some(palette) = &self.palette else { return ansi };
+        let remap = |colour: Colour| match COLOURS.iter().position(|c| *c == colour) {
+            Some(i) => palette[i],
+            None    => colour,
+        };
+        let colour = ansi.colour();
+        ansi.with_colour(Colours::new(remap(colour.fg()), remap(colour.bg())))
+    }
+}
+
+impl fmt::Display for AnsiArt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Collect the runs of cells with the same style into one buffer, so that
+        // they can be written as a single batch with minimal transitions
+        let mut text = String::new();
+        let mut runs = Vec::new();
+        for row in &self.rows {
+            let row = &row[..self.max_width.map_or(row.len(), |max| row.len().min(max))];
+            // Trailing blanks are invisible, unless they have a background colour
+            let len = row.iter().rposition(|(c, ansi)| *c != ' ' || !is_invisible(*ansi)).map_or(0, |i| i + 1);
+            for (i, (c, ansi)) in row[..len].iter().enumerate() {
+                if i == 0 || row[i - 1].1 != *ansi {
+                    runs.push((self.remap(*ansi), text.len()));
+                }
+                text.push(*c);
+            }
+            runs.push((Ansi::unspecified(), text.len()));
+            text.push('\n');
+        }
+        let ends = runs.iter().skip(1).map(|(_, start)| *start).chain([text.len()]);
+        let fragments: Vec<_> = runs.iter().zip(ends)
+            .map(|((ansi, start), end)| (*ansi, &text[*start..end]))
+            .collect();
+        write!(f, "{}", StyledBatch::new(&fragments))
+    }
+}
+
+/// Determines whether a space in the given style would be invisible.
+fn is_invisible(ansi: Ansi) -> bool {
+    let bg = ansi.colour().bg();
+    (bg.is_unspecified() || bg.is_reset()) && !ansi.effect().has_effect(crate::Effect::Reverse)
+}
+
+/// The state of the virtual terminal onto which ANSI art is drawn.
+struct Canvas {
+    rows:    Vec<Vec<Cell>>,
+    row:     usize,
+    col:     usize,
+    saved:   (usize, usize),
+    ansi:    Ansi,
+    columns: usize,
+}
+
+impl Canvas {
+    fn put(&mut self, c: char) {
+        match c {
+            '\r'   => self.col = 0,
+            '\n'   => { self.row += 1; self.col = 0; self.touch(); },
+            '\t'   => self.col = ((self.col / 8 + 1) * 8).min(self.columns - 1),
+            '\x00' => {},
+            c      => {
+                // Wrap only when writing past the last column, so that a
+                // full-width row followed by a newline is not a blank row
+                if self.col >= self.columns {
+                    self.row += 1;
+                    self.col = 0;
+                }
+                self.touch();
+                let row = &mut self.rows[self.row];
+                if row.len() <= self.col {
+                    row.resize(self.col + 1, BLANK);
+                }
+                row[self.col] = (c, self.ansi);
+                self.col += 1;
+            },
+        }
+    }
+
+    /// Ensures that the current row exists.
+    fn touch(&mut self) {
+        if self.rows.len() <= self.row {
+            self.rows.resize(self.row + 1, Vec::new());
+        }
+    }
+
+    fn escape(&mut self, escape: &str) {
+        if let Some(sgr) = parse_sgr(escape) {
+            self.ansi = sgr.apply(self.ansi);
+            return;
+        }
+        let Some(csi) = escape.strip_prefix("\x1B[") else { return };
+        let Some(command) = csi.chars().last() else { return };
+        let mut params = csi[..csi.len() - command.len_utf8()].split(';').map(|p| p.parse::<usize>().ok());
+        let mut param = |default: usize| params.next().flatten().filter(|n| *n > 0).unwrap_or(default);
+        match command {
+            'A'       => self.row = self.row.saturating_sub(param(1)),
+            'B'       => { self.row += param(1); self.touch(); },
+            'C'       => self.col = (self.col + param(1)).min(self.columns),
+            'D'       => self.col = self.col.min(self.columns - 1).saturating_sub(param(1)),
+            'H' | 'f' => {
+                self.row = param(1) - 1;
+                self.col = (param(1) - 1).min(self.columns - 1);
+                self.touch();
+            },
+            'J' if param(0) == 2 => {
+                self.rows.clear();
+                (self.row, self.col) = (0, 0);
+            },
+            'K'       => if let Some(row) = self.rows.get_mut(self.row) {
+                row.truncate(self.col);
+            },
+            's'       => self.saved = (self.row, self.col),
+            'u'       => (self.row, self.col) = self.saved,
+            _         => {},
+        }
+    }
+}
+
+/// Splits `bytes` into the art's content and the width given by its SAUCE
+/// metadata, if any. See <https://www.acid.org/info/sauce/sauce.htm>.
+fn split_sauce(bytes: &[u8]) -> (&[u8], Option<usize>) {
+    let sauce = bytes.len().checked_sub(128)
+        .map(|start| &bytes[start..])
+        .filter(|sauce| sauce.starts_with(b"SAUCE00"));
+    // DataType 1 is character-based, and FileType 0-2 are ASCII, ANSi and ANSiMation
+    let columns = sauce
+        .filter(|sauce| sauce[94] == 1 && sauce[95] <= 2)
+        .map(|sauce| u16::from_le_bytes([sauce[96], sauce[97]]) as usize)
+        .filter(|columns| *columns > 0);
+    let end = match bytes.iter().position(|b| *b == EOF) {
+        Some(end) => end,
+        None      => bytes.len() - sauce.map_or(0, |sauce| 128 + comments_len(bytes, sauce[104])),
+    };
+    (&bytes[..end], columns)
+}
+
+/// Gets the length of the SAUCE comment block with the given number of
+/// comment lines, if present before the SAUCE record.
+fn comments_len(bytes: &[u8], comments: u8) -> usize {
+    let len = 5 + 64 * comments as usize;
+    let start = bytes.len().checked_sub(128 + len);
+    match start {
+        Some(start) if comments > 0 && bytes[start..].starts_with(b"COMNT") => len,
+        _                                                                   => 0,
+    }
+}
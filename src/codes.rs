@@ -0,0 +1,168 @@
+//! Machine-readable tables of the SGR parameters written by this crate.
+//!
+//! These tables enumerate every [`Effect`] and [`Colour`] along with the SGR parameters
+//! that this crate writes for them, so that external code generators (e.g. for other
+//! languages, or for documentation) can stay in sync with this crate's behaviour.
+//! They are available as `const`s, and as JSON via [`to_json()`].
+//!
+//! The names used are the same as those used by [`Ansi::spec()`](crate::Ansi::spec()).
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{codes, Colour, Effect};
+//!
+//! let bold = &codes::EFFECTS[0];
+//! assert_eq!((bold.effect(), bold.name(), bold.set(), bold.reset()), (Effect::Bold, "bold", 1, 22));
+//!
+//! let red = &codes::COLOURS[1];
+//! assert_eq!((red.colour(), red.name(), red.fg(), red.bg()), (Colour::Red, "red", 31, 41));
+//!
+//! assert!(codes::to_json().starts_with(r#"{"reset":0,"#));
+//! ```
+
+use crate::{Colour, Effect};
+use std::fmt::Write;
+
+/// The SGR parameter that resets all attributes.
+pub const RESET: u8 = 0;
+/// The SGR parameter that resets the foreground colour.
+pub const FG_RESET: u8 = 39;
+/// The SGR parameter that resets the background colour.
+pub const BG_RESET: u8 = 49;
+/// The SGR parameter that introduces an extended foreground colour, i.e. `38;5;n` or `38;2;r;g;b`.
+pub const FG_EXTENDED: u8 = 38;
+/// The SGR parameter that introduces an extended background colour, i.e. `48;5;n` or `48;2;r;g;b`.
+pub const BG_EXTENDED: u8 = 48;
+/// The parameter following [`FG_EXTENDED`] or [`BG_EXTENDED`] for an
+/// 8-bit colour, e.g. [`Colour::Ansi256`].
+pub const EXTENDED_ANSI256: u8 = 5;
+/// The parameter following [`FG_EXTENDED`] or [`BG_EXTENDED`] for a
+/// 24-bit colour, e.g. [`Colour::Rgb`].
+pub const EXTENDED_RGB: u8 = 2;
+
+/// The SGR parameters for an [`Effect`] and its opposite, e.g. [`Effect::Bold`]
+/// and [`Effect::NotBold`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct EffectCode {
+    effect: Effect,
+    name:   &'static str,
+    set:    u8,
+    reset:  u8,
+}
+
+impl EffectCode {
+    const fn new(effect: Effect, name: &'static str, set: u8, reset: u8) -> Self {
+        Self { effect, name, set, reset }
+    }
+    /// Gets the effect, e.g. [`Effect::Bold`].
+    #[inline]
+    pub const fn effect(&self) -> Effect { self.effect }
+    /// Gets the effect's opposite, e.g. [`Effect::NotBold`].
+    #[inline]
+    pub const fn not_effect(&self) -> Effect { self.effect.not() }
+    /// Gets the effect's name, e.g. `"bold"`.
+    #[inline]
+    pub const fn name(&self) -> &'static str { self.name }
+    /// Gets the SGR parameter that sets the effect, e.g. `1`.
+    #[inline]
+    pub const fn set(&self) -> u8 { self.set }
+    /// Gets the SGR parameter that resets the effect, e.g. `22`.
+    ///
+    /// Note: the reset parameter `22` is shared by [`Effect::Bold`] and [`Effect::Faint`].
+    #[inline]
+    pub const fn reset(&self) -> u8 { self.reset }
+}
+
+/// The SGR parameters for a [`Colour`] used as foreground or background colour.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ColourCode {
+    colour: Colour,
+    name:   &'static str,
+    fg:     u8,
+    bg:     u8,
+}
+
+impl ColourCode {
+    const fn new(colour: Colour, name: &'static str, fg: u8) -> Self {
+        Self { colour, name, fg, bg: fg + 10 }
+    }
+    /// Gets the colour, e.g. [`Colour::Red`].
+    #[inline]
+    pub const fn colour(&self) -> Colour { self.colour }
+    /// Gets the colour's name, e.g. `"red"`.
+    #[inline]
+    pub const fn name(&self) -> &'static str { self.name }
+    /// Gets the SGR parameter that sets the foreground colour, e.g. `31`.
+    #[inline]
+    pub const fn fg(&self) -> u8 { self.fg }
+    /// Gets the SGR parameter that sets the background colour, e.g. `41`.
+    #[inline]
+    pub const fn bg(&self) -> u8 { self.bg }
+}
+
+/// All [`Effect`]s, in the order their SGR parameters are written.
+pub const EFFECTS: [EffectCode; 8] = [
+    EffectCode::new(Effect::Bold,      "bold",      1, 22),
+    EffectCode::new(Effect::Faint,     "faint",     2, 22),
+    EffectCode::new(Effect::Italic,    "italic",    3, 23),
+    EffectCode::new(Effect::Underline, "underline", 4, 24),
+    EffectCode::new(Effect::Blink,     "blink",     5, 25),
+    EffectCode::new(Effect::Reverse,   "reverse",   7, 27),
+    EffectCode::new(Effect::Hidden,    "hidden",    8, 28),
+    EffectCode::new(Effect::Strike,    "strike",    9, 29),
+];
+
+/// All 16 named [`Colour`]s, i.e. the 8 normal colours followed by the 8 bright colours.
+pub const COLOURS: [ColourCode; 16] = [
+    ColourCode::new(Colour::Black,        "black",          30),
+    ColourCode::new(Colour::Red,          "red",            31),
+    ColourCode::new(Colour::Green,        "green",          32),
+    ColourCode::new(Colour::Yellow,       "yellow",         33),
+    ColourCode::new(Colour::Blue,         "blue",           34),
+    ColourCode::new(Colour::Purple,       "purple",         35),
+    ColourCode::new(Colour::Cyan,         "cyan",           36),
+    ColourCode::new(Colour::White,        "white",          37),
+    ColourCode::new(Colour::BrightBlack,  "bright_black",   90),
+    ColourCode::new(Colour::BrightRed,    "bright_red",     91),
+    ColourCode::new(Colour::BrightGreen,  "bright_green",   92),
+    ColourCode::new(Colour::BrightYellow, "bright_yellow",  93),
+    ColourCode::new(Colour::BrightBlue,   "bright_blue",    94),
+    ColourCode::new(Colour::BrightPurple, "bright_purple",  95),
+    ColourCode::new(Colour::BrightCyan,   "bright_cyan",    96),
+    ColourCode::new(Colour::BrightWhite,  "bright_white",   97),
+];
+
+/// Renders all of this module's tables as a single-line JSON object.
+///
+/// The format is stable, i.e. keys are only ever added. It looks like:
+///
+/// ```json
+/// {
+///   "reset": 0,
+///   "fg_reset": 39, "bg_reset": 49,
+///   "fg_extended": 38, "bg_extended": 48,
+///   "extended_ansi256": 5, "extended_rgb": 2,
+///   "effects": [{"name": "bold", "set": 1, "reset": 22}, ...],
+///   "colours": [{"name": "black", "fg": 30, "bg": 40}, ...]
+/// }
+/// ```
+pub fn to_json() -> String {
+    let mut json = String::new();
+    // Note: writing to a String cannot fail
+    let _ = write!(json,
+        r#"{{"reset":{},"fg_reset":{},"bg_reset":{},"fg_extended":{},"bg_extended":{},"extended_ansi256":{},"extended_rgb":{},"effects":["#,
+        RESET, FG_RESET, BG_RESET, FG_EXTENDED, BG_EXTENDED, EXTENDED_ANSI256, EXTENDED_RGB,
+    );
+    for (i, code) in EFFECTS.iter().enumerate() {
+        let sep = if i > 0 { "," } else { "" };
+        let _ = write!(json, r#"{}{{"name":"{}","set":{},"reset":{}}}"#, sep, code.name, code.set, code.reset);
+    }
+    json.push_str(r#"],"colours":["#);
+    for (i, code) in COLOURS.iter().enumerate() {
+        let sep = if i > 0 { "," } else { "" };
+        let _ = write!(json, r#"{}{{"name":"{}","fg":{},"bg":{}}}"#, sep, code.name, code.fg, code.bg);
+    }
+    json.push_str("]}");
+    json
+}
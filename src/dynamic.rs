@@ -0,0 +1,151 @@
+use crate::state::{DYNAMIC, STYLES, STYLE_MAP};
+use crate::{Attrs, Colour};
+use std::fmt;
+
+/// The terminal's *dynamic* colours, i.e. those of regions other than text, such as the
+/// cursor, which many terminals (e.g. xterm, VTE, kitty) allow setting with `OSC 10` to
+/// `OSC 19` escape sequences, and resetting with `OSC 110` to `OSC 119`.
+///
+/// Use [`wrap()`](Self::wrap()) to set a colour while a target is formatted. Like an
+/// [`UnderlineStyle`](crate::UnderlineStyle), a `DynamicColour` is not part of an
+/// [`Ansi`](crate::Ansi), which keeps `Ansi` instances small. Instead, the colour is
+/// set before the target, and afterwards an enclosing colour of the same region is restored,
+/// else the region's colour is reset to the terminal's default.
+///
+/// Colours are written as RGB components (see [`Colour::to_rgb()`]), after applying any
+/// [style map](crate::map), e.g. of a `Writer`. Dynamic colours respect
+/// [protection](crate::Ansi::protect_attrs()): nothing is written while the corresponding
+/// [`Attrs::Foreground`] or [`Attrs::Background`] is protected, e.g. inside
+/// [`Ansi::no_ansi()`](crate::Ansi::no_ansi()). A [`Colour::Reset`] resets the region's
+/// colour, and a [`Colour::Unspecified`] leaves it unchanged.
+///
+/// Terminals that do not support dynamic colours usually ignore them.
+///
+/// *Note: only available with `feature=rgb`*
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, Colour::{Red, Rgb}, DynamicColour::Cursor};
+///
+/// assert_eq!(
+///     Cursor.wrap(Red, "a").to_string(),
+///     "\x1B]12;rgb:cd/00/00\x1B\\a\x1B]112\x1B\\",
+/// );
+/// assert_eq!(
+///     Cursor.wrap(Red, format_args!("a {} c", Cursor.wrap(Rgb(0, 255, 0), "b"))).to_string(),
+///     "\x1B]12;rgb:cd/00/00\x1B\\a \x1B]12;rgb:00/ff/00\x1B\\b\x1B]12;rgb:cd/00/00\x1B\\ c\x1B]112\x1B\\",
+/// );
+/// assert_eq!(
+///     styled_format!(Ansi::no_ansi(), "{}", Cursor.wrap(Red, "plain")),
+///     "plain",
+/// );
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DynamicColour {
+    /// The default text colour, i.e. `OSC 10`
+    Foreground,
+    /// The default background colour, i.e. `OSC 11`
+    Background,
+    /// The cursor colour, i.e. `OSC 12`
+    Cursor,
+    /// The background colour of selected text, i.e. `OSC 17`
+    Highlight,
+    /// The text colour of selected text, i.e. `OSC 19`
+    HighlightText,
+}
+
+impl DynamicColour {
+    /// Gets the `OSC` number that sets this colour, e.g. `12` for `Cursor`.
+    /// Adding `100` gives the number that resets it, e.g. `112`.
+    #[inline]
+    pub const fn code(&self) -> u8 {
+        match self {
+            Self::Foreground    => 10,
+            Self::Background    => 11,
+            Self::Cursor        => 12,
+            Self::Highlight     => 17,
+            Self::HighlightText => 19,
+        }
+    }
+    /// Gets the attribute whose [protection](crate::Ansi::protect_attrs()) prevents
+    /// setting this colour, i.e. [`Attrs::Background`] for `Background` and `Highlight`,
+    /// else [`Attrs::Foreground`].
+    #[inline]
+    pub const fn attrs(&self) -> Attrs {
+        match self {
+            Self::Background | Self::Highlight => Attrs::Background,
+            _                                  => Attrs::Foreground,
+        }
+    }
+    /// Sets this colour to `colour` while the given target is formatted.
+    #[inline]
+    pub const fn wrap<T: fmt::Display>(&self, colour: Colour, target: T) -> DynamicColoured<T> {
+        DynamicColoured { dynamic: *self, colour, target }
+    }
+    /// The index of this colour's enclosing colour in the nesting state.
+    const fn index(&self) -> usize {
+        match self {
+            Self::Foreground    => 0,
+            Self::Background    => 1,
+            Self::Cursor        => 2,
+            Self::Highlight     => 3,
+            Self::HighlightText => 4,
+        }
+    }
+    /// Maps `colour` with the current style map, if any, as for the text's colours.
+    fn map(&self, colour: Colour) -> Colour {
+        match (STYLE_MAP.get(), self.attrs() == Attrs::Background) {
+            (None,      _    ) => colour,
+            (Some(map), false) => map(colour.fg()).colour().fg(),
+            (Some(map), true ) => map(colour.bg()).colour().bg(),
+        }
+    }
+    /// Writes the sequence that sets this colour to `colour`, or resets it if `None`.
+    fn write(&self, f: &mut fmt::Formatter<'_>, colour: Option<Colour>) -> fmt::Result {
+        match colour.and_then(Colour::to_rgb) {
+            Some((r, g, b)) => write!(f, "\x1B]{};rgb:{:02x}/{:02x}/{:02x}\x1B\\", self.code(), r, g, b),
+            None            => write!(f, "\x1B]{}\x1B\\", self.code() as u16 + 100),
+        }
+    }
+}
+
+/// Associates a [`Display`](fmt::Display) *target* with a [`DynamicColour`] and its colour.
+///
+/// Created by [`DynamicColour::wrap()`].
+#[derive(Clone, Copy, Debug)]
+pub struct DynamicColoured<T: fmt::Display> {
+    dynamic: DynamicColour,
+    colour:  Colour,
+    target:  T,
+}
+
+impl<T: fmt::Display> DynamicColoured<T> {
+    /// Gets the [`DynamicColour`].
+    #[inline]
+    pub const fn dynamic(&self) -> DynamicColour { self.dynamic }
+    /// Gets the colour.
+    #[inline]
+    pub const fn colour(&self) -> Colour { self.colour }
+    /// Gets the target.
+    #[inline]
+    pub const fn target(&self) -> &T { &self.target }
+}
+
+impl<T: fmt::Display> fmt::Display for DynamicColoured<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, old_out) = STYLES.get();
+        let colour = self.dynamic.map(self.colour);
+        if colour.is_unspecified() || old_out.protected_attrs().intersects(self.dynamic.attrs()) {
+            return self.target.fmt(f);
+        }
+        let mut colours = DYNAMIC.get();
+        let parent = colours[self.dynamic.index()].replace(colour);
+        DYNAMIC.set(colours);
+        let result = self.dynamic.write(f, Some(colour)).and_then(|_| self.target.fmt(f));
+        colours[self.dynamic.index()] = parent;
+        DYNAMIC.set(colours);
+        result?;
+        self.dynamic.write(f, parent)
+    }
+}
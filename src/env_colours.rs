@@ -0,0 +1,261 @@
+//! Read user palettes from environment variables such as `LS_COLORS` and `GCC_COLORS`.
+//!
+//! These variables share a simple format: a colon-separated list of `key=params` entries,
+//! where each key names something to style, e.g. `di` (directories) in `LS_COLORS` or
+//! `error` in `GCC_COLORS`, and its params are the parameters of an SGR escape sequence,
+//! e.g. `01;31` for `"\x1B[01;31m"`, i.e. bold red.
+//!
+//! An entry's params may instead be a [style string](Ansi::from_style_str()), e.g.
+//! `error=bold red`, which is easier for users to write, e.g. to customize an application's
+//! colours with a `MYAPP_COLORS` env variable, whose keys may be the names of the [`Slot`]s
+//! of a theme (see [`THEME_SLOTS`]).
+//!
+//! An [`EnvColours`] maps each key to its [`Ansi`] style, so that a program can style its
+//! output consistently with the user's other tools, e.g. by using the styles to build a
+//! [`Theme`] with [`to_theme()`](EnvColours::to_theme()), or to style file names like `ls`
+//! does, with [`file_style()`](EnvColours::file_style()).
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{*, env_colours::{self, EnvColours, FileKind}, theme::{self, Slot}, Colour::*, Effect::Bold};
+//!
+//! let colours = EnvColours::parse("error=01;31:warning=01;35:note=01;36:locus=01").unwrap();
+//!
+//! assert_eq!(colours.get("error"), Some(ansi!(Bold, Red)));
+//! assert_eq!(colours.get("quote"), None);
+//!
+//! let theme = colours.to_theme(theme::DEFAULT, env_colours::GCC_SLOTS);
+//! assert_eq!(theme.get(Slot::Warning), ansi!(Bold, Purple));
+//!
+//! // Or, from the environment, ignoring invalid entries:
+//! std::env::set_var("MY_COLORS", "error=01;31:warning=oops");
+//! let colours = EnvColours::from_env("MY_COLORS");
+//! assert_eq!(colours.get("error"),   Some(ansi!(Bold, Red)));
+//! assert_eq!(colours.get("warning"), None);
+//!
+//! // Or, with style strings, to theme an application:
+//! std::env::set_var("MYAPP_COLORS", "error=bold red:warning=yellow");
+//! let theme = EnvColours::from_env("MYAPP_COLORS").to_theme(theme::DEFAULT, env_colours::THEME_SLOTS);
+//! theme::set_theme(&theme);
+//! assert_eq!(Slot::Warning.ansi(), ansi!(Yellow));
+//! # theme::reset_theme();
+//!
+//! // Or, to style file names like `ls`:
+//! let colours = EnvColours::parse("di=01;34:ex=01;32:*.rs=33").unwrap();
+//! assert_eq!(colours.file_style("main.rs", FileKind::Regular),    Some(ansi!(Yellow)));
+//! assert_eq!(colours.file_style("src",     FileKind::Directory),  Some(ansi!(Bold, Blue)));
+//! assert_eq!(colours.file_style("build",   FileKind::Executable), Some(ansi!(Bold, Green)));
+//! ```
+
+use crate::{Ansi, ParseAnsiError};
+use crate::theme::{Slot, Theme};
+use std::{env, fs};
+
+/// The keys of `GCC_COLORS` that correspond to [`Slot`]s, e.g. for [`EnvColours::to_theme()`].
+pub const GCC_SLOTS: &[(&str, Slot)] = &[
+    ("error",   Slot::Error),
+    ("warning", Slot::Warning),
+    ("note",    Slot::Info),
+    ("locus",   Slot::Emphasis),
+    ("quote",   Slot::Muted),
+];
+
+/// The keys that are the names of [`Slot`]s, e.g. `heading`, for [`EnvColours::to_theme()`].
+pub const THEME_SLOTS: &[(&str, Slot)] = &[
+    ("heading",    Slot::Heading),
+    ("subheading", Slot::Subheading),
+    ("emphasis",   Slot::Emphasis),
+    ("muted",      Slot::Muted),
+    ("info",       Slot::Info),
+    ("success",    Slot::Success),
+    ("warning",    Slot::Warning),
+    ("error",      Slot::Error),
+];
+
+/// The kind of a file, whose style is given by its key in `LS_COLORS`, e.g. `di`
+/// for directories, for [`EnvColours::file_style()`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum FileKind {
+    /// A regular file, i.e. `fi`
+    Regular,
+    /// A regular file that is executable, i.e. `ex`
+    Executable,
+    /// A directory, i.e. `di`
+    Directory,
+    /// A symbolic link, i.e. `ln`
+    Symlink,
+    /// A named pipe, i.e. `pi`
+    Fifo,
+    /// A socket, i.e. `so`
+    Socket,
+    /// A block device, i.e. `bd`
+    BlockDevice,
+    /// A character device, i.e. `cd`
+    CharDevice,
+}
+
+impl FileKind {
+    /// Gets this kind's key in `LS_COLORS`, e.g. `"di"` for `Directory`.
+    #[inline]
+    pub const fn key(&self) -> &'static str {
+        match self {
+            Self::Regular     => "fi",
+            Self::Executable  => "ex",
+            Self::Directory   => "di",
+            Self::Symlink     => "ln",
+            Self::Fifo        => "pi",
+            Self::Socket      => "so",
+            Self::BlockDevice => "bd",
+            Self::CharDevice  => "cd",
+        }
+    }
+
+    /// Gets the kind of file of the given metadata, e.g. from [`fs::symlink_metadata()`],
+    /// since [`fs::metadata()`] follows symbolic links.
+    ///
+    /// Other than on unix, files are never `Executable`, nor pipes, sockets or devices.
+    pub fn from_metadata(metadata: &fs::Metadata) -> Self {
+        let file_type = metadata.file_type();
+        if file_type.is_dir() {
+            return Self::Directory;
+        }
+        if file_type.is_symlink() {
+            return Self::Symlink;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+            if file_type.is_fifo()         { return Self::Fifo; }
+            if file_type.is_socket()       { return Self::Socket; }
+            if file_type.is_block_device() { return Self::BlockDevice; }
+            if file_type.is_char_device()  { return Self::CharDevice; }
+            if metadata.permissions().mode() & 0o111 != 0 { return Self::Executable; }
+        }
+        Self::Regular
+    }
+}
+
+/// The styles of a `key=params` list, e.g. the value of `LS_COLORS` or `GCC_COLORS`.
+///
+/// See the [module-level documentation](crate::env_colours).
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct EnvColours { entries: Vec<(String, Ansi)> }
+
+impl EnvColours {
+    /// Creates an empty instance.
+    #[inline]
+    pub const fn new() -> Self { Self { entries: Vec::new() } }
+
+    /// Parses a colon-separated list of `key=params` entries, where params are either the
+    /// parameters of an SGR escape sequence, e.g. `"di=01;34:ln=01;36"`, or a
+    /// [style string](Ansi::from_style_str()), e.g. `"error=bold red:warning=yellow"`.
+    ///
+    /// Empty entries are ignored, and an entry with empty params, e.g. `"error="`,
+    /// has an [`unspecified`](Ansi::unspecified()) style. If a key occurs more than once,
+    /// its last entry takes precedence.
+    ///
+    /// Returns an error naming the first invalid entry, i.e. one without `=`, or whose
+    /// params are neither SGR parameters nor a style string, e.g. `"ln=target"` as allowed by `LS_COLORS`.
+    /// To ignore invalid entries instead, see [`from_env()`](Self::from_env()).
+    pub fn parse(s: &str) -> Result<Self, ParseAnsiError> {
+        let mut colours = Self::new();
+        for entry in s.split(':').filter(|entry| !entry.is_empty()) {
+            let (key, ansi) = parse_entry(entry).ok_or_else(|| ParseAnsiError::new(entry))?;
+            colours.insert(key, ansi);
+        }
+        Ok(colours)
+    }
+
+    /// Parses the value of the given environment variable, e.g. `"LS_COLORS"`, like
+    /// [`parse()`](Self::parse()), except that invalid entries are ignored, since the
+    /// value is supplied by the user.
+    ///
+    /// The result is empty if the variable is not set, or is not valid unicode.
+    pub fn from_env(var: &str) -> Self {
+        let mut colours = Self::new();
+        for entry in env::var(var).unwrap_or_default().split(':') {
+            if let Some((key, ansi)) = parse_entry(entry) {
+                colours.insert(key, ansi);
+            }
+        }
+        colours
+    }
+
+    /// Gets the style of the given key, if any.
+    pub fn get(&self, key: &str) -> Option<Ansi> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, ansi)| *ansi)
+    }
+
+    /// Sets the style of the given key, replacing any existing style.
+    pub fn insert(&mut self, key: &str, ansi: Ansi) {
+        match self.entries.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = ansi,
+            None        => self.entries.push((key.to_string(), ansi)),
+        }
+    }
+
+    /// Iterates over the keys and their styles, in the order they were first given.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Ansi)> + '_ {
+        self.entries.iter().map(|(key, ansi)| (key.as_str(), *ansi))
+    }
+
+    /// Gets the number of keys.
+    #[inline]
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// True if there are no keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// Gets the style of a file with the given name and kind, as `ls` does with `LS_COLORS`,
+    /// e.g. `*.rs=33` for regular files whose name ends with `.rs`.
+    ///
+    /// The style of a regular file is that of the longest `*` key that its name ends with,
+    /// ignoring ASCII case, if any, else that of `fi`. The style of any other kind of file
+    /// is that of its [key](FileKind::key()), e.g. `di`. If there is no such key, the style
+    /// is that of `no`, i.e. of normal text, if any.
+    pub fn file_style(&self, name: &str, kind: FileKind) -> Option<Ansi> {
+        let suffix = match kind {
+            FileKind::Regular => self.iter()
+                .filter_map(|(key, ansi)| Some((key.strip_prefix('*')?, ansi)))
+                .filter(|(suffix, _)| ends_with_ignore_case(name, suffix))
+                .max_by_key(|(suffix, _)| suffix.len())
+                .map(|(_, ansi)| ansi),
+            _ => None,
+        };
+        suffix.or_else(|| self.get(kind.key())).or_else(|| self.get("no"))
+    }
+
+    /// Creates a theme from `base`, with the style of each slot of `slots` replaced by the
+    /// style of its key, if present, e.g. using [`GCC_SLOTS`] to theme
+    /// [`paint_error!`](crate::paint_error) and [`paint_warning!`](crate::paint_warning) like gcc's diagnostics.
+    ///
+    /// The resulting theme may then be set with [`theme::set_theme()`](crate::theme::set_theme()).
+    pub fn to_theme(&self, base: Theme, slots: &[(&str, Slot)]) -> Theme {
+        slots.iter().fold(base, |theme, (key, slot)| match self.get(key) {
+            Some(ansi) => theme.with(*slot, ansi),
+            None       => theme,
+        })
+    }
+}
+
+/// True if `name` ends with `suffix`, ignoring ASCII case.
+fn ends_with_ignore_case(name: &str, suffix: &str) -> bool {
+    name.len() >= suffix.len() && name.as_bytes()[name.len() - suffix.len()..].eq_ignore_ascii_case(suffix.as_bytes())
+}
+
+/// Parses a `key=params` entry, or returns `None` if it is invalid.
+fn parse_entry(entry: &str) -> Option<(&str, Ansi)> {
+    let (key, params) = entry.split_once('=')?;
+    if key.is_empty() {
+        return None;
+    }
+    if !params.bytes().all(|b| b.is_ascii_digit() || b == b';') {
+        return Ansi::from_style_str(params).ok().map(|ansi| (key, ansi));
+    }
+    if params.is_empty() {
+        return Some((key, Ansi::unspecified()));
+    }
+    Ansi::parse_sgr(&format!("\x1B[{params}m")).ok().map(|ansi| (key, ansi))
+}
@@ -1,15 +1,31 @@
 use crate::ansi::{Ansi, Colour, Colours, Effect, Effects};
+use crate::io::{Composition, FlushPolicy, LinePrefix};
+use crate::layout::Repeat;
+use crate::map::StyleMap;
+use crate::state::{BUDGET, FALLBACK, STYLES, STYLE_MAP};
+use crate::text::{self, Sanitize};
+use crate::underline;
 use crate::write::run_time::Formatter;
 
 use std::fmt;
-use std::cell::Cell;
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 
 #[inline]
 fn fmt_ansi(f: &mut fmt::Formatter<'_>, ansi: Ansi, allow_alternate: bool) -> fmt::Result {
     Formatter::fmt_ansi(f, if allow_alternate && f.alternate() { ansi.not() } else { ansi })
 }
 
+/// Writes a transition between nested styles, followed by the enclosing [`UnderlineStyle`](crate::UnderlineStyle),
+/// if any, when the transition (re)enables the plain [`Effect::Underline`].
+#[inline]
+pub(crate) fn fmt_transition(f: &mut fmt::Formatter<'_>, ansi: Ansi) -> fmt::Result {
+    fmt_ansi(f, ansi, false)?;
+    if ansi.effect().has_effect(Effect::Underline) {
+        underline::restore(f)?;
+    }
+    Ok(())
+}
+
 impl fmt::Display for Effect {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt_ansi(f, self.ansi(), true)
@@ -151,8 +167,8 @@ impl fmt::Display for Ansi {
 /// is achieved using [`thread_local!`] to pass [`Ansi`] instances between
 /// parents and children.
 ///
-/// However, any associated overhead is likely minimal because the only value being
-/// stored in [`thread_local!`] is a single [`Ansi`], which is relatively small.
+/// However, any associated overhead is likely minimal because the values being
+/// stored in [`thread_local!`] are mainly [`Ansi`]s, which are relatively small.
 ///
 /// # Examples
 ///
@@ -206,25 +222,473 @@ impl<T: fmt::Display> Styled<T> {
     {
         Styled::new(self.ansi, self.target.deref())
     }
+    /// Converts to a `Styled` that repeats the target the given number of times, writing
+    /// the style just once, i.e. the same style, then all repetitions, then the reset.
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::Blue};
+    ///
+    /// assert_eq!(styled!(Blue, "=-").repeat(3).to_string(), "\x1B[34m=-=-=-\x1B[39m");
+    /// ```
+    #[inline]
+    pub fn repeat(self, count: usize) -> Styled<Repeat<T>> {
+        Styled::new(self.ansi, Repeat::new(self.target, count))
+    }
+    /// Converts to spans of text, each paired with its style, in order, i.e. the text
+    /// between the ANSI codes that formatting this instance would write, and the
+    /// style that is active for it, including any nested styles.
+    ///
+    /// See [`text::spans()`](crate::text::spans()) for details.
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::{Blue, Red}};
+    ///
+    /// assert_eq!(styled_format_args!(Blue, "a {} c", styled!(Red, "b")).to_spans(), [
+    ///     ("a ".to_string(), ansi!(Blue)),
+    ///     ("b".to_string(),  ansi!(Red)),
+    ///     (" c".to_string(), ansi!(Blue)),
+    /// ]);
+    /// ```
+    pub fn to_spans(&self) -> Vec<(String, Ansi)> { text::spans(&self.to_string()) }
+    /// Gets an iterator over the visible chars that formatting this instance would write,
+    /// i.e. excluding ANSI codes, including those of nested styles, without allocating.
+    ///
+    /// Since formatting *pushes* text to a `Writer`, the iterator formats this instance
+    /// once per chunk of chars that it yields, so prefer [`visible_width()`](Self::visible_width())
+    /// for counting the chars of long text.
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::{Blue, Red}};
+    ///
+    /// let styled = styled!(Blue, styled!(Red, "a b c"));
+    /// assert_eq!(styled.visible_chars().collect::<String>(), "a b c");
+    /// assert_eq!(styled.visible_chars().filter(|c| c.is_alphabetic()).count(), 3);
+    /// ```
+    pub fn visible_chars(&self) -> VisibleChars<'_, T> {
+        VisibleChars { styled: self, chunk: ['\0'; 32], pos: 0, len: 0, skip: 0, done: false }
+    }
+    /// Calls `f` for each visible char that formatting this instance would write, as
+    /// for [`visible_chars()`](Self::visible_chars()).
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::{Blue, Red}};
+    ///
+    /// let mut visible = String::new();
+    /// styled_format_args!(Blue, "a {} c", styled!(Red, "b")).for_each_visible_char(|c| visible.push(c));
+    /// assert_eq!(visible, "a b c");
+    /// ```
+    pub fn for_each_visible_char(&self, f: impl FnMut(char)) {
+        self.visible_chars().for_each(f);
+    }
+    /// Gets the number of visible chars that formatting this instance would write, as for
+    /// [`text::visible_width()`](crate::text::visible_width()), but without allocating.
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::{Blue, Red}};
+    ///
+    /// assert_eq!(styled_format_args!(Blue, "a {} c", styled!(Red, "héllo")).visible_width(), 9);
+    /// ```
+    pub fn visible_width(&self) -> usize {
+        let mut width = 0;
+        // Note: only fails if the target's Display impl does, in which case the chars so far are counted
+        let _ = fmt::Write::write_fmt(&mut text::VisibleCharsWriter::new(|_| width += 1), format_args!("{}", self));
+        width
+    }
+}
+
+/// An iterator over the visible chars that formatting a [`Styled`] would write,
+/// created by [`Styled::visible_chars()`].
+pub struct VisibleChars<'a, T: fmt::Display> {
+    styled: &'a Styled<T>,
+    /// The current chunk of chars, of which `chunk[pos..len]` are yet to be yielded
+    chunk:  [char; 32],
+    pos:    usize,
+    len:    usize,
+    /// The number of chars in the current and previous chunks
+    skip:   usize,
+    /// Whether the current chunk is the last
+    done:   bool,
+}
+
+impl<T: fmt::Display> VisibleChars<'_, T> {
+    /// Formats the `Styled` again, keeping the next chunk of chars.
+    fn next_chunk(&mut self) {
+        let (chunk, skip) = (&mut self.chunk, self.skip);
+        let (mut count, mut len) = (0, 0);
+        // Note: only fails if the target's Display impl does, in which case the chars so far are visited
+        let _ = fmt::Write::write_fmt(&mut text::VisibleCharsWriter::new(|c| {
+            if count >= skip && len < chunk.len() {
+                chunk[len] = c;
+                len += 1;
+            }
+            count += 1;
+        }), format_args!("{}", self.styled));
+        self.pos   = 0;
+        self.len   = len;
+        self.skip += len;
+        self.done  = count <= self.skip;
+    }
+}
+
+impl<T: fmt::Display> Iterator for VisibleChars<'_, T> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.pos == self.len {
+            if self.done {
+                return None;
+            }
+            self.next_chunk();
+            if self.len == 0 {
+                return None;
+            }
+        }
+        self.pos += 1;
+        Some(self.chunk[self.pos - 1])
+    }
+}
+
+/// A [`Display`](fmt::Display) adapter that formats its target with [`Debug`](fmt::Debug),
+/// so that it can be [`Styled`].
+///
+/// Usually created by [`styled_debug!`](crate::styled_debug). The formatting flags are
+/// passed to the target, so e.g. `{:#}` formats it with `{:#?}`, i.e. pretty-printed.
+///
+/// To style other formatting traits, e.g. [`LowerHex`](fmt::LowerHex), style their
+/// [`format_args!`] instead, e.g. `styled!(Red, format_args!("{:x}", 255))`.
+#[derive(Clone, Copy, Debug)]
+pub struct AsDebug<T: fmt::Debug>(pub T);
+
+impl<T: fmt::Debug> fmt::Display for AsDebug<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.0.fmt(f) }
 }
 
 impl<T: fmt::Display> fmt::Display for Styled<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        thread_local!(static ANSI: Cell<Ansi> = Cell::new(Ansi::unspecified()));
-        let old_ansi = ANSI.get();
-        let new_ansi = old_ansi.add(self.ansi);
+        let (old_ansi, old_out) = STYLES.get();
+        let fallback = FALLBACK.get();
+        if !fallback.is_unspecified() && !self.ansi.is_unspecified() {
+            // Applies only to styles nested directly in the write, not to those nested in them
+            FALLBACK.set(Ansi::unspecified());
+            let result = self.fmt_styled(f, old_ansi, old_out, old_ansi.add(fallback).add(self.ansi));
+            FALLBACK.set(fallback);
+            return result;
+        }
+        self.fmt_styled(f, old_ansi, old_out, old_ansi.add(self.ansi))
+    }
+}
+
+impl<T: fmt::Display> Styled<T> {
+    fn fmt_styled(&self, f: &mut fmt::Formatter<'_>, old_ansi: Ansi, old_out: Ansi, new_ansi: Ansi) -> fmt::Result {
         // Uncomment for debugging:
         // println!("[DISPLAY]\nold: {:?}\nnew: {:?}\nres: {:?}", old_ansi, self.ansi, new_ansi);
         if new_ansi == old_ansi {
             return self.target.fmt(f);
         }
-        let old_to_new = old_ansi.transition(new_ansi);
-        let new_to_old = new_ansi.transition(old_ansi);
-        ANSI.set(new_ansi);
-        fmt_ansi(f, old_to_new, false)?;
+        // The styles actually written, which differ from the above if remapped or degraded
+        let mut new_out = match STYLE_MAP.get() {
+            Some(map) => map(new_ansi),
+            None      => new_ansi,
+        };
+        if let Some(budget) = BUDGET.get() {
+            let (out, cost) = within_budget(old_out, new_out, budget);
+            new_out = out;
+            BUDGET.set(Some(budget - cost));
+        }
+        if new_out == old_out {
+            // E.g. only the protected attributes differ, so there are no codes to write
+            STYLES.set((new_ansi, new_out));
+            let result = self.target.fmt(f);
+            STYLES.set((old_ansi, old_out));
+            return result;
+        }
+        let old_to_new = old_out.transition(new_out);
+        let new_to_old = new_out.transition(old_out);
+        STYLES.set((new_ansi, new_out));
+        fmt_transition(f, old_to_new)?;
         self.target.fmt(f)?;
-        fmt_ansi(f, new_to_old, false)?;
-        ANSI.set(old_ansi);
+        fmt_transition(f, new_to_old)?;
+        STYLES.set((old_ansi, old_out));
         Ok(())
     }
 }
+
+/// A sequence of consecutive text fragments, each with its own [`Ansi`] style, that
+/// are formatted in a single pass.
+///
+/// Formatting a `StyledBatch` produces the same visible result as formatting each fragment
+/// as a [`Styled`] in turn, but transitions directly from each fragment's style to the
+/// next, rather than back to the parent's style in between, and so writes fewer ANSI
+/// codes. It is also faster, which matters for renderers that emit many styled fragments.
+///
+/// Like a [`Styled`], each fragment's style is combined with any parent style, and is
+/// subject to any [style map](crate::map) or [style budget](crate::io::AnsiWrite::set_style_budget())
+/// (which is charged as if each fragment were a [`Styled`]).
+///
+/// # Examples
+///
+/// ```
+/// use ansiconst::*;
+/// use ansiconst::Colour::{Red, Green};
+/// use ansiconst::Effect::Bold;
+///
+/// const LINE: StyledBatch = StyledBatch::new(&[
+///     (ansi!(Red),   "error"),
+///     (ansi!(Bold),  ": "),
+///     (ansi!(Green), "fixed"),
+/// ]);
+///
+/// assert_eq!(LINE.to_string(), "\x1B[31merror\x1B[1;39m: \x1B[22;32mfixed\x1B[39m");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct StyledBatch<'a> { fragments: &'a [(Ansi, &'a str)] }
+
+impl<'a> StyledBatch<'a> {
+    /// Creates an instance with the given fragments.
+    #[inline]
+    pub const fn new(fragments: &'a [(Ansi, &'a str)]) -> Self { Self { fragments } }
+    /// Gets the fragments.
+    #[inline]
+    pub const fn fragments(&self) -> &'a [(Ansi, &'a str)] { self.fragments }
+    /// Converts to spans of text, each paired with its style, in order.
+    ///
+    /// Unlike [`fragments()`](Self::fragments()), the spans are as written, i.e. consecutive
+    /// fragments with the same style are merged, and empty fragments are omitted.
+    /// See [`Styled::to_spans()`].
+    pub fn to_spans(&self) -> Vec<(String, Ansi)> { text::spans(&self.to_string()) }
+}
+
+impl fmt::Display for StyledBatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_fragments(f, self.fragments.iter().copied())
+    }
+}
+
+/// A string with [`Ansi`] styles applied to ranges of its bytes, e.g. to highlight
+/// matches found at run-time, which are formatted in a single pass.
+///
+/// Each span's range is of byte indices, as for slicing a `&str`. Where spans overlap,
+/// their styles are combined in the order given, so later spans take precedence, e.g. a
+/// match highlighted within a line styled as a whole. Text outside any span is unstyled,
+/// i.e. has the parent's style.
+///
+/// Like a [`StyledBatch`], a `StyledSpans` transitions directly from each span's style to
+/// the next, and is combined with any parent style, style map or style budget.
+///
+/// # Examples
+///
+/// ```
+/// use ansiconst::*;
+/// use ansiconst::Colour::{Red, Yellow};
+/// use ansiconst::Effect::Bold;
+///
+/// let line = "error: not found";
+/// let spans = StyledSpans::new(line)
+///     .span(0..5, ansi!(Red))
+///     .span(0..line.len(), ansi!(Bold))
+///     .span(7..10, ansi!(Yellow));
+///
+/// assert_eq!(
+///     spans.to_string(),
+///     "\x1B[1;31merror\x1B[39m: \x1B[33mnot\x1B[39m found\x1B[22m",
+/// );
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct StyledSpans<'a> {
+    text:  &'a str,
+    spans: Vec<(Range<usize>, Ansi)>,
+}
+
+impl<'a> StyledSpans<'a> {
+    /// Creates an instance with the given text, without any spans.
+    #[inline]
+    pub const fn new(text: &'a str) -> Self { Self { text, spans: Vec::new() } }
+
+    /// Adds a span that styles the given range of the text's bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or does not start and end on
+    /// char boundaries, as for slicing the text.
+    pub fn span(mut self, range: Range<usize>, ansi: Ansi) -> Self {
+        self.push(range, ansi);
+        self
+    }
+
+    /// Adds a span, as for [`span()`](Self::span()), by reference.
+    pub fn push(&mut self, range: Range<usize>, ansi: Ansi) {
+        let _ = &self.text[range.clone()];
+        self.spans.push((range, ansi));
+    }
+
+    /// Adds a span for each match of `pattern` in the text, i.e. for each non-overlapping
+    /// occurrence, as found by [`str::match_indices()`]. An empty pattern matches nothing.
+    ///
+    /// See also [`text::highlight()`].
+    pub fn highlight(mut self, pattern: &str, ansi: Ansi) -> Self {
+        if !pattern.is_empty() {
+            for (start, _) in self.text.match_indices(pattern) {
+                self.spans.push((start..start + pattern.len(), ansi));
+            }
+        }
+        self
+    }
+
+    /// Gets the text.
+    #[inline]
+    pub const fn text(&self) -> &'a str { self.text }
+
+    /// Gets the spans, in the order they were added.
+    #[inline]
+    pub fn spans(&self) -> &[(Range<usize>, Ansi)] { &self.spans }
+
+    /// Converts to spans of text, each paired with its style, in order.
+    ///
+    /// The spans are as written, i.e. consecutive text with the same style is merged.
+    /// See [`Styled::to_spans()`].
+    pub fn to_spans(&self) -> Vec<(String, Ansi)> { text::spans(&self.to_string()) }
+}
+
+impl fmt::Display for StyledSpans<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Split the text wherever a span starts or ends, so each fragment has one style
+        let mut bounds: Vec<usize> = self.spans.iter()
+            .flat_map(|(range, _)| [range.start, range.end])
+            .chain([0, self.text.len()])
+            .collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+        fmt_fragments(f, bounds.windows(2).map(|bound| {
+            let ansi = self.spans.iter()
+                .filter(|(range, _)| range.start <= bound[0] && bound[1] <= range.end)
+                .fold(Ansi::unspecified(), |ansi, (_, span)| ansi.add(*span));
+            (ansi, &self.text[bound[0]..bound[1]])
+        }))
+    }
+}
+
+/// Formats consecutive text fragments, each with its own style, as for a [`StyledBatch`].
+pub(crate) fn fmt_fragments<'a>(
+    f: &mut fmt::Formatter<'_>, fragments: impl Iterator<Item = (Ansi, &'a str)>,
+) -> fmt::Result {
+    // Note: the thread_locals are accessed once per batch, not per fragment
+    let (parent_ansi, parent_out) = STYLES.get();
+    let fallback = FALLBACK.get();
+    let map = STYLE_MAP.get();
+    let mut budget = BUDGET.get();
+    let mut old_out = parent_out;
+    for (ansi, text) in fragments {
+        let new_ansi = if ansi.is_unspecified() { parent_ansi } else { parent_ansi.add(fallback).add(ansi) };
+        let new_out = if new_ansi == parent_ansi {
+            parent_out
+        } else {
+            let new_out = map.map_or(new_ansi, |map| map(new_ansi));
+            match budget {
+                Some(remaining) => {
+                    let (new_out, cost) = within_budget(parent_out, new_out, remaining);
+                    budget = Some(remaining - cost);
+                    new_out
+                },
+                None => new_out,
+            }
+        };
+        fmt_transition(f, old_out.transition(new_out))?;
+        f.write_str(text)?;
+        old_out = new_out;
+    }
+    fmt_transition(f, old_out.transition(parent_out))?;
+    BUDGET.set(budget);
+    Ok(())
+}
+
+/// Degrades `new` until the escape bytes needed to transition to it from `old`
+/// and back again fit within `budget`: first by keeping `old`'s effects, then
+/// by keeping `old` entirely (which needs no escape bytes).
+///
+/// Returns the resulting style and its cost in escape bytes.
+#[cold]
+fn within_budget(old: Ansi, new: Ansi, budget: usize) -> (Ansi, usize) {
+    let cost = |new: Ansi| ansi_len(old.transition(new)) + ansi_len(new.transition(old));
+    [new, new.with_effect(old.effect())].into_iter()
+        .map(|new| (new, cost(new)))
+        .find(|(_, cost)| *cost <= budget)
+        .unwrap_or((old, 0))
+}
+
+/// Gets the number of bytes written when formatting `ansi`.
+fn ansi_len(ansi: Ansi) -> usize {
+    struct Counter(usize);
+    impl fmt::Write for Counter {
+        fn write_str(&mut self, s: &str) -> fmt::Result { self.0 += s.len(); Ok(()) }
+    }
+    let mut counter = Counter(0);
+    // Note: writing to a Counter cannot fail
+    let _ = fmt::Write::write_fmt(&mut counter, format_args!("{}", ansi));
+    counter.0
+}
+
+/// Runs `f` such that styles nested directly in its output first add the given
+/// `fallback` style, e.g. to reset a `Writer`'s default style (see [`Composition::Fallback`]).
+pub(crate) fn with_fallback<R>(fallback: Ansi, f: impl FnOnce() -> R) -> R {
+    struct Restore(Ansi);
+    impl Drop for Restore {
+        fn drop(&mut self) { FALLBACK.set(self.0) }
+    }
+    let _restore = Restore(FALLBACK.replace(fallback));
+    f()
+}
+
+/// Options configured on `Writer`s, most of which are applied to all `Styled<T>`s
+/// formatted during a write.
+#[derive(Clone, Copy)]
+pub(crate) struct WriteOptions {
+    pub(crate) style_map:    Option<StyleMap>,
+    pub(crate) style_budget: Option<usize>,
+    pub(crate) flush_policy: FlushPolicy,
+    pub(crate) line_prefix:  Option<LinePrefix>,
+    pub(crate) sanitize:     Option<Sanitize>,
+    pub(crate) composition:  Composition,
+    /// Not an option as such, but the state of whether the next write starts a new line.
+    pub(crate) at_line_start: bool,
+    /// Not an option as such, but the most recent style emitted by a write.
+    pub(crate) last_style:    Ansi,
+}
+
+impl WriteOptions {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            style_map:     None,
+            style_budget:  None,
+            flush_policy:  FlushPolicy::Manual,
+            line_prefix:   None,
+            sanitize:      None,
+            composition:   Composition::Outer,
+            at_line_start: true,
+            last_style:    Ansi::unspecified(),
+        }
+    }
+}
+
+/// Runs `f` with the given [`WriteOptions`] applied to all `Styled<T>`s formatted
+/// on this thread, restoring the previous options afterwards (even if `f` panics).
+///
+/// Options that are `None` leave the currently applied option, if any, unchanged.
+/// A style budget is reset on each call, i.e. applies per write.
+pub(crate) fn with_options<R>(options: WriteOptions, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<StyleMap>, Option<usize>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            STYLE_MAP.set(self.0);
+            BUDGET.set(self.1);
+        }
+    }
+    let _restore = Restore(STYLE_MAP.get(), BUDGET.get());
+    if let Some(map) = options.style_map {
+        STYLE_MAP.set(Some(map));
+    }
+    if let Some(budget) = options.style_budget {
+        BUDGET.set(Some(budget));
+    }
+    f()
+}
@@ -0,0 +1,99 @@
+//! Style text with a smooth foreground colour gradient.
+//!
+//! A [`Gradient`] formats its text with each char's foreground colour interpolated
+//! between two or more colours, i.e. its *stops*, which are spaced evenly along the text.
+//! The colours are interpolated between the RGB components of the stops (see
+//! [`Colour::to_rgb()`]), so any stops with RGB components may be used, e.g. `Red`.
+//!
+//! Like a [`StyledBatch`](crate::StyledBatch), a `Gradient` writes only the codes needed to
+//! transition from each char's colour to the next, and is combined with any parent style,
+//! e.g. one that is [`Bold`](crate::Effect::Bold). However, a parent style that protects
+//! the foreground colour overrides the gradient.
+//!
+//! *Note: only available with `feature=rgb`*
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{*, gradient::Gradient, Colour::Rgb, Effect::Bold};
+//!
+//! const SUNSET: &[Colour] = &[Rgb(255, 0, 0), Rgb(255, 200, 0)];
+//!
+//! assert_eq!(
+//!     Gradient::new(SUNSET, "abc").to_string(),
+//!     "\x1B[38;2;255;0;0ma\x1B[38;2;255;100;0mb\x1B[38;2;255;200;0mc\x1B[39m",
+//! );
+//!
+//! // Combined with a parent style
+//! assert_eq!(
+//!     styled_format!(Bold, "{}", Gradient::new(SUNSET, "ab")),
+//!     "\x1B[1m\x1B[38;2;255;0;0ma\x1B[38;2;255;200;0mb\x1B[39m\x1B[22m",
+//! );
+//! ```
+
+use crate::{fmt::fmt_fragments, Colour};
+use std::fmt;
+
+/// A [`Display`](fmt::Display) adapter that formats text with a foreground colour gradient.
+///
+/// See the [module-level documentation](crate::gradient).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Gradient<'a> {
+    stops: &'a [Colour],
+    text:  &'a str,
+}
+
+impl<'a> Gradient<'a> {
+    /// Creates a gradient through the given stops, from the first to the last char of `text`.
+    ///
+    /// Stops without RGB components, e.g. [`Colour::Unspecified`], are ignored, and
+    /// if no stops remain, the text is formatted without a colour.
+    #[inline]
+    pub const fn new(stops: &'a [Colour], text: &'a str) -> Self { Self { stops, text } }
+
+    /// Gets the stops.
+    #[inline]
+    pub const fn stops(&self) -> &'a [Colour] { self.stops }
+
+    /// Gets the text.
+    #[inline]
+    pub const fn text(&self) -> &'a str { self.text }
+
+    /// Gets the colour of the char at `index` of a gradient with `len` chars, or
+    /// [`Colour::Unspecified`] if there are no stops with RGB components.
+    ///
+    /// ```
+    /// use ansiconst::{gradient::Gradient, Colour::{self, Rgb}};
+    ///
+    /// let gradient = Gradient::new(&[Rgb(0, 0, 0), Rgb(200, 0, 0), Rgb(200, 200, 200)], "");
+    ///
+    /// assert_eq!(gradient.colour_at(0, 5), Rgb(0, 0, 0));
+    /// assert_eq!(gradient.colour_at(1, 5), Rgb(100, 0, 0));
+    /// assert_eq!(gradient.colour_at(2, 5), Rgb(200, 0, 0));
+    /// assert_eq!(gradient.colour_at(3, 5), Rgb(200, 100, 100));
+    /// assert_eq!(gradient.colour_at(4, 5), Rgb(200, 200, 200));
+    /// ```
+    pub fn colour_at(&self, index: usize, len: usize) -> Colour {
+        let mut stops = self.stops.iter().filter_map(|stop| stop.to_rgb());
+        let count = stops.clone().count();
+        if count <= 1 || len <= 1 {
+            return stops.next().map_or(Colour::Unspecified, |(r, g, b)| Colour::Rgb(r, g, b));
+        }
+        // The position of the char amongst the stops, i.e. between stop `seg` and the next
+        let (num, den) = (index.min(len - 1) * (count - 1), len - 1);
+        let seg = (num / den).min(count - 2);
+        let frac = num - seg * den;
+        let (a, b) = (stops.nth(seg).unwrap(), stops.next().unwrap());
+        let lerp = |a: u8, b: u8| (a as usize * (den - frac) * 2 + b as usize * frac * 2 + den) / (den * 2);
+        Colour::Rgb(lerp(a.0, b.0) as u8, lerp(a.1, b.1) as u8, lerp(a.2, b.2) as u8)
+    }
+}
+
+impl fmt::Display for Gradient<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.text.chars().count();
+        fmt_fragments(f, self.text.char_indices().enumerate().map(|(index, (start, c))| {
+            (self.colour_at(index, len).fg(), &self.text[start..start + c.len_utf8()])
+        }))
+    }
+}
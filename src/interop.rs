@@ -0,0 +1,12 @@
+//! Conversions to and from the style types of other terminal crates, to CSS, and with `serde`.
+
+#[cfg(feature="crossterm")]
+mod crossterm;
+#[cfg(feature="ratatui")]
+mod ratatui;
+#[cfg(feature="serde")]
+mod serde;
+#[cfg(feature="termion")]
+mod termion;
+#[cfg(feature="web")]
+mod web;
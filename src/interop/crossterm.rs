@@ -0,0 +1,237 @@
+use crate::{Ansi, Colour, Effect};
+#[cfg(not(feature="ansi256"))]
+use crate::text::COLOURS;
+use ::crossterm::style::{Attribute, Attributes, Color, ContentStyle};
+
+const EFFECTS: [Effect; 16] = [
+    Effect::NotBold, Effect::NotFaint, Effect::NotItalic, Effect::NotUnderline,
+    Effect::NotBlink, Effect::NotReverse, Effect::NotHidden, Effect::NotStrike,
+    Effect::Bold, Effect::Faint, Effect::Italic, Effect::Underline,
+    Effect::Blink, Effect::Reverse, Effect::Hidden, Effect::Strike,
+];
+
+impl Colour {
+    /// Converts to a [`crossterm`](::crossterm) colour, or `None` if [`Colour::Unspecified`].
+    ///
+    /// Note: crossterm's names for the 16 named colours differ from this crate's, e.g.
+    /// [`Colour::Red`] is `Color::DarkRed`, and [`Colour::BrightRed`] is `Color::Red`.
+    ///
+    /// *Note: only available with `feature=crossterm`*
+    pub const fn to_crossterm(self) -> Option<Color> {
+        Some(match self {
+            Colour::Unspecified  => return None,
+            Colour::Reset        => Color::Reset,
+            Colour::Black        => Color::Black,
+            Colour::Red          => Color::DarkRed,
+            Colour::Green        => Color::DarkGreen,
+            Colour::Yellow       => Color::DarkYellow,
+            Colour::Blue         => Color::DarkBlue,
+            Colour::Purple       => Color::DarkMagenta,
+            Colour::Cyan         => Color::DarkCyan,
+            Colour::White        => Color::Grey,
+            Colour::BrightBlack  => Color::DarkGrey,
+            Colour::BrightRed    => Color::Red,
+            Colour::BrightGreen  => Color::Green,
+            Colour::BrightYellow => Color::Yellow,
+            Colour::BrightBlue   => Color::Blue,
+            Colour::BrightPurple => Color::Magenta,
+            Colour::BrightCyan   => Color::Cyan,
+            Colour::BrightWhite  => Color::White,
+            #[cfg(feature="ansi256")]
+            Colour::Ansi256(num) => Color::AnsiValue(num),
+            #[cfg(feature="rgb")]
+            Colour::Rgb(r, g, b) => Color::Rgb { r, g, b },
+        })
+    }
+
+    /// Converts from a [`crossterm`](::crossterm) colour.
+    ///
+    /// This is lossy if the colour is not supported by the enabled features, i.e.
+    /// `Color::AnsiValue` requires `feature=ansi256` (except for values below 16, which
+    /// become named colours), and `Color::Rgb` requires `feature=rgb`. Unsupported
+    /// colours become [`Colour::Unspecified`].
+    ///
+    /// *Note: only available with `feature=crossterm`*
+    #[allow(unused_variables)]
+    pub const fn from_crossterm(color: Color) -> Colour {
+        match color {
+            Color::Reset           => Colour::Reset,
+            Color::Black           => Colour::Black,
+            Color::DarkRed         => Colour::Red,
+            Color::DarkGreen       => Colour::Green,
+            Color::DarkYellow      => Colour::Yellow,
+            Color::DarkBlue        => Colour::Blue,
+            Color::DarkMagenta     => Colour::Purple,
+            Color::DarkCyan        => Colour::Cyan,
+            Color::Grey            => Colour::White,
+            Color::DarkGrey        => Colour::BrightBlack,
+            Color::Red             => Colour::BrightRed,
+            Color::Green           => Colour::BrightGreen,
+            Color::Yellow          => Colour::BrightYellow,
+            Color::Blue            => Colour::BrightBlue,
+            Color::Magenta         => Colour::BrightPurple,
+            Color::Cyan            => Colour::BrightCyan,
+            Color::White           => Colour::BrightWhite,
+            #[cfg(feature="ansi256")]
+            Color::AnsiValue(num)  => Colour::Ansi256(num),
+            #[cfg(not(feature="ansi256"))]
+            Color::AnsiValue(num)  => if (num as usize) < COLOURS.len() { COLOURS[num as usize] } else { Colour::Unspecified },
+            #[cfg(feature="rgb")]
+            Color::Rgb { r, g, b } => Colour::Rgb(r, g, b),
+            #[cfg(not(feature="rgb"))]
+            Color::Rgb { .. }      => Colour::Unspecified,
+        }
+    }
+}
+
+impl Effect {
+    /// Converts to a [`crossterm`](::crossterm) attribute, or `None` if [`Effect::Unspecified`].
+    ///
+    /// Note: both [`Effect::NotBold`] and [`Effect::NotFaint`] become `Attribute::NormalIntensity`,
+    /// because they share the same SGR code.
+    ///
+    /// *Note: only available with `feature=crossterm`*
+    pub const fn to_crossterm(self) -> Option<Attribute> {
+        Some(match self {
+            Effect::Unspecified  => return None,
+            Effect::Bold         => Attribute::Bold,
+            Effect::NotBold      => Attribute::NormalIntensity,
+            Effect::Faint        => Attribute::Dim,
+            Effect::NotFaint     => Attribute::NormalIntensity,
+            Effect::Italic       => Attribute::Italic,
+            Effect::NotItalic    => Attribute::NoItalic,
+            Effect::Underline    => Attribute::Underlined,
+            Effect::NotUnderline => Attribute::NoUnderline,
+            Effect::Blink        => Attribute::SlowBlink,
+            Effect::NotBlink     => Attribute::NoBlink,
+            Effect::Reverse      => Attribute::Reverse,
+            Effect::NotReverse   => Attribute::NoReverse,
+            Effect::Hidden       => Attribute::Hidden,
+            Effect::NotHidden    => Attribute::NoHidden,
+            Effect::Strike       => Attribute::CrossedOut,
+            Effect::NotStrike    => Attribute::NotCrossedOut,
+        })
+    }
+
+    /// Converts from a [`crossterm`](::crossterm) attribute, or [`Effect::Unspecified`] if the
+    /// attribute has no equivalent, e.g. `Attribute::Framed`.
+    ///
+    /// *Note: only available with `feature=crossterm`*
+    pub const fn from_crossterm(attribute: Attribute) -> Effect {
+        match attribute {
+            Attribute::Bold            => Effect::Bold,
+            Attribute::Dim             => Effect::Faint,
+            Attribute::NormalIntensity => Effect::NotBold,
+            Attribute::Italic          => Effect::Italic,
+            Attribute::NoItalic        => Effect::NotItalic,
+            Attribute::Underlined      => Effect::Underline,
+            Attribute::NoUnderline     => Effect::NotUnderline,
+            Attribute::SlowBlink
+            | Attribute::RapidBlink    => Effect::Blink,
+            Attribute::NoBlink         => Effect::NotBlink,
+            Attribute::Reverse         => Effect::Reverse,
+            Attribute::NoReverse       => Effect::NotReverse,
+            Attribute::Hidden          => Effect::Hidden,
+            Attribute::NoHidden        => Effect::NotHidden,
+            Attribute::CrossedOut      => Effect::Strike,
+            Attribute::NotCrossedOut   => Effect::NotStrike,
+            _                          => Effect::Unspecified,
+        }
+    }
+}
+
+impl Ansi {
+    /// Converts to a [`crossterm`](::crossterm) style, e.g. for use with
+    /// `crossterm::style::StyledContent`.
+    ///
+    /// Unspecified colours become `None`. Protected attributes are not represented.
+    ///
+    /// *Note: only available with `feature=crossterm`*
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::Red, Effect::Bold};
+    /// use crossterm::style::{Attribute, Color, ContentStyle};
+    ///
+    /// const STYLE: ContentStyle = ansi!(Red, Bold).to_crossterm();
+    ///
+    /// assert_eq!(STYLE.foreground_color, Some(Color::DarkRed));
+    /// assert_eq!(STYLE.background_color, None);
+    /// assert!(STYLE.attributes.has(Attribute::Bold));
+    /// assert_eq!(Ansi::from_crossterm(STYLE), ansi!(Red, Bold));
+    /// ```
+    pub const fn to_crossterm(&self) -> ContentStyle {
+        let mut attributes = Attributes::none();
+        let mut i = 0;
+        while i < EFFECTS.len() {
+            if self.effect().has_effect(EFFECTS[i]) {
+                if let Some(attribute) = EFFECTS[i].to_crossterm() {
+                    attributes = attributes.with(attribute);
+                }
+            }
+            i += 1;
+        }
+        ContentStyle {
+            foreground_color: self.colour().fg().to_crossterm(),
+            background_color: self.colour().bg().to_crossterm(),
+            underline_color:  None,
+            attributes,
+        }
+    }
+
+    /// Converts from a [`crossterm`](::crossterm) style.
+    ///
+    /// This is lossy for colours and attributes that have no equivalent, see
+    /// [`Colour::from_crossterm()`] and [`Effect::from_crossterm()`].
+    ///
+    /// *Note: only available with `feature=crossterm`*
+    pub fn from_crossterm(style: ContentStyle) -> Ansi {
+        let mut ansi = Ansi::unspecified();
+        for attribute in Attribute::iterator().filter(|attribute| style.attributes.has(*attribute)) {
+            ansi = ansi.add(Effect::from_crossterm(attribute).ansi());
+        }
+        if let Some(fg) = style.foreground_color {
+            ansi = ansi.add(Colour::from_crossterm(fg).fg());
+        }
+        if let Some(bg) = style.background_color {
+            ansi = ansi.add(Colour::from_crossterm(bg).bg());
+        }
+        ansi
+    }
+}
+
+impl From<Colour> for Color {
+    /// See [`Colour::to_crossterm()`]. [`Colour::Unspecified`] becomes `Color::Reset`.
+    fn from(colour: Colour) -> Self { colour.to_crossterm().unwrap_or(Color::Reset) }
+}
+
+impl From<Color> for Colour {
+    /// See [`Colour::from_crossterm()`].
+    fn from(color: Color) -> Self { Colour::from_crossterm(color) }
+}
+
+impl From<Effect> for Attributes {
+    /// See [`Effect::to_crossterm()`]. [`Effect::Unspecified`] becomes no attributes.
+    fn from(effect: Effect) -> Self {
+        match effect.to_crossterm() {
+            Some(attribute) => attribute.into(),
+            None            => Attributes::none(),
+        }
+    }
+}
+
+impl From<Attribute> for Effect {
+    /// See [`Effect::from_crossterm()`].
+    fn from(attribute: Attribute) -> Self { Effect::from_crossterm(attribute) }
+}
+
+impl From<Ansi> for ContentStyle {
+    /// See [`Ansi::to_crossterm()`].
+    fn from(ansi: Ansi) -> Self { ansi.to_crossterm() }
+}
+
+impl From<ContentStyle> for Ansi {
+    /// See [`Ansi::from_crossterm()`].
+    fn from(style: ContentStyle) -> Self { Ansi::from_crossterm(style) }
+}
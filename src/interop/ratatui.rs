@@ -0,0 +1,179 @@
+use crate::{Ansi, Colour, Effect};
+#[cfg(not(feature="ansi256"))]
+use crate::text::COLOURS;
+use ::ratatui::style::{Color, Modifier, Style};
+
+const MODIFIERS: [(Effect, Modifier); 8] = [
+    (Effect::Bold,      Modifier::BOLD),
+    (Effect::Faint,     Modifier::DIM),
+    (Effect::Italic,    Modifier::ITALIC),
+    (Effect::Underline, Modifier::UNDERLINED),
+    (Effect::Blink,     Modifier::SLOW_BLINK),
+    (Effect::Reverse,   Modifier::REVERSED),
+    (Effect::Hidden,    Modifier::HIDDEN),
+    (Effect::Strike,    Modifier::CROSSED_OUT),
+];
+
+impl Colour {
+    /// Converts to a [`ratatui`](::ratatui) colour, or `None` if [`Colour::Unspecified`].
+    ///
+    /// Note: ratatui's names for some colours differ from this crate's, e.g. [`Colour::White`]
+    /// is `Color::Gray`, and [`Colour::BrightRed`] is `Color::LightRed`.
+    ///
+    /// *Note: only available with `feature=ratatui`*
+    pub const fn to_ratatui(self) -> Option<Color> {
+        Some(match self {
+            Colour::Unspecified  => return None,
+            Colour::Reset        => Color::Reset,
+            Colour::Black        => Color::Black,
+            Colour::Red          => Color::Red,
+            Colour::Green        => Color::Green,
+            Colour::Yellow       => Color::Yellow,
+            Colour::Blue         => Color::Blue,
+            Colour::Purple       => Color::Magenta,
+            Colour::Cyan         => Color::Cyan,
+            Colour::White        => Color::Gray,
+            Colour::BrightBlack  => Color::DarkGray,
+            Colour::BrightRed    => Color::LightRed,
+            Colour::BrightGreen  => Color::LightGreen,
+            Colour::BrightYellow => Color::LightYellow,
+            Colour::BrightBlue   => Color::LightBlue,
+            Colour::BrightPurple => Color::LightMagenta,
+            Colour::BrightCyan   => Color::LightCyan,
+            Colour::BrightWhite  => Color::White,
+            #[cfg(feature="ansi256")]
+            Colour::Ansi256(num) => Color::Indexed(num),
+            #[cfg(feature="rgb")]
+            Colour::Rgb(r, g, b) => Color::Rgb(r, g, b),
+        })
+    }
+
+    /// Converts from a [`ratatui`](::ratatui) colour.
+    ///
+    /// This is lossy if the colour is not supported by the enabled features, i.e.
+    /// `Color::Indexed` requires `feature=ansi256` (except for values below 16, which
+    /// become named colours), and `Color::Rgb` requires `feature=rgb`. Unsupported
+    /// colours become [`Colour::Unspecified`].
+    ///
+    /// *Note: only available with `feature=ratatui`*
+    #[allow(unused_variables)]
+    pub const fn from_ratatui(color: Color) -> Colour {
+        match color {
+            Color::Reset        => Colour::Reset,
+            Color::Black        => Colour::Black,
+            Color::Red          => Colour::Red,
+            Color::Green        => Colour::Green,
+            Color::Yellow       => Colour::Yellow,
+            Color::Blue         => Colour::Blue,
+            Color::Magenta      => Colour::Purple,
+            Color::Cyan         => Colour::Cyan,
+            Color::Gray         => Colour::White,
+            Color::DarkGray     => Colour::BrightBlack,
+            Color::LightRed     => Colour::BrightRed,
+            Color::LightGreen   => Colour::BrightGreen,
+            Color::LightYellow  => Colour::BrightYellow,
+            Color::LightBlue    => Colour::BrightBlue,
+            Color::LightMagenta => Colour::BrightPurple,
+            Color::LightCyan    => Colour::BrightCyan,
+            Color::White        => Colour::BrightWhite,
+            #[cfg(feature="ansi256")]
+            Color::Indexed(num) => Colour::Ansi256(num),
+            #[cfg(not(feature="ansi256"))]
+            Color::Indexed(num) => if (num as usize) < COLOURS.len() { COLOURS[num as usize] } else { Colour::Unspecified },
+            #[cfg(feature="rgb")]
+            Color::Rgb(r, g, b) => Colour::Rgb(r, g, b),
+            #[cfg(not(feature="rgb"))]
+            Color::Rgb(..)      => Colour::Unspecified,
+        }
+    }
+}
+
+impl Ansi {
+    /// Converts to a [`ratatui`](::ratatui) style, e.g. for use in widgets.
+    ///
+    /// Unspecified colours become `None`, and effects such as [`Effect::NotBold`] become
+    /// removed modifiers. Protected attributes are not represented.
+    ///
+    /// *Note: only available with `feature=ratatui`*
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::Red, Effect::{Bold, Italic}};
+    /// use ratatui::style::{Color, Modifier, Style};
+    ///
+    /// const HEADING: Ansi  = ansi!(Red, Bold, Italic.not());
+    /// const STYLE:   Style = HEADING.to_ratatui();
+    ///
+    /// assert_eq!(STYLE, Style::new().fg(Color::Red).add_modifier(Modifier::BOLD).remove_modifier(Modifier::ITALIC));
+    /// assert_eq!(Ansi::from_ratatui(STYLE), HEADING);
+    /// ```
+    pub const fn to_ratatui(&self) -> Style {
+        let mut style = Style::new();
+        if let Some(fg) = self.colour().fg().to_ratatui() {
+            style = style.fg(fg);
+        }
+        if let Some(bg) = self.colour().bg().to_ratatui() {
+            style = style.bg(bg);
+        }
+        let mut i = 0;
+        while i < MODIFIERS.len() {
+            let (effect, modifier) = MODIFIERS[i];
+            if self.effect().has_effect(effect) {
+                style = style.add_modifier(modifier);
+            } else if self.effect().has_effect(effect.not()) {
+                style = style.remove_modifier(modifier);
+            }
+            i += 1;
+        }
+        style
+    }
+
+    /// Converts from a [`ratatui`](::ratatui) style.
+    ///
+    /// This is lossy for colours that have no equivalent, see [`Colour::from_ratatui()`].
+    /// `Modifier::RAPID_BLINK` becomes [`Effect::Blink`], and any underline colour is ignored.
+    ///
+    /// *Note: only available with `feature=ratatui`*
+    pub const fn from_ratatui(style: Style) -> Ansi {
+        let mut ansi = Ansi::unspecified();
+        if let Some(fg) = style.fg {
+            ansi = ansi.add(Colour::from_ratatui(fg).fg());
+        }
+        if let Some(bg) = style.bg {
+            ansi = ansi.add(Colour::from_ratatui(bg).bg());
+        }
+        let mut i = 0;
+        while i < MODIFIERS.len() {
+            let (effect, modifier) = MODIFIERS[i];
+            let is_rapid_blink = matches!(effect, Effect::Blink) && style.add_modifier.contains(Modifier::RAPID_BLINK);
+            if style.add_modifier.contains(modifier) || is_rapid_blink {
+                ansi = ansi.add(effect.ansi());
+            } else if style.sub_modifier.contains(modifier) {
+                ansi = ansi.add(effect.not().ansi());
+            }
+            i += 1;
+        }
+        ansi
+    }
+}
+
+impl From<Colour> for Color {
+    /// See [`Colour::to_ratatui()`]. [`Colour::Unspecified`] becomes `Color::Reset`.
+    fn from(colour: Colour) -> Self { colour.to_ratatui().unwrap_or(Color::Reset) }
+}
+
+impl From<Color> for Colour {
+    /// See [`Colour::from_ratatui()`].
+    fn from(color: Color) -> Self { Colour::from_ratatui(color) }
+}
+
+impl From<Ansi> for Style {
+    /// See [`Ansi::to_ratatui()`].
+    fn from(ansi: Ansi) -> Self { ansi.to_ratatui() }
+}
+
+impl From<Style> for Ansi {
+    /// See [`Ansi::from_ratatui()`].
+    fn from(style: Style) -> Self { Ansi::from_ratatui(style) }
+}
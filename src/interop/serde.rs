@@ -0,0 +1,146 @@
+use crate::{Ansi, Colour, Effect, Styled};
+use crate::ansi::{style_colour, ColourSpec};
+use crate::theme::{self, Slot, Theme};
+use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Deserializes a string, then parses it, failing with an error that names the
+/// `expected` values if it is invalid.
+fn parse_str<'de, D, T>(deserializer: D, parse: impl FnOnce(&str) -> Option<T>, expected: &str) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse(&s).ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(&s), &expected))
+}
+
+/// Serializes as its [`spec()`](Ansi::spec()) string, e.g. `"bold,fg=red,bg=blue"`.
+impl Serialize for Ansi {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.spec())
+    }
+}
+
+/// Deserializes from a [`spec()`](Ansi::spec()) string, e.g. `"bold,fg=red,bg=blue"`,
+/// or a [style string](Ansi::from_style_str()), e.g. `"bold red on_blue"`.
+impl<'de> Deserialize<'de> for Ansi {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ansi::from_spec(&s).or_else(|_| Ansi::from_style_str(&s)).map_err(de::Error::custom)
+    }
+}
+
+/// Serializes as its [`name()`](Colour::name()), e.g. `"bright_red"`, or as its number
+/// or hex RGB components, e.g. `"196"` or `"#ff0000"`, or as `""` if `Unspecified`.
+impl Serialize for Colour {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&ColourSpec(*self))
+    }
+}
+
+/// Deserializes from a string produced by serializing a `Colour`, ignoring case.
+impl<'de> Deserialize<'de> for Colour {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let parse = |s: &str| if s.is_empty() { Some(Colour::Unspecified) } else { style_colour(s.as_bytes(), 0, s.len()) };
+        parse_str(deserializer, parse, "a colour name, e.g. \"bright_red\"")
+    }
+}
+
+/// Serializes as its [`name()`](Effect::name()), e.g. `"bold"`, or as `""` if `Unspecified`.
+impl Serialize for Effect {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name().unwrap_or_default())
+    }
+}
+
+/// Deserializes from a string produced by serializing an `Effect`, ignoring case.
+impl<'de> Deserialize<'de> for Effect {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let parse = |s: &str| if s.is_empty() { Some(Effect::Unspecified) } else { Effect::by_name(s) };
+        parse_str(deserializer, parse, "an effect name, e.g. \"bold\"")
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename="Styled")]
+struct StyledRef<'a, T> { style: Ansi, text: &'a T }
+
+#[derive(Deserialize)]
+#[serde(rename="Styled", deny_unknown_fields)]
+struct StyledOwned<T> { style: Ansi, text: T }
+
+/// Serializes as a struct with fields `style` and `text`.
+impl<T: fmt::Display + Serialize> Serialize for Styled<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StyledRef { style: self.ansi(), text: self.target() }.serialize(serializer)
+    }
+}
+
+/// Deserializes from a struct with fields `style` and `text`.
+impl<'de, T: fmt::Display + Deserialize<'de>> Deserialize<'de> for Styled<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let StyledOwned { style, text } = StyledOwned::deserialize(deserializer)?;
+        Ok(Styled::new(style, text))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename="Theme", default, deny_unknown_fields)]
+struct ThemeRepr {
+    heading:    Ansi,
+    subheading: Ansi,
+    emphasis:   Ansi,
+    muted:      Ansi,
+    info:       Ansi,
+    success:    Ansi,
+    warning:    Ansi,
+    error:      Ansi,
+}
+
+impl From<Theme> for ThemeRepr {
+    fn from(theme: Theme) -> Self {
+        Self {
+            heading:    theme.get(Slot::Heading),
+            subheading: theme.get(Slot::Subheading),
+            emphasis:   theme.get(Slot::Emphasis),
+            muted:      theme.get(Slot::Muted),
+            info:       theme.get(Slot::Info),
+            success:    theme.get(Slot::Success),
+            warning:    theme.get(Slot::Warning),
+            error:      theme.get(Slot::Error),
+        }
+    }
+}
+
+impl From<ThemeRepr> for Theme {
+    fn from(repr: ThemeRepr) -> Self {
+        Theme::new()
+            .with(Slot::Heading,    repr.heading)
+            .with(Slot::Subheading, repr.subheading)
+            .with(Slot::Emphasis,   repr.emphasis)
+            .with(Slot::Muted,      repr.muted)
+            .with(Slot::Info,       repr.info)
+            .with(Slot::Success,    repr.success)
+            .with(Slot::Warning,    repr.warning)
+            .with(Slot::Error,      repr.error)
+    }
+}
+
+impl Default for ThemeRepr {
+    fn default() -> Self { theme::DEFAULT.into() }
+}
+
+/// Serializes as a struct with a field per [`Slot`], e.g. `heading`.
+impl Serialize for Theme {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ThemeRepr::from(*self).serialize(serializer)
+    }
+}
+
+/// Deserializes from a struct with a field per [`Slot`], e.g. `heading`, where
+/// missing fields have the style of the [`DEFAULT`](theme::DEFAULT) theme.
+impl<'de> Deserialize<'de> for Theme {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ThemeRepr::deserialize(deserializer).map(Theme::from)
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{Colour, Effect};
+#[cfg(not(feature="ansi256"))]
+use crate::text::COLOURS;
+use ::termion::{color, style};
+use std::fmt;
+
+/// Allows a [`Colour`] to be used wherever [`termion`](::termion) expects a colour,
+/// e.g. `termion::color::Fg(Colour::Red)`, including in `const` context.
+///
+/// *Note: only available with `feature=termion`*
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::Colour;
+/// use termion::color::{Fg, Bg};
+///
+/// const ERROR: Fg<Colour> = Fg(Colour::Red);
+///
+/// assert_eq!(ERROR.to_string(),               "\x1B[31m");
+/// assert_eq!(Bg(Colour::BrightBlue).to_string(), "\x1B[104m");
+/// assert_eq!(Colour::from(termion::color::LightBlue), Colour::BrightBlue);
+/// ```
+impl color::Color for Colour {
+    fn write_fg(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Display::fmt(&self.fg(), f) }
+    fn write_bg(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Display::fmt(&self.bg(), f) }
+}
+
+macro_rules! impl_from {
+    ($($from:path => $to:expr,)*) => {$(
+        impl From<$from> for Colour {
+            fn from(_: $from) -> Self { $to }
+        }
+    )*};
+}
+
+macro_rules! impl_from_style {
+    ($($from:path => $to:expr,)*) => {$(
+        impl From<$from> for Effect {
+            fn from(_: $from) -> Self { $to }
+        }
+    )*};
+}
+
+impl_from! {
+    color::Reset        => Colour::Reset,
+    color::Black        => Colour::Black,
+    color::Red          => Colour::Red,
+    color::Green        => Colour::Green,
+    color::Yellow       => Colour::Yellow,
+    color::Blue         => Colour::Blue,
+    color::Magenta      => Colour::Purple,
+    color::Cyan         => Colour::Cyan,
+    color::White        => Colour::White,
+    color::LightBlack   => Colour::BrightBlack,
+    color::LightRed     => Colour::BrightRed,
+    color::LightGreen   => Colour::BrightGreen,
+    color::LightYellow  => Colour::BrightYellow,
+    color::LightBlue    => Colour::BrightBlue,
+    color::LightMagenta => Colour::BrightPurple,
+    color::LightCyan    => Colour::BrightCyan,
+    color::LightWhite   => Colour::BrightWhite,
+}
+
+impl From<color::AnsiValue> for Colour {
+    /// Lossy without `feature=ansi256`: values below 16 become named colours,
+    /// and other values become [`Colour::Unspecified`].
+    fn from(value: color::AnsiValue) -> Self {
+        #[cfg(feature="ansi256")]
+        return Colour::Ansi256(value.0);
+        #[cfg(not(feature="ansi256"))]
+        return COLOURS.get(value.0 as usize).copied().unwrap_or(Colour::Unspecified);
+    }
+}
+
+impl From<color::Rgb> for Colour {
+    /// Lossy without `feature=rgb`: all values become [`Colour::Unspecified`].
+    #[allow(unused_variables)]
+    fn from(rgb: color::Rgb) -> Self {
+        #[cfg(feature="rgb")]
+        return Colour::Rgb(rgb.0, rgb.1, rgb.2);
+        #[cfg(not(feature="rgb"))]
+        return Colour::Unspecified;
+    }
+}
+
+impl_from_style! {
+    style::Bold         => Effect::Bold,
+    style::NoBold       => Effect::NotBold,
+    style::Faint        => Effect::Faint,
+    style::NoFaint      => Effect::NotFaint,
+    style::Italic       => Effect::Italic,
+    style::NoItalic     => Effect::NotItalic,
+    style::Underline    => Effect::Underline,
+    style::NoUnderline  => Effect::NotUnderline,
+    style::Blink        => Effect::Blink,
+    style::NoBlink      => Effect::NotBlink,
+    style::Invert       => Effect::Reverse,
+    style::NoInvert     => Effect::NotReverse,
+    style::CrossedOut   => Effect::Strike,
+    style::NoCrossedOut => Effect::NotStrike,
+}
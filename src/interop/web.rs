@@ -0,0 +1,76 @@
+use crate::{Ansi, Colour, Effect};
+#[cfg(feature="ansi256")]
+use crate::ansi::ansi256_rgb;
+use crate::ansi::PALETTE;
+use crate::text::COLOURS;
+
+impl Colour {
+    /// Converts to a CSS colour, e.g. `"#cd0000"` for [`Colour::Red`], or `None`
+    /// if [`Colour::Unspecified`] or [`Colour::Reset`], i.e. the console's default.
+    ///
+    /// The 16 named colours use xterm's default palette, as do [`Ansi256`](Colour::Ansi256) colours.
+    ///
+    /// *Note: only available with `feature=web`*
+    pub fn to_css(self) -> Option<String> {
+        let (r, g, b) = match self {
+            Colour::Unspecified | Colour::Reset => return None,
+            #[cfg(feature="ansi256")]
+            Colour::Ansi256(num) => ansi256_rgb(num),
+            #[cfg(feature="rgb")]
+            Colour::Rgb(r, g, b) => (r, g, b),
+            named => PALETTE[COLOURS.iter().position(|c| *c == named).unwrap_or(0)],
+        };
+        Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+    }
+}
+
+impl Ansi {
+    /// Converts to CSS declarations, e.g. for styling text in a browser's console with `%c`
+    /// (see [`text::console_args()`](crate::text::console_args())), or for HTML.
+    ///
+    /// Only the attributes that this style *sets* are converted, i.e. unspecified colours and
+    /// effects such as [`Effect::NotBold`] become the console's defaults. [`Effect::Blink`]
+    /// has no equivalent, and [`Effect::Reverse`] swaps the colours that are specified.
+    ///
+    /// *Note: only available with `feature=web`*
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, Colour::{Red, Blue}, Effect::{Bold, Underline}};
+    ///
+    /// assert_eq!(ansi!(Red, Blue.bg(), Bold, Underline).to_css(),
+    ///     "color: #cd0000; background-color: #0000ee; font-weight: bold; text-decoration: underline");
+    /// assert_eq!(Ansi::unspecified().to_css(), "");
+    /// ```
+    pub fn to_css(&self) -> String {
+        let effect = self.effect();
+        let (mut fg, mut bg) = (self.colour().fg(), self.colour().bg());
+        if effect.has_effect(Effect::Reverse) {
+            (fg, bg) = (bg, fg);
+        }
+        let mut css = Vec::new();
+        if effect.has_effect(Effect::Hidden) {
+            css.push("color: transparent".to_string());
+        } else if let Some(fg) = fg.to_css() {
+            css.push(format!("color: {}", fg));
+        }
+        if let Some(bg) = bg.to_css() {
+            css.push(format!("background-color: {}", bg));
+        }
+        if effect.has_effect(Effect::Bold) {
+            css.push("font-weight: bold".to_string());
+        } else if effect.has_effect(Effect::Faint) {
+            css.push("font-weight: lighter".to_string());
+        }
+        if effect.has_effect(Effect::Italic) {
+            css.push("font-style: italic".to_string());
+        }
+        let decorations: Vec<&str> = [(Effect::Underline, "underline"), (Effect::Strike, "line-through")]
+            .into_iter().filter(|(e, _)| effect.has_effect(*e)).map(|(_, decoration)| decoration).collect();
+        if !decorations.is_empty() {
+            css.push(format!("text-decoration: {}", decorations.join(" ")));
+        }
+        css.join("; ")
+    }
+}
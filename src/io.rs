@@ -16,13 +16,44 @@
 //!
 //! 1. Detecting the ANSI-styling capability of a `Writer` or `Stream` at runtime.
 //! 2. Configuring a `Writer` or `Stream` to automatically disable/override nested ANSI
-//! styles during writes.
+//!    styles during writes.
+//! 3. Configuring a `Writer` or `Stream` to remap nested ANSI styles during writes,
+//!    using a [style map](crate::map).
+//! 4. Configuring a `Writer` or `Stream` to limit the bytes of ANSI escape sequences
+//!    written, using a [style budget](AnsiWrite::set_style_budget()).
+//! 5. Configuring a `Writer` or `Stream` to start each line written with a styled
+//!    [`LinePrefix`], e.g. a timestamp or log level.
+//! 6. Configuring a `Writer` or `Stream` to [sanitize](AnsiWrite::set_sanitize()) untrusted
+//!    output, i.e. to strip unsafe escape sequences.
+//! 7. Configuring how a `Writer` or `Stream` [composes](Composition) its default style with
+//!    nested ANSI styles.
+//! 8. Routing long output written to `stdout` through a [`Pager`].
+//! 9. Asking the user for input with a styled [`Prompt`].
+//! 10. Emphasizing a status line by cycling its style, with [`Emphasis`].
+//! 11. Recording styled output with its timing, with a [`Recorder`].
+//! 12. Reporting how styling capabilities were detected, with [`capability_report()`],
+//!     e.g. for a `--debug-colors` flag.
+//! 13. Writing styled output in size-limited chunks that never split escape sequences,
+//!     with a [`ChunkedWriter`].
+//! 14. Redirecting output written to `stdout` by [`paint!`](crate::paint) etc. to a global
+//!     `Writer` registered at startup, with `feature=global-writer`.
+//! 15. Showing a status line, e.g. progress, that is rewritten in place at a limited rate,
+//!     with a [`StatusLine`].
+//! 16. Removing all ANSI escape sequences, including those from foreign sources, from
+//!     output written to a `Writer`, e.g. a log file, with a [`StripWriter`].
+//! 17. Enabling ANSI escape sequences on the Windows console, with `feature=windows`.
+//! 18. Recording the style of each span of output as it is written, without keeping the
+//!     output, with a [`SpanRecorder`].
+//! 19. Overriding a `Writer`'s default ANSI style until the end of a scope, with
+//!     [`scoped_ansi()`](AnsiWrite::scoped_ansi()).
 //!
 //! The above support is available as follows:
 //!
 //! - To set the default ANSI style for an existing `Writer`, wrap it in an [`AnsiWriter`].
+//! - To set the default ANSI style for an existing [`fmt::Write`](std::fmt::Write), e.g. a
+//!   `String`, wrap it in an [`AnsiFmtWriter`].
 //! - To set the default ANSI style when printing to `stdout`, `stderr`, use [`ansiout()`]
-//! and [`ansierr()`].
+//!   and [`ansierr()`].
 //!
 //! *Note:* in order to configure the default ANSI style, trait [`AnsiWrite`] must be in scope.
 //!
@@ -64,14 +95,49 @@
 //! paintln!(Purple, "Purple");
 //! ```
 
+mod capability;
+mod chunk;
+mod emphasis;
+mod fmt_writer;
+#[cfg(feature="global-writer")]
+mod global;
+mod pager;
+mod prompt;
+mod record;
+mod reset;
+mod scoped;
+mod span;
+mod status;
 mod stream;
+mod strip;
+#[cfg(feature="windows")]
+mod windows;
 mod writer;
 
+pub use capability::*;
+pub use chunk::*;
+pub use emphasis::*;
+pub use fmt_writer::*;
+#[cfg(feature="global-writer")]
+pub use global::*;
+pub use pager::*;
+pub use prompt::*;
+pub use record::*;
+pub use reset::*;
+pub use scoped::*;
+pub use span::*;
+pub use status::*;
 pub use stream::*;
+pub use strip::*;
+#[cfg(feature="windows")]
+pub use windows::*;
 pub use writer::*;
 
-use std::{env, io};
-use crate::Ansi;
+use std::{env, fmt, io, str};
+use crate::{styled_write, text::Sanitize, Ansi};
+use crate::fmt::{with_fallback, with_options, WriteOptions};
+use crate::text::parse_sgr;
+use crate::map::StyleMap;
 
 /// Used to indicate if ANSI styles can/should be written by a `Writer`.
 ///
@@ -92,13 +158,23 @@ pub trait AnsiPreference {
     /// Determines if ANSI codes should be *enabled* because the`FORCE_COLOR`
     /// env variable has been set.
     fn is_ansi_forced(&self) -> bool {
-        env::var_os("FORCE_COLOR").unwrap_or("".into()).len() > 0
+        !env::var_os("FORCE_COLOR").unwrap_or("".into()).is_empty()
     }
 
     /// Determines if ANSI codes should be *disabled* because the`NO_COLOR`
     /// env variable has been set.
     fn is_ansi_banned(&self) -> bool {
-        env::var_os("NO_COLOR").unwrap_or("".into()).len() > 0
+        !env::var_os("NO_COLOR").unwrap_or("".into()).is_empty()
+    }
+
+    /// Creates the default [`StyleMap`] of this `Writer`, which is [`map::from_env()`](crate::map::from_env()),
+    /// i.e. [`map::no_backgrounds()`](crate::map::no_backgrounds()) if the `ANSICONST_NO_BG` env variable
+    /// has been set, else `None`.
+    ///
+    /// Used by [`ansiout()`] and [`ansierr()`], whose style map may still be changed with
+    /// [`set_style_map()`](AnsiWrite::set_style_map()).
+    fn preferred_style_map(&self) -> Option<StyleMap> {
+        crate::map::from_env()
     }
 
     /// Creates an [`Ansi`] intended to be used to enable/disable ANSI styles
@@ -161,6 +237,178 @@ pub trait AnsiWrite: io::Write + AnsiPreference {
     /// ```
     fn set_ansi(&mut self, ansi: Ansi);
 
+    /// Gets this `Writer`'s [`StyleMap`], if any.
+    ///
+    /// See [`set_style_map`](AnsiWrite::set_style_map).
+    fn style_map(&self) -> Option<StyleMap>;
+
+    /// Sets this `Writer`'s [`StyleMap`], which rewrites each nested ANSI style in the
+    /// [`Arguments`](std::fmt::Arguments) passed to [`write_fmt()`](io::Write::write_fmt())
+    /// before it is written. `None` disables remapping.
+    ///
+    /// See the [`map`](crate::map) module for the provided style maps.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, io::*, Colour::*, Effect::Bold};
+    ///
+    /// io::ansiout().set_style_map(Some(map::bold_as_bright));
+    /// paintln!(Red, Bold, "Hello world");
+    /// // Prints "\x1B[91mHello world\x1B[39m", i.e. bright red colour (not bold)
+    ///
+    /// io::ansiout().set_style_map(None);
+    /// ```
+    fn set_style_map(&mut self, map: Option<StyleMap>);
+
+    /// Gets this `Writer`'s style budget, if any.
+    ///
+    /// See [`set_style_budget`](AnsiWrite::set_style_budget).
+    fn style_budget(&self) -> Option<usize>;
+
+    /// Sets this `Writer`'s style budget, i.e. the maximum number of bytes of ANSI escape
+    /// sequences written by each call to [`write_fmt()`](io::Write::write_fmt()).
+    /// `None` disables the budget.
+    ///
+    /// This is intended for constrained transports, e.g. serial consoles or size-limited logs.
+    /// Nested ANSI styles that would exceed the budget are degraded deterministically:
+    /// first their [`Effect`](crate::Effect)s are dropped and then, if still over budget,
+    /// their [`Colour`](crate::Colour)s too. Text is never dropped.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, io::*, Colour::*, Effect::Bold};
+    ///
+    /// io::ansiout().set_style_budget(Some(10));
+    /// paintln!("{}", styled!(Red, Bold, "Hello world"));
+    /// // Prints "\x1B[31mHello world\x1B[39m", i.e. red colour (not bold)
+    /// // because "\x1B[1;31m" and "\x1B[22;39m" together need 15 bytes
+    ///
+    /// io::ansiout().set_style_budget(None);
+    /// ```
+    fn set_style_budget(&mut self, budget: Option<usize>);
+
+    /// Gets this `Writer`'s [`FlushPolicy`].
+    ///
+    /// See [`set_flush_policy`](AnsiWrite::set_flush_policy).
+    fn flush_policy(&self) -> FlushPolicy;
+
+    /// Sets this `Writer`'s [`FlushPolicy`], which determines when it is flushed
+    /// after calls to [`write_fmt()`](io::Write::write_fmt()).
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, io::*, Colour::Red};
+    ///
+    /// // Ensure a prompt is shown, although it does not end with a newline
+    /// io::ansiout().set_flush_policy(FlushPolicy::Always);
+    /// paint!(Red, "Continue? [y/n] ");
+    ///
+    /// io::ansiout().set_flush_policy(FlushPolicy::Manual);
+    /// ```
+    fn set_flush_policy(&mut self, policy: FlushPolicy);
+
+    /// Gets this `Writer`'s [`LinePrefix`], if any.
+    ///
+    /// See [`set_line_prefix`](AnsiWrite::set_line_prefix).
+    fn line_prefix(&self) -> Option<LinePrefix>;
+
+    /// Sets this `Writer`'s [`LinePrefix`], which is written at the start of each line
+    /// written by [`write_fmt()`](io::Write::write_fmt()). `None` disables the prefix.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, io::*, Colour::{Blue, Red}};
+    ///
+    /// io::ansiout().set_line_prefix(Some(LinePrefix::new(ansi!(Blue), |f| f.write_str("[app] "))));
+    /// paintln!(Red, "Hello\nworld");
+    /// // Prints "\x1B[34m[app] \x1B[39m\x1B[31mHello\n\x1B[34m[app] \x1B[31mworld\x1B[39m\n"
+    ///
+    /// io::ansiout().set_line_prefix(None);
+    /// ```
+    fn set_line_prefix(&mut self, prefix: Option<LinePrefix>);
+
+    /// Gets this `Writer`'s [`Sanitize`] mode, if any.
+    ///
+    /// See [`set_sanitize`](AnsiWrite::set_sanitize).
+    fn sanitize(&self) -> Option<Sanitize>;
+
+    /// Sets this `Writer`'s [`Sanitize`] mode, which is applied to all output written by
+    /// [`write_fmt()`](io::Write::write_fmt()), so that untrusted input (e.g. filenames,
+    /// network data) cannot spoof or corrupt the terminal. `None` disables sanitizing.
+    ///
+    /// Only SGR (colour/effect) escape sequences are preserved, see
+    /// [`Sanitize::apply()`]. [`LinePrefix`]es are not sanitized.
+    ///
+    /// [`Sanitize::Reveal`] instead makes all escape sequences visible, e.g. `"␛[31m"`,
+    /// so that the exact output can be inspected.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, io::*, text::Sanitize, Colour::Red};
+    ///
+    /// let filename = "evil\x1B]0;pwned\x07.txt";
+    ///
+    /// io::ansiout().set_sanitize(Some(Sanitize::Escape));
+    /// paintln!(Red, "Not found: {}", filename);
+    /// // Prints "\x1B[31mNot found: evil\\u{1b}]0;pwned\\u{7}.txt\x1B[39m"
+    ///
+    /// io::ansiout().set_sanitize(None);
+    /// ```
+    fn set_sanitize(&mut self, mode: Option<Sanitize>);
+
+    /// Gets this `Writer`'s [`Composition`].
+    ///
+    /// See [`set_composition`](AnsiWrite::set_composition).
+    fn composition(&self) -> Composition;
+
+    /// Sets this `Writer`'s [`Composition`], which determines how its default
+    /// [`Ansi`](AnsiWrite::ansi()) style is composed with the styles nested in the
+    /// [`Arguments`](std::fmt::Arguments) passed to [`write_fmt()`](io::Write::write_fmt()).
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, io::*, Colour::{Blue, Red}, Effect::Bold};
+    ///
+    /// io::ansiout().set_ansi(ansi!(Blue));
+    /// paintln!("Plain {}", styled!(Bold, "bold"));
+    /// // Prints "\x1B[34mPlain \x1B[1mbold\x1B[22m\x1B[39m", i.e. blue bold text
+    ///
+    /// io::ansiout().set_composition(Composition::Fallback);
+    /// paintln!("Plain {}", styled!(Bold, "bold"));
+    /// // Prints "\x1B[34mPlain \x1B[1;39mbold\x1B[22;34m\x1B[39m", i.e. bold text that is not blue
+    ///
+    /// io::ansiout().set_composition(Composition::Outer);
+    /// io::ansiout().all_ansi();
+    /// ```
+    fn set_composition(&mut self, composition: Composition);
+
+    /// Gets the most recent style emitted by this `Writer`'s writes via
+    /// [`write_fmt()`](io::Write::write_fmt()), i.e. the style of the last styled text
+    /// written, according to the SGR sequences written. Returns [`Ansi::unspecified()`]
+    /// if no styles have been emitted.
+    ///
+    /// This is intended for diagnostics, e.g. to check the effect of a [style map](crate::map)
+    /// or [style budget](AnsiWrite::set_style_budget()).
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, io::*, Colour::Blue, Effect::Bold};
+    ///
+    /// io::ansiout().all_ansi();
+    /// assert_eq!(io::ansiout().last_style(), Ansi::unspecified());
+    ///
+    /// paintln!(Blue, Bold, "Hello world");
+    /// assert_eq!(io::ansiout().last_style(), ansi!(Blue, Bold));
+    /// ```
+    fn last_style(&self) -> Ansi;
+
     /// Determines whether this `Writer`'s default [`Ansi`](AnsiWrite::ansi()) style prohibits
     /// writing of all nested ANSI styles.
     fn is_no_ansi(&self) -> bool {
@@ -208,4 +456,287 @@ pub trait AnsiWrite: io::Write + AnsiPreference {
     fn auto_ansi(&mut self) {
         self.set_ansi(AnsiPreference::preferred_ansi(self))
     }
+
+    /// Overrides this `Writer`'s default [`Ansi`](AnsiWrite::set_ansi()) style until the
+    /// returned [`ScopedAnsi`] guard is dropped, which then restores the previous style.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, io::*, Colour::Red};
+    ///
+    /// {
+    ///     let _guard = io::ansiout().scoped_ansi(Ansi::no_ansi());
+    ///     paintln!(Red, "Hello world");
+    ///     // Prints "Hello world", i.e. without any ANSI codes
+    /// }
+    /// paintln!(Red, "Hello world");
+    /// // Prints "\x1B[31mHello world\x1B[39m", i.e. red colour, if stdout is a terminal
+    /// ```
+    fn scoped_ansi(self, ansi: Ansi) -> ScopedAnsi<Self> where Self: Sized {
+        ScopedAnsi::new(self, ansi)
+    }
+}
+
+/// Determines when an [`AnsiWrite`] is flushed after calls to [`write_fmt()`](io::Write::write_fmt()).
+///
+/// See [`set_flush_policy`](AnsiWrite::set_flush_policy).
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum FlushPolicy {
+    /// Never flush automatically, i.e. only flush when [`flush()`](io::Write::flush()) is
+    /// called, or when the inner `Writer` flushes itself (e.g. [`Stdout`](io::Stdout) is
+    /// line-buffered).
+    #[default]
+    Manual,
+    /// Flush after each write that contains a newline.
+    Line,
+    /// Flush after every write.
+    Always,
+}
+
+/// Determines how an [`AnsiWrite`]'s default [`Ansi`](AnsiWrite::ansi()) style is composed
+/// with the styles nested in its writes.
+///
+/// In either case, [protected](Ansi::protect_attrs()) attributes of the default style
+/// are never changed by nested styles, e.g. [`Ansi::no_ansi()`] still disables them.
+///
+/// See [`set_composition`](AnsiWrite::set_composition).
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum Composition {
+    /// The default style is the outer style of each write, i.e. nested styles are
+    /// added to it as if the whole write were [`Styled`](crate::Styled) with it.
+    #[default]
+    Outer,
+    /// The default style applies only to text that is not nested in another style,
+    /// i.e. nested styles do not inherit its unprotected attributes.
+    Fallback,
+}
+
+/// A styled prefix written by an [`AnsiWrite`] at the start of each line, e.g. a
+/// timestamp or log level.
+///
+/// The prefix is written by a function, so it may differ for each line. It is styled
+/// only with its own [`Ansi`] style, i.e. independently of any style that is active
+/// where the line starts, which is restored afterwards. Like any other nested style,
+/// its style is subject to the `Writer`'s default [`Ansi`](AnsiWrite::ansi()) style,
+/// e.g. it is unstyled if that is [`Ansi::no_ansi()`].
+///
+/// The prefix is only written once a line has a visible char (including its newline), so
+/// that e.g. the reset written at the end of `paint!(Red, "Done\n")` does not start a line.
+///
+/// See [`set_line_prefix`](AnsiWrite::set_line_prefix).
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, io::*, Colour::Green};
+/// use std::time::{SystemTime, UNIX_EPOCH};
+///
+/// const TIMESTAMP: LinePrefix = LinePrefix::new(ansi!(Green), |f| {
+///     let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+///     write!(f, "{:>10} ", secs)
+/// });
+///
+/// let mut writer = AnsiFmtWriter::new(String::new(), Ansi::unspecified());
+/// writer.set_line_prefix(Some(TIMESTAMP));
+/// writeln!(writer, "Started").unwrap();
+///
+/// // E.g. "\x1B[32m1700000000 \x1B[39mStarted\n"
+/// let output = writer.into_inner();
+/// assert!(output.starts_with("\x1B[32m") && output.ends_with(" \x1B[39mStarted\n"));
+///
+/// // Likewise for the output of paint!, paintln! etc.
+/// io::ansiout().set_line_prefix(Some(TIMESTAMP));
+/// paintln!("Started");
+/// io::ansiout().set_line_prefix(None);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct LinePrefix {
+    ansi:  Ansi,
+    write: fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+}
+
+impl LinePrefix {
+    /// Creates a new instance with the given style, whose text is written by `write`.
+    #[inline]
+    pub const fn new(ansi: Ansi, write: fn(&mut fmt::Formatter<'_>) -> fmt::Result) -> Self {
+        Self { ansi, write }
+    }
+    /// Gets this prefix's style.
+    #[inline]
+    pub const fn ansi(&self) -> Ansi { self.ansi }
+}
+
+struct PrefixText(fn(&mut fmt::Formatter<'_>) -> fmt::Result);
+
+impl fmt::Display for PrefixText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { (self.0)(f) }
+}
+
+/// Writes `fmt` to `w` using the default `ansi` style and the given `options`,
+/// updating the options' record of whether the next write starts a new line.
+///
+/// Returns the style left active in the output, according to the SGR sequences written,
+/// which is normally [`Ansi::unspecified()`].
+pub(crate) fn write_fmt<W: io::Write>(w: &mut W, ansi: Ansi, options: &mut WriteOptions, fmt: fmt::Arguments<'_>) -> io::Result<Ansi> {
+    let mut w = LineWriter {
+        inner:         w,
+        ansi,
+        active:        Ansi::unspecified(),
+        escape:        None,
+        prefix:        options.line_prefix,
+        at_line_start: options.at_line_start,
+        held:          Vec::new(),
+        has_newline:   false,
+        last_style:    options.last_style,
+    };
+    let result = match options.sanitize {
+        // Note: escape sequences may be split across several writes, so sanitize all at once
+        Some(mode) => {
+            let mut buf = Vec::new();
+            write_styled(&mut buf, ansi, *options, fmt)
+                .and_then(|_| io::Write::write_all(&mut w, mode.apply(&String::from_utf8_lossy(&buf)).as_bytes()))
+        },
+        None => write_styled(&mut w, ansi, *options, fmt),
+    };
+    let result = result.and_then(|_| w.release());
+    options.at_line_start = w.at_line_start;
+    options.last_style    = w.last_style;
+    result?;
+    match options.flush_policy {
+        FlushPolicy::Manual => {},
+        FlushPolicy::Line   => if w.has_newline { w.inner.flush()? },
+        FlushPolicy::Always => w.inner.flush()?,
+    }
+    Ok(w.active)
+}
+
+fn write_styled<W: io::Write>(w: &mut W, ansi: Ansi, options: WriteOptions, fmt: fmt::Arguments<'_>) -> io::Result<()> {
+    with_options(options, || {
+        if ! ansi.is_empty() {
+            match options.composition {
+                Composition::Outer    => styled_write!(w, ansi, "{}", fmt),
+                Composition::Fallback => styled_write!(w, ansi, "{}", Fallback(ansi, fmt)),
+            }
+        } else {
+            w.write_fmt(fmt)
+        }
+    })
+}
+
+/// Formats its target such that styles nested directly in it do not inherit the
+/// unprotected attributes of the given default style, see [`Composition::Fallback`].
+struct Fallback<'a>(Ansi, fmt::Arguments<'a>);
+
+impl fmt::Display for Fallback<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Fallback(ansi, fmt) = self;
+        with_fallback(ansi.filter(ansi.protected_attrs().complement()).not(), || f.write_fmt(*fmt))
+    }
+}
+
+/// Writes a [`LinePrefix`], if any, at the start of each line written to the inner
+/// `Writer`, and records whether any newlines were written.
+struct LineWriter<'a, W: io::Write> {
+    inner:         &'a mut W,
+    /// The `Writer`'s default style.
+    ansi:          Ansi,
+    /// The style active in the output so far, according to the SGR sequences written.
+    active:        Ansi,
+    /// An incomplete escape sequence written so far.
+    escape:        Option<Vec<u8>>,
+    prefix:        Option<LinePrefix>,
+    at_line_start: bool,
+    /// Escape sequences written at the start of a line, which are held until the line's
+    /// first visible char, so that the prefix is not written for a line without any.
+    held:          Vec<u8>,
+    has_newline:   bool,
+    /// The style of the most recent text written with a style.
+    last_style:    Ansi,
+}
+
+impl<W: io::Write> LineWriter<'_, W> {
+    /// Determines whether `buf`, written after any incomplete escape sequence written
+    /// so far, contains any chars that are not part of an escape sequence.
+    fn has_visible(&self, buf: &[u8]) -> bool {
+        // The length of the escape sequence so far, and whether it is a CSI sequence
+        let mut escape = self.escape.as_ref().map(|escape| (escape.len(), escape.get(1) == Some(&b'[')));
+        for &b in buf {
+            escape = match escape {
+                None if b == 0x1B => Some((1, false)),
+                None              => return true,
+                Some((len, is_csi)) => {
+                    let is_csi = if len == 1 { b == b'[' } else { is_csi };
+                    if !is_csi || (len > 1 && (0x40..=0x7E).contains(&b)) { None } else { Some((len + 1, is_csi)) }
+                },
+            };
+        }
+        false
+    }
+
+    /// Writes any held escape sequences, e.g. at the end of a write.
+    fn release(&mut self) -> io::Result<()> {
+        if self.held.is_empty() {
+            return Ok(());
+        }
+        let held = std::mem::take(&mut self.held);
+        self.inner.write_all(&held)?;
+        self.track_sgr(&held);
+        Ok(())
+    }
+
+    /// Updates the active style according to any SGR sequences in `buf`, and the
+    /// last style according to any text in `buf`.
+    ///
+    /// Note: escape sequences may be split across several writes.
+    fn track_sgr(&mut self, buf: &[u8]) {
+        // Fast path for text without any escape sequences
+        if self.escape.is_none() && !buf.contains(&0x1B) {
+            if !buf.is_empty() && !self.active.is_unspecified() {
+                self.last_style = self.active;
+            }
+            return;
+        }
+        for &b in buf {
+            match &mut self.escape {
+                None if b == 0x1B => self.escape = Some(vec![b]),
+                None              => if !self.active.is_unspecified() { self.last_style = self.active },
+                Some(escape)      => {
+                    escape.push(b);
+                    let is_csi = escape[1] == b'[';
+                    if !is_csi || (escape.len() > 2 && (0x40..=0x7E).contains(&b)) {
+                        if let Some(sgr) = str::from_utf8(escape).ok().and_then(parse_sgr) {
+                            self.active = sgr.apply(self.active);
+                        }
+                        self.escape = None;
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<W: io::Write> io::Write for LineWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for line in buf.split_inclusive(|b| *b == b'\n') {
+            if let (true, Some(prefix)) = (self.at_line_start, self.prefix) {
+                self.held.extend_from_slice(line);
+                if !self.has_visible(&self.held) {
+                    continue;
+                }
+                // Style the prefix independently of the active style, then restore it
+                let ansi = self.ansi.add(prefix.ansi);
+                write!(self.inner, "{}{}{}",
+                    self.active.transition(ansi), PrefixText(prefix.write), ansi.transition(self.active))?;
+                self.release()?;
+            } else {
+                self.inner.write_all(line)?;
+                self.track_sgr(line);
+            }
+            self.at_line_start = line.ends_with(b"\n");
+            self.has_newline  |= self.at_line_start;
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
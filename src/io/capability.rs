@@ -0,0 +1,247 @@
+use std::env;
+use std::fmt;
+use std::io::{self, IsTerminal};
+
+use super::{ansierr, ansiout, is_coordinated, terminal_width, AnsiWrite};
+
+/// The env variables that affect styled output, in the order reported.
+const ENV_VARS: [&str; 5] = ["FORCE_COLOR", "NO_COLOR", "TERM", "COLORTERM", "COLUMNS"];
+
+/// The colours supported by a terminal, from least to most.
+///
+/// See [`colour_support()`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
+pub enum ColourSupport {
+    /// No colours, e.g. `TERM=dumb`.
+    Monochrome,
+    /// The 16 basic [`Colour`](crate::Colour)s, i.e. `Black` to `BrightWhite`.
+    Ansi16,
+    /// 8-bit [`Ansi256`](crate::Colour::Ansi256) colours.
+    Ansi256,
+    /// 24-bit [`Rgb`](crate::Colour::Rgb) colours.
+    Rgb,
+}
+
+impl fmt::Display for ColourSupport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Monochrome => "monochrome",
+            Self::Ansi16     => "16 colours",
+            Self::Ansi256    => "256 colours",
+            Self::Rgb        => "24-bit RGB colours",
+        })
+    }
+}
+
+/// Detects the [`ColourSupport`] of the terminal from the `COLORTERM` and `TERM`
+/// env variables, as follows:
+///
+/// 1. `COLORTERM=truecolor` or `COLORTERM=24bit` means [`Rgb`](ColourSupport::Rgb).
+/// 2. `TERM=dumb` means [`Monochrome`](ColourSupport::Monochrome).
+/// 3. A `TERM` containing `256color` means [`Ansi256`](ColourSupport::Ansi256),
+///    e.g. `TERM=xterm-256color`.
+/// 4. Otherwise, [`Ansi16`](ColourSupport::Ansi16).
+///
+/// *Note:* this does not consider whether ANSI styles are enabled at all, e.g.
+/// whether `stdout` is a terminal. See [`AnsiPreference`](super::AnsiPreference) for that.
+pub fn colour_support() -> ColourSupport { detect_colour_support().0 }
+
+/// Gets the detected [`ColourSupport`] and the reason for it.
+fn detect_colour_support() -> (ColourSupport, &'static str) {
+    let colorterm = env::var("COLORTERM").unwrap_or_default();
+    let term = env::var("TERM").unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        (ColourSupport::Rgb, "COLORTERM indicates 24-bit colour")
+    } else if term == "dumb" {
+        (ColourSupport::Monochrome, "TERM is dumb")
+    } else if term.contains("256color") {
+        (ColourSupport::Ansi256, "TERM contains 256color")
+    } else {
+        (ColourSupport::Ansi16, "default")
+    }
+}
+
+/// The results of detecting whether a stream, i.e. `stdout` or `stderr`, is styled.
+///
+/// See [`capability_report()`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct StreamCapability {
+    is_terminal:     bool,
+    is_preferred:    bool,
+    is_ansi_forced:  bool,
+    is_ansi_banned:  bool,
+    is_ansi_enabled: bool,
+}
+
+impl StreamCapability {
+    fn new(w: &impl AnsiWrite, is_terminal: bool) -> Self {
+        Self {
+            is_terminal,
+            is_preferred:    w.is_ansi_preferred(),
+            is_ansi_forced:  w.is_ansi_forced(),
+            is_ansi_banned:  w.is_ansi_banned(),
+            is_ansi_enabled: !w.is_no_ansi(),
+        }
+    }
+    /// True if the stream is a terminal/tty.
+    #[inline]
+    pub const fn is_terminal(&self) -> bool { self.is_terminal }
+    /// True if ANSI styles are forced by the `FORCE_COLOR` env variable,
+    /// see [`AnsiPreference::is_ansi_forced()`](super::AnsiPreference::is_ansi_forced()).
+    #[inline]
+    pub const fn is_ansi_forced(&self) -> bool { self.is_ansi_forced }
+    /// True if ANSI styles are banned by the `NO_COLOR` env variable,
+    /// see [`AnsiPreference::is_ansi_banned()`](super::AnsiPreference::is_ansi_banned()).
+    #[inline]
+    pub const fn is_ansi_banned(&self) -> bool { self.is_ansi_banned }
+    /// True if ANSI styles would be enabled by [`auto_ansi()`](AnsiWrite::auto_ansi()),
+    /// see [`AnsiPreference::preferred_ansi()`](super::AnsiPreference::preferred_ansi()).
+    #[inline]
+    pub const fn is_ansi_preferred(&self) -> bool {
+        self.is_ansi_forced || (!self.is_ansi_banned && self.is_preferred)
+    }
+    /// True if the stream's default style currently writes nested ANSI styles, i.e.
+    /// is not [`no_ansi()`](AnsiWrite::no_ansi()). This differs from
+    /// [`is_ansi_preferred()`](Self::is_ansi_preferred()) if the style was set explicitly.
+    #[inline]
+    pub const fn is_ansi_enabled(&self) -> bool { self.is_ansi_enabled }
+
+    /// Gets the reason for [`is_ansi_preferred()`](Self::is_ansi_preferred()).
+    fn reason(&self) -> &'static str {
+        if self.is_ansi_forced {
+            "FORCE_COLOR is set"
+        } else if self.is_ansi_banned {
+            "NO_COLOR is set"
+        } else if self.is_terminal {
+            "is a terminal"
+        } else if self.is_preferred {
+            "TERM is set on WASI"
+        } else {
+            "not a terminal"
+        }
+    }
+}
+
+impl fmt::Display for StreamCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let enabled = |is_enabled| if is_enabled { "enabled" } else { "disabled" };
+        write!(f, "{} ({})", enabled(self.is_ansi_preferred()), self.reason())?;
+        if self.is_ansi_enabled != self.is_ansi_preferred() {
+            write!(f, ", but currently {} explicitly", enabled(self.is_ansi_enabled))?;
+        }
+        Ok(())
+    }
+}
+
+/// A report of how this crate detects the styling capabilities of the terminal
+/// and of `stdout` and `stderr`, e.g. for an application's `--debug-colors` flag.
+///
+/// Its [`Display`](fmt::Display) output is intended for users, to diagnose why output
+/// is (or is not) styled the way it is. It contains no ANSI codes itself.
+///
+/// Created by [`capability_report()`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CapabilityReport {
+    env:                   Vec<(&'static str, Option<String>)>,
+    colour_support:        ColourSupport,
+    colour_support_reason: &'static str,
+    compiled_support:      ColourSupport,
+    terminal_width:        usize,
+    is_coordinated:        bool,
+    stdout:                StreamCapability,
+    stderr:                StreamCapability,
+}
+
+impl CapabilityReport {
+    /// Gets the values of the env variables that affect styled output, in order:
+    /// `FORCE_COLOR`, `NO_COLOR`, `TERM`, `COLORTERM` and `COLUMNS`.
+    #[inline]
+    pub fn env(&self) -> &[(&'static str, Option<String>)] { &self.env }
+    /// Gets the terminal's detected colours, see [`colour_support()`].
+    #[inline]
+    pub const fn colour_support(&self) -> ColourSupport { self.colour_support }
+    /// Gets the most colours supported by this crate's enabled features, i.e.
+    /// [`Rgb`](ColourSupport::Rgb) with `feature=rgb`, [`Ansi256`](ColourSupport::Ansi256)
+    /// with `feature=ansi256`, else [`Ansi16`](ColourSupport::Ansi16).
+    #[inline]
+    pub const fn compiled_support(&self) -> ColourSupport { self.compiled_support }
+    /// Gets the terminal's width, see [`terminal_width()`].
+    #[inline]
+    pub const fn terminal_width(&self) -> usize { self.terminal_width }
+    /// True if writes to `stdout` and `stderr` are coordinated, see [`is_coordinated()`].
+    #[inline]
+    pub const fn is_coordinated(&self) -> bool { self.is_coordinated }
+    /// Gets the detection results for `stdout`, i.e. [`ansiout()`].
+    #[inline]
+    pub const fn stdout(&self) -> StreamCapability { self.stdout }
+    /// Gets the detection results for `stderr`, i.e. [`ansierr()`].
+    #[inline]
+    pub const fn stderr(&self) -> StreamCapability { self.stderr }
+}
+
+impl fmt::Display for CapabilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Environment:")?;
+        for (name, value) in &self.env {
+            match value {
+                Some(value) => writeln!(f, "  {:<11} = {:?}", name, value)?,
+                None        => writeln!(f, "  {:<11} (unset)", name)?,
+            }
+        }
+        writeln!(f, "Colours:        {} ({})", self.colour_support, self.colour_support_reason)?;
+        writeln!(f, "Compiled for:   {}", self.compiled_support)?;
+        writeln!(f, "Terminal width: {}", self.terminal_width)?;
+        writeln!(f, "Coordinated:    {}", if self.is_coordinated { "yes" } else { "no" })?;
+        writeln!(f, "stdout:         {}", self.stdout)?;
+        writeln!(f, "stderr:         {}", self.stderr)
+    }
+}
+
+/// Creates a [`CapabilityReport`], aggregating the results of detecting the styling
+/// capabilities of the terminal and of `stdout` and `stderr`.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::io;
+///
+/// if std::env::args().any(|arg| arg == "--debug-colors") {
+///     eprint!("{}", io::capability_report());
+///     // E.g. prints:
+///     //   Environment:
+///     //     FORCE_COLOR (unset)
+///     //     NO_COLOR    = "1"
+///     //     TERM        = "xterm-256color"
+///     //     COLORTERM   (unset)
+///     //     COLUMNS     (unset)
+///     //   Colours:        256 colours (TERM contains 256color)
+///     //   Compiled for:   16 colours
+///     //   Terminal width: 80
+///     //   Coordinated:    no
+///     //   stdout:         disabled (NO_COLOR is set)
+///     //   stderr:         disabled (NO_COLOR is set)
+/// }
+/// ```
+pub fn capability_report() -> CapabilityReport {
+    let (colour_support, colour_support_reason) = detect_colour_support();
+    CapabilityReport {
+        env: ENV_VARS.iter().map(|name| (*name, env::var_os(name).map(|value| value.to_string_lossy().into_owned()))).collect(),
+        colour_support,
+        colour_support_reason,
+        compiled_support: compiled_support(),
+        terminal_width: terminal_width(),
+        is_coordinated: is_coordinated(),
+        stdout: StreamCapability::new(&ansiout(), io::stdout().is_terminal()),
+        stderr: StreamCapability::new(&ansierr(), io::stderr().is_terminal()),
+    }
+}
+
+const fn compiled_support() -> ColourSupport {
+    if cfg!(feature="rgb") {
+        ColourSupport::Rgb
+    } else if cfg!(feature="ansi256") {
+        ColourSupport::Ansi256
+    } else {
+        ColourSupport::Ansi16
+    }
+}
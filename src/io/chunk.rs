@@ -0,0 +1,124 @@
+use std::io;
+
+use crate::text::{escape_end, utf8_len};
+use super::AnsiPreference;
+
+const ESC: u8 = 0x1B;
+
+/// A `Writer` that writes to an inner `Writer` in chunks of a limited size, without
+/// ever splitting an escape sequence (or a UTF-8 char) across chunks, e.g. for output
+/// sent over a network, or through a PTY, whose buffers would otherwise split it anywhere.
+///
+/// Output is buffered until a chunk is full, and each chunk is written to the inner
+/// `Writer` with a single call to [`write_all()`](io::Write::write_all()). A single escape
+/// sequence that is longer than the [`max_chunk()`](ChunkedWriter::max_chunk()) size
+/// gets a chunk of its own, which is the only time a chunk exceeds it.
+///
+/// [`flush()`](io::Write::flush()) writes the buffered output, except for an incomplete
+/// escape sequence at its end, which is written once the rest of it has been written.
+/// [`into_inner()`](ChunkedWriter::into_inner()) writes everything, even if incomplete.
+///
+/// *Note:* like [`BufWriter`](io::BufWriter), output that has not been flushed is lost
+/// when a `ChunkedWriter` is dropped, so it should be flushed, or unwrapped, when done.
+///
+/// To write styled output, wrap a `ChunkedWriter` in an [`AnsiWriter`](super::AnsiWriter),
+/// whose [`AnsiPreference`] is that of the inner `Writer`.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, io::ChunkedWriter, Colour::Red};
+/// use std::io::Write;
+///
+/// let mut writer = ChunkedWriter::new(Vec::new(), 8);
+/// write!(writer, "{}", styled!(Red, "Hello")).unwrap();
+/// writer.flush().unwrap();
+///
+/// assert_eq!(writer.get_ref(), b"\x1B[31mHello\x1B[39m");
+/// ```
+pub struct ChunkedWriter<W: io::Write> {
+    inner:     W,
+    max_chunk: usize,
+    pending:   Vec<u8>,
+}
+
+impl<W: io::Write> ChunkedWriter<W> {
+    /// Creates a new instance that writes to the given `Writer` in chunks of at most
+    /// `max_chunk` bytes, which is at least 1.
+    pub fn new(inner: W, max_chunk: usize) -> Self {
+        Self { inner, max_chunk: max_chunk.max(1), pending: Vec::new() }
+    }
+    /// Gets the maximum size of each chunk in bytes, except for escape sequences
+    /// that are longer.
+    #[inline]
+    pub fn max_chunk(&self) -> usize { self.max_chunk }
+    /// Gets the output that is buffered, i.e. has not yet been written to the inner `Writer`.
+    #[inline]
+    pub fn buffer(&self) -> &[u8] { &self.pending }
+    /// Gets a reference to the inner `Writer`.
+    #[inline]
+    pub fn get_ref(&self) -> &W { &self.inner }
+    /// Writes all buffered output, even an incomplete escape sequence, then unwraps
+    /// this `ChunkedWriter`, returning the inner `Writer`.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.write_chunks(true)?;
+        if !self.pending.is_empty() {
+            self.inner.write_all(&self.pending)?;
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Writes the buffered output in chunks, removing it from the buffer.
+    /// Unless `all` is true, only full chunks are written.
+    ///
+    /// Output is only ever split between whole escape sequences and chars, so an
+    /// incomplete one at the end remains buffered.
+    fn write_chunks(&mut self, all: bool) -> io::Result<()> {
+        let (mut start, mut end) = (0, 0);
+        let mut result = Ok(());
+        while end < self.pending.len() && result.is_ok() {
+            let rest = &self.pending[end..];
+            let len = match rest[0] {
+                ESC  => match escape_end(rest) {
+                    Some(len) => len,
+                    None      => break,
+                },
+                byte => utf8_len(byte),
+            };
+            if len > rest.len() {
+                break;
+            }
+            if end + len - start > self.max_chunk && end > start {
+                result = self.inner.write_all(&self.pending[start..end]);
+                start = end;
+            }
+            end += len;
+        }
+        if all && end > start && result.is_ok() {
+            result = self.inner.write_all(&self.pending[start..end]);
+            start = end;
+        }
+        // Note: a chunk that failed to write is discarded, as by `write_all()`
+        self.pending.drain(..start);
+        result
+    }
+}
+
+impl<W: io::Write> io::Write for ChunkedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        if self.pending.len() > self.max_chunk {
+            self.write_chunks(false)?;
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_chunks(true)?;
+        self.inner.flush()
+    }
+}
+
+impl<W: io::Write + AnsiPreference> AnsiPreference for ChunkedWriter<W> {
+    fn is_ansi_preferred(&self) -> bool { self.inner.is_ansi_preferred() }
+}
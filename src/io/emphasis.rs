@@ -0,0 +1,123 @@
+use std::fmt::Display;
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{Ansi, Styled};
+use super::AnsiWrite;
+
+/// Returns the cursor to the start of the line, before rewriting it.
+const LINE_START: &[u8] = b"\r";
+/// Erases from the cursor to the end of the line, i.e. any remains of the previous text.
+const ERASE_TO_END: &[u8] = b"\x1B[K";
+
+/// Emphasizes a status line by re-rendering it, cycling between two styles over time.
+///
+/// This is an alternative to [`Effect::Blink`](crate::Effect::Blink), which many terminals
+/// ignore, or which users disable for accessibility reasons. Instead, the line is rewritten
+/// in place (i.e. by returning to the start of the line with `\r`) in each style in turn,
+/// switching every [`interval()`](Emphasis::interval()).
+///
+/// ### Examples
+///
+/// ```no_run
+/// use ansiconst::{*, io::{self, Emphasis}, Colour::{Red, Yellow}, Effect::Bold};
+/// use std::time::Duration;
+///
+/// const ALERT: Emphasis = Emphasis::new(ansi!(Red, Bold), ansi!(Yellow), Duration::from_millis(500));
+///
+/// // Alternates between the two styles for 3 seconds, then ends the line
+/// ALERT.cycle(&mut io::ansiout(), "Disk almost full!", Duration::from_secs(3)).unwrap();
+/// paintln!();
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Emphasis {
+    styles:   [Ansi; 2],
+    interval: Duration,
+}
+
+impl Emphasis {
+    /// Creates a new instance that cycles between the given styles, switching every `interval`.
+    #[inline]
+    pub const fn new(first: Ansi, second: Ansi, interval: Duration) -> Self {
+        Self { styles: [first, second], interval }
+    }
+
+    /// Gets the two styles cycled between.
+    #[inline]
+    pub const fn styles(&self) -> [Ansi; 2] { self.styles }
+
+    /// Gets the interval after which the style switches.
+    #[inline]
+    pub const fn interval(&self) -> Duration { self.interval }
+
+    /// Gets the style to show once `elapsed` time has passed since the cycle started.
+    ///
+    /// A zero [`interval()`](Emphasis::interval()) never switches from the first style.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, io::Emphasis, Colour::{Red, Yellow}};
+    /// use std::time::Duration;
+    ///
+    /// const ALERT: Emphasis = Emphasis::new(ansi!(Red), ansi!(Yellow), Duration::from_millis(500));
+    ///
+    /// assert_eq!(ALERT.style_at(Duration::from_millis(499)),  ansi!(Red));
+    /// assert_eq!(ALERT.style_at(Duration::from_millis(500)),  ansi!(Yellow));
+    /// assert_eq!(ALERT.style_at(Duration::from_millis(1250)), ansi!(Red));
+    /// ```
+    pub const fn style_at(&self, elapsed: Duration) -> Ansi {
+        let interval = self.interval.as_nanos();
+        if interval == 0 {
+            return self.styles[0];
+        }
+        self.styles[((elapsed.as_nanos() / interval) % 2) as usize]
+    }
+
+    /// Rewrites the current line of `w` with `text`, in the style for the given `elapsed` time.
+    ///
+    /// *Note:* `text` should not contain newlines, and should fit on one line of the terminal.
+    pub fn rewrite<W: io::Write>(&self, w: &mut W, text: impl Display, elapsed: Duration) -> io::Result<()> {
+        rewrite_line(w, Styled::new(self.style_at(elapsed), &text))
+    }
+
+    /// Repeatedly [rewrites](Emphasis::rewrite()) the current line of `w` with `text`, for the
+    /// `total` duration, blocking the current thread. Finally, the line is left in the first style,
+    /// without a newline, so that it may be rewritten again.
+    ///
+    /// If `w` does not write nested styles at all (see [`AnsiWrite::is_no_ansi()`]), e.g.
+    /// because it is not a terminal, then `text` is written just once, without cycling.
+    pub fn cycle<W: io::Write + AnsiWrite>(&self, w: &mut W, text: impl Display, total: Duration) -> io::Result<()> {
+        if w.is_no_ansi() {
+            write!(w, "{}", text)?;
+            return w.flush();
+        }
+        let start = Instant::now();
+        loop {
+            let elapsed = start.elapsed();
+            if elapsed >= total {
+                break;
+            }
+            self.rewrite(w, &text, elapsed)?;
+            // Sleep until the next switch of style, or the end of the cycle
+            let remaining = total - elapsed;
+            let interval = self.interval.as_nanos();
+            thread::sleep(match interval {
+                0 => remaining,
+                _ => Duration::from_nanos((interval - elapsed.as_nanos() % interval) as u64).min(remaining),
+            });
+        }
+        self.rewrite(w, &text, Duration::ZERO)
+    }
+}
+
+/// Rewrites the current line of `w` with `text`.
+pub(super) fn rewrite_line<W: io::Write>(w: &mut W, text: impl Display) -> io::Result<()> {
+    // Note: the control sequences are written directly, i.e. not via write_fmt(),
+    // so that they are not affected by the Writer's options, e.g. sanitizing
+    w.write_all(LINE_START)?;
+    write!(w, "{}", text)?;
+    w.write_all(ERASE_TO_END)?;
+    w.flush()
+}
@@ -0,0 +1,108 @@
+use std::{fmt, io, str};
+
+use crate::Ansi;
+use crate::fmt::WriteOptions;
+use crate::map::StyleMap;
+use crate::text::Sanitize;
+use super::{write_fmt, AnsiPreference, AnsiWrite, Composition, FlushPolicy, LinePrefix};
+
+/// A `Writer` that writes styled output to an inner [`fmt::Write`], e.g. a `String`,
+/// using a configurable default [`Ansi`] instance, as for an [`AnsiWriter`](super::AnsiWriter).
+///
+/// So that its default style may be configured with the methods of [`AnsiWrite`], an
+/// `AnsiFmtWriter` also implements [`io::Write`], whose writes must be valid UTF-8.
+/// Its inherent [`write_fmt()`](Self::write_fmt()) returns a [`fmt::Result`], so
+/// [`write!`] works with either trait in scope.
+///
+/// An `AnsiFmtWriter` never prefers ANSI styles (see [`AnsiPreference`]), like a `Writer`
+/// that is not a terminal/tty, so [`auto_ansi()`](AnsiWrite::auto_ansi()) disables them,
+/// unless forced with the `FORCE_COLOR` env variable.
+///
+/// **Note**: only calls to this `Writer`'s `write_fmt()` method will have the default ANSI
+/// styling applied. Calls to [`write_str()`](fmt::Write::write_str()) are unaffected.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, io::{AnsiFmtWriter, AnsiWrite}, Colour::{Blue, Red}};
+///
+/// let mut writer = AnsiFmtWriter::new(String::new(), ansi!(Blue.only()));
+/// write!(writer, "{}", styled!(Red, "Hello")).unwrap();
+/// writer.no_ansi();
+/// write!(writer, " {}", styled!(Red, "world")).unwrap();
+///
+/// assert_eq!(writer.into_inner(), "\x1B[34mHello\x1B[39m world");
+/// ```
+pub struct AnsiFmtWriter<W: fmt::Write> {
+    ansi: Ansi,
+    options: WriteOptions,
+    writer: W,
+}
+
+impl<W: fmt::Write> AnsiFmtWriter<W> {
+    /// Creates a new instance with the given `Writer` and ANSI style
+    #[inline]
+    pub fn new(writer: W, ansi: Ansi) -> Self { Self { writer, ansi, options: WriteOptions::new() } }
+    /// Gets a reference to the inner `Writer`.
+    #[inline]
+    pub fn get_ref(&self) -> &W { &self.writer }
+    /// Gets a mutable reference to the inner `Writer`.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W { &mut self.writer }
+    /// Unwraps this `AnsiFmtWriter`, returning the inner `Writer`.
+    #[inline]
+    pub fn into_inner(self) -> W { self.writer }
+
+    // Needed so that write! is unambiguous when both fmt::Write and io::Write are in scope
+    /// Writes the given arguments with this `Writer`'s default ANSI style applied.
+    pub fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> fmt::Result {
+        io::Write::write_fmt(self, fmt).map_err(|_| fmt::Error)
+    }
+}
+
+/// Adapts a [`fmt::Write`] to an [`io::Write`], whose writes must be valid UTF-8.
+struct Adapter<'a, W: fmt::Write>(&'a mut W);
+
+impl<W: fmt::Write> io::Write for Adapter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let s = str::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.0.write_str(s).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+impl<W: fmt::Write> AnsiWrite for AnsiFmtWriter<W> {
+    fn ansi(&self) -> Ansi { self.ansi }
+    fn set_ansi(&mut self, ansi: Ansi) { self.ansi = ansi }
+    fn style_map(&self) -> Option<StyleMap> { self.options.style_map }
+    fn set_style_map(&mut self, map: Option<StyleMap>) { self.options.style_map = map }
+    fn style_budget(&self) -> Option<usize> { self.options.style_budget }
+    fn set_style_budget(&mut self, budget: Option<usize>) { self.options.style_budget = budget }
+    fn flush_policy(&self) -> FlushPolicy { self.options.flush_policy }
+    fn set_flush_policy(&mut self, policy: FlushPolicy) { self.options.flush_policy = policy }
+    fn line_prefix(&self) -> Option<LinePrefix> { self.options.line_prefix }
+    fn set_line_prefix(&mut self, prefix: Option<LinePrefix>) { self.options.line_prefix = prefix }
+    fn sanitize(&self) -> Option<Sanitize> { self.options.sanitize }
+    fn set_sanitize(&mut self, mode: Option<Sanitize>) { self.options.sanitize = mode }
+    fn composition(&self) -> Composition { self.options.composition }
+    fn set_composition(&mut self, composition: Composition) { self.options.composition = composition }
+    fn last_style(&self) -> Ansi { self.options.last_style }
+}
+
+impl<W: fmt::Write> AnsiPreference for AnsiFmtWriter<W> {
+    fn is_ansi_preferred(&self) -> bool { false }
+}
+
+impl<W: fmt::Write> fmt::Write for AnsiFmtWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result { self.writer.write_str(s) }
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> fmt::Result { AnsiFmtWriter::write_fmt(self, fmt) }
+}
+
+impl<W: fmt::Write> io::Write for AnsiFmtWriter<W> {
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
+        write_fmt(&mut Adapter(&mut self.writer), self.ansi, &mut self.options, fmt).map(|_| ())
+    }
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { Adapter(&mut self.writer).write(buf) }
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
@@ -0,0 +1,63 @@
+use std::io;
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
+
+/// A `Writer` registered with [`set_global_writer()`].
+pub type GlobalWriter = Box<dyn io::Write + Send>;
+
+static WRITER: OnceLock<Mutex<GlobalWriter>> = OnceLock::new();
+
+/// Sets the `Writer` to which [`ansiout()`](super::ansiout()) (and therefore [`paint!`](crate::paint),
+/// [`paintln!`](crate::paintln) etc.) writes, instead of `stdout`, which may be done once only,
+/// e.g. at startup.
+///
+/// This allows a framework to capture all output of plugins that use this crate's standard
+/// macros, without the plugins having to be written against a particular `Writer`.
+/// Calls to [`paint!`](crate::paint) and [`paintln!`](crate::paintln) without any styles
+/// are written to it too.
+///
+/// Returns the given `Writer` as an error if a `Writer` was already set.
+///
+/// *Note:* the default style of [`ansiout()`](super::ansiout()) is still determined by
+/// `stdout`, e.g. it disables ANSI styles if `stdout` is not a terminal/tty, unless
+/// overridden with [`set_ansi()`](super::AnsiWrite::set_ansi()). Writes to the global
+/// `Writer` are serialized, so it must not itself write to [`ansiout()`](super::ansiout()).
+///
+/// *Note: only available with `feature=global-writer`*
+///
+/// ### Examples
+///
+/// ```
+/// # #[cfg(feature="global-writer")] {
+/// use ansiconst::{*, io::{self, AnsiWrite}, Colour::Red};
+/// use std::sync::Mutex;
+///
+/// static CAPTURED: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+///
+/// struct Capture;
+///
+/// impl std::io::Write for Capture {
+///     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+///         CAPTURED.lock().unwrap().extend_from_slice(buf);
+///         Ok(buf.len())
+///     }
+///     fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+/// }
+///
+/// assert!(io::set_global_writer(Box::new(Capture)).is_ok());
+/// io::ansiout().all_ansi();
+///
+/// paintln!(Red, "Captured");
+/// assert_eq!(CAPTURED.lock().unwrap().as_slice(), b"\x1B[31mCaptured\x1B[39m\n");
+/// # }
+/// ```
+pub fn set_global_writer(writer: GlobalWriter) -> Result<(), GlobalWriter> {
+    WRITER.set(Mutex::new(writer)).map_err(|writer| writer.into_inner().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Determines whether a `Writer` was set with [`set_global_writer()`].
+pub fn has_global_writer() -> bool { WRITER.get().is_some() }
+
+/// Locks the `Writer` set with [`set_global_writer()`], if any.
+pub(super) fn global_writer() -> Option<MutexGuard<'static, GlobalWriter>> {
+    WRITER.get().map(|writer| writer.lock().unwrap_or_else(PoisonError::into_inner))
+}
@@ -0,0 +1,226 @@
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The state of the pager, if any, to which [`Ansiout`](super::Ansiout) writes are redirected.
+enum State {
+    /// Output is buffered until it exceeds the threshold number of lines.
+    Buffering { buf: Vec<u8>, lines: usize, pager: Pager },
+    /// Output is piped to the pager, or is discarded if the pager has
+    /// closed its `stdin`, e.g. because the user quit it.
+    Piping(Child),
+    /// Output is written to stdout, because the pager could not be spawned.
+    Direct,
+}
+
+static STATE: Mutex<Option<State>> = Mutex::new(None);
+/// Whether `STATE` is `Some`, so that writes need not lock it when no pager is active.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+fn state() -> MutexGuard<'static, Option<State>> {
+    STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Routes output written to [`ansiout()`](super::ansiout()) (e.g. by [`paint!`](crate::paint))
+/// through a pager such as `less`, if the output is long.
+///
+/// The pager is started by [`start()`](Pager::start()), and applies until the returned
+/// [`Paging`] guard is dropped or [finished](Paging::finish()). Output is buffered until
+/// it exceeds the [threshold](Pager::threshold()) number of lines (by default, the terminal
+/// height), only then spawning the pager. If the output never exceeds the threshold, or if
+/// the pager cannot be spawned, the output is written to `stdout` as normal.
+///
+/// The pager command is given by the `PAGER` env variable, and defaults to `less`. The `-R`
+/// flag is added to the `LESS` env variable (defaulting to `-FRX`), so that `less` passes
+/// ANSI codes through.
+///
+/// Unstyled output written by [`paint!`](crate::paint) and [`paintln!`](crate::paintln)
+/// is paged too, in order with styled output.
+///
+/// *Note:* output not written via this crate is not paged, e.g. output written by [`print!`].
+///
+/// ### Examples
+///
+/// ```no_run
+/// use ansiconst::{*, io::Pager, Colour::Green};
+///
+/// let paging = Pager::new().start();
+/// for i in 0..1000 {
+///     paintln!(Green, "Line {}", i);
+/// }
+/// paging.finish().unwrap();
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Pager {
+    threshold:     Option<usize>,
+    command:       Option<&'static str>,
+    terminal_only: bool,
+}
+
+impl Pager {
+    /// Creates a new instance with the default threshold and command.
+    #[inline]
+    pub const fn new() -> Self { Self { threshold: None, command: None, terminal_only: true } }
+
+    /// Sets the number of lines that output must exceed for the pager to be spawned.
+    #[inline]
+    pub const fn threshold(self, lines: usize) -> Self { Self { threshold: Some(lines), ..self } }
+
+    /// Sets the pager command, overriding the `PAGER` env variable. The command
+    /// is split on whitespace into the program and its arguments.
+    #[inline]
+    pub const fn command(self, command: &'static str) -> Self { Self { command: Some(command), ..self } }
+
+    /// Sets whether to page only when `stdout` is a terminal/tty, which is the default.
+    #[inline]
+    pub const fn terminal_only(self, terminal_only: bool) -> Self { Self { terminal_only, ..self } }
+
+    /// Starts routing output written to [`ansiout()`](super::ansiout()) through this pager,
+    /// until the returned guard is dropped or [finished](Paging::finish()).
+    ///
+    /// Does nothing if output is already being paged, or if this pager is
+    /// [terminal-only](Pager::terminal_only()) and `stdout` is not a terminal/tty.
+    pub fn start(self) -> Paging {
+        let mut state = state();
+        let is_active = state.is_none() && (!self.terminal_only || io::stdout().is_terminal());
+        if is_active {
+            *state = Some(State::Buffering { buf: Vec::new(), lines: 0, pager: self });
+            ACTIVE.store(true, Ordering::Release);
+        }
+        Paging { is_active }
+    }
+
+    fn threshold_lines(&self) -> usize {
+        self.threshold.unwrap_or_else(|| {
+            env::var("LINES").ok().and_then(|lines| lines.parse().ok()).unwrap_or(24)
+        })
+    }
+
+    fn spawn(&self) -> io::Result<Child> {
+        let command = self.command.map(String::from)
+            .or_else(|| env::var("PAGER").ok().filter(|pager| !pager.trim().is_empty()))
+            .unwrap_or_else(|| "less".into());
+        let mut args = command.split_whitespace();
+        let program = args.next().ok_or(io::ErrorKind::InvalidInput)?;
+        let less = match env::var("LESS") {
+            Ok(less) if less.contains('R') => less,
+            Ok(less)                       => format!("{} -R", less),
+            Err(_)                         => "-FRX".into(),
+        };
+        Command::new(program).args(args).env("LESS", less).stdin(Stdio::piped()).spawn()
+    }
+}
+
+impl Default for Pager {
+    fn default() -> Self { Self::new() }
+}
+
+/// A guard that routes output written to [`ansiout()`](super::ansiout()) through a [`Pager`].
+///
+/// Dropping the guard is equivalent to calling [`finish()`](Paging::finish()), ignoring errors.
+#[must_use = "output is only paged while the guard is alive"]
+pub struct Paging { is_active: bool }
+
+impl Paging {
+    /// Determines whether this guard is routing output through a pager, i.e.
+    /// whether [`Pager::start()`] did anything.
+    #[inline]
+    pub const fn is_active(&self) -> bool { self.is_active }
+
+    /// Stops routing output through the pager, writing any buffered output to
+    /// `stdout`, else waiting for the user to exit the pager.
+    pub fn finish(mut self) -> io::Result<()> { self.stop() }
+
+    fn stop(&mut self) -> io::Result<()> {
+        if !std::mem::take(&mut self.is_active) {
+            return Ok(());
+        }
+        // Note: release the lock before writing to stdout
+        let state = {
+            let mut state = state();
+            ACTIVE.store(false, Ordering::Release);
+            state.take()
+        };
+        match state {
+            Some(State::Buffering { buf, .. }) => {
+                let mut stdout = io::stdout().lock();
+                stdout.write_all(&buf)?;
+                stdout.flush()
+            },
+            Some(State::Piping(mut child)) => {
+                drop(child.stdin.take());
+                child.wait().map(|_| ())
+            },
+            Some(State::Direct) | None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Paging {
+    fn drop(&mut self) { let _ = self.stop(); }
+}
+
+/// A `Writer` to the active pager, if any.
+pub(crate) struct PagerWriter(MutexGuard<'static, Option<State>>);
+
+/// Gets a `Writer` to the active pager, if any.
+pub(crate) fn pager_writer() -> Option<PagerWriter> {
+    if !ACTIVE.load(Ordering::Acquire) {
+        return None;
+    }
+    let state = state();
+    if state.is_some() { Some(PagerWriter(state)) } else { None }
+}
+
+impl io::Write for PagerWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let state = self.0.as_mut().expect("pager is active");
+        match state {
+            State::Buffering { buf: buffered, lines, pager } => {
+                buffered.extend_from_slice(buf);
+                *lines += buf.iter().filter(|b| **b == b'\n').count();
+                if *lines > pager.threshold_lines() {
+                    *state = match pager.spawn() {
+                        Ok(mut child) => {
+                            write_to_child(&mut child, buffered)?;
+                            State::Piping(child)
+                        },
+                        // Fall back to writing directly to stdout
+                        Err(_) => {
+                            io::stdout().lock().write_all(buffered)?;
+                            State::Direct
+                        },
+                    };
+                }
+                Ok(buf.len())
+            },
+            State::Piping(child) => {
+                write_to_child(child, buf)?;
+                Ok(buf.len())
+            },
+            State::Direct => io::stdout().lock().write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.0.as_mut() {
+            Some(State::Piping(child)) => child.stdin.as_mut().map_or(Ok(()), |stdin| stdin.flush()),
+            Some(State::Direct)        => io::stdout().lock().flush(),
+            _                          => Ok(()),
+        }
+    }
+}
+
+/// Writes `buf` to the pager's `stdin`, if still open, closing it if the pager has exited.
+fn write_to_child(child: &mut Child, buf: &[u8]) -> io::Result<()> {
+    match child.stdin.as_mut().map(|stdin| stdin.write_all(buf)) {
+        Some(Err(e)) if e.kind() == io::ErrorKind::BrokenPipe => {
+            child.stdin = None;
+            Ok(())
+        },
+        Some(Err(e)) => Err(e),
+        _            => Ok(()),
+    }
+}
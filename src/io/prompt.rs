@@ -0,0 +1,186 @@
+use std::fmt::Display;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+use crate::{ansi, Ansi, Styled};
+use crate::Colour::{Cyan, Yellow};
+use crate::Effect::Bold;
+use super::ansiout;
+
+/// The styles used by a [`Prompt`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct PromptStyle {
+    message: Ansi,
+    hint:    Ansi,
+    warning: Ansi,
+}
+
+impl PromptStyle {
+    /// The default styles: a bold message, a cyan hint, and a yellow warning.
+    pub const DEFAULT: PromptStyle = PromptStyle::new(ansi!(Bold), ansi!(Cyan), ansi!(Yellow));
+
+    /// Creates a new instance with the given styles for the prompt's message, its hint
+    /// (e.g. `"[y/N]"` or the default answer), and the warning shown for invalid input.
+    #[inline]
+    pub const fn new(message: Ansi, hint: Ansi, warning: Ansi) -> Self { Self { message, hint, warning } }
+    /// Gets the style of the prompt's message.
+    #[inline]
+    pub const fn message(&self) -> Ansi { self.message }
+    /// Gets the style of the prompt's hint.
+    #[inline]
+    pub const fn hint(&self) -> Ansi { self.hint }
+    /// Gets the style of the warning shown for invalid input.
+    #[inline]
+    pub const fn warning(&self) -> Ansi { self.warning }
+}
+
+impl Default for PromptStyle {
+    fn default() -> Self { Self::DEFAULT }
+}
+
+/// Asks the user for input, printing a styled prompt to [`ansiout()`] and reading
+/// the answer from `stdin`.
+///
+/// Invalid answers are followed by a styled warning and the prompt is asked again.
+/// The prompt's hint shows which answer is the default, e.g. `"[Y/n]"`, so the prompt
+/// remains usable when styles are disabled, e.g. by [`no_ansi()`](super::AnsiWrite::no_ansi()).
+///
+/// If `stdin` reaches its end before a valid answer, the default answer is used if
+/// any, else an error of kind [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) is returned.
+///
+/// See also [`confirm()`] and [`input()`].
+///
+/// ### Examples
+///
+/// ```no_run
+/// use ansiconst::{*, io::{Prompt, PromptStyle}, Colour::{Green, Red}};
+///
+/// const STYLE: PromptStyle = PromptStyle::new(ansi!(Green), Ansi::unspecified(), ansi!(Red));
+///
+/// let name = Prompt::new("Name?").default("anonymous").style(STYLE).input().unwrap();
+/// let age: u8 = Prompt::new("Age?").style(STYLE).parse().unwrap();
+/// if Prompt::new("Save?").default("y").style(STYLE).confirm().unwrap() {
+///     println!("Saved {} ({})", name, age);
+/// }
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Prompt<'a> {
+    message: &'a str,
+    default: Option<&'a str>,
+    style:   PromptStyle,
+}
+
+impl<'a> Prompt<'a> {
+    /// Creates a new instance with the given message, no default answer and the
+    /// [default styles](PromptStyle::DEFAULT).
+    #[inline]
+    pub const fn new(message: &'a str) -> Self { Self { message, default: None, style: PromptStyle::DEFAULT } }
+
+    /// Sets the answer used when the user enters nothing, e.g. `"y"` for [`confirm()`](Prompt::confirm()).
+    #[inline]
+    pub const fn default(self, default: &'a str) -> Self { Self { default: Some(default), ..self } }
+
+    /// Sets the styles.
+    #[inline]
+    pub const fn style(self, style: PromptStyle) -> Self { Self { style, ..self } }
+
+    /// Asks a yes/no question, accepting `y`, `yes`, `n` or `no` (ignoring case).
+    pub fn confirm(self) -> io::Result<bool> { self.confirm_with(&mut io::stdin().lock(), &mut ansiout()) }
+
+    /// Asks for a line of text, without its trailing newline.
+    pub fn input(self) -> io::Result<String> { self.input_with(&mut io::stdin().lock(), &mut ansiout()) }
+
+    /// Asks for a value that is parsed using [`FromStr`], with parse errors shown as warnings.
+    pub fn parse<T: FromStr>(self) -> io::Result<T> where T::Err: Display {
+        self.parse_with(&mut io::stdin().lock(), &mut ansiout())
+    }
+
+    /// Like [`confirm()`](Prompt::confirm()), except reading from `input` and writing to `output`.
+    pub fn confirm_with(self, input: &mut impl BufRead, output: &mut impl io::Write) -> io::Result<bool> {
+        let hint = match self.default.map(parse_yes_no) {
+            Some(Some(true))  => "[Y/n]",
+            Some(Some(false)) => "[y/N]",
+            _                 => "[y/n]",
+        };
+        self.ask(hint, input, output, |answer| parse_yes_no(answer).ok_or_else(|| "Please answer y or n".into()))
+    }
+
+    /// Like [`input()`](Prompt::input()), except reading from `input` and writing to `output`.
+    pub fn input_with(self, input: &mut impl BufRead, output: &mut impl io::Write) -> io::Result<String> {
+        self.ask(&self.default_hint(), input, output, |answer| Ok(answer.into()))
+    }
+
+    /// Like [`parse()`](Prompt::parse()), except reading from `input` and writing to `output`.
+    pub fn parse_with<T: FromStr>(self, input: &mut impl BufRead, output: &mut impl io::Write) -> io::Result<T>
+    where T::Err: Display {
+        self.ask(&self.default_hint(), input, output, |answer| {
+            answer.parse().map_err(|e| format!("Invalid input {:?}: {}", answer, e))
+        })
+    }
+
+    fn default_hint(&self) -> String {
+        self.default.map_or_else(String::new, |default| format!("({})", default))
+    }
+
+    fn ask<T>(
+        &self,
+        hint: &str,
+        input: &mut impl BufRead,
+        output: &mut impl io::Write,
+        parse: impl Fn(&str) -> Result<T, String>,
+    ) -> io::Result<T> {
+        let mut line = String::new();
+        loop {
+            if hint.is_empty() {
+                write!(output, "{} ", Styled::new(self.style.message, self.message))?;
+            } else {
+                write!(output, "{} {} ", Styled::new(self.style.message, self.message), Styled::new(self.style.hint, hint))?;
+            }
+            output.flush()?;
+            line.clear();
+            let is_eof = input.read_line(&mut line)? == 0;
+            if is_eof {
+                // End the prompt's line, since the user did not
+                writeln!(output)?;
+            }
+            let answer = match (line.trim(), self.default) {
+                ("", Some(default))  => default,
+                ("", None) if is_eof => return Err(io::ErrorKind::UnexpectedEof.into()),
+                (answer, _)          => answer,
+            };
+            match parse(answer) {
+                Ok(value)        => return Ok(value),
+                Err(_) if is_eof => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Err(warning)     => writeln!(output, "{}", Styled::new(self.style.warning, warning))?,
+            }
+        }
+    }
+}
+
+fn parse_yes_no(answer: &str) -> Option<bool> {
+    match answer.to_ascii_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no"  => Some(false),
+        _           => None,
+    }
+}
+
+/// Asks a yes/no question, see [`Prompt::confirm()`].
+///
+/// ### Examples
+///
+/// ```no_run
+/// if ansiconst::io::confirm("Delete all files?").unwrap() {
+///     // ...
+/// }
+/// ```
+pub fn confirm(message: &str) -> io::Result<bool> { Prompt::new(message).confirm() }
+
+/// Asks for a line of text, see [`Prompt::input()`].
+///
+/// ### Examples
+///
+/// ```no_run
+/// let name = ansiconst::io::input("What is your name?").unwrap();
+/// ```
+pub fn input(message: &str) -> io::Result<String> { Prompt::new(message).input() }
@@ -0,0 +1,170 @@
+use std::fmt::Write as _;
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::AnsiPreference;
+
+/// Writes within this duration of the previous chunk are appended to it, since a single
+/// call to [`write_fmt()`](io::Write::write_fmt()) usually makes several smaller writes.
+const COALESCE: Duration = Duration::from_millis(1);
+
+/// A `Writer` that records the output written to an inner `Writer`, along with when
+/// it was written, e.g. to capture a styled CLI session for a demo or bug report.
+///
+/// To record styled output, wrap a `Recorder` in an [`AnsiWriter`](super::AnsiWriter),
+/// whose [`AnsiPreference`] is that of the inner `Writer`. The resulting [`Recording`]
+/// can be [replayed](Recording::replay()) with its original timing, or exported in
+/// [asciinema](https://asciinema.org)'s cast format.
+///
+/// ### Examples
+///
+/// ```no_run
+/// use ansiconst::{*, io::{AnsiWriter, Recorder}, Colour::Green};
+/// use std::io::{self, Write};
+///
+/// let mut writer = AnsiWriter::default(Recorder::new(io::stdout()));
+/// writeln!(writer, "{}", styled!(Green, "Compiling...")).unwrap();
+///
+/// let (_, recording) = writer.into_inner().into_parts();
+/// std::fs::write("session.cast", recording.to_cast(80, 24)).unwrap();
+/// recording.replay(&mut io::stdout(), 2.0).unwrap();
+/// ```
+pub struct Recorder<W: io::Write> {
+    inner:     W,
+    start:     Instant,
+    recording: Recording,
+}
+
+impl<W: io::Write> Recorder<W> {
+    /// Creates a new instance that writes to the given `Writer`, with
+    /// timestamps relative to now.
+    pub fn new(inner: W) -> Self {
+        Self { inner, start: Instant::now(), recording: Recording { chunks: Vec::new() } }
+    }
+    /// Gets the output recorded so far.
+    #[inline]
+    pub fn recording(&self) -> &Recording { &self.recording }
+    /// Gets a reference to the inner `Writer`.
+    #[inline]
+    pub fn get_ref(&self) -> &W { &self.inner }
+    /// Unwraps this `Recorder`, returning the inner `Writer` and the recorded output.
+    #[inline]
+    pub fn into_parts(self) -> (W, Recording) { (self.inner, self.recording) }
+}
+
+impl<W: io::Write> io::Write for Recorder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        let time = self.start.elapsed();
+        match self.recording.chunks.last_mut() {
+            Some(chunk) if time - chunk.time < COALESCE => chunk.data.extend_from_slice(&buf[..len]),
+            _ => self.recording.chunks.push(Chunk { time, data: buf[..len].to_vec() }),
+        }
+        Ok(len)
+    }
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
+
+impl<W: io::Write + AnsiPreference> AnsiPreference for Recorder<W> {
+    fn is_ansi_preferred(&self) -> bool { self.inner.is_ansi_preferred() }
+}
+
+/// A chunk of output recorded by a [`Recorder`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Chunk {
+    time: Duration,
+    data: Vec<u8>,
+}
+
+impl Chunk {
+    /// Creates a new instance with the given output, written `time` after the recording started.
+    #[inline]
+    pub fn new(time: Duration, data: impl Into<Vec<u8>>) -> Self { Self { time, data: data.into() } }
+    /// Gets when the output was written, relative to the start of the recording.
+    #[inline]
+    pub fn time(&self) -> Duration { self.time }
+    /// Gets the output, including any ANSI escape sequences.
+    #[inline]
+    pub fn data(&self) -> &[u8] { &self.data }
+}
+
+/// The output recorded by a [`Recorder`], as timestamped [`Chunk`]s.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct Recording {
+    chunks: Vec<Chunk>,
+}
+
+impl Recording {
+    /// Creates a new instance with the given chunks, which should be in order of time.
+    #[inline]
+    pub fn new(chunks: Vec<Chunk>) -> Self { Self { chunks } }
+
+    /// Gets the recorded chunks, in order of time.
+    #[inline]
+    pub fn chunks(&self) -> &[Chunk] { &self.chunks }
+
+    /// Gets the time of the last chunk, i.e. the duration of a replay at normal speed.
+    pub fn duration(&self) -> Duration { self.chunks.last().map_or(Duration::ZERO, Chunk::time) }
+
+    /// Writes the recorded output to `w` with its original timing, divided by `speed`,
+    /// blocking the current thread. E.g. a `speed` of `2.0` replays twice as fast. A
+    /// `speed` that is not positive writes all output immediately.
+    ///
+    /// *Note:* the output is written as recorded, i.e. `w` should be a terminal that
+    /// supports the recorded ANSI escape sequences.
+    pub fn replay(&self, w: &mut impl io::Write, speed: f64) -> io::Result<()> {
+        let start = Instant::now();
+        for chunk in &self.chunks {
+            if speed > 0.0 {
+                let due = chunk.time.div_f64(speed);
+                thread::sleep(due.saturating_sub(start.elapsed()));
+            }
+            w.write_all(&chunk.data)?;
+            w.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Exports the recorded output in [asciinema](https://asciinema.org)'s cast format
+    /// (version 2), for a terminal of the given size.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::io::{Chunk, Recording};
+    /// use std::time::Duration;
+    ///
+    /// let recording = Recording::new(vec![
+    ///     Chunk::new(Duration::ZERO,               "\x1B[32mOK\x1B[39m"),
+    ///     Chunk::new(Duration::from_millis(1500),  "\r\n"),
+    /// ]);
+    ///
+    /// assert_eq!(recording.to_cast(80, 24), concat!(
+    ///     r#"{"version":2,"width":80,"height":24}"#, "\n",
+    ///     r#"[0.000000,"o","\u001b[32mOK\u001b[39m"]"#, "\n",
+    ///     r#"[1.500000,"o","\r\n"]"#, "\n",
+    /// ));
+    /// ```
+    pub fn to_cast(&self, width: u16, height: u16) -> String {
+        let mut cast = String::new();
+        // Note: writing to a String cannot fail
+        let _ = writeln!(cast, r#"{{"version":2,"width":{},"height":{}}}"#, width, height);
+        for chunk in &self.chunks {
+            let _ = write!(cast, r#"[{:.6},"o",""#, chunk.time.as_secs_f64());
+            for c in String::from_utf8_lossy(&chunk.data).chars() {
+                let _ = match c {
+                    '"'                 => cast.write_str(r#"\""#),
+                    '\\'                => cast.write_str(r"\\"),
+                    '\n'                => cast.write_str(r"\n"),
+                    '\r'                => cast.write_str(r"\r"),
+                    '\t'                => cast.write_str(r"\t"),
+                    c if c.is_control() => write!(cast, r"\u{:04x}", c as u32),
+                    c                   => cast.write_char(c),
+                };
+            }
+            cast.push_str("\"]\n");
+        }
+        cast
+    }
+}
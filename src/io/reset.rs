@@ -0,0 +1,110 @@
+use std::io::{self, IsTerminal, Write};
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+use super::stream::{STDERR, STDOUT};
+
+/// Whether a style may be active on `stdout` and `stderr`, respectively.
+static MAY_BE_STYLED: [AtomicBool; 2] = [AtomicBool::new(false), AtomicBool::new(false)];
+
+static INSTALL: Once = Once::new();
+
+/// Resets all ANSI styles, and shows the cursor.
+const RESET: &[u8] = b"\x1B[0m\x1B[?25h";
+
+/// Records whether a style may be left active on `stream` (i.e. `STDOUT` or `STDERR`).
+pub(super) fn set_may_be_styled(stream: u8, may_be_styled: bool) {
+    MAY_BE_STYLED[stream as usize - 1].store(may_be_styled, Ordering::Relaxed);
+}
+
+/// Restores the terminal's default style if any style may have been left active by
+/// writes to [`ansiout()`](super::ansiout()) or [`ansierr()`](super::ansierr()),
+/// e.g. because a write panicked part-way.
+///
+/// This writes `"\x1B[0m"` (reset all styles) and `"\x1B[?25h"` (show the cursor) to
+/// each of `stdout` and `stderr` that is a terminal/tty and may have an active style.
+/// Nothing is written to streams that are not terminals, or whose writes have all
+/// completed normally, because each write resets the styles it sets.
+///
+/// This is called automatically by the hooks installed by [`install_reset_hook()`], and
+/// should be called before [`std::process::exit()`], which does not run destructors.
+pub fn reset_terminal() {
+    for (stream, is_terminal) in [(STDOUT, io::stdout().is_terminal()), (STDERR, io::stderr().is_terminal())] {
+        if MAY_BE_STYLED[stream as usize - 1].swap(false, Ordering::Relaxed) && is_terminal {
+            // Note: errors are ignored, since there is no better way to restore the terminal
+            let _ = if stream == STDOUT {
+                io::stdout().write_all(RESET).and_then(|_| io::stdout().flush())
+            } else {
+                io::stderr().write_all(RESET)
+            };
+        }
+    }
+}
+
+/// Installs hooks that call [`reset_terminal()`] when the program panics or exits, so
+/// that a crashed program doesn't leave the user's terminal in a broken state.
+///
+/// The panic hook wraps any previously installed panic hook, and runs before it, i.e.
+/// before the panic message is printed. The exit hook runs when the returned guard is
+/// dropped, so the guard should be held for the duration of `main()`.
+///
+/// Installing the panic hook more than once has no further effect.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, io, Colour::Red};
+///
+/// fn main() {
+///     let _reset = io::install_reset_hook();
+///
+///     paintln!(Red, "Hello world");
+///     // ... any panic from here on resets the terminal's style
+/// }
+/// ```
+pub fn install_reset_hook() -> ResetGuard {
+    INSTALL.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            reset_terminal();
+            hook(info);
+        }));
+    });
+    ResetGuard(())
+}
+
+/// A guard that calls [`reset_terminal()`] when dropped.
+///
+/// Created by [`install_reset_hook()`].
+#[must_use = "the terminal is reset when the guard is dropped"]
+pub struct ResetGuard(());
+
+impl Drop for ResetGuard {
+    fn drop(&mut self) { reset_terminal(); }
+}
+
+/// Installs a Ctrl-C (i.e. `SIGINT`) handler that calls [`reset_terminal()`] and then
+/// exits the process with status `130`, i.e. the conventional status for `SIGINT`.
+///
+/// Only one Ctrl-C handler can be installed per process, so an application that needs
+/// its own handler should instead call [`reset_terminal()`] from it.
+///
+/// Requires feature `ctrlc`.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, io, Colour::Red};
+///
+/// io::install_ctrlc_reset().expect("ctrl-c handler");
+///
+/// paintln!(Red, "Press Ctrl-C at any time");
+/// ```
+#[cfg(feature="ctrlc")]
+pub fn install_ctrlc_reset() -> Result<(), ctrlc::Error> {
+    ctrlc::set_handler(|| {
+        reset_terminal();
+        std::process::exit(130);
+    })
+}
@@ -0,0 +1,43 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::Ansi;
+use super::AnsiWrite;
+
+/// A guard that overrides a `Writer`'s default [`Ansi`](AnsiWrite::ansi()) style, and
+/// restores the previous style when dropped.
+///
+/// The guard owns the `Writer`, to which it dereferences. For [`Ansiout`](super::Ansiout)
+/// and [`Ansierr`](super::Ansierr), the guard therefore holds the lock on `stdout`/`stderr`,
+/// so other threads neither write nor see the overriding style until it is dropped, while
+/// the current thread may still write, e.g. with [`paintln!`](crate::paintln).
+///
+/// Created by [`AnsiWrite::scoped_ansi()`].
+#[must_use = "the previous style is restored when the guard is dropped"]
+pub struct ScopedAnsi<W: AnsiWrite> {
+    writer:   W,
+    previous: Ansi,
+}
+
+impl<W: AnsiWrite> ScopedAnsi<W> {
+    pub(crate) fn new(mut writer: W, ansi: Ansi) -> Self {
+        let previous = writer.ansi();
+        writer.set_ansi(ansi);
+        Self { writer, previous }
+    }
+    /// Gets the default style that is restored when this guard is dropped.
+    #[inline]
+    pub fn previous(&self) -> Ansi { self.previous }
+}
+
+impl<W: AnsiWrite> Deref for ScopedAnsi<W> {
+    type Target = W;
+    fn deref(&self) -> &W { &self.writer }
+}
+
+impl<W: AnsiWrite> DerefMut for ScopedAnsi<W> {
+    fn deref_mut(&mut self) -> &mut W { &mut self.writer }
+}
+
+impl<W: AnsiWrite> Drop for ScopedAnsi<W> {
+    fn drop(&mut self) { self.writer.set_ansi(self.previous) }
+}
@@ -0,0 +1,116 @@
+use std::ops::Range;
+use std::{io, mem, str};
+
+use crate::Ansi;
+use crate::text::{escape_end, parse_sgr};
+use super::AnsiPreference;
+
+const ESC: u8 = 0x1B;
+
+/// A `Writer` that records the structure of the styled output written to an inner `Writer`,
+/// i.e. the style of each span of its visible text, without keeping the text itself.
+///
+/// This is the streaming equivalent of [`text::spans()`](crate::text::spans()), e.g. for
+/// indexing a large styled report as it is written to a file, without first formatting
+/// it to a `String`. Each span's range is of byte indices into the output's visible text,
+/// i.e. with all escape sequences removed, as by [`text::strip_ansi()`](crate::text::strip_ansi()).
+/// As for `text::spans()`, consecutive text with the same style is a single span.
+///
+/// All output is written to the inner `Writer` unchanged. An escape sequence that is split
+/// across several writes takes effect once complete.
+///
+/// To record styled output, wrap a `SpanRecorder` in an [`AnsiWriter`](super::AnsiWriter),
+/// whose [`AnsiPreference`] is that of the inner `Writer`.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, io::SpanRecorder, Colour::Red, Effect::Bold};
+/// use std::io::Write;
+///
+/// let mut recorder = SpanRecorder::new(Vec::new());
+/// write!(recorder, "{}", styled_format_args!(Bold, "Error: {}!", styled!(Red, "failed"))).unwrap();
+///
+/// let (output, spans) = recorder.into_parts();
+/// assert_eq!(output, b"\x1B[1mError: \x1B[31mfailed\x1B[39m!\x1B[22m");
+/// assert_eq!(spans, [(0..7, ansi!(Bold)), (7..13, ansi!(Bold, Red)), (13..14, ansi!(Bold))]);
+/// ```
+pub struct SpanRecorder<W: io::Write> {
+    inner:   W,
+    active:  Ansi,
+    len:     usize,
+    spans:   Vec<(Range<usize>, Ansi)>,
+    pending: Vec<u8>,
+}
+
+impl<W: io::Write> SpanRecorder<W> {
+    /// Creates a new instance that writes to the given `Writer`, with no active style.
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Self { inner, active: Ansi::unspecified(), len: 0, spans: Vec::new(), pending: Vec::new() }
+    }
+    /// Gets the spans recorded so far, in order.
+    #[inline]
+    pub fn spans(&self) -> &[(Range<usize>, Ansi)] { &self.spans }
+    /// Gets the style that is active at the end of the output written so far.
+    #[inline]
+    pub fn active(&self) -> Ansi { self.active }
+    /// Gets the number of bytes of visible text written so far.
+    #[inline]
+    pub fn text_len(&self) -> usize { self.len }
+    /// Gets a reference to the inner `Writer`.
+    #[inline]
+    pub fn get_ref(&self) -> &W { &self.inner }
+    /// Unwraps this `SpanRecorder`, returning the inner `Writer` and the recorded spans.
+    /// An incomplete escape sequence is ignored.
+    #[inline]
+    pub fn into_parts(self) -> (W, Vec<(Range<usize>, Ansi)>) { (self.inner, self.spans) }
+
+    fn record_text(&mut self, len: usize) {
+        let range = self.len..self.len + len;
+        self.len += len;
+        match self.spans.last_mut() {
+            Some((last, ansi)) if *ansi == self.active => last.end = range.end,
+            _ => self.spans.push((range, self.active)),
+        }
+    }
+}
+
+impl<W: io::Write> io::Write for SpanRecorder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        let joined;
+        let mut rest = &buf[..len];
+        if !self.pending.is_empty() {
+            self.pending.extend_from_slice(rest);
+            joined = mem::take(&mut self.pending);
+            rest = &joined;
+        }
+        while !rest.is_empty() {
+            let start = rest.iter().position(|b| *b == ESC).unwrap_or(rest.len());
+            if start > 0 {
+                self.record_text(start);
+                rest = &rest[start..];
+                continue;
+            }
+            match escape_end(rest) {
+                Some(end) => {
+                    if let Some(sgr) = str::from_utf8(&rest[..end]).ok().and_then(parse_sgr) {
+                        self.active = sgr.apply(self.active);
+                    }
+                    rest = &rest[end..];
+                },
+                None      => {
+                    self.pending.extend_from_slice(rest);
+                    break;
+                },
+            }
+        }
+        Ok(len)
+    }
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
+
+impl<W: io::Write + AnsiPreference> AnsiPreference for SpanRecorder<W> {
+    fn is_ansi_preferred(&self) -> bool { self.inner.is_ansi_preferred() }
+}
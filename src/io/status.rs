@@ -0,0 +1,118 @@
+use std::fmt::Display;
+use std::io;
+use std::time::{Duration, Instant};
+
+use super::emphasis::rewrite_line;
+
+/// Shows a status line, e.g. the progress of a task, by rewriting the current line in place,
+/// at most once per [`interval()`](StatusLine::interval()) however often it is updated.
+///
+/// Updating a status line in a tight loop would otherwise write far more output than any
+/// terminal can show, slowing down both the loop and the terminal. Instead, an update that
+/// arrives within the interval of the last rendered one is kept, and replaced by any later
+/// update, and the latest update is always rendered by [`finish()`](StatusLine::finish()),
+/// so the final state is never lost.
+///
+/// Each status is a [`Display`] value of type `T`, which is only formatted when rendered,
+/// e.g. a [`Styled`](crate::Styled) value, or a struct of counters. The line is rewritten
+/// as by [`Emphasis::rewrite()`](super::Emphasis::rewrite()), so a status should not contain
+/// newlines, and should fit on one line of the terminal.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, io::{self, StatusLine}, Colour::Green};
+/// use std::time::Duration;
+///
+/// // At most 10 refreshes per second
+/// let mut status = StatusLine::new(io::ansiout(), Duration::from_millis(100));
+/// for i in 1..=100_000 {
+///     status.update(styled!(Green, i));
+/// }
+/// // Always shows "100000", then ends the line
+/// status.finish().unwrap();
+/// ```
+pub struct StatusLine<W: io::Write, T: Display> {
+    writer:   W,
+    interval: Duration,
+    start:    Instant,
+    rendered: Option<Duration>,
+    pending:  Option<T>,
+}
+
+impl<W: io::Write, T: Display> StatusLine<W, T> {
+    /// Creates a new instance that writes to the given `Writer`, rendering at most one
+    /// update per `interval`, e.g. 100ms for at most 10 refreshes per second.
+    pub fn new(writer: W, interval: Duration) -> Self {
+        Self { writer, interval, start: Instant::now(), rendered: None, pending: None }
+    }
+
+    /// Gets the minimum time between rendered updates.
+    #[inline]
+    pub fn interval(&self) -> Duration { self.interval }
+
+    /// Gets the latest update that has not been rendered yet, if any.
+    #[inline]
+    pub fn pending(&self) -> Option<&T> { self.pending.as_ref() }
+
+    /// Gets a reference to the inner `Writer`.
+    #[inline]
+    pub fn get_ref(&self) -> &W { &self.writer }
+
+    /// Updates the status, rendering it unless the last update was rendered less than
+    /// an [`interval()`](StatusLine::interval()) ago.
+    ///
+    /// Panics if writing fails. See [`try_update()`](StatusLine::try_update()) for a
+    /// non-panicking alternative.
+    pub fn update(&mut self, status: T) { self.try_update(status).unwrap() }
+
+    /// Like [`update()`](StatusLine::update()), except returns an [`io::Result`] instead of panicking.
+    pub fn try_update(&mut self, status: T) -> io::Result<()> {
+        let elapsed = self.start.elapsed();
+        self.update_at(status, elapsed)
+    }
+
+    /// Updates the status, as if `elapsed` time had passed since this instance was created,
+    /// rendering it unless the last update was rendered less than an
+    /// [`interval()`](StatusLine::interval()) earlier.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::io::StatusLine;
+    /// use std::time::Duration;
+    ///
+    /// let mut status = StatusLine::new(Vec::new(), Duration::from_millis(100));
+    /// status.update_at(1, Duration::from_millis(0)).unwrap();
+    /// status.update_at(2, Duration::from_millis(50)).unwrap();
+    /// assert_eq!(status.pending(), Some(&2));
+    /// status.update_at(3, Duration::from_millis(100)).unwrap();
+    /// assert_eq!(status.pending(), None);
+    ///
+    /// assert_eq!(status.finish().unwrap(), b"\r1\x1B[K\r3\x1B[K\n");
+    /// ```
+    pub fn update_at(&mut self, status: T, elapsed: Duration) -> io::Result<()> {
+        let is_due = match self.rendered {
+            Some(rendered) => elapsed >= rendered + self.interval || elapsed < rendered,
+            None           => true,
+        };
+        if !is_due {
+            self.pending = Some(status);
+            return Ok(());
+        }
+        self.pending = None;
+        self.rendered = Some(elapsed);
+        rewrite_line(&mut self.writer, status)
+    }
+
+    /// Renders the latest update, if not rendered already, then ends the line,
+    /// returning the inner `Writer`.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(status) = self.pending.take() {
+            rewrite_line(&mut self.writer, status)?;
+        }
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
@@ -1,22 +1,149 @@
-use crate::{styled_write, Ansi};
+use crate::Ansi;
+use crate::fmt::WriteOptions;
+use crate::map::StyleMap;
+use crate::text::Sanitize;
+use std::env;
 use std::io::{self, IsTerminal};
 use std::fmt;
-use std::cell::Cell;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 
-use super::{AnsiPreference, AnsiWrite};
+#[cfg(feature="global-writer")]
+use super::global::global_writer;
+use super::pager::pager_writer;
+use super::reset::set_may_be_styled;
+#[cfg(feature="windows")]
+use super::windows::is_windows_ansi_enabled;
+use super::{write_fmt, AnsiPreference, AnsiWrite, Composition, FlushPolicy, LinePrefix};
 
-static mut ANSIOUT: Cell<Option<Ansi>> = Cell::new(None);
-static mut ANSIERR: Cell<Option<Ansi>> = Cell::new(None);
+/// The default style and options shared by all [`Ansiout`]s or [`Ansierr`]s, which are
+/// initialised from the stream's [preferences](AnsiPreference) on first use.
+///
+/// Note: locked only briefly, i.e. not for the lifetime of an `Ansiout`/`Ansierr`,
+/// since a thread may create several at once, e.g. by nesting calls to `paint!`.
+struct Defaults {
+    ansi:    Option<Ansi>,
+    options: Option<WriteOptions>,
+}
+
+/// The [`Defaults`] of a stream, and whether writes of plain text may bypass them.
+struct Shared {
+    defaults: Mutex<Defaults>,
+    /// One of [`NOT_PLAIN`], [`PLAIN_AT_LINE_START`] or [`PLAIN_MID_LINE`], updated
+    /// whenever the defaults are unlocked.
+    plain:    AtomicU8,
+}
+
+static ANSIOUT: Shared = Shared::new();
+static ANSIERR: Shared = Shared::new();
+
+/// Writes may need styling, prefixing, sanitizing or flushing, so must lock the defaults.
+const NOT_PLAIN: u8 = 0;
+/// Writes of plain text are written unchanged, and the last write ended a line.
+const PLAIN_AT_LINE_START: u8 = 1;
+/// Writes of plain text are written unchanged, and the last write did not end a line.
+const PLAIN_MID_LINE: u8 = 2;
+
+impl Shared {
+    const fn new() -> Self {
+        Self { defaults: Mutex::new(Defaults { ansi: None, options: None }), plain: AtomicU8::new(NOT_PLAIN) }
+    }
+}
+
+/// A lock on a stream's [`Defaults`], which updates whether writes of plain text
+/// may bypass them when dropped.
+struct Locked(MutexGuard<'static, Defaults>, &'static AtomicU8);
+
+impl Deref for Locked {
+    type Target = Defaults;
+    fn deref(&self) -> &Defaults { &self.0 }
+}
+
+impl DerefMut for Locked {
+    fn deref_mut(&mut self) -> &mut Defaults { &mut self.0 }
+}
+
+impl Drop for Locked {
+    fn drop(&mut self) { self.1.store(self.0.plain(), Ordering::Relaxed) }
+}
+
+fn lock(shared: &'static Shared) -> Locked {
+    Locked(shared.defaults.lock().unwrap_or_else(PoisonError::into_inner), &shared.plain)
+}
+
+impl Defaults {
+    fn ansi(&mut self, stream: &impl AnsiPreference) -> Ansi {
+        *self.ansi.get_or_insert_with(|| stream.preferred_ansi())
+    }
+
+    fn options(&mut self, stream: &impl AnsiPreference) -> WriteOptions {
+        *self.options.get_or_insert_with(|| {
+            WriteOptions { style_map: stream.preferred_style_map(), ..WriteOptions::new() }
+        })
+    }
+
+    /// Determines whether plain text, i.e. without escape sequences, would be written unchanged,
+    /// and need not be flushed, once initialised.
+    fn plain(&self) -> u8 {
+        match (self.ansi, self.options) {
+            (Some(ansi), Some(options)) if (ansi.is_empty() || ansi.is_no_ansi())
+                && options.style_map.is_none() && options.style_budget.is_none()
+                && options.line_prefix.is_none() && options.sanitize.is_none()
+                && options.flush_policy == FlushPolicy::Manual => {
+                if options.at_line_start { PLAIN_AT_LINE_START } else { PLAIN_MID_LINE }
+            },
+            _ => NOT_PLAIN,
+        }
+    }
+}
+
+/// Records the options changed by a write, i.e. the state of the line written so far,
+/// locking `defaults` only if they changed.
+fn record_write(defaults: &'static Shared, before: WriteOptions, after: WriteOptions) {
+    if (before.at_line_start, before.last_style) != (after.at_line_start, after.last_style) {
+        if let Some(options) = &mut lock(defaults).options {
+            options.at_line_start = after.at_line_start;
+            options.last_style    = after.last_style;
+        }
+    }
+}
+
+/// Writes `text` straight to `w`, without locking the stream's [`Defaults`], if it is
+/// plain text that they would write unchanged, else returns `None`.
+///
+/// Only the line's state is recorded, locking the defaults only if it changed,
+/// e.g. not for consecutive writes of whole lines.
+fn write_plain<W: io::Write>(w: &mut W, shared: &'static Shared, stream: u8, text: &str) -> Option<io::Result<()>> {
+    let plain = shared.plain.load(Ordering::Relaxed);
+    if plain == NOT_PLAIN || is_coordinated() || text.contains('\x1B') {
+        return None;
+    }
+    if text.is_empty() {
+        return Some(Ok(()));
+    }
+    let result = w.write_all(text.as_bytes());
+    set_may_be_styled(stream, false);
+    let at_line_start = text.ends_with('\n');
+    if at_line_start != (plain == PLAIN_AT_LINE_START) {
+        if let Some(options) = &mut lock(shared).options {
+            options.at_line_start = at_line_start;
+        }
+    }
+    Some(result)
+}
 
 /// A `Writer` that writes styled output to an inner [`StdoutLock`](std::io::StdoutLock) using
 /// a configurable default [`Ansi`] instance.
 ///
 /// Like its inner [`StdoutLock`](std::io::StdoutLock), each instance of this `Writer` is
 /// a handle to a *shared* global construct. The configurable default [`Ansi`] is therefore
-/// shared amongst all `Ansiout` instances. Threadsafe access is ensured thanks to the
-/// inner lock's mutex.
+/// shared amongst all `Ansiout` instances, and is stored behind a mutex.
 ///
-/// Created by the [`ansiout`] method.
+/// Created by the [`ansiout`] method, which locks `stdout`. The lock is held until the instance
+/// is dropped, so output written through a single instance is not interleaved with output
+/// written to `stdout` by other threads. Like [`StdoutLock`](std::io::StdoutLock), the lock is
+/// reentrant, i.e. the same thread may create several instances at once.
 ///
 /// **Note**: only calls to this `Writer`'s [`write_fmt()`](io::Write::write_fmt()) method
 /// will have the default ANSI styling applied. Calls to any other [`Write`](io::Write)
@@ -27,22 +154,139 @@ pub struct Ansiout(io::StdoutLock<'static>);
 ///
 /// Like its inner [`StderrLock`](std::io::StderrLock), each instance of this `Writer` is
 /// a handle to a *shared* global construct. The configurable default [`Ansi`] is therefore
-/// shared amongst all `Ansierr` instances. Threadsafe access is ensured thanks to the
-/// inner lock's mutex.
+/// shared amongst all `Ansierr` instances, and is stored behind a mutex.
 ///
-/// Created by the [`ansierr`] method.
+/// Created by the [`ansierr`] method, which locks `stderr`. The lock is held until the instance
+/// is dropped, so output written through a single instance is not interleaved with output
+/// written to `stderr` by other threads. Like [`StderrLock`](std::io::StderrLock), the lock is
+/// reentrant, i.e. the same thread may create several instances at once.
 ///
 /// **Note**: only calls to this `Writer`'s [`write_fmt()`](io::Write::write_fmt()) method
 /// will have the default ANSI styling applied. Calls to any other [`Write`](io::Write)
 /// methods are unaffected.
 pub struct Ansierr(io::StderrLock<'static>);
 
-/// Creates an [`Ansiout`] that wraps the result of locking [`stdout()`](io::stdout())
+/// A guard that buffers output written to an [`Ansiout`], e.g. to write several lines
+/// atomically, created by [`Ansiout::lock()`].
 ///
+/// The output is styled as if written to the `Ansiout` itself, and is written to it
+/// when the guard is [flushed](io::Write::flush()) or dropped. Errors when dropped
+/// are ignored, so call [`flush()`](io::Write::flush()) to handle them.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, io::ansiout, Colour::Green, Effect::Bold};
+/// use std::io::Write;
+///
+/// let mut out = ansiout().lock();
+/// styled_writeln!(out, Bold, "Summary").unwrap();
+/// styled_writeln!(out, Green, "All tests passed").unwrap();
+/// out.flush().unwrap();
+/// ```
+pub struct AnsioutLock { inner: Ansiout, buf: Vec<u8> }
+/// A guard that buffers output written to an [`Ansierr`], created by [`Ansierr::lock()`],
+/// as for [`AnsioutLock`].
+pub struct AnsierrLock { inner: Ansierr, buf: Vec<u8> }
+
+/// Whether writes to [`Ansiout`] and [`Ansierr`] are coordinated.
+static COORDINATED: AtomicBool = AtomicBool::new(false);
+/// Which stream, if any, left a style active at the end of its last coordinated write.
+static DIRTY: AtomicU8 = AtomicU8::new(CLEAN);
+
+const CLEAN: u8 = 0;
+pub(super) const STDOUT: u8 = 1;
+pub(super) const STDERR: u8 = 2;
+
+/// Sets whether writes to [`Ansiout`] and [`Ansierr`] are coordinated, such that
+/// styles interleaved between `stdout` and `stderr` never bleed from one to the other.
+///
+/// Normally, every write to these `Writer`s resets any styles it sets. However, a style
+/// may be left active, e.g. by text containing its own escape sequences, or by a write
+/// that fails part-way. When both `stdout` and `stderr` are terminals, such a style
+/// affects the other stream's output too. When coordinated, the style left active by each
+/// stream's writes is shared, and a reset is written before the other stream's next write.
+///
+/// Coordination is disabled by default, and only applies when both `stdout` and `stderr`
+/// are terminals/ttys. No reset is written to a stream whose default style is
+/// [`Ansi::no_ansi()`].
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::io::{self, ansierr, ansiout};
+///
+/// io::set_coordinated(true);
+///
+/// // Leaves red text active on stdout...
+/// write!(ansiout(), "\x1B[31mRed").unwrap();
+/// // ... so, on a terminal, prints "\x1B[0mNot red\n" to stderr
+/// writeln!(ansierr(), "Not red").unwrap();
+///
+/// io::set_coordinated(false);
+/// ```
+pub fn set_coordinated(coordinated: bool) {
+    COORDINATED.store(coordinated, Ordering::Relaxed);
+    DIRTY.store(CLEAN, Ordering::Relaxed);
+}
+
+/// Determines whether writes to [`Ansiout`] and [`Ansierr`] are coordinated.
+///
+/// See [`set_coordinated()`].
+pub fn is_coordinated() -> bool {
+    COORDINATED.load(Ordering::Relaxed)
+}
+
+/// Writes to `stream` (i.e. [`STDOUT`] or [`STDERR`]), first resetting any style
+/// left active by the other stream, if coordinated, and recording whether a style
+/// may be left active afterwards.
+fn write_stream<W: io::Write>(
+    w: &mut W, stream: u8, ansi: Ansi, options: &mut WriteOptions, fmt: fmt::Arguments<'_>,
+) -> io::Result<()> {
+    // Note: remains set if the write fails part-way, e.g. panics
+    set_may_be_styled(stream, !ansi.is_no_ansi());
+    let active = if !(is_coordinated() && io::stdout().is_terminal() && io::stderr().is_terminal()) {
+        write_fmt(w, ansi, options, fmt)?
+    } else {
+        let other = if stream == STDOUT { STDERR } else { STDOUT };
+        if DIRTY.load(Ordering::Relaxed) == other && !ansi.is_no_ansi() {
+            write!(w, "{}", Ansi::reset())?;
+            DIRTY.store(CLEAN, Ordering::Relaxed);
+        }
+        let active = write_fmt(w, ansi, options, fmt)?;
+        DIRTY.store(if active.is_unspecified() { CLEAN } else { stream }, Ordering::Relaxed);
+        active
+    };
+    set_may_be_styled(stream, !active.is_unspecified());
+    Ok(())
+}
+
+/// Creates an [`Ansiout`] that wraps the result of locking [`stdout()`](io::stdout()).
+///
+/// Keep the returned instance to write several lines without other threads' output
+/// in between, e.g.:
+///
+/// ```
+/// use ansiconst::{*, io::ansiout, Colour::Green, Effect::Bold};
+///
+/// let mut out = ansiout();
+/// writeln!(out, "{}", styled!(Bold, "Summary")).unwrap();
+/// writeln!(out, "{}", styled!(Green, "All tests passed")).unwrap();
+/// drop(out);
+/// ```
+///
+/// To also buffer the output, writing it in one go, see [`Ansiout::lock()`].
 pub fn ansiout() -> Ansiout { Ansiout(io::stdout().lock()) }
-/// Creates an [`Ansierr`] that wraps the result of locking [`stderr()`](io::stderr())
+/// Creates an [`Ansierr`] that wraps the result of locking [`stderr()`](io::stderr()),
+/// as for [`ansiout()`].
 pub fn ansierr() -> Ansierr { Ansierr(io::stderr().lock()) }
 
+/// Gets the width of the terminal in columns, according to the `COLUMNS` env variable
+/// (which most shells set, when exported), else `80`.
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS").ok().and_then(|columns| columns.parse().ok()).filter(|columns| *columns > 0).unwrap_or(80)
+}
+
  impl Ansiout {
     // Needed so that this crate's paint*! macros work without having std::io::Write in scope
     #[inline]
@@ -50,6 +294,25 @@ pub fn ansierr() -> Ansierr { Ansierr(io::stderr().lock()) }
     pub fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
         io::Write::write_fmt(self, fmt)
     }
+
+    /// Locks this instance, returning a guard that buffers the output written through it,
+    /// and writes it to `stdout` in one go when [flushed](io::Write::flush()) or dropped.
+    ///
+    /// Like this instance, the guard holds the lock on `stdout` until dropped, so
+    /// its output is not interleaved with output written by other threads.
+    pub fn lock(self) -> AnsioutLock { AnsioutLock { inner: self, buf: Vec::new() } }
+
+    fn options(&self) -> WriteOptions { lock(&ANSIOUT).options(self) }
+
+    /// Gets the default style and options with a single lock, e.g. for a write.
+    fn defaults(&self) -> (Ansi, WriteOptions) {
+        let mut defaults = lock(&ANSIOUT);
+        (defaults.ansi(self), defaults.options(self))
+    }
+
+    fn set_options(&mut self, options: WriteOptions) {
+        lock(&ANSIOUT).options = Some(options);
+    }
 }
 
 impl Ansierr {
@@ -59,76 +322,270 @@ impl Ansierr {
     pub fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
         io::Write::write_fmt(self, fmt)
     }
+
+    /// Locks this instance, returning a guard that buffers the output written through it,
+    /// and writes it to `stderr` in one go when [flushed](io::Write::flush()) or dropped.
+    ///
+    /// Like this instance, the guard holds the lock on `stderr` until dropped, so
+    /// its output is not interleaved with output written by other threads.
+    pub fn lock(self) -> AnsierrLock { AnsierrLock { inner: self, buf: Vec::new() } }
+
+    fn options(&self) -> WriteOptions { lock(&ANSIERR).options(self) }
+
+    /// Gets the default style and options with a single lock, e.g. for a write.
+    fn defaults(&self) -> (Ansi, WriteOptions) {
+        let mut defaults = lock(&ANSIERR);
+        (defaults.ansi(self), defaults.options(self))
+    }
+
+    fn set_options(&mut self, options: WriteOptions) {
+        lock(&ANSIERR).options = Some(options);
+    }
 }
 
 impl AnsiWrite for Ansiout {
-    fn ansi(&self) -> Ansi {
-        // Note: actually safe, because we're holding a StdoutLock
-        unsafe {
-            match ANSIOUT.get() {
-                None => {
-                    let ansi: Ansi = self.preferred_ansi();
-                    ANSIOUT.set(Some(ansi));
-                    ansi
-                },
-                Some(ansi) => ansi,
-            }
-        }
-    }
+    fn ansi(&self) -> Ansi { lock(&ANSIOUT).ansi(self) }
 
     fn set_ansi(&mut self, ansi: Ansi) {
-        // Note: actually safe, because we're holding a StdoutLock
-        unsafe { ANSIOUT.set(Some(ansi)); }
+        lock(&ANSIOUT).ansi = Some(ansi);
     }
+
+    fn style_map(&self) -> Option<StyleMap> { self.options().style_map }
+
+    fn set_style_map(&mut self, map: Option<StyleMap>) {
+        self.set_options(WriteOptions { style_map: map, ..self.options() })
+    }
+
+    fn style_budget(&self) -> Option<usize> { self.options().style_budget }
+
+    fn set_style_budget(&mut self, budget: Option<usize>) {
+        self.set_options(WriteOptions { style_budget: budget, ..self.options() })
+    }
+
+    fn flush_policy(&self) -> FlushPolicy { self.options().flush_policy }
+
+    fn set_flush_policy(&mut self, policy: FlushPolicy) {
+        self.set_options(WriteOptions { flush_policy: policy, ..self.options() })
+    }
+
+    fn line_prefix(&self) -> Option<LinePrefix> { self.options().line_prefix }
+
+    fn set_line_prefix(&mut self, prefix: Option<LinePrefix>) {
+        self.set_options(WriteOptions { line_prefix: prefix, ..self.options() })
+    }
+
+    fn sanitize(&self) -> Option<Sanitize> { self.options().sanitize }
+
+    fn set_sanitize(&mut self, mode: Option<Sanitize>) {
+        self.set_options(WriteOptions { sanitize: mode, ..self.options() })
+    }
+
+    fn composition(&self) -> Composition { self.options().composition }
+
+    fn set_composition(&mut self, composition: Composition) {
+        self.set_options(WriteOptions { composition, ..self.options() })
+    }
+
+    fn last_style(&self) -> Ansi { self.options().last_style }
 }
 
 impl AnsiWrite for Ansierr {
-    fn ansi(&self) -> Ansi {
-        // Note: actually safe, because we're holding a StderrLock
-        unsafe {
-            match ANSIERR.get() {
-                None => {
-                    let ansi: Ansi = self.preferred_ansi();
-                    ANSIERR.set(Some(ansi));
-                    ansi
-                },
-                Some(ansi) => ansi,
-            }
-        }
-    }
+    fn ansi(&self) -> Ansi { lock(&ANSIERR).ansi(self) }
 
     fn set_ansi(&mut self, ansi: Ansi) {
-        // Note: actually safe, because we're holding a StderrLock
-        unsafe { ANSIERR.set(Some(ansi)); }
+        lock(&ANSIERR).ansi = Some(ansi);
     }
+
+    fn style_map(&self) -> Option<StyleMap> { self.options().style_map }
+
+    fn set_style_map(&mut self, map: Option<StyleMap>) {
+        self.set_options(WriteOptions { style_map: map, ..self.options() })
+    }
+
+    fn style_budget(&self) -> Option<usize> { self.options().style_budget }
+
+    fn set_style_budget(&mut self, budget: Option<usize>) {
+        self.set_options(WriteOptions { style_budget: budget, ..self.options() })
+    }
+
+    fn flush_policy(&self) -> FlushPolicy { self.options().flush_policy }
+
+    fn set_flush_policy(&mut self, policy: FlushPolicy) {
+        self.set_options(WriteOptions { flush_policy: policy, ..self.options() })
+    }
+
+    fn line_prefix(&self) -> Option<LinePrefix> { self.options().line_prefix }
+
+    fn set_line_prefix(&mut self, prefix: Option<LinePrefix>) {
+        self.set_options(WriteOptions { line_prefix: prefix, ..self.options() })
+    }
+
+    fn sanitize(&self) -> Option<Sanitize> { self.options().sanitize }
+
+    fn set_sanitize(&mut self, mode: Option<Sanitize>) {
+        self.set_options(WriteOptions { sanitize: mode, ..self.options() })
+    }
+
+    fn composition(&self) -> Composition { self.options().composition }
+
+    fn set_composition(&mut self, composition: Composition) {
+        self.set_options(WriteOptions { composition, ..self.options() })
+    }
+
+    fn last_style(&self) -> Ansi { self.options().last_style }
 }
 
 impl AnsiPreference for Ansiout {
-    fn is_ansi_preferred(&self) -> bool { self.0.is_terminal() }
+    fn is_ansi_preferred(&self) -> bool { is_std_ansi_preferred(self.0.is_terminal()) }
 }
 impl AnsiPreference for Ansierr {
-    fn is_ansi_preferred(&self) -> bool { self.0.is_terminal() }
+    fn is_ansi_preferred(&self) -> bool { is_std_ansi_preferred(self.0.is_terminal()) }
+}
+
+/// Determines if `stdout` or `stderr` prefers ANSI styles.
+///
+/// On WASI, runtimes usually do not report whether a stream is a terminal, so a
+/// non-`dumb` `TERM` env variable (which runtimes only pass through on request)
+/// is taken to mean a terminal too.
+///
+/// With `feature=windows`, a terminal only prefers ANSI styles if they could be enabled
+/// on the Windows console, see [`enable_windows_ansi()`](super::enable_windows_ansi()).
+pub(super) fn is_std_ansi_preferred(is_terminal: bool) -> bool {
+    if cfg!(target_os="wasi") {
+        is_terminal || env::var_os("TERM").is_some_and(|term| !term.is_empty() && term != "dumb")
+    } else {
+        #[cfg(feature="windows")]
+        if is_terminal {
+            return is_windows_ansi_enabled();
+        }
+        is_terminal
+    }
 }
 
 impl io::Write for Ansiout {
     fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
-        if ! self.ansi().is_empty() {
-            styled_write!(self.0, self.ansi(), "{}", fmt)
-        } else {
-            self.0.write_fmt(fmt)
+        // Fast path for plain writes, e.g. `paint!("text")`, which need not lock the defaults
+        if let Some(result) = fmt.as_str().and_then(|text| write_plain(self, &ANSIOUT, STDOUT, text)) {
+            return result;
+        }
+        let (ansi, before) = self.defaults();
+        let mut options = before;
+        #[cfg(feature="global-writer")]
+        if let Some(mut writer) = global_writer() {
+            let result = write_fmt(&mut *writer, ansi, &mut options, fmt).map(|_| ());
+            record_write(&ANSIOUT, before, options);
+            return result;
+        }
+        let result = match pager_writer() {
+            Some(mut pager) => write_fmt(&mut pager, ansi, &mut options, fmt).map(|_| ()),
+            None            => write_stream(&mut self.0, STDOUT, ansi, &mut options, fmt),
+        };
+        record_write(&ANSIOUT, before, options);
+        result
+    }
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        #[cfg(feature="global-writer")]
+        if let Some(mut writer) = global_writer() {
+            return writer.write(buf);
+        }
+        match pager_writer() {
+            Some(mut pager) => pager.write(buf),
+            None            => self.0.write(buf),
+        }
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        #[cfg(feature="global-writer")]
+        if let Some(mut writer) = global_writer() {
+            return writer.flush();
+        }
+        match pager_writer() {
+            Some(mut pager) => pager.flush(),
+            None            => self.0.flush(),
         }
     }
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
-    fn flush(&mut self) -> io::Result<()> { self.0.flush() }
 }
 impl io::Write for Ansierr {
     fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
-        if ! self.ansi().is_empty() {
-            styled_write!(self.0, self.ansi(), "{}", fmt)
-        } else {
-            self.0.write_fmt(fmt)
+        // Fast path for plain writes, e.g. `epaint!("text")`, which need not lock the defaults
+        if let Some(result) = fmt.as_str().and_then(|text| write_plain(&mut self.0, &ANSIERR, STDERR, text)) {
+            return result;
         }
+        let (ansi, before) = self.defaults();
+        let mut options = before;
+        let result = write_stream(&mut self.0, STDERR, ansi, &mut options, fmt);
+        record_write(&ANSIERR, before, options);
+        result
     }
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
     fn flush(&mut self) -> io::Result<()> { self.0.flush() }
 }
+
+impl AnsioutLock {
+    // Needed so that this crate's styled_write*! macros work without having std::io::Write in scope
+    #[inline]
+    #[doc(hidden)]
+    pub fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
+        io::Write::write_fmt(self, fmt)
+    }
+}
+
+impl io::Write for AnsioutLock {
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
+        if let Some(result) = fmt.as_str().and_then(|text| write_plain(&mut self.buf, &ANSIOUT, STDOUT, text)) {
+            return result;
+        }
+        let (ansi, before) = self.inner.defaults();
+        let mut options = before;
+        let result = write_stream(&mut self.buf, STDOUT, ansi, &mut options, fmt);
+        record_write(&ANSIOUT, before, options);
+        result
+    }
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.write_all(&self.buf)?;
+        self.buf.clear();
+        self.inner.flush()
+    }
+}
+
+impl Drop for AnsioutLock {
+    fn drop(&mut self) { let _ = io::Write::flush(self); }
+}
+
+impl AnsierrLock {
+    // Needed so that this crate's styled_write*! macros work without having std::io::Write in scope
+    #[inline]
+    #[doc(hidden)]
+    pub fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
+        io::Write::write_fmt(self, fmt)
+    }
+}
+
+impl io::Write for AnsierrLock {
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
+        if let Some(result) = fmt.as_str().and_then(|text| write_plain(&mut self.buf, &ANSIERR, STDERR, text)) {
+            return result;
+        }
+        let (ansi, before) = self.inner.defaults();
+        let mut options = before;
+        let result = write_stream(&mut self.buf, STDERR, ansi, &mut options, fmt);
+        record_write(&ANSIERR, before, options);
+        result
+    }
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.write_all(&self.buf)?;
+        self.buf.clear();
+        self.inner.flush()
+    }
+}
+
+impl Drop for AnsierrLock {
+    fn drop(&mut self) { let _ = io::Write::flush(self); }
+}
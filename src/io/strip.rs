@@ -0,0 +1,88 @@
+use std::{io, mem};
+
+use crate::text::escape_end;
+use super::AnsiPreference;
+
+const ESC: u8 = 0x1B;
+
+/// A `Writer` that removes all ANSI escape sequences from the output written to an
+/// inner `Writer`, e.g. for writing styled output to a log file, including any escape
+/// sequences from foreign sources, such as the output of other programs.
+///
+/// The same escape sequences are removed as by [`text::strip_ansi()`](crate::text::strip_ansi()),
+/// even when a sequence is split across several writes: an incomplete escape sequence at
+/// the end of a write is held back until the rest of it has been written.
+/// [`into_inner()`](StripWriter::into_inner()) discards an incomplete escape sequence.
+///
+/// A `StripWriter` never prefers ANSI styles (see [`AnsiPreference`]), so wrapping it in
+/// an [`AnsiWriter`](super::AnsiWriter) with its [default](super::AnsiWriter::default())
+/// style avoids writing the crate's own styles in the first place.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, io::StripWriter, Colour::Red};
+/// use std::io::Write;
+///
+/// let mut writer = StripWriter::new(Vec::new());
+/// write!(writer, "{} \x1B]0;title", styled!(Red, "Hello")).unwrap();
+/// write!(writer, "\x07world").unwrap();
+///
+/// assert_eq!(writer.get_ref(), b"Hello world");
+/// ```
+pub struct StripWriter<W: io::Write> {
+    inner:   W,
+    pending: Vec<u8>,
+}
+
+impl<W: io::Write> StripWriter<W> {
+    /// Creates a new instance that writes to the given `Writer`.
+    #[inline]
+    pub fn new(inner: W) -> Self { Self { inner, pending: Vec::new() } }
+    /// Gets the incomplete escape sequence, if any, that is held back until the rest
+    /// of it has been written.
+    #[inline]
+    pub fn buffer(&self) -> &[u8] { &self.pending }
+    /// Gets a reference to the inner `Writer`.
+    #[inline]
+    pub fn get_ref(&self) -> &W { &self.inner }
+    /// Discards any incomplete escape sequence, then unwraps this `StripWriter`,
+    /// returning the inner `Writer`.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: io::Write> io::Write for StripWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let joined;
+        let mut rest = buf;
+        if !self.pending.is_empty() {
+            self.pending.extend_from_slice(buf);
+            joined = mem::take(&mut self.pending);
+            rest = &joined;
+        }
+        while !rest.is_empty() {
+            let start = rest.iter().position(|b| *b == ESC).unwrap_or(rest.len());
+            if start > 0 {
+                self.inner.write_all(&rest[..start])?;
+                rest = &rest[start..];
+                continue;
+            }
+            match escape_end(rest) {
+                Some(len) => rest = &rest[len..],
+                None      => {
+                    self.pending.extend_from_slice(rest);
+                    break;
+                },
+            }
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
+
+impl<W: io::Write> AnsiPreference for StripWriter<W> {
+    fn is_ansi_preferred(&self) -> bool { false }
+}
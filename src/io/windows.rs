@@ -0,0 +1,77 @@
+use std::io;
+use std::sync::OnceLock;
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+/// Enables ANSI escape sequences on the Windows console, i.e. its *virtual terminal
+/// processing*, for each of `stdout` and `stderr` that is a console, which is otherwise
+/// disabled by default on many Windows consoles, which then show raw escape sequences.
+///
+/// This is done automatically when determining the default style of [`ansiout()`](super::ansiout())
+/// and [`ansierr()`](super::ansierr()), which disables ANSI styles if it fails, so it need only
+/// be called when writing styled output to the console by other means, e.g. to a `Writer`
+/// wrapping `stdout`. It may be called any number of times.
+///
+/// Returns an error if a console's mode cannot be set, e.g. on Windows versions before
+/// Windows 10. Streams that are not consoles, e.g. pipes, are ignored. On other platforms,
+/// this does nothing.
+///
+/// *Note: only available with `feature=windows`*
+///
+/// ### Examples
+///
+/// ```
+/// # #[cfg(feature="windows")] {
+/// use ansiconst::{*, io::{self, AnsiWriter}, Colour::Red};
+/// use std::io::Write;
+///
+/// if io::enable_windows_ansi().is_ok() {
+///     let mut writer = AnsiWriter::new(std::io::stdout(), Ansi::unspecified());
+///     writeln!(writer, "{}", styled!(Red, "Red on all platforms")).unwrap();
+/// }
+/// # }
+/// ```
+pub fn enable_windows_ansi() -> io::Result<()> {
+    #[cfg(windows)]
+    {
+        use std::os::windows::io::AsRawHandle;
+        sys::enable_virtual_terminal(io::stdout().as_raw_handle())?;
+        sys::enable_virtual_terminal(io::stderr().as_raw_handle())?;
+    }
+    Ok(())
+}
+
+/// Determines whether [`enable_windows_ansi()`] succeeded, calling it the first time only.
+pub(super) fn is_windows_ansi_enabled() -> bool {
+    *ENABLED.get_or_init(|| enable_windows_ansi().is_ok())
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::io;
+    use std::os::windows::io::RawHandle;
+
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    #[link(name="kernel32")]
+    extern "system" {
+        fn GetConsoleMode(handle: RawHandle, mode: *mut u32) -> i32;
+        fn SetConsoleMode(handle: RawHandle, mode: u32) -> i32;
+    }
+
+    /// Enables virtual terminal processing for `handle`, if it is a console.
+    pub(super) fn enable_virtual_terminal(handle: RawHandle) -> io::Result<()> {
+        let mut mode = 0;
+        // Note: fails if `handle` is not a console, e.g. if redirected to a file or pipe
+        if unsafe { GetConsoleMode(handle, &mut mode) } == 0 {
+            return Ok(());
+        }
+        if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 {
+            return Ok(());
+        }
+        if unsafe { SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,11 @@
 use std::io;
 use std::fmt;
 
-use crate::{styled_write, Ansi};
-use super::{AnsiPreference, AnsiWrite};
+use crate::Ansi;
+use crate::fmt::WriteOptions;
+use crate::map::StyleMap;
+use crate::text::Sanitize;
+use super::{write_fmt, AnsiPreference, AnsiWrite, Composition, FlushPolicy, LinePrefix};
 
 /// A `Writer` that writes styled output to an inner [`Write`](io::Write) using
 /// a configurable default [`Ansi`] instance.
@@ -12,22 +15,45 @@ use super::{AnsiPreference, AnsiWrite};
 /// methods are unaffected.
 pub struct AnsiWriter<W: io::Write + AnsiPreference> {
     ansi: Ansi,
+    options: WriteOptions,
     writer: W,
 }
 
 impl<W: io::Write + AnsiPreference> AnsiWriter<W> {
     /// Creates a new instance with the given `Writer` and ANSI style
     #[inline]
-    pub fn new(writer: W, ansi: Ansi) -> Self { Self { writer, ansi } }
+    pub fn new(writer: W, ansi: Ansi) -> Self { Self { writer, ansi, options: WriteOptions::new() } }
     /// Creates a new instance with the given `Writer`, using its
     /// [preferred](AnsiPreference::preferred_ansi) ANSI style.
     #[inline]
-    pub fn default(writer: W) -> Self { Self { ansi: writer.preferred_ansi(), writer } }
+    pub fn default(writer: W) -> Self { Self { ansi: writer.preferred_ansi(), options: WriteOptions::new(), writer } }
+    /// Gets a reference to the inner `Writer`.
+    #[inline]
+    pub fn get_ref(&self) -> &W { &self.writer }
+    /// Gets a mutable reference to the inner `Writer`.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W { &mut self.writer }
+    /// Unwraps this `AnsiWriter`, returning the inner `Writer`.
+    #[inline]
+    pub fn into_inner(self) -> W { self.writer }
 }
 
 impl<W: io::Write + AnsiPreference> AnsiWrite for AnsiWriter<W> {
     fn ansi(&self) -> Ansi { self.ansi }
     fn set_ansi(&mut self, ansi: Ansi) { self.ansi = ansi }
+    fn style_map(&self) -> Option<StyleMap> { self.options.style_map }
+    fn set_style_map(&mut self, map: Option<StyleMap>) { self.options.style_map = map }
+    fn style_budget(&self) -> Option<usize> { self.options.style_budget }
+    fn set_style_budget(&mut self, budget: Option<usize>) { self.options.style_budget = budget }
+    fn flush_policy(&self) -> FlushPolicy { self.options.flush_policy }
+    fn set_flush_policy(&mut self, policy: FlushPolicy) { self.options.flush_policy = policy }
+    fn line_prefix(&self) -> Option<LinePrefix> { self.options.line_prefix }
+    fn set_line_prefix(&mut self, prefix: Option<LinePrefix>) { self.options.line_prefix = prefix }
+    fn sanitize(&self) -> Option<Sanitize> { self.options.sanitize }
+    fn set_sanitize(&mut self, mode: Option<Sanitize>) { self.options.sanitize = mode }
+    fn composition(&self) -> Composition { self.options.composition }
+    fn set_composition(&mut self, composition: Composition) { self.options.composition = composition }
+    fn last_style(&self) -> Ansi { self.options.last_style }
 }
 
 impl<W: io::Write + AnsiPreference> AnsiPreference for AnsiWriter<W> {
@@ -36,11 +62,7 @@ impl<W: io::Write + AnsiPreference> AnsiPreference for AnsiWriter<W> {
 
 impl<W: io::Write + AnsiPreference> io::Write for AnsiWriter<W> {
     fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
-        if ! self.ansi.is_empty() {
-            styled_write!(self.writer, self.ansi, "{}", fmt)
-        } else {
-            self.writer.write_fmt(fmt)
-        }
+        write_fmt(&mut self.writer, self.ansi, &mut self.options, fmt).map(|_| ())
     }
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.writer.write(buf) }
     fn flush(&mut self) -> io::Result<()> { self.writer.flush() }
@@ -0,0 +1,78 @@
+//! Assign stable colours to identifiers, e.g. thread names, modules or hosts, as logging
+//! tools often do so that related lines stand out.
+//!
+//! A [`KeyColours`] hashes each key to one of its colours, so the same key always has the
+//! same colour, across runs, processes and versions of this crate. Keys are hashed with
+//! 64-bit FNV-1a, rather than with [`std::hash`], whose output is not guaranteed to be stable.
+//!
+//! The [`DEFAULT`] colours are the named colours that are readable on both dark and light
+//! backgrounds, i.e. excluding black, white, grey and dark blue.
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{*, key_colour::{self, KeyColours}, Colour::{Green, Red}, Effect::Bold};
+//!
+//! let colour = key_colour::get("worker-1");
+//! assert_eq!(colour, key_colour::get("worker-1"));
+//! assert!(key_colour::DEFAULT.colours().contains(&colour));
+//!
+//! const MY_COLOURS: KeyColours = KeyColours::new(&[ansi!(Red, Bold), ansi!(Green, Bold)]);
+//! assert_eq!(MY_COLOURS.get("host-a"), ansi!(Green, Bold));
+//! assert_eq!(MY_COLOURS.get("host-b"), ansi!(Red, Bold));
+//! ```
+
+use crate::{ansi, Ansi};
+use crate::Colour::{BrightBlue, BrightCyan, BrightGreen, BrightPurple, BrightRed, BrightYellow};
+use crate::Colour::{Cyan, Green, Purple, Red, Yellow};
+
+/// A palette of colours, to which keys are assigned by hashing them.
+///
+/// See the [module-level documentation](crate::key_colour).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct KeyColours<'a> { colours: &'a [Ansi] }
+
+impl<'a> KeyColours<'a> {
+    /// Creates a new instance with the given colours.
+    #[inline]
+    pub const fn new(colours: &'a [Ansi]) -> Self { Self { colours } }
+
+    /// Gets the colours.
+    #[inline]
+    pub const fn colours(&self) -> &'a [Ansi] { self.colours }
+
+    /// Gets the colour of the given key, or an [`unspecified`](Ansi::unspecified()) style
+    /// if there are no colours.
+    pub const fn get(&self, key: &str) -> Ansi {
+        if self.colours.is_empty() { return Ansi::unspecified(); }
+        self.colours[(hash(key) % self.colours.len() as u64) as usize]
+    }
+}
+
+impl Default for KeyColours<'_> {
+    fn default() -> Self { DEFAULT }
+}
+
+/// The default colours, which are readable on both dark and light backgrounds.
+pub const DEFAULT: KeyColours<'static> = KeyColours::new(&[
+    ansi!(Red),       ansi!(Green),       ansi!(Yellow),       ansi!(Purple),       ansi!(Cyan),
+    ansi!(BrightRed), ansi!(BrightGreen), ansi!(BrightYellow), ansi!(BrightPurple), ansi!(BrightCyan),
+    ansi!(BrightBlue),
+]);
+
+/// Gets the colour of the given key in the [`DEFAULT`] colours.
+#[inline]
+pub const fn get(key: &str) -> Ansi { DEFAULT.get(key) }
+
+/// Hashes the given key with 64-bit FNV-1a.
+const fn hash(key: &str) -> u64 {
+    let bytes = key.as_bytes();
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+        i += 1;
+    }
+    hash
+}
@@ -0,0 +1,357 @@
+//! Lay out styled text, e.g. in columns, aligned within a width, or as rules and section headings.
+//!
+//! The helpers in this module measure text by its [visible width](crate::text::visible_width()),
+//! i.e. ignoring escape sequences, so styled text lines up as expected.
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{*, layout::Columns, Colour::Blue};
+//!
+//! let files = [styled!(Blue, "src"), styled!(Blue, "tests"), styled!(Blue, "benches")];
+//!
+//! assert_eq!(Columns::new(&files).width(15).to_string(),
+//!     "\x1B[34msrc\x1B[39m    \x1B[34mbenches\x1B[39m\n\x1B[34mtests\x1B[39m\n");
+//! ```
+
+use crate::{io, text, Ansi, Styled};
+use std::fmt::{self, Write};
+
+/// A [`Display`](fmt::Display) adapter that arranges many short items into columns,
+/// like `ls` does, i.e. filling each column from top to bottom, in turn.
+///
+/// The number of columns is the most that fit within the [`width()`](Columns::width())
+/// (by default, the [terminal's width](io::terminal_width())), where each column is as
+/// wide as its widest item, and columns are separated by a [`gap()`](Columns::gap()).
+/// Padding is written outside of the items' styles, and any style left active at the
+/// end of an item is reset, so that styles never leak into the next cell.
+///
+/// Each row ends with a newline, and an item that is wider than the width gets a row
+/// of its own.
+#[derive(Clone, Copy, Debug)]
+pub struct Columns<'a, T: fmt::Display> {
+    items: &'a [T],
+    width: Option<usize>,
+    gap:   usize,
+}
+
+impl<'a, T: fmt::Display> Columns<'a, T> {
+    /// Creates a new instance for the given items, with the terminal's width and a
+    /// gap of 2 spaces between columns.
+    #[inline]
+    pub const fn new(items: &'a [T]) -> Self { Self { items, width: None, gap: 2 } }
+    /// Sets the total width in columns, instead of the terminal's width.
+    #[inline]
+    pub const fn width(self, width: usize) -> Self { Self { width: Some(width), ..self } }
+    /// Sets the number of spaces between columns.
+    #[inline]
+    pub const fn gap(self, gap: usize) -> Self { Self { gap, ..self } }
+    /// Gets the items.
+    #[inline]
+    pub const fn items(&self) -> &'a [T] { self.items }
+}
+
+impl<T: fmt::Display> fmt::Display for Columns<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<(String, usize)> = self.items.iter().map(|item| {
+            let mut cell = item.to_string();
+            // Reset any style left active by the item, e.g. if it contains raw escape sequences
+            let active = text::style_at_end(&cell);
+            let _ = write!(cell, "{}", active.transition(Ansi::unspecified()));
+            let width = text::visible_width(&cell);
+            (cell, width)
+        }).collect();
+        if cells.is_empty() {
+            return Ok(());
+        }
+        let width = self.width.unwrap_or_else(io::terminal_width);
+        let (rows, widths) = (1..=cells.len())
+            .map(|rows| (rows, column_widths(&cells, rows)))
+            .find(|(_, widths)| widths.iter().sum::<usize>() + self.gap * (widths.len() - 1) <= width)
+            .unwrap_or_else(|| (cells.len(), vec![0]));
+        for row in 0..rows {
+            let mut cols = (0..widths.len()).filter_map(|col| cells.get(col * rows + row).map(|cell| (col, cell))).peekable();
+            while let Some((col, (cell, cell_width))) = cols.next() {
+                f.write_str(cell)?;
+                if cols.peek().is_some() {
+                    let padding = widths[col] - cell_width + self.gap;
+                    write!(f, "{:padding$}", "", padding = padding)?;
+                }
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+/// Gets the width of each column when laying out `cells` in the given number of `rows`.
+fn column_widths(cells: &[(String, usize)], rows: usize) -> Vec<usize> {
+    cells.chunks(rows).map(|column| column.iter().map(|(_, width)| *width).max().unwrap_or(0)).collect()
+}
+
+/// A [`Display`](fmt::Display) adapter that repeats its target a number of times.
+///
+/// Usually created by [`Styled::repeat()`], or [`styled_repeat()`], so that the
+/// style is written once around all the repetitions, rather than around each one.
+#[derive(Clone, Copy, Debug)]
+pub struct Repeat<T: fmt::Display> {
+    target: T,
+    count:  usize,
+}
+
+impl<T: fmt::Display> Repeat<T> {
+    /// Creates a new instance that repeats `target` the given number of times.
+    #[inline]
+    pub const fn new(target: T, count: usize) -> Self { Self { target, count } }
+    /// Gets the target.
+    #[inline]
+    pub const fn target(&self) -> &T { &self.target }
+    /// Gets the number of repetitions.
+    #[inline]
+    pub const fn count(&self) -> usize { self.count }
+}
+
+impl<T: fmt::Display> fmt::Display for Repeat<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for _ in 0..self.count {
+            self.target.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Creates a [`Styled`] that repeats `pattern` the given number of times, writing the
+/// style just once, e.g. for drawing a rule or separator.
+///
+/// Equivalent to [`Styled::new(ansi, pattern).repeat(count)`](Styled::repeat()).
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, layout::styled_repeat, Effect::Faint};
+///
+/// assert_eq!(styled_repeat(ansi!(Faint), "─", 5).to_string(), "\x1B[2m─────\x1B[22m");
+/// ```
+#[inline]
+pub const fn styled_repeat<T: fmt::Display>(ansi: Ansi, pattern: T, count: usize) -> Styled<Repeat<T>> {
+    Styled::new(ansi, Repeat::new(pattern, count))
+}
+
+/// How an [`Aligned`] target is positioned within its width.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Alignment {
+    /// Padded on the right, see [`left()`].
+    Left,
+    /// Padded on both sides, with any odd column of padding on the right, see [`centre()`].
+    Centre,
+    /// Padded on the left, see [`right()`].
+    Right,
+}
+
+/// A [`Display`](fmt::Display) adapter that pads its target to a width, measured by its
+/// [visible width](text::visible_width()), so that styled text is aligned correctly,
+/// unlike with [`format!`]'s alignment (e.g. `{:>10}`), which also counts escape sequences.
+///
+/// Usually created by [`left()`], [`centre()`] or [`right()`]. Padding is written outside
+/// of the target's style, and a target that is too wide is written without padding.
+#[derive(Clone, Copy, Debug)]
+pub struct Aligned<T: fmt::Display> {
+    target:    T,
+    width:     usize,
+    alignment: Alignment,
+    fill:      char,
+}
+
+impl<T: fmt::Display> Aligned<T> {
+    /// Creates a new instance that aligns `target` within the given width, padding with spaces.
+    #[inline]
+    pub const fn new(target: T, width: usize, alignment: Alignment) -> Self {
+        Self { target, width, alignment, fill: ' ' }
+    }
+    /// Sets the character to pad with, instead of a space.
+    #[inline]
+    pub fn fill(self, fill: char) -> Self { Self { fill, ..self } }
+    /// Gets the target.
+    #[inline]
+    pub const fn target(&self) -> &T { &self.target }
+    /// Gets the width in columns.
+    #[inline]
+    pub const fn width(&self) -> usize { self.width }
+    /// Gets the alignment.
+    #[inline]
+    pub const fn alignment(&self) -> Alignment { self.alignment }
+}
+
+impl<T: fmt::Display> fmt::Display for Aligned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Note: the target is measured separately, so that it is still formatted nested
+        let padding = self.width.saturating_sub(text::visible_width(&self.target.to_string()));
+        let left = match self.alignment {
+            Alignment::Left   => 0,
+            Alignment::Centre => padding / 2,
+            Alignment::Right  => padding,
+        };
+        write!(f, "{}{}{}", Repeat::new(self.fill, left), self.target, Repeat::new(self.fill, padding - left))
+    }
+}
+
+/// Left-aligns `target` within `width` columns, i.e. pads it on the right, ignoring
+/// escape sequences when measuring it (see [`Aligned`]).
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, layout, Colour::Red};
+///
+/// assert_eq!(layout::left(styled!(Red, "ok"), 5).to_string(), "\x1B[31mok\x1B[39m   ");
+/// ```
+#[inline]
+pub const fn left<T: fmt::Display>(target: T, width: usize) -> Aligned<T> { Aligned::new(target, width, Alignment::Left) }
+
+/// Centres `target` within `width` columns, i.e. pads it on both sides, ignoring
+/// escape sequences when measuring it (see [`Aligned`]).
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, layout, Colour::Red};
+///
+/// assert_eq!(layout::centre(styled!(Red, "ok"), 5).to_string(), " \x1B[31mok\x1B[39m  ");
+/// assert_eq!(layout::centre("ok", 6).fill('.').to_string(), "..ok..");
+/// ```
+#[inline]
+pub const fn centre<T: fmt::Display>(target: T, width: usize) -> Aligned<T> { Aligned::new(target, width, Alignment::Centre) }
+
+/// Right-aligns `target` within `width` columns, i.e. pads it on the left, ignoring
+/// escape sequences when measuring it (see [`Aligned`]).
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, layout, Colour::Red};
+///
+/// assert_eq!(styled_format!("[{}]", layout::right(styled!(Red, 42), 5)), "[   \x1B[31m42\x1B[39m]");
+/// ```
+#[inline]
+pub const fn right<T: fmt::Display>(target: T, width: usize) -> Aligned<T> { Aligned::new(target, width, Alignment::Right) }
+
+/// The default character used to draw a [`Rule`] or [`Section`].
+pub const RULE: char = '─';
+
+/// The number of rule characters before the title of a left-aligned [`Section`].
+const SECTION_INDENT: usize = 2;
+
+/// A [`Display`](fmt::Display) adapter that draws a styled horizontal rule, which is
+/// as wide as the terminal, see [`hr()`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Rule {
+    ansi:    Ansi,
+    width:   Option<usize>,
+    pattern: char,
+}
+
+impl Rule {
+    /// Creates a new instance with the given style, the terminal's width and the [`RULE`] character.
+    #[inline]
+    pub const fn new(ansi: Ansi) -> Self { Self { ansi, width: None, pattern: RULE } }
+    /// Sets the width in columns, instead of the terminal's width.
+    #[inline]
+    pub const fn width(self, width: usize) -> Self { Self { width: Some(width), ..self } }
+    /// Sets the character the rule is drawn with.
+    #[inline]
+    pub const fn pattern(self, pattern: char) -> Self { Self { pattern, ..self } }
+    /// Gets the style.
+    #[inline]
+    pub const fn ansi(&self) -> Ansi { self.ansi }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.width.unwrap_or_else(io::terminal_width);
+        styled_repeat(self.ansi, self.pattern, width).fmt(f)
+    }
+}
+
+/// Creates a styled horizontal rule that is as wide as the terminal,
+/// see [`io::terminal_width()`].
+///
+/// *Note:* the rule does not end with a newline.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, layout::hr, Effect::Faint};
+///
+/// paintln!("{}", hr(ansi!(Faint)));
+/// assert_eq!(hr(ansi!(Faint)).width(5).to_string(), "\x1B[2m─────\x1B[22m");
+/// ```
+#[inline]
+pub const fn hr(ansi: Ansi) -> Rule { Rule::new(ansi) }
+
+/// A [`Display`](fmt::Display) adapter that draws a styled section heading, i.e. a
+/// title padded with rule characters to the terminal's width, see [`section()`].
+///
+/// The title is nested in the style, so it may have its own style too. Its width is
+/// measured by its [visible width](text::visible_width()), so styled titles are padded
+/// correctly. A title that is too wide is written without any rule characters.
+#[derive(Clone, Copy, Debug)]
+pub struct Section<T: fmt::Display> {
+    title:   T,
+    rule:    Rule,
+    centred: bool,
+}
+
+impl<T: fmt::Display> Section<T> {
+    /// Creates a new instance with the given title and style, that is left-aligned,
+    /// with the terminal's width and the [`RULE`] character.
+    #[inline]
+    pub const fn new(title: T, ansi: Ansi) -> Self { Self { title, rule: Rule::new(ansi), centred: false } }
+    /// Sets the width in columns, instead of the terminal's width.
+    #[inline]
+    pub fn width(self, width: usize) -> Self { Self { rule: self.rule.width(width), ..self } }
+    /// Sets the character the rule is drawn with.
+    #[inline]
+    pub fn pattern(self, pattern: char) -> Self { Self { rule: self.rule.pattern(pattern), ..self } }
+    /// Centres the title, instead of left-aligning it.
+    #[inline]
+    pub fn centred(self) -> Self { Self { centred: true, ..self } }
+    /// Gets the title.
+    #[inline]
+    pub const fn title(&self) -> &T { &self.title }
+}
+
+impl<T: fmt::Display> fmt::Display for Section<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Note: the title is measured separately, but formatted nested in the style
+        let title_width = text::visible_width(&self.title.to_string());
+        let width = self.rule.width.unwrap_or_else(io::terminal_width);
+        let padding = width.saturating_sub(title_width + 2);
+        let left = if self.centred { padding / 2 } else { padding.min(SECTION_INDENT) };
+        let right = padding - left;
+        if padding == 0 {
+            return Styled::new(self.rule.ansi, &self.title).fmt(f);
+        }
+        write!(f, "{}", Styled::new(self.rule.ansi, format_args!("{} {} {}",
+            Repeat::new(self.rule.pattern, left), self.title, Repeat::new(self.rule.pattern, right))))
+    }
+}
+
+/// Creates a styled section heading, i.e. the title padded with rule characters so that
+/// it is as wide as the terminal (see [`io::terminal_width()`]), e.g. `"── Title ────"`.
+///
+/// *Note:* the heading does not end with a newline.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, layout::section, Colour::{Blue, Yellow}, Effect::Bold};
+///
+/// paintln!("{}", section("Results", ansi!(Blue)));
+///
+/// assert_eq!(section("Results", ansi!(Blue)).width(16).to_string(),
+///     "\x1B[34m── Results ─────\x1B[39m");
+/// assert_eq!(section(styled!(Yellow, Bold, "Results"), ansi!(Blue)).width(16).centred().to_string(),
+///     "\x1B[34m─── \x1B[1;33mResults\x1B[22;34m ────\x1B[39m");
+/// ```
+#[inline]
+pub const fn section<T: fmt::Display>(title: T, ansi: Ansi) -> Section<T> { Section::new(title, ansi) }
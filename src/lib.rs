@@ -84,12 +84,25 @@
 //! Apply ANSI codes using macros:
 //!
 //! - [`styled!`] creates ANSI-styled values without interpolation (e.g. `&'static str`, `u8`).
+//! - [`styled_debug!`] is the same as [`styled!`], except that it styles the [`Debug`](std::fmt::Debug)
+//!   output of its value (e.g. a struct).
 //! - [`styled_format!`], [`styled_format_args!`] are analogous to
-//! [`format!`], [`format_args!`] except that they create ANSI-styled results.
+//!   [`format!`], [`format_args!`] except that they create ANSI-styled results.
 //! - [`styled_write!`], [`styled_writeln!`] are analogous to
-//! [`write!`], [`writeln!`] except that they write ANSI-styled output.
+//!   [`write!`], [`writeln!`] except that they write ANSI-styled output.
 //! - [`paint!`], [`paintln!`], [`epaint!`], [`epaintln!`] are analogous to
-//! [`print!`], [`println!`], [`eprint!`], [`eprintln!`] except that they print ANSI-styled output.
+//!   [`print!`], [`println!`], [`eprint!`], [`eprintln!`] except that they print ANSI-styled output.
+//! - [`try_paint!`], [`try_paintln!`], [`try_epaint!`], [`try_epaintln!`] are the same as
+//!   the above, except that they return an [`io::Result`](std::io::Result) instead of panicking.
+//! - [`paint_success!`], [`paint_warning!`], [`paint_error!`] print messages prefixed with
+//!   a styled label, e.g. `"error:"`, and [`paint_message!`], [`epaint_message!`] do the same
+//!   for custom labels.
+//! - [`assert_styled_eq!`] is analogous to [`assert_eq!`], except that it checks styled
+//!   output both with and without ANSI codes.
+//! - [`palette!`] declares a struct of named [`Ansi`] styles, e.g. an application's theme.
+//! - [`ansi_style!`] parses a style string, e.g. `"bold red on_blue"`, as an [`Ansi`] at compile time.
+//!
+//! All macros are also available from the [`macros`] module, e.g. to avoid name collisions.
 //!
 //! ##### Examples
 //!
@@ -111,7 +124,6 @@
 //! epaintln!(Red, Bold, "My {} is {} years old", pet, age);
 //!
 //! // Write "\x1B[1;31mMy cat is 5 years old\x1B[22;39m\n" to a writer:
-//! use std::fmt::Write;
 //! let mut sink = String::new();
 //! styled_writeln!(&mut sink, Red, Bold, "My {} is {} years old", pet, age).unwrap();
 //! assert_eq!(sink, "\x1B[1;31mMy cat is 5 years old\x1B[22;39m\n");
@@ -167,9 +179,14 @@
 //!
 //! ```
 //!
+//! More recipes, e.g. for [`.add()`](Ansi::add) and [`.transition()`](Ansi::transition)
+//! and priorities, are checked in the crate's `examples/cookbook.rs`, which can be browsed
+//! with `cargo run --example cookbook`.
+//!
 //! _Note:_ automatic handling of nested styles is achieved by storing the last-applied
 //! ANSI style in a [`thread_local!`] static variable, and therefore this library
-//! requires `std`. See [`Styled<T>`] for details.
+//! requires `std`. See [`Styled<T>`] for details. For runtimes without `thread_local!`
+//! support, `feature=custom-state` allows the storage to be replaced, see the `state` module.
 //!
 //! ## Examples
 //!
@@ -206,19 +223,57 @@
 //! ```
 
 mod ansi;
+pub mod art;
+pub mod codes;
+#[cfg(feature="rgb")]
+mod dynamic;
+pub mod env_colours;
 mod fmt;
+#[cfg(feature="rgb")]
+pub mod gradient;
+mod interop;
 pub mod io;
+pub mod key_colour;
+pub mod layout;
+pub mod macros;
+pub mod map;
+pub mod message;
+pub mod num;
+pub mod prelude;
+mod raw;
+pub mod registry;
+#[cfg(feature="custom-state")]
+pub mod state;
+#[cfg(not(feature="custom-state"))]
+mod state;
+#[doc(hidden)]
+pub mod testing;
+pub mod text;
+pub mod theme;
+mod underline;
 pub(crate) mod write;
 #[doc(hidden)]
 pub mod str;
 
-pub use ansi::{Ansi, Attrs, Colour, Effect};
-pub use fmt::Styled;
+pub use ansi::{Ansi, AnsiParts, Attrs, Colour, Effect, Explanation, ParseAnsiError, Spec};
+#[cfg(feature="rgb")]
+pub use ansi::BlendMode;
+#[cfg(feature="rgb")]
+pub use dynamic::{DynamicColour, DynamicColoured};
+pub use fmt::{AsDebug, Styled, StyledBatch, StyledSpans, VisibleChars};
+pub use raw::{RawSgr, RawStyled};
+pub use underline::{UnderlineStyle, Underlined};
+
+// Checks that the README's examples produce the output they claim
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+struct ReadmeDoctests;
 
 /// Creates an ANSI style as an [`Ansi`] `const`.
 ///
 /// Accepts any number of [`Ansi`]s, [`Colour`]s, [`Effect`]s or any values with an
-/// `ansi()` method.
+/// `ansi()` method, or else a single style string literal, e.g. `"bold red on blue"`,
+/// which is parsed at compile time as by [`ansi_style!`].
 ///
 /// The benefit of an [`Ansi`] `const` over a `&'static str` ANSI code is that
 /// nesting of styles is handled automatically. See [`Styled<T>`] for details.
@@ -231,13 +286,17 @@ pub use fmt::Styled;
 /// use ansiconst::Effect::{Bold, Underline, Italic};
 ///
 /// const MY_ANSI: Ansi = ansi!(Green, Blue.bg(), Bold, Underline, Italic);
+/// const MY_STR:  Ansi = ansi!("green on blue bold underline italic");
 ///
 /// assert_eq!(&MY_ANSI.to_string(), "\x1B[1;3;4;32;44m");
+/// assert_eq!(MY_STR, MY_ANSI);
 /// ```
 #[macro_export]
 macro_rules! ansi {
     // Base case:
     () => ($crate::Ansi::unspecified());
+    // Base case: a style string
+    ($style:literal) => ($crate::ansi_style!($style));
     // Base case:
     ($x:expr) => ($x.ansi());
     // Recurse:
@@ -268,8 +327,10 @@ macro_rules! ansi_code {
         const CODES: $crate::str::Buffer<[u8;25]> = $crate::str::Buffer::from_ansi($ansi.ansi());
         const BYTES_LEN: usize                    = $crate::str::len_as_ansi_bytes(&CODES);
         const BYTES: [u8; BYTES_LEN]              = $crate::str::to_ansi_bytes::<BYTES_LEN>(&CODES);
-        const BYTES_PTR: *const [u8]              = &BYTES;
-        const STR: &str                           = unsafe { std::mem::transmute(BYTES_PTR) };
+        const STR: &str                           = match ::std::str::from_utf8(&BYTES) {
+            Ok(str) => str,
+            Err(_)  => panic!("ANSI codes are always valid UTF-8"),
+        };
         STR
     }};
     ($($ansi:expr),+) => {{
@@ -278,6 +339,229 @@ macro_rules! ansi_code {
     }}
 }
 
+/// Concatenates ANSI codes and text into a single `&'static str` at compile time.
+///
+/// Accepts any number of `&'static str` constants, e.g. the results of [`ansi_code!`]
+/// and string literals. Unlike [`concat!`], which accepts only literals, this allows
+/// banners and fixed prompts to be fully baked at compile time.
+///
+/// ### Example
+///
+/// ```
+/// use ansiconst::*;
+/// use ansiconst::Colour::Green;
+/// use ansiconst::Effect::Bold;
+///
+/// const HEADING: &str = ansi_code!(Green, Bold);
+/// const RESET:   &str = ansi_code!(Ansi::reset());
+/// const BANNER:  &str = const_styled_concat!(HEADING, "My App", RESET, " v1.0");
+///
+/// assert_eq!(BANNER, "\x1B[1;32mMy App\x1B[0m v1.0");
+/// ```
+#[macro_export]
+macro_rules! const_styled_concat {
+    ($($part:expr),* $(,)?) => {{
+        const PARTS: &[&str]         = &[$($part),*];
+        const BYTES_LEN: usize       = $crate::str::concat_len(PARTS);
+        const BYTES: [u8; BYTES_LEN] = $crate::str::concat_bytes::<BYTES_LEN>(PARTS);
+        const STR: &str              = match ::std::str::from_utf8(&BYTES) {
+            Ok(str) => str,
+            Err(_)  => panic!("concatenated strs are always valid UTF-8"),
+        };
+        STR
+    }};
+}
+
+/// Creates a pair of ANSI styles as `&'static str`s: the style as given, and its
+/// fallback for terminals that only support the 16 named colours.
+///
+/// Accepts the same arguments as [`ansi_code!`]. The fallback's colours are converted
+/// at compile time using [`Ansi::downgraded()`], so selecting between the two at runtime,
+/// e.g. based on [`io::colour_support()`](crate::io::colour_support()), costs a single branch.
+///
+/// ### Example
+///
+/// ```
+/// # #[cfg(feature="rgb")] {
+/// use ansiconst::{*, io::ColourSupport, Colour::Rgb, Effect::Bold};
+///
+/// const BRAND: (&str, &str) = ansi_code_fallback!(Rgb(255, 0, 0), Bold);
+///
+/// assert_eq!(BRAND, ("\x1B[1;38;2;255;0;0m", "\x1B[1;91m"));
+///
+/// let brand = if io::colour_support() >= ColourSupport::Ansi256 { BRAND.0 } else { BRAND.1 };
+/// # }
+/// ```
+#[macro_export]
+macro_rules! ansi_code_fallback {
+    ($($ansi:expr),+) => {{
+        const ANSI: $crate::Ansi = $crate::ansi!($($ansi),+);
+        ($crate::ansi_code!(ANSI), $crate::ansi_code!(ANSI.downgraded($crate::io::ColourSupport::Ansi16)))
+    }}
+}
+
+/// Creates an [`Rgb`](Colour::Rgb) [`Colour`] `const` from a hex literal of the
+/// form `"#rrggbb"` or `"#rgb"` (ignoring case).
+///
+/// The literal is parsed at compile time, using [`Colour::from_hex()`], so a malformed
+/// literal is a compile error rather than a runtime one.
+///
+/// For a hex integer, e.g. `0xff8800`, use [`Colour::hex()`] instead.
+///
+/// *Note: only available with `feature=rgb`*
+///
+/// ### Example
+///
+/// ```
+/// use ansiconst::*;
+///
+/// const BRAND:   Colour = rgb!("#ff8800");
+/// const ACCENT:  Colour = rgb!("#0af");
+/// const HEADING: Ansi   = ansi!(BRAND, ACCENT.bg());
+///
+/// assert_eq!(BRAND, Colour::Rgb(255, 136, 0));
+/// assert_eq!(HEADING.to_string(), "\x1B[38;2;255;136;0;48;2;0;170;255m");
+/// ```
+///
+/// ```compile_fail
+/// // Fails to compile, because "#ff880g" is not a valid hex colour
+/// const BRAND: ansiconst::Colour = ansiconst::rgb!("#ff880g");
+/// ```
+#[cfg(feature="rgb")]
+#[macro_export]
+macro_rules! rgb {
+    ($hex:literal) => {{
+        const COLOUR: $crate::Colour = match $crate::Colour::from_hex($hex) {
+            Some(colour) => colour,
+            None         => panic!(concat!("invalid hex colour ", stringify!($hex), ", expected \"#rrggbb\" or \"#rgb\"")),
+        };
+        COLOUR
+    }};
+}
+
+/// Creates an [`Ansi`] `const` from a style string, e.g. `"bold underline bright_red on_black"`.
+///
+/// The literal is parsed at compile time, as by [`Ansi::from_style_str()`], so a
+/// malformed literal is a compile error rather than a runtime one. [`ansi!`] also
+/// accepts a style string, e.g. `ansi!("bold #ff8800 on blue")`.
+///
+/// ### Example
+///
+/// ```
+/// use ansiconst::{*, Colour::{Black, BrightRed}, Effect::{Bold, Underline}};
+///
+/// const WARNING: Ansi = ansi_style!("bold underline bright_red on_black");
+///
+/// assert_eq!(WARNING, ansi!(Bold, Underline, BrightRed, Black.bg()));
+/// assert_eq!(WARNING.to_string(), "\x1B[1;4;91;40m");
+/// ```
+///
+/// ```compile_fail
+/// // Fails to compile, because "pink" is not a valid colour
+/// const WARNING: ansiconst::Ansi = ansiconst::ansi_style!("bold pink");
+/// ```
+#[macro_export]
+macro_rules! ansi_style {
+    ($style:literal) => {{
+        const ANSI: $crate::Ansi = match $crate::Ansi::parse_style_str($style) {
+            Ok(ansi) => ansi,
+            Err(_)   => panic!(concat!("invalid style string ", stringify!($style))),
+        };
+        ANSI
+    }};
+}
+
+/// Declares a palette, i.e. a struct of named [`Ansi`] styles, from a concise table.
+///
+/// Each entry is a name, followed by its style as `+`-separated [`Ansi`]s, [`Colour`]s,
+/// [`Effect`]s or any values with an `ansi()` method (i.e. as accepted by [`ansi!`]).
+///
+/// The struct has a public `Ansi` field for each entry, a `DEFAULT` `const` containing
+/// the declared styles, and a `const fn styles()` that enumerates the names and styles in
+/// order of declaration, e.g. for listing or [registering](crate::registry) them. The struct
+/// name and attributes may be given, as below, else it is declared as `pub struct Palette`.
+///
+/// ### Example
+///
+/// ```
+/// use ansiconst::{*, Colour::{Green, Red, Blue}, Effect::{Bold, Underline}};
+///
+/// palette! {
+///     /// The styles of my app
+///     pub struct Theme {
+///         /// Used for headings
+///         heading: Green + Bold + Underline,
+///         error:   Red + Bold,
+///         info:    Blue.bg(),
+///     }
+/// }
+///
+/// const THEME: Theme = Theme::DEFAULT;
+/// const HIGH_CONTRAST: Theme = Theme { info: ansi!(Bold), ..THEME };
+///
+/// assert_eq!(THEME.heading, ansi!(Green, Bold, Underline));
+/// assert_eq!(HIGH_CONTRAST.styles(), [
+///     ("heading", ansi!(Green, Bold, Underline)),
+///     ("error",   ansi!(Red, Bold)),
+///     ("info",    ansi!(Bold)),
+/// ]);
+/// ```
+#[macro_export]
+macro_rules! palette {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident { $($body:tt)* }) => {
+        $crate::__palette!(@entries [$(#[$meta])* $vis $name] [] $($body)*);
+    };
+    ($($body:tt)*) => {
+        $crate::palette!(pub struct Palette { $($body)* });
+    };
+}
+
+// Munches the entries of `palette!`, splitting each style on `+`, because an `expr`
+// fragment cannot be followed by `+` (and would otherwise parse it as addition).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __palette {
+    // Start of entry:
+    (@entries $head:tt [$($done:tt)*] $(#[$fmeta:meta])* $field:ident : $($rest:tt)*) => {
+        $crate::__palette!(@style $head [$($done)*] [$(#[$fmeta])* $field] [] [] $($rest)*);
+    };
+    // End of entries:
+    (@entries [$(#[$meta:meta])* $vis:vis $name:ident] [$([$(#[$fmeta:meta])* $field:ident $([$($part:tt)*])+])*]) => {
+        $(#[$meta])*
+        #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+        $vis struct $name {
+            $($(#[$fmeta])* pub $field: $crate::Ansi,)*
+        }
+
+        impl $name {
+            /// The declared styles.
+            pub const DEFAULT: $name = $name { $($field: $crate::ansi!($($($part)*),+),)* };
+
+            /// Gets the names and styles of this palette, in order of declaration.
+            #[allow(dead_code)]
+            pub const fn styles(&self) -> [(&'static str, $crate::Ansi); [$(stringify!($field)),*].len()] {
+                [$((stringify!($field), self.$field)),*]
+            }
+        }
+
+        impl ::std::default::Default for $name {
+            fn default() -> Self { Self::DEFAULT }
+        }
+    };
+    // End of part:
+    (@style $head:tt [$($done:tt)*] [$($field:tt)*] [$($parts:tt)*] [$($cur:tt)+] + $($rest:tt)*) => {
+        $crate::__palette!(@style $head [$($done)*] [$($field)*] [$($parts)* [$($cur)+]] [] $($rest)*);
+    };
+    // End of style:
+    (@style $head:tt [$($done:tt)*] [$($field:tt)*] [$($parts:tt)*] [$($cur:tt)+] $(, $($rest:tt)*)?) => {
+        $crate::__palette!(@entries $head [$($done)* [$($field)* $($parts)* [$($cur)+]]] $($($rest)*)?);
+    };
+    // Token of part:
+    (@style $head:tt [$($done:tt)*] [$($field:tt)*] [$($parts:tt)*] [$($cur:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::__palette!(@style $head [$($done)*] [$($field)*] [$($parts)*] [$($cur)* $token] $($rest)*);
+    };
+}
+
 /// Creates an ANSI-styled value.
 ///
 /// Accepts any number of [`Ansi`]s, [`Colour`]s, [`Effect`]s or any values with an
@@ -303,6 +587,31 @@ macro_rules! styled {
     )
 }
 
+/// Like [`styled!`] except styles the [`Debug`](std::fmt::Debug) output of the final argument,
+/// i.e. as formatted by `{:?}`, or by `{:#?}` if the result is formatted with `{:#}`.
+///
+/// Returns a [`Styled<AsDebug<T>>`](AsDebug).
+///
+/// ### Example
+/// ```
+/// use ansiconst::{*, Colour::Red};
+///
+/// #[derive(Debug)]
+/// struct Point { x: i32 }
+///
+/// assert_eq!(styled_debug!(Red, Some("a")).to_string(), "\x1B[31mSome(\"a\")\x1B[39m");
+/// assert_eq!(format!("{:#}", styled_debug!(Red, Point { x: 1 })), "\x1B[31mPoint {\n    x: 1,\n}\x1B[39m");
+/// ```
+#[macro_export]
+macro_rules! styled_debug {
+    // Base case:
+    ($ansi:expr, $target:expr) => ($crate::Styled::new($ansi.ansi(), $crate::AsDebug($target)));
+    // Recurse:
+    ($x:expr, $y:expr, $($args:tt)+) => (
+        $crate::styled_debug!($x.ansi().add($y.ansi()), $($args)+)
+    )
+}
+
 /// Like [`format!`] except creates an ANSI-styled `String`.
 ///
 /// The syntax is the same as [`format!`], except that any parameters before the
@@ -341,9 +650,15 @@ macro_rules! styled_format {
 ///
 /// Returns a [`Styled<std::fmt::Arguments>`].
 ///
+/// Individual arguments after the format literal may be styled inline by following them
+/// with `=>` and their style as `+`-separated [`Ansi`]s, [`Colour`]s or [`Effect`]s, e.g.
+/// `age => Red + Bold`, which is short for `styled!(Red, Bold, &age)`. The parameters
+/// before the format literal may then be omitted, if the message itself is unstyled.
+/// The same applies to the other `styled_*!` macros, and the `paint!` family of macros.
+///
 /// ### Example
 /// ```
-/// use ansiconst::{*, Colour::Red, Effect::{Italic, Blink}};
+/// use ansiconst::{*, Colour::{Green, Red}, Effect::{Italic, Blink, Bold}};
 ///
 /// let pet = "cat";
 /// let age = 5;
@@ -352,45 +667,101 @@ macro_rules! styled_format {
 ///     styled_format_args!(Red.bg(), Italic, Blink, "My {} is {} years old", pet, age).to_string(),
 ///     "\x1B[3;5;41mMy cat is 5 years old\x1B[23;25;49m"
 /// );
+/// assert_eq!(
+///     styled_format_args!("My {} is {} years old", pet => Green, age => Red + Bold).to_string(),
+///     "My \x1B[32mcat\x1B[39m is \x1B[1;31m5\x1B[22;39m years old"
+/// );
 /// ```
 #[macro_export]
 macro_rules! styled_format_args {
+    // Unstyled:
+    ($lit:literal $(,)?) => ($crate::Styled::new($crate::Ansi::unspecified(), format_args!($lit)));
+    // Unstyled:
+    ($lit:literal, $($args:tt)*) => ($crate::Styled::new($crate::Ansi::unspecified(), $crate::__format_args!([$lit] [] $($args)*)));
     // Base case:
     ($ansi:expr, $lit:literal $(,)?) => ($crate::Styled::new($ansi.ansi(), format_args!($lit)));
     // Base case:
-    ($ansi:expr, $lit:literal, $($args:tt)*) => ($crate::Styled::new($ansi.ansi(), format_args!($lit, $($args)*)));
+    ($ansi:expr, $lit:literal, $($args:tt)*) => ($crate::Styled::new($ansi.ansi(), $crate::__format_args!([$lit] [] $($args)*)));
     // Recurse:
     ($x:expr, $y:expr, $($args:tt)+) => (
         $crate::styled_format_args!($x.ansi().add($y.ansi()), $($args)+)
     )
 }
 
+// Munches the arguments of `format_args!`, replacing each `arg => Style + ...` with
+// `styled!(Style, ..., &arg)`, splitting each style on `+` as in `__palette!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __format_args {
+    // End of part:
+    (@style $fmt:tt $done:tt $arg:tt [$($parts:tt)*] [$($cur:tt)+] + $($rest:tt)*) => (
+        $crate::__format_args!(@style $fmt $done $arg [$($parts)* [$($cur)+]] [] $($rest)*)
+    );
+    // End of style:
+    (@style $fmt:tt [$($done:tt)*] [$arg:expr] [$([$($part:tt)+])*] [$($cur:tt)+] $(, $($rest:tt)*)?) => (
+        $crate::__format_args!($fmt [$($done)* $crate::styled!($($($part)+,)* $($cur)+, &$arg),] $($($rest)*)?)
+    );
+    // Token of part:
+    (@style $fmt:tt $done:tt $arg:tt $parts:tt [$($cur:tt)*] $token:tt $($rest:tt)*) => (
+        $crate::__format_args!(@style $fmt $done $arg $parts [$($cur)* $token] $($rest)*)
+    );
+    // End of arguments:
+    ([$($fmt:tt)*] [$($done:tt)*] $(,)?) => (format_args!($($fmt)*, $($done)*));
+    // Styled named argument:
+    ($fmt:tt [$($done:tt)*] $name:ident = $arg:expr => $($rest:tt)*) => (
+        $crate::__format_args!(@style $fmt [$($done)* $name =] [$arg] [] [] $($rest)*)
+    );
+    // Named argument:
+    ($fmt:tt [$($done:tt)*] $name:ident = $arg:expr $(, $($rest:tt)*)?) => (
+        $crate::__format_args!($fmt [$($done)* $name = $arg,] $($($rest)*)?)
+    );
+    // Styled argument:
+    ($fmt:tt $done:tt $arg:expr => $($rest:tt)*) => (
+        $crate::__format_args!(@style $fmt $done [$arg] [] [] $($rest)*)
+    );
+    // Argument:
+    ($fmt:tt [$($done:tt)*] $arg:expr $(, $($rest:tt)*)?) => (
+        $crate::__format_args!($fmt [$($done)* $arg,] $($($rest)*)?)
+    );
+}
+
 /// Like [`write!`] except with ANSI-styled output.
 ///
 /// The syntax is the same as [`write!`], except that any parameters before the
 /// format literal must be either instances of [`Ansi`], [`Colour`] or [`Effect`],
 /// or else values that have an `ansi()` method.
 ///
-/// ### Example
+/// The destination may be either a [`fmt::Write`](std::fmt::Write) or an
+/// [`io::Write`](std::io::Write), and neither trait needs to be in scope.
+/// The result is the same as that of the destination's `write_fmt()` method.
+///
+/// ### Examples
 /// ```
 /// use ansiconst::{*, Colour::Red, Effect::{Italic, Blink}};
-/// use std::fmt::Write;
 ///
 /// let pet = "cat";
 /// let age = 5;
 ///
+/// // Writing to a fmt::Write
 /// let mut output = String::new();
-/// styled_write!(&mut output, Red.bg(), Italic, Blink, "My {} is {} years old", pet, age);
+/// styled_write!(&mut output, Red.bg(), Italic, Blink, "My {} is {} years old", pet, age).unwrap();
 ///
 /// assert_eq!(output, "\x1B[3;5;41mMy cat is 5 years old\x1B[23;25;49m");
+///
+/// // Writing to an io::Write, propagating any error with ?
+/// let mut output = Vec::new();
+/// styled_write!(&mut output, Red.bg(), Italic, Blink, "My {} is {} years old", pet, age)?;
+///
+/// assert_eq!(output, b"\x1B[3;5;41mMy cat is 5 years old\x1B[23;25;49m");
+/// # Ok::<(), std::io::Error>(())
 /// ```
 #[macro_export]
 macro_rules! styled_write {
     // Unstyled
-    ($dst:expr, $lit:literal) => {{ write!($dst, $lit) }};
-    ($dst:expr, $lit:literal, $($args:tt)*) => {{ write!($dst, $lit, $($args)*) }};
+    ($dst:expr, $lit:literal $(,)?) => {{ $crate::__write_fmt!($dst, format_args!($lit)) }};
+    ($dst:expr, $lit:literal, $($args:tt)*) => {{ $crate::__write_fmt!($dst, $crate::__format_args!([$lit] [] $($args)*)) }};
     // Styled
-    ($dst:expr, $($args:tt)+) => {{ write!($dst, "{}", $crate::styled_format_args!($($args)*)) }};
+    ($dst:expr, $($args:tt)+) => {{ $crate::__write_fmt!($dst, format_args!("{}", $crate::styled_format_args!($($args)*))) }};
 }
 
 /// Like [`writeln!`] except with ANSI-styled output.
@@ -399,27 +770,41 @@ macro_rules! styled_write {
 /// format literal must be either instances of [`Ansi`], [`Colour`] or [`Effect`],
 /// or else values that have an `ansi()` method.
 ///
+/// As with [`styled_write!`], the destination may be either a [`fmt::Write`](std::fmt::Write)
+/// or an [`io::Write`](std::io::Write).
+///
 /// ### Example
 /// ```
 /// use ansiconst::{*, Colour::Red, Effect::{Italic, Blink}};
-/// use std::fmt::Write;
 ///
 /// let pet = "cat";
 /// let age = 5;
 ///
 /// let mut output = String::new();
-/// styled_writeln!(&mut output, Red.bg(), Italic, Blink, "My {} is {} years old", pet, age);
+/// styled_writeln!(&mut output, Red.bg(), Italic, Blink, "My {} is {} years old", pet, age).unwrap();
 ///
 /// assert_eq!(output, "\x1B[3;5;41mMy cat is 5 years old\x1B[23;25;49m\n");
 /// ```
 #[macro_export]
 macro_rules! styled_writeln {
     // Unstyled
-    ($dst:expr $(,)?) => {{ writeln!($dst) }};
-    ($dst:expr, $lit:literal) => {{ writeln!($dst, $lit) }};
-    ($dst:expr, $lit:literal, $($args:tt)*) => {{ writeln!($dst, $lit, $($args)*) }};
+    ($dst:expr $(,)?) => {{ $crate::__write_fmt!($dst, format_args!("\n")) }};
+    ($dst:expr, $lit:literal $(,)?) => {{ $crate::__write_fmt!($dst, format_args!("{}\n", format_args!($lit))) }};
+    ($dst:expr, $lit:literal, $($args:tt)*) => {{ $crate::__write_fmt!($dst, format_args!("{}\n", $crate::__format_args!([$lit] [] $($args)*))) }};
     // Styled
-    ($dst:expr, $($args:tt)+) => {{ writeln!($dst, "{}", $crate::styled_format_args!($($args)*)) }};
+    ($dst:expr, $($args:tt)+) => {{ $crate::__write_fmt!($dst, format_args!("{}\n", $crate::styled_format_args!($($args)*))) }};
+}
+
+// Calls `write_fmt()` on a destination implementing either fmt::Write or io::Write,
+// without requiring the caller to have imported either trait.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __write_fmt {
+    ($dst:expr, $args:expr) => {{
+        #[allow(unused_imports)]
+        use ::std::{fmt::Write as _, io::Write as _};
+        $dst.write_fmt($args)
+    }};
 }
 
 /// Like [`print!`] except with ANSI-styled output.
@@ -430,6 +815,12 @@ macro_rules! styled_writeln {
 ///
 /// Prints to [`io::ansiout()`], which may optionally disable ANSI-styles.
 ///
+/// As with [`print!`], stdout is line-buffered, so output may not appear until a newline
+/// is printed. To show it immediately, call [`flush()`](std::io::Write::flush()) on
+/// [`io::ansiout()`], or set its [`FlushPolicy`](io::FlushPolicy).
+///
+/// Panics if writing fails. See [`try_paint!`] for a non-panicking alternative.
+///
 /// ### Example
 /// ```
 /// use ansiconst::{paint, Colour::Red, Effect::{Italic, Blink}};
@@ -443,8 +834,8 @@ macro_rules! styled_writeln {
 #[macro_export]
 macro_rules! paint {
     // Unstyled
-    ($lit:literal) => {{ print!($lit) }};
-    ($lit:literal, $($args:tt)*) => {{ print!($lit, $($args)*) }};
+    ($lit:literal) => {{ write!($crate::io::ansiout(), $lit).unwrap() }};
+    ($lit:literal, $($args:tt)*) => {{ write!($crate::io::ansiout(), "{}", $crate::__format_args!([$lit] [] $($args)*)).unwrap() }};
     // Styled
     ($($args:tt)*) => {{
         write!($crate::io::ansiout(), "{}", $crate::styled_format_args!($($args)*)).unwrap()
@@ -459,6 +850,8 @@ macro_rules! paint {
 ///
 /// Prints to [`io::ansiout()`], which may optionally disable ANSI-styles.
 ///
+/// Panics if writing fails. See [`try_paintln!`] for a non-panicking alternative.
+///
 /// ### Example
 /// ```
 /// use ansiconst::{paintln, Colour::Red, Effect::{Italic, Blink}};
@@ -472,9 +865,9 @@ macro_rules! paint {
 #[macro_export]
 macro_rules! paintln {
     // Unstyled
-    () => {{ println!() }};
-    ($lit:literal) => {{ println!($lit) }};
-    ($lit:literal, $($args:tt)*) => {{ println!($lit, $($args)*) }};
+    () => {{ writeln!($crate::io::ansiout()).unwrap() }};
+    ($lit:literal) => {{ writeln!($crate::io::ansiout(), $lit).unwrap() }};
+    ($lit:literal, $($args:tt)*) => {{ writeln!($crate::io::ansiout(), "{}", $crate::__format_args!([$lit] [] $($args)*)).unwrap() }};
     // Styled
     ($($args:tt)*) => {{
         writeln!($crate::io::ansiout(), "{}", $crate::styled_format_args!($($args)*)).unwrap()
@@ -489,6 +882,8 @@ macro_rules! paintln {
 ///
 /// Prints to [`io::ansierr()`], which may optionally disable ANSI-styles.
 ///
+/// Panics if writing fails. See [`try_epaint!`] for a non-panicking alternative.
+///
 /// ### Example
 /// ```
 /// use ansiconst::{epaint, Colour::Red, Effect::{Italic, Blink}};
@@ -502,8 +897,8 @@ macro_rules! paintln {
 #[macro_export]
 macro_rules! epaint {
     // Unstyled
-    ($lit:literal) => {{ eprint!($lit) }};
-    ($lit:literal, $($args:tt)*) => {{ eprint!($lit, $($args)*) }};
+    ($lit:literal) => {{ write!($crate::io::ansierr(), $lit).unwrap() }};
+    ($lit:literal, $($args:tt)*) => {{ write!($crate::io::ansierr(), "{}", $crate::__format_args!([$lit] [] $($args)*)).unwrap() }};
     // Styled
     ($($args:tt)*) => {{
         write!($crate::io::ansierr(), "{}", $crate::styled_format_args!($($args)*)).unwrap()
@@ -518,6 +913,8 @@ macro_rules! epaint {
 ///
 /// Prints to [`io::ansierr()`], which may optionally disable ANSI-styles.
 ///
+/// Panics if writing fails. See [`try_epaintln!`] for a non-panicking alternative.
+///
 /// ### Example
 /// ```
 /// use ansiconst::{epaintln, Colour::Red, Effect::{Italic, Blink}};
@@ -531,11 +928,206 @@ macro_rules! epaint {
 #[macro_export]
 macro_rules! epaintln {
     // Unstyled
-    () => {{ eprintln!() }};
-    ($lit:literal) => {{ eprintln!($lit) }};
-    ($lit:literal, $($args:tt)*) => {{ eprintln!($lit, $($args)*) }};
+    () => {{ writeln!($crate::io::ansierr()).unwrap() }};
+    ($lit:literal) => {{ writeln!($crate::io::ansierr(), $lit).unwrap() }};
+    ($lit:literal, $($args:tt)*) => {{ writeln!($crate::io::ansierr(), "{}", $crate::__format_args!([$lit] [] $($args)*)).unwrap() }};
     // Styled
     ($($args:tt)*) => {{
         writeln!($crate::io::ansierr(), "{}", $crate::styled_format_args!($($args)*)).unwrap()
     }};
 }
+
+/// Like [`paint!`] except returns an [`io::Result`](std::io::Result) instead of panicking.
+///
+/// ### Example
+/// ```
+/// use ansiconst::{try_paint, Colour::Red};
+///
+/// fn main() -> std::io::Result<()> {
+///     try_paint!(Red, "My {} is {} years old", "cat", 5)?;
+///     // Prints "\x1B[31mMy cat is 5 years old\x1B[39m"
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_paint {
+    ($($args:tt)+) => {{ $crate::styled_write!($crate::io::ansiout(), $($args)+) }};
+}
+
+/// Like [`paintln!`] except returns an [`io::Result`](std::io::Result) instead of panicking.
+///
+/// ### Example
+/// ```
+/// use ansiconst::{try_paintln, Colour::Red};
+///
+/// fn main() -> std::io::Result<()> {
+///     try_paintln!(Red, "My {} is {} years old", "cat", 5)?;
+///     // Prints "\x1B[31mMy cat is 5 years old\x1B[39m\n"
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_paintln {
+    ($($args:tt)*) => {{ $crate::styled_writeln!($crate::io::ansiout(), $($args)*) }};
+}
+
+/// Like [`epaint!`] except returns an [`io::Result`](std::io::Result) instead of panicking.
+///
+/// ### Example
+/// ```
+/// use ansiconst::{try_epaint, Colour::Red};
+///
+/// fn main() -> std::io::Result<()> {
+///     try_epaint!(Red, "My {} is {} years old", "cat", 5)?;
+///     // Prints "\x1B[31mMy cat is 5 years old\x1B[39m"
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_epaint {
+    ($($args:tt)+) => {{ $crate::styled_write!($crate::io::ansierr(), $($args)+) }};
+}
+
+/// Like [`epaintln!`] except returns an [`io::Result`](std::io::Result) instead of panicking.
+///
+/// ### Example
+/// ```
+/// use ansiconst::{try_epaintln, Colour::Red};
+///
+/// fn main() -> std::io::Result<()> {
+///     try_epaintln!(Red, "My {} is {} years old", "cat", 5)?;
+///     // Prints "\x1B[31mMy cat is 5 years old\x1B[39m\n"
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_epaintln {
+    ($($args:tt)*) => {{ $crate::styled_writeln!($crate::io::ansierr(), $($args)*) }};
+}
+
+/// Like [`paintln!`] except that the message is prefixed with a styled
+/// [`Label`](message::Label), followed by a space.
+///
+/// The first argument is the label, the rest are the same as [`println!`].
+///
+/// Prints to [`io::ansiout()`]. Panics if writing fails.
+///
+/// ### Example
+/// ```
+/// use ansiconst::{*, message::Label, registry::NamedStyle, Colour::Blue};
+///
+/// const INFO: Label = Label::new(NamedStyle::new("myapp::info", ansi!(Blue)), "info:");
+///
+/// paint_message!(INFO, "Found {} files", 3);
+/// // Prints "\x1B[34minfo:\x1B[39m Found 3 files\n"
+/// ```
+#[macro_export]
+macro_rules! paint_message {
+    ($label:expr, $($args:tt)+) => {{
+        writeln!($crate::io::ansiout(), "{} {}", $label, format_args!($($args)+)).unwrap()
+    }};
+}
+
+/// Like [`paint_message!`] except that it prints to [`io::ansierr()`].
+///
+/// ### Example
+/// ```
+/// use ansiconst::{*, message::Label, registry::NamedStyle, Colour::Blue};
+///
+/// const INFO: Label = Label::new(NamedStyle::new("myapp::info", ansi!(Blue)), "info:");
+///
+/// epaint_message!(INFO, "Found {} files", 3);
+/// // Prints "\x1B[34minfo:\x1B[39m Found 3 files\n"
+/// ```
+#[macro_export]
+macro_rules! epaint_message {
+    ($label:expr, $($args:tt)+) => {{
+        writeln!($crate::io::ansierr(), "{} {}", $label, format_args!($($args)+)).unwrap()
+    }};
+}
+
+/// Prints a success message to [`io::ansiout()`], prefixed with the
+/// [`SUCCESS`](message::SUCCESS) label.
+///
+/// The syntax is the same as [`println!`]. See the [`message`](mod@message) module.
+///
+/// ### Example
+/// ```
+/// use ansiconst::paint_success;
+///
+/// paint_success!("Compiled {} files", 3);
+/// // Prints "\x1B[1;32msuccess:\x1B[22;39m Compiled 3 files\n"
+/// ```
+#[macro_export]
+macro_rules! paint_success {
+    ($($args:tt)+) => {{ $crate::paint_message!($crate::message::SUCCESS, $($args)+) }};
+}
+
+/// Prints a warning message to [`io::ansierr()`], prefixed with the
+/// [`WARNING`](message::WARNING) label.
+///
+/// The syntax is the same as [`eprintln!`]. See the [`message`](mod@message) module.
+///
+/// ### Example
+/// ```
+/// use ansiconst::paint_warning;
+///
+/// paint_warning!("Unused variable {:?}", "x");
+/// // Prints "\x1B[1;33mwarning:\x1B[22;39m Unused variable \"x\"\n"
+/// ```
+#[macro_export]
+macro_rules! paint_warning {
+    ($($args:tt)+) => {{ $crate::epaint_message!($crate::message::WARNING, $($args)+) }};
+}
+
+/// Prints an error message to [`io::ansierr()`], prefixed with the
+/// [`ERROR`](message::ERROR) label.
+///
+/// The syntax is the same as [`eprintln!`]. See the [`message`](mod@message) module.
+///
+/// ### Example
+/// ```
+/// use ansiconst::paint_error;
+///
+/// paint_error!("File {:?} not found", "main.rs");
+/// // Prints "\x1B[1;31merror:\x1B[22;39m File \"main.rs\" not found\n"
+/// ```
+#[macro_export]
+macro_rules! paint_error {
+    ($($args:tt)+) => {{ $crate::epaint_message!($crate::message::ERROR, $($args)+) }};
+}
+
+/// Asserts that styled output is equal to the expected output, both with and without ANSI codes.
+///
+/// The first argument is the expected output *without* ANSI codes, the second is the expected
+/// output *with* ANSI codes and the third is the actual output, which may be any
+/// [`Display`](std::fmt::Display) value. The plain comparison is done first, so that
+/// differences in text are reported before differences in styling.
+///
+/// On failure, panics with a message showing both outputs with escape sequences made visible,
+/// and where they differ highlighted in colour (unless disabled for [`ansierr()`](io::ansierr)).
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, Colour::Red};
+///
+/// assert_styled_eq!("Hello world", "\x1B[31mHello world\x1B[39m", styled!(Red, "Hello world"));
+/// ```
+///
+/// ```should_panic
+/// use ansiconst::{*, Colour::{Red, Blue}};
+///
+/// // Panics because the text is the same, but the colour is not
+/// assert_styled_eq!("Hello world", "\x1B[31mHello world\x1B[39m", styled!(Blue, "Hello world"));
+/// ```
+#[macro_export]
+macro_rules! assert_styled_eq {
+    ($expected_plain:expr, $expected_ansi:expr, $actual:expr $(,)?) => {
+        $crate::testing::assert_styled_eq(
+            ::std::convert::AsRef::<str>::as_ref(&$expected_plain),
+            ::std::convert::AsRef::<str>::as_ref(&$expected_ansi),
+            &::std::string::ToString::to_string(&$actual),
+        )
+    };
+}
@@ -0,0 +1,26 @@
+//! This crate's macros, for use via a module path.
+//!
+//! All of this crate's macros are exported at the crate root, e.g. `ansiconst::paintln!`.
+//! This module re-exports them, so they can be brought into scope selectively, or used
+//! under a namespace, which avoids collisions with macros of the same name defined
+//! elsewhere (e.g. a project's own `paint!` macro).
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{macros as ac, Colour::Red};
+//!
+//! // A project's own macro, which would otherwise collide with ansiconst's:
+//! macro_rules! paint { ($($arg:tt)*) => { format!($($arg)*) } }
+//!
+//! assert_eq!(paint!("{}", 1), "1");
+//! assert_eq!(ac::styled_format!(Red, "{}", 1), "\x1B[31m1\x1B[39m");
+//! ac::paintln!(Red, "Hello world");
+//! ```
+
+pub use crate::{ansi, ansi_code, ansi_style, ansi_code_fallback, const_styled_concat, palette, styled, styled_debug, styled_format, styled_format_args, styled_write, styled_writeln};
+pub use crate::{paint, paintln, epaint, epaintln, try_paint, try_paintln, try_epaint, try_epaintln};
+pub use crate::{paint_message, epaint_message, paint_success, paint_warning, paint_error};
+pub use crate::assert_styled_eq;
+#[cfg(feature="rgb")]
+pub use crate::rgb;
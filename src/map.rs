@@ -0,0 +1,133 @@
+//! Remap ANSI styles as they are written.
+//!
+//! A [`StyleMap`] is a function that rewrites each effective [`Ansi`] style just before
+//! its ANSI codes are written. This is useful for normalizing the appearance of
+//! styled output across terminals, without having to change the `const` styles
+//! declared throughout a program.
+//!
+//! A style map is configured on a `Writer` by calling [`set_style_map()`](crate::io::AnsiWrite::set_style_map())
+//! and is then applied to all [`Styled<T>`](crate::Styled)s written with
+//! [`write_fmt()`](std::io::Write::write_fmt()), including those nested inside each other.
+//!
+//! This module provides the following style maps:
+//!
+//! - [`bright_as_bold()`]: renders bright colours as their normal colour plus [`Bold`](Effect::Bold).
+//! - [`bold_as_bright()`]: renders [`Bold`](Effect::Bold) normal colours as their bright colour.
+//! - [`no_backgrounds()`]: removes background colours, keeping foreground colours and effects.
+//!
+//! Some terminals render e.g. `BrightRed` and `Red, Bold` identically, while others do not.
+//! Choosing one of the above style maps makes output look the same on both.
+//!
+//! Some log aggregators render background colours badly, so [`no_backgrounds()`] is also
+//! the default style map of [`ansiout()`](crate::io::ansiout()) and [`ansierr()`](crate::io::ansierr())
+//! if the `ANSICONST_NO_BG` env variable is set, see [`from_env()`].
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{*, io::*, Colour::BrightRed};
+//!
+//! io::ansiout().all_ansi();
+//! io::ansiout().set_style_map(Some(map::bright_as_bold));
+//! paintln!(BrightRed, "Error");
+//! // Prints "\x1B[1;31mError\x1B[22;39m", i.e. red colour and bold
+//!
+//! io::ansiout().set_style_map(None);
+//! paintln!(BrightRed, "Error");
+//! // Prints "\x1B[91mError\x1B[39m", i.e. bright red colour
+//! ```
+
+use crate::ansi::{Ansi, Colour, Colours, Effect};
+use std::env;
+
+/// A function that rewrites an effective [`Ansi`] style before it is written.
+///
+/// See the [module-level documentation](crate::map).
+pub type StyleMap = fn(Ansi) -> Ansi;
+
+/// A [`StyleMap`] that replaces a bright foreground colour with its normal
+/// colour plus [`Bold`](Effect::Bold).
+///
+/// Styles without a bright foreground colour are unchanged.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, Colour::{BrightRed, Red}, Effect::Bold};
+///
+/// assert_eq!(map::bright_as_bold(ansi!(BrightRed)), ansi!(Red, Bold));
+/// assert_eq!(map::bright_as_bold(ansi!(Red)),       ansi!(Red));
+/// ```
+pub const fn bright_as_bold(ansi: Ansi) -> Ansi {
+    let fg = ansi.colour().fg();
+    if !fg.is_bright() {
+        return ansi;
+    }
+    ansi.with_colour(ansi.colour().with_fg(fg.to_normal()))
+        .with_effect(ansi.effect().with_effect(Effect::Bold))
+}
+
+/// A [`StyleMap`] that replaces a normal foreground colour plus [`Bold`](Effect::Bold)
+/// with its bright colour, without `Bold`.
+///
+/// Styles without both `Bold` and a normal foreground colour are unchanged.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, Colour::{BrightRed, Red}, Effect::Bold};
+///
+/// assert_eq!(map::bold_as_bright(ansi!(Red, Bold)), ansi!(BrightRed));
+/// assert_eq!(map::bold_as_bright(ansi!(Bold)),      ansi!(Bold));
+/// ```
+pub const fn bold_as_bright(ansi: Ansi) -> Ansi {
+    let fg = ansi.colour().fg();
+    if !fg.is_normal() || !ansi.effect().has_effect(Effect::Bold) {
+        return ansi;
+    }
+    ansi.with_colour(ansi.colour().with_fg(fg.to_bright()))
+        .with_effect(ansi.effect().without_effect(Effect::Bold))
+}
+
+/// A [`StyleMap`] that removes the background colour, keeping the foreground colour
+/// and effects.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, Colour::{Blue, Red}, Effect::Bold};
+///
+/// assert_eq!(map::no_backgrounds(ansi!(Red, Blue.bg(), Bold)), ansi!(Red, Bold));
+/// assert_eq!(map::no_backgrounds(ansi!(Red)),                  ansi!(Red));
+/// ```
+pub const fn no_backgrounds(ansi: Ansi) -> Ansi {
+    ansi.with_colour(Colours::new(ansi.colour().fg(), Colour::Unspecified))
+}
+
+/// Gets the style map selected by env variables, if any, i.e. [`no_backgrounds()`] if
+/// the `ANSICONST_NO_BG` env variable is set to a non-empty value, else `None`.
+///
+/// This is the default style map of [`ansiout()`](crate::io::ansiout()) and
+/// [`ansierr()`](crate::io::ansierr()), see [`AnsiPreference::preferred_style_map()`](crate::io::AnsiPreference::preferred_style_map()).
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, io::{self, AnsiWrite}};
+///
+/// std::env::set_var("ANSICONST_NO_BG", "1");
+/// assert!(map::from_env().is_some());
+///
+/// // Selected explicitly, e.g. after changing the style map:
+/// io::ansiout().set_style_map(map::from_env());
+///
+/// std::env::remove_var("ANSICONST_NO_BG");
+/// assert!(map::from_env().is_none());
+/// ```
+pub fn from_env() -> Option<StyleMap> {
+    if env::var_os("ANSICONST_NO_BG").is_some_and(|value| !value.is_empty()) {
+        Some(no_backgrounds)
+    } else {
+        None
+    }
+}
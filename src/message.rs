@@ -0,0 +1,70 @@
+//! Print status messages prefixed with a styled label, e.g. `"error: file not found"`.
+//!
+//! Nearly every CLI prints success, warning and error messages in a consistent style.
+//! The [`paint_success!`](crate::paint_success), [`paint_warning!`](crate::paint_warning) and
+//! [`paint_error!`](crate::paint_error) macros do exactly this, using this module's [`SUCCESS`],
+//! [`WARNING`] and [`ERROR`] labels.
+//! Success messages are printed to [`ansiout()`](crate::io::ansiout()), and warning and error
+//! messages to [`ansierr()`](crate::io::ansierr()).
+//!
+//! The labels' styles are [`NamedStyle`]s, so they can be re-themed via the
+//! [`registry`](crate::registry), e.g. [`registry::set_style("ansiconst::error", ...)`](crate::registry::set_style()).
+//! They are also the [`Success`](crate::theme::Slot::Success), [`Warning`](crate::theme::Slot::Warning)
+//! and [`Error`](crate::theme::Slot::Error) slots of the [`theme`](crate::theme).
+//! To use other labels entirely, declare them as `const` [`Label`]s and print them with
+//! [`paint_message!`](crate::paint_message) or [`epaint_message!`](crate::epaint_message).
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{*, message::Label, registry::NamedStyle, Colour::Blue, Effect::Bold};
+//!
+//! paint_success!("Compiled {} files", 3);
+//! // Prints "\x1B[1;32msuccess:\x1B[22;39m Compiled 3 files\n"
+//! paint_error!("File {:?} not found", "main.rs");
+//! // Prints "\x1B[1;31merror:\x1B[22;39m File \"main.rs\" not found\n" to stderr
+//!
+//! const NOTE: Label = Label::new(NamedStyle::new("myapp::note", ansi!(Blue, Bold)), "note:");
+//! paint_message!(NOTE, "Using cached results");
+//! // Prints "\x1B[1;34mnote:\x1B[22;39m Using cached results\n"
+//! ```
+
+use crate::{registry::NamedStyle, theme::Slot, Styled};
+use std::fmt;
+
+/// A styled label that prefixes a message, e.g. `"error:"`.
+///
+/// See the [module-level documentation](mod@crate::message).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Label {
+    style: NamedStyle,
+    text:  &'static str,
+}
+
+impl Label {
+    /// Creates a new instance with the given style and text.
+    #[inline]
+    pub const fn new(style: NamedStyle, text: &'static str) -> Self { Self { style, text } }
+    /// Gets the style.
+    #[inline]
+    pub const fn style(&self) -> NamedStyle { self.style }
+    /// Gets the text.
+    #[inline]
+    pub const fn text(&self) -> &'static str { self.text }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Styled::new(self.style.ansi(), self.text))
+    }
+}
+
+/// The label used by [`paint_success!`](crate::paint_success).
+pub const SUCCESS: Label = Label::new(Slot::Success.style(), "success:");
+/// The label used by [`paint_warning!`](crate::paint_warning).
+pub const WARNING: Label = Label::new(Slot::Warning.style(), "warning:");
+/// The label used by [`paint_error!`](crate::paint_error).
+pub const ERROR: Label = Label::new(Slot::Error.style(), "error:");
+
+/// The styles of this module's labels, e.g. for [`registry::register()`](crate::registry::register()).
+pub const STYLES: &[NamedStyle] = &[SUCCESS.style, WARNING.style, ERROR.style];
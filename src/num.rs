@@ -0,0 +1,216 @@
+//! Format numbers with thousands separators.
+//!
+//! Wrapping a number in a [`Grouped`] formats it with its digits grouped in thousands,
+//! e.g. `1234567` becomes `1,234,567`. Like any other [`Display`](fmt::Display) target,
+//! a [`Grouped`] can then be styled, e.g. with [`styled!`](crate::styled), and the result
+//! is a [`StyledNum`].
+//!
+//! Formatting a [`Grouped`] respects the [`Formatter`](fmt::Formatter)'s width, fill and
+//! alignment (numbers are right-aligned by default), as well as its precision for floats.
+//! When styled, the padding is applied *inside* the ANSI codes, so columns of styled
+//! numbers line up as expected.
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{*, num::{self, StyledNum}, Colour::Green};
+//!
+//! const TOTAL: StyledNum<u32> = styled!(Green, num::grouped(1234567));
+//!
+//! assert_eq!(num::grouped(-9876543.21).to_string(),            "-9,876,543.21");
+//! assert_eq!(format!("{:.1}", num::grouped(1234.56)),          "1,234.6");
+//! assert_eq!(num::grouped(1234567).separator('_').to_string(), "1_234_567");
+//!
+//! assert_eq!(format!("[{:>12}]", TOTAL),  "[\x1B[32m   1,234,567\x1B[39m]");
+//! assert_eq!(format!("[{:<12}]", TOTAL),  "[\x1B[32m1,234,567   \x1B[39m]");
+//! ```
+//!
+//! Additionally, a number can be mapped to a style and label using a `const` table
+//! of [`Threshold`]s, e.g. to render a percentage as a traffic-light [`badge()`].
+
+use crate::{Ansi, Styled};
+use std::fmt::{self, Write};
+
+/// A [`Styled`] number with its digits grouped in thousands.
+///
+/// See the [module-level documentation](crate::num).
+pub type StyledNum<T> = Styled<Grouped<T>>;
+
+/// A [`Display`](fmt::Display) adapter that formats a number with its digits grouped in thousands.
+///
+/// Created by [`grouped()`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Grouped<T: Number> {
+    value:     T,
+    separator: char,
+}
+
+/// Creates a [`Grouped`] for the given number, using `,` as the thousands separator.
+#[inline]
+pub const fn grouped<T: Number>(value: T) -> Grouped<T> { Grouped { value, separator: ',' } }
+
+impl<T: Number> Grouped<T> {
+    /// Sets the thousands separator.
+    #[inline]
+    pub const fn separator(self, separator: char) -> Self { Self { separator, ..self } }
+    /// Gets the number.
+    #[inline]
+    pub const fn value(&self) -> T { self.value }
+}
+
+impl<T: Number> fmt::Display for Grouped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ungrouped = self.value.to_plain_string(f.precision());
+        let (sign, rest)  = ungrouped.split_at(if ungrouped.starts_with('-') { 1 } else { 0 });
+        let int_len       = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let (int, suffix) = rest.split_at(int_len);
+
+        let mut grouped = String::with_capacity(ungrouped.len() + int_len / 3);
+        grouped.push_str(sign);
+        for (i, digit) in int.chars().enumerate() {
+            if i > 0 && (int_len - i) % 3 == 0 {
+                grouped.push(self.separator);
+            }
+            grouped.push(digit);
+        }
+        grouped.push_str(suffix);
+        pad(f, &grouped)
+    }
+}
+
+/// Writes `s` padded according to the [`Formatter`](fmt::Formatter)'s width, fill and
+/// alignment, defaulting to right-alignment. Unlike [`pad()`](fmt::Formatter::pad()),
+/// the precision is ignored, because it has already been applied to the number.
+fn pad(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    let padding = f.width().map_or(0, |width| width.saturating_sub(s.chars().count()));
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left)   => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _                            => (padding, 0),
+    };
+    let fill = f.fill();
+    for _ in 0..before { f.write_char(fill)?; }
+    f.write_str(s)?;
+    for _ in 0..after { f.write_char(fill)?; }
+    Ok(())
+}
+
+/// Associates an [`Ansi`] style and a label with all numbers below a limit.
+///
+/// Thresholds are intended to be declared as `const` tables, in ascending order of limit,
+/// and looked up with [`threshold()`] or [`badge()`].
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Threshold {
+    limit: f64,
+    ansi:  Ansi,
+    label: &'static str,
+}
+
+impl Threshold {
+    /// Creates a threshold that matches numbers less than `limit`.
+    #[inline]
+    pub const fn below(limit: f64, ansi: Ansi, label: &'static str) -> Self { Self { limit, ansi, label } }
+    /// Creates a threshold that matches all numbers, for use at the end of a table.
+    #[inline]
+    pub const fn otherwise(ansi: Ansi, label: &'static str) -> Self { Self::below(f64::INFINITY, ansi, label) }
+    /// Gets the limit, which matching numbers are less than.
+    #[inline]
+    pub const fn limit(&self) -> f64 { self.limit }
+    /// Gets the style.
+    #[inline]
+    pub const fn ansi(&self) -> Ansi { self.ansi }
+    /// Gets the label.
+    #[inline]
+    pub const fn label(&self) -> &'static str { self.label }
+}
+
+/// Finds the first of the given `thresholds` that matches `value`, i.e. whose
+/// [`limit()`](Threshold::limit()) is greater than `value`.
+///
+/// Returns `None` if none match, or if `value` is `NaN`.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, num::{self, Threshold}, Colour::{Green, Red}};
+///
+/// const LATENCY_MS: [Threshold; 2] = [
+///     Threshold::below(100.0, ansi!(Green), "fast"),
+///     Threshold::otherwise(   ansi!(Red),   "slow"),
+/// ];
+///
+/// assert_eq!(num::threshold(42.0,  &LATENCY_MS).map(Threshold::label), Some("fast"));
+/// assert_eq!(num::threshold(100.0, &LATENCY_MS).map(Threshold::label), Some("slow"));
+/// ```
+pub fn threshold(value: f64, thresholds: &[Threshold]) -> Option<&Threshold> {
+    thresholds.iter().find(|threshold| value < threshold.limit)
+}
+
+/// Formats a `ratio` as a percentage badge, styled and labelled according to the
+/// first of the given `thresholds` that matches it (see [`threshold()`]).
+///
+/// The percentage is rounded to a whole number, and is followed by the threshold's
+/// label, if not empty. The thresholds' limits are ratios, i.e. `0.5` means 50%.
+/// If no threshold matches, the badge is unstyled and unlabelled.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, num::{self, Threshold}, Colour::{Green, Yellow, Red}, Effect::Bold};
+///
+/// const USAGE: [Threshold; 3] = [
+///     Threshold::below(0.5, ansi!(Green),      ""),
+///     Threshold::below(0.8, ansi!(Yellow),     "high"),
+///     Threshold::otherwise( ansi!(Red, Bold),  "critical"),
+/// ];
+///
+/// assert_eq!(num::badge(0.25, &USAGE).to_string(), "\x1B[32m25%\x1B[39m");
+/// assert_eq!(num::badge(0.5,  &USAGE).to_string(), "\x1B[33m50% high\x1B[39m");
+/// assert_eq!(num::badge(0.97, &USAGE).to_string(), "\x1B[1;31m97% critical\x1B[22;39m");
+/// ```
+pub fn badge(ratio: f64, thresholds: &[Threshold]) -> Styled<String> {
+    let percent = format!("{:.0}%", ratio * 100.0);
+    match threshold(ratio, thresholds) {
+        Some(Threshold { ansi, label: "", .. }) => Styled::new(*ansi, percent),
+        Some(Threshold { ansi, label, .. })     => Styled::new(*ansi, format!("{} {}", percent, label)),
+        None                                    => Styled::unstyled(percent),
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A primitive number type that can be [`Grouped`].
+///
+/// This trait is sealed, i.e. implemented only for Rust's primitive integers and floats.
+pub trait Number: Copy + private::Sealed {
+    #[doc(hidden)]
+    fn to_plain_string(&self, precision: Option<usize>) -> String;
+}
+
+macro_rules! impl_integer {
+    ($($ty:ty)*) => {$(
+        impl private::Sealed for $ty {}
+        impl Number for $ty {
+            fn to_plain_string(&self, _: Option<usize>) -> String { self.to_string() }
+        }
+    )*};
+}
+
+macro_rules! impl_float {
+    ($($ty:ty)*) => {$(
+        impl private::Sealed for $ty {}
+        impl Number for $ty {
+            fn to_plain_string(&self, precision: Option<usize>) -> String {
+                match precision {
+                    Some(precision) => format!("{:.*}", precision, self),
+                    None            => self.to_string(),
+                }
+            }
+        }
+    )*};
+}
+
+impl_integer!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+impl_float!(f32 f64);
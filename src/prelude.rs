@@ -0,0 +1,35 @@
+//! Convenience re-exports for getting started with a single import.
+//!
+//! Brings into scope this crate's core styling macros (e.g. [`ansi!`](crate::ansi),
+//! [`styled!`](crate::styled) and [`paintln!`](crate::paintln)), its core types and the
+//! [`AnsiWrite`] trait, along with the [`fmt::Write`](std::fmt::Write) and [`io::Write`](std::io::Write)
+//! traits that are needed when calling [`write!`] or [`writeln!`] directly.
+//! The `Write` traits are imported anonymously, so they do not clash with any
+//! other items named `Write`.
+//!
+//! The [`message`](crate::message) macros, e.g. [`paint_error!`](crate::paint_error), are not
+//! brought into scope. Import them from the crate root or [`macros`](crate::macros) instead.
+//!
+//! ### Example
+//!
+//! ```
+//! use ansiconst::prelude::*;
+//! use ansiconst::Colour::Red;
+//!
+//! const ERROR: Ansi = ansi!(Red, Effect::Bold);
+//!
+//! let mut output = String::new();
+//! styled_write!(&mut output, ERROR, "Oops").unwrap();
+//!
+//! assert_eq!(output, "\x1B[1;31mOops\x1B[22;39m");
+//! ```
+
+pub use crate::{ansi, ansi_code, styled, styled_format, styled_format_args, styled_write, styled_writeln};
+pub use crate::{paint, paintln, epaint, epaintln, try_paint, try_paintln, try_epaint, try_epaintln};
+#[cfg(feature="rgb")]
+pub use crate::rgb;
+pub use crate::{Ansi, Attrs, Colour, Effect, Styled, StyledBatch, StyledSpans};
+pub use crate::io::{AnsiPreference, AnsiWrite};
+
+pub use std::fmt::Write as _;
+pub use std::io::Write as _;
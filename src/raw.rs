@@ -0,0 +1,133 @@
+use crate::fmt::fmt_transition;
+use crate::state::{RAW, STYLES};
+use crate::text::parse_sgr_params;
+use crate::Attrs;
+use std::fmt;
+
+/// An escape hatch for writing SGR parameters that are not otherwise supported by
+/// this crate, e.g. terminal-specific extensions such as curly underlines (`4:3`).
+///
+/// A `RawSgr` is an opaque pair of parameter strings: one that *sets* the style,
+/// and one that *resets* it. Use [`wrap()`](Self::wrap()) to apply it to a target,
+/// which writes the set parameters before the target and the reset parameters after it.
+///
+/// Raw parameters are opaque to this crate, so they do not take part in the calculation
+/// of transitions between [`Ansi`](crate::Ansi) styles. However, they do respect
+/// [protection](crate::Ansi::protect_attrs()): no raw parameters are written while
+/// *all* attributes are protected, e.g. inside [`Ansi::no_ansi()`](crate::Ansi::no_ansi())
+/// or [`only()`](crate::Ansi::only()), nor while any attribute that they set or reset
+/// is protected, e.g. `4:3` while [`Attrs::Underline`] is protected. The attributes are
+/// those that this crate recognizes in the parameters, e.g. none for `53` (overline).
+///
+/// When nested, the *reset* parameters are followed by those that restore the enclosing
+/// styles, i.e. the *set* parameters of an enclosing `RawSgr`, and any attributes of the
+/// enclosing [`Ansi`](crate::Ansi) style that the reset parameters reset.
+///
+/// *Note:* for underline styles, prefer [`UnderlineStyle`](crate::UnderlineStyle),
+/// which integrates with transitions between [`Ansi`](crate::Ansi) styles.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, Colour::Red};
+///
+/// const CURLY: RawSgr = RawSgr::new("4:3", "4:0");
+///
+/// assert_eq!(
+///     styled_format!(Red, "A {} C", CURLY.wrap("B")),
+///     "\x1B[31mA \x1B[4:3mB\x1B[4:0m C\x1B[39m",
+/// );
+/// assert_eq!(
+///     styled_format!(Ansi::no_ansi(), "A {} C", CURLY.wrap("B")),
+///     "A B C",
+/// );
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct RawSgr {
+    set:   &'static str,
+    reset: &'static str,
+}
+
+impl RawSgr {
+    /// Creates an instance with the given *set* and *reset* SGR parameters,
+    /// i.e. the part of an SGR sequence between `"\x1B["` and `"m"`.
+    ///
+    /// # Panics
+    ///
+    /// If either parameter string contains characters other than ASCII digits,
+    /// `;` and `:`. In a `const` context, this is a compile-time error.
+    pub const fn new(set: &'static str, reset: &'static str) -> Self {
+        assert!(is_valid(set) && is_valid(reset), "SGR parameters may only contain digits, ';' and ':'");
+        Self { set, reset }
+    }
+    /// Gets the parameters that set this style.
+    #[inline]
+    pub const fn set(&self) -> &'static str { self.set }
+    /// Gets the parameters that reset this style.
+    #[inline]
+    pub const fn reset(&self) -> &'static str { self.reset }
+    /// Applies this style to the given target.
+    #[inline]
+    pub const fn wrap<T: fmt::Display>(&self, target: T) -> RawStyled<T> { RawStyled { raw: *self, target } }
+    /// The attributes that this instance's parameters set or reset, as far as recognized.
+    fn attrs(&self) -> Attrs {
+        [self.set, self.reset].iter().filter_map(|params| parse_sgr_params(params))
+            .fold(Attrs::empty(), |attrs, sgr| attrs.union(sgr.set.attrs()).union(sgr.reset))
+    }
+}
+
+const fn is_valid(params: &str) -> bool {
+    let bytes = params.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !matches!(bytes[i], b'0'..=b'9' | b';' | b':') { return false; }
+        i += 1;
+    }
+    true
+}
+
+/// Associates a [`Display`](fmt::Display) *target* with a [`RawSgr`].
+///
+/// Created by [`RawSgr::wrap()`].
+#[derive(Clone, Copy, Debug)]
+pub struct RawStyled<T: fmt::Display> {
+    raw:    RawSgr,
+    target: T,
+}
+
+impl<T: fmt::Display> RawStyled<T> {
+    /// Gets the [`RawSgr`] style.
+    #[inline]
+    pub const fn raw(&self) -> RawSgr { self.raw }
+    /// Gets the target.
+    #[inline]
+    pub const fn target(&self) -> &T { &self.target }
+}
+
+impl<T: fmt::Display> fmt::Display for RawStyled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // As for an `Underlined`, the protection of the enclosing style as written applies
+        let (_, out) = STYLES.get();
+        let protected = out.protected_attrs();
+        if protected == Attrs::all() || protected.intersects(self.raw.attrs()) {
+            return self.target.fmt(f);
+        }
+        let parent = RAW.replace(Some(self.raw));
+        let result = if self.raw.set.is_empty() { Ok(()) } else { write!(f, "\x1B[{}m", self.raw.set) };
+        let result = result.and_then(|_| self.target.fmt(f));
+        RAW.set(parent);
+        result?;
+        if self.raw.reset.is_empty() {
+            return Ok(());
+        }
+        write!(f, "\x1B[{}m", self.raw.reset)?;
+        // Restore whatever the reset parameters reset
+        if let Some(sgr) = parse_sgr_params(self.raw.reset) {
+            fmt_transition(f, out.filter(sgr.reset))?;
+        }
+        match parent {
+            Some(parent) if !parent.set.is_empty() => write!(f, "\x1B[{}m", parent.set),
+            _                                      => Ok(()),
+        }
+    }
+}
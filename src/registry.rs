@@ -0,0 +1,127 @@
+//! Declare semantic styles that can be enumerated and re-themed at runtime.
+//!
+//! The `const` styles declared by a library are normally fixed, so an application
+//! using several such libraries has no way to give their output a consistent look.
+//! This module provides a *registry* that solves this:
+//!
+//! 1. A library declares each of its semantic styles as a [`NamedStyle`], and
+//!    uses [`NamedStyle::ansi()`] whenever it writes styled output.
+//! 2. The library exposes its styles, e.g. as a `pub const STYLES` slice, and either
+//!    the library or the application [`register()`]s them.
+//! 3. The application enumerates all registered [`styles()`] and
+//!    [re-themes](set_style()) any of them.
+//!
+//! By convention, style names are prefixed with the declaring crate's name
+//! (e.g. `"mylib::heading"`), so styles from different crates do not clash.
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{*, registry::{self, NamedStyle}, Colour::{Green, Blue}, Effect::Bold};
+//!
+//! // In a library:
+//! pub const HEADING: NamedStyle = NamedStyle::new(
+//!     concat!(env!("CARGO_PKG_NAME"), "::heading"), ansi!(Green, Bold)
+//! );
+//! pub const STYLES: &[NamedStyle] = &[HEADING];
+//!
+//! fn print_heading(heading: &str) -> String {
+//!     styled_format!(HEADING.ansi(), "{}", heading)
+//! }
+//!
+//! // In an application:
+//! registry::register(STYLES);
+//!
+//! for style in registry::styles() {
+//!     println!("{}: {}", style.name(), style.ansi().spec());
+//! }
+//! assert_eq!(print_heading("Title"), "\x1B[1;32mTitle\x1B[22;39m");
+//!
+//! registry::set_style(HEADING.name(), ansi!(Blue));
+//! assert_eq!(print_heading("Title"), "\x1B[34mTitle\x1B[39m");
+//! ```
+
+use crate::Ansi;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+struct Registry {
+    styles:    Vec<NamedStyle>,
+    overrides: Vec<(String, Ansi)>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry { styles: Vec::new(), overrides: Vec::new() });
+
+fn registry() -> MutexGuard<'static, Registry> {
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A semantic [`Ansi`] style with a name, whose style can be overridden at runtime.
+///
+/// See the [module-level documentation](crate::registry).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct NamedStyle {
+    name: &'static str,
+    ansi: Ansi,
+}
+
+impl NamedStyle {
+    /// Creates a new instance with the given name and default style.
+    #[inline]
+    pub const fn new(name: &'static str, ansi: Ansi) -> Self { Self { name, ansi } }
+
+    /// Gets this style's name.
+    #[inline]
+    pub const fn name(&self) -> &'static str { self.name }
+
+    /// Gets the style this instance was declared with, ignoring any override.
+    #[inline]
+    pub const fn default_ansi(&self) -> Ansi { self.ansi }
+
+    /// Gets the style to use for this instance: the style [set](set_style()) for its
+    /// name if any, else its [default](Self::default_ansi()) style.
+    pub fn ansi(&self) -> Ansi {
+        registry().overrides.iter()
+            .find(|(name, _)| name == self.name)
+            .map_or(self.ansi, |(_, ansi)| *ansi)
+    }
+}
+
+/// Registers the given styles, so they are returned by [`styles()`].
+///
+/// Styles whose name is already registered are ignored, so it is safe for
+/// both a library and an application to register the same styles.
+pub fn register(styles: &[NamedStyle]) {
+    let mut registry = registry();
+    for style in styles {
+        if !registry.styles.iter().any(|s| s.name == style.name) {
+            registry.styles.push(*style);
+        }
+    }
+}
+
+/// Gets all registered styles, in the order they were registered.
+pub fn styles() -> Vec<NamedStyle> {
+    registry().styles.clone()
+}
+
+/// Overrides the style used by all [`NamedStyle`]s with the given name.
+///
+/// The name need not be registered yet, so an application may apply its
+/// theme before its dependencies register their styles.
+pub fn set_style(name: &str, ansi: Ansi) {
+    let mut registry = registry();
+    match registry.overrides.iter_mut().find(|(n, _)| n == name) {
+        Some((_, old)) => *old = ansi,
+        None           => registry.overrides.push((name.into(), ansi)),
+    }
+}
+
+/// Removes the override for the given name, if any, restoring its default style.
+pub fn unset_style(name: &str) {
+    registry().overrides.retain(|(n, _)| n != name);
+}
+
+/// Removes all overrides, restoring all styles to their defaults.
+pub fn unset_styles() {
+    registry().overrides.clear();
+}
@@ -0,0 +1,194 @@
+//! Replace the storage of the state used for nesting styles.
+//!
+//! By default, the state of the [`Styled<T>`](crate::Styled)s being formatted (i.e. the
+//! styles of their parents, and the options of the `Writer` being written to) is stored
+//! in [`thread_local!`] static variables. This does not suit some runtimes, e.g. embedded
+//! runtimes whose tasks are not tied to OS threads, or which do not support `thread_local!`.
+//! With `feature=custom-state`, the state may instead be stored by a [`StateStorage`],
+//! provided by [`set_storage()`].
+//!
+//! Until a storage is set, the state is still stored in a `thread_local!`, and the public
+//! APIs of this crate are otherwise identical.
+//!
+//! *Note: only available with `feature=custom-state`*
+//!
+//! ### Examples
+//!
+//! ```
+//! # #[cfg(feature="custom-state")] {
+//! use ansiconst::{*, state::{self, NestingState, StateStorage}, Colour::Red};
+//! use std::sync::Mutex;
+//!
+//! // E.g. a storage that delegates to the host runtime
+//! struct HostStorage(Mutex<NestingState>);
+//!
+//! impl StateStorage for HostStorage {
+//!     fn get(&self) -> NestingState { *self.0.lock().unwrap() }
+//!     fn set(&self, state: NestingState) { *self.0.lock().unwrap() = state }
+//! }
+//!
+//! static STORAGE: HostStorage = HostStorage(Mutex::new(NestingState::new()));
+//!
+//! assert!(state::set_storage(&STORAGE).is_ok());
+//! assert_eq!(styled!(Red, "Red").to_string(), "\x1B[31mRed\x1B[39m");
+//! # }
+//! ```
+
+use crate::{map::StyleMap, Ansi, RawSgr, UnderlineStyle};
+#[cfg(feature="rgb")]
+use crate::Colour;
+
+use std::cell::Cell;
+#[cfg(feature="custom-state")]
+use std::{fmt, sync::OnceLock};
+
+#[cfg(not(feature="custom-state"))]
+thread_local! {
+    /// The effective style of the `Styled<T>` being formatted, and the style actually written.
+    pub(crate) static STYLES:    Cell<(Ansi, Ansi)>     = const { Cell::new((Ansi::unspecified(), Ansi::unspecified())) };
+    pub(crate) static STYLE_MAP: Cell<Option<StyleMap>> = const { Cell::new(None) };
+    pub(crate) static BUDGET:    Cell<Option<usize>>    = const { Cell::new(None) };
+    /// The style that resets a `Writer`'s default style, for styles nested directly in a
+    /// write with [`Composition::Fallback`](crate::io::Composition::Fallback).
+    pub(crate) static FALLBACK:  Cell<Ansi>             = const { Cell::new(Ansi::unspecified()) };
+    /// The [`UnderlineStyle`] of the `Underlined<T>` being formatted, if any.
+    pub(crate) static UNDERLINE: Cell<Option<UnderlineStyle>> = const { Cell::new(None) };
+    /// The [`RawSgr`] of the `RawStyled<T>` being formatted, if any.
+    pub(crate) static RAW:       Cell<Option<RawSgr>>   = const { Cell::new(None) };
+}
+
+#[cfg(all(feature="rgb", not(feature="custom-state")))]
+thread_local! {
+    /// The colours of the `DynamicColoured<T>`s being formatted, if any, by `DynamicColour`.
+    pub(crate) static DYNAMIC: Cell<[Option<Colour>; 5]> = const { Cell::new([None; 5]) };
+}
+
+/// The state used for nesting styles, which a [`StateStorage`] stores.
+///
+/// Its contents are private, i.e. a storage should simply return the most recent
+/// state it was given, or [`NestingState::new()`] if none.
+#[cfg(feature="custom-state")]
+#[derive(Clone, Copy)]
+pub struct NestingState {
+    styles:    (Ansi, Ansi),
+    style_map: Option<StyleMap>,
+    budget:    Option<usize>,
+    fallback:  Ansi,
+    underline: Option<UnderlineStyle>,
+    raw:       Option<RawSgr>,
+    #[cfg(feature="rgb")]
+    dynamic:   [Option<Colour>; 5],
+}
+
+#[cfg(feature="custom-state")]
+impl NestingState {
+    /// Creates the initial state, i.e. when no `Styled<T>`s are being formatted.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            styles:    (Ansi::unspecified(), Ansi::unspecified()),
+            style_map: None,
+            budget:    None,
+            fallback:  Ansi::unspecified(),
+            underline: None,
+            raw:       None,
+            #[cfg(feature="rgb")]
+            dynamic:   [None; 5],
+        }
+    }
+}
+
+#[cfg(feature="custom-state")]
+impl Default for NestingState {
+    fn default() -> Self { Self::new() }
+}
+
+#[cfg(feature="custom-state")]
+impl fmt::Debug for NestingState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NestingState").field("styles", &self.styles).finish_non_exhaustive()
+    }
+}
+
+/// Stores the [`NestingState`], instead of a [`thread_local!`].
+///
+/// The state is read and written several times while formatting each `Styled<T>`,
+/// so these methods should be cheap.
+#[cfg(feature="custom-state")]
+pub trait StateStorage: Sync {
+    /// Gets the most recently set state, else [`NestingState::new()`].
+    fn get(&self) -> NestingState;
+    /// Sets the state.
+    fn set(&self, state: NestingState);
+}
+
+/// The default storage, i.e. a `thread_local!` state.
+#[cfg(feature="custom-state")]
+struct ThreadLocalStorage;
+
+#[cfg(feature="custom-state")]
+thread_local! {
+    static STATE: Cell<NestingState> = const { Cell::new(NestingState::new()) };
+}
+
+#[cfg(feature="custom-state")]
+impl StateStorage for ThreadLocalStorage {
+    fn get(&self) -> NestingState { STATE.get() }
+    fn set(&self, state: NestingState) { STATE.set(state) }
+}
+
+#[cfg(feature="custom-state")]
+static STORAGE: OnceLock<&'static dyn StateStorage> = OnceLock::new();
+
+/// Sets the [`StateStorage`], which may be done once only, before formatting any
+/// `Styled<T>`s. Otherwise, the default `thread_local!` storage is used.
+///
+/// Returns the given storage as an error if a storage was already set, or if
+/// any `Styled<T>`s were already formatted.
+#[cfg(feature="custom-state")]
+pub fn set_storage(storage: &'static dyn StateStorage) -> Result<(), &'static dyn StateStorage> {
+    STORAGE.set(storage)
+}
+
+#[cfg(feature="custom-state")]
+fn storage() -> &'static dyn StateStorage { *STORAGE.get_or_init(|| &ThreadLocalStorage) }
+
+/// A field of the [`NestingState`], with the same methods as a `thread_local!` `Cell`.
+#[cfg(feature="custom-state")]
+pub(crate) struct Field<T> {
+    get: fn(&NestingState) -> T,
+    set: fn(&mut NestingState, T),
+}
+
+#[cfg(feature="custom-state")]
+impl<T> Field<T> {
+    #[inline]
+    pub(crate) fn get(&self) -> T { (self.get)(&storage().get()) }
+    #[inline]
+    pub(crate) fn set(&self, value: T) {
+        let mut state = storage().get();
+        (self.set)(&mut state, value);
+        storage().set(state);
+    }
+    #[inline]
+    pub(crate) fn replace(&self, value: T) -> T {
+        let old = self.get();
+        self.set(value);
+        old
+    }
+}
+
+#[cfg(feature="custom-state")]
+pub(crate) const STYLES: Field<(Ansi, Ansi)> = Field { get: |s| s.styles, set: |s, v| s.styles = v };
+#[cfg(feature="custom-state")]
+pub(crate) const STYLE_MAP: Field<Option<StyleMap>> = Field { get: |s| s.style_map, set: |s, v| s.style_map = v };
+#[cfg(feature="custom-state")]
+pub(crate) const BUDGET: Field<Option<usize>> = Field { get: |s| s.budget, set: |s, v| s.budget = v };
+#[cfg(feature="custom-state")]
+pub(crate) const FALLBACK: Field<Ansi> = Field { get: |s| s.fallback, set: |s, v| s.fallback = v };
+#[cfg(feature="custom-state")]
+pub(crate) const UNDERLINE: Field<Option<UnderlineStyle>> = Field { get: |s| s.underline, set: |s, v| s.underline = v };
+#[cfg(feature="custom-state")]
+pub(crate) const RAW: Field<Option<RawSgr>> = Field { get: |s| s.raw, set: |s, v| s.raw = v };
+#[cfg(all(feature="rgb", feature="custom-state"))]
+pub(crate) const DYNAMIC: Field<[Option<Colour>; 5]> = Field { get: |s| s.dynamic, set: |s, v| s.dynamic = v };
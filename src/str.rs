@@ -31,6 +31,28 @@ pub const fn to_ansi_bytes<const N: usize>(buf: &Buffer<[u8;25]>) -> [u8; N] {
     writer.take().array
 }
 
+#[doc(hidden)]
+pub const fn concat_len(parts: &[&'static str]) -> usize {
+    let mut result: usize = 0;
+    let mut i: usize = 0;
+    while i < parts.len() {
+        result += parts[i].len();
+        i += 1;
+    }
+    result
+}
+
+#[doc(hidden)]
+pub const fn concat_bytes<const N: usize>(parts: &[&'static str]) -> [u8; N] {
+    let mut writer = AnsiWriter::<N>::new();
+    let mut i: usize = 0;
+    while i < parts.len() {
+        writer = writer.write_str(parts[i]);
+        i += 1;
+    }
+    writer.take().array
+}
+
 const fn number_of_digits(mut value: u8) -> usize {
     let mut len: usize = 1;
     while value > 9 {
@@ -62,7 +84,7 @@ impl<const N: usize> AnsiWriter<N> {
         let mut i = 0usize;
         loop {
             let digit = value % 10;
-            self.state.array[self.state.len + number_of_digits - 1 - i] = b'0' + digit as u8;
+            self.state.array[self.state.len + number_of_digits - 1 - i] = b'0' + digit;
             value = value / 10;
             i += 1;
             if i == number_of_digits { break }
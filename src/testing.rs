@@ -0,0 +1,173 @@
+//! Helpers for testing styled output.
+//!
+//! See [`assert_styled_eq!`](crate::assert_styled_eq).
+//!
+//! With `feature=test-util`, this module also provides helpers for testing the styled
+//! output of a subprocess, e.g. a binary or a test that checks the output detected for
+//! terminals: spawn it with `run()`, then check the lines it prints between markers
+//! (see `marked()`) using `marked_lines()`.
+//!
+//! ### Examples
+//!
+//! ```no_run
+//! # #[cfg(feature="test-util")] {
+//! use ansiconst::{*, testing, Colour::Red};
+//! use std::process::Command;
+//!
+//! // The binary under test calls e.g. testing::marked(|| paintln!(Red, "Failed"))
+//! let output = testing::run(Command::new("target/debug/myapp")).unwrap();
+//! let mut lines = testing::marked_lines(output.stdout());
+//!
+//! assert_styled_eq!("Failed", "\x1B[31mFailed\x1B[39m", lines.next().unwrap());
+//! assert!(output.status().success());
+//! # }
+//! ```
+
+use crate::{io::{ansierr, AnsiWrite}, styled_write, styled_format_args, text, Ansi, Colour::{Green, Red}};
+use std::fmt;
+#[cfg(feature="test-util")]
+use std::{borrow::Cow, io, process::{Command, ExitStatus}, str};
+
+/// Implementation of [`assert_styled_eq!`](crate::assert_styled_eq).
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_styled_eq(expected_plain: &str, expected_ansi: &str, actual: &str) {
+    let ansi = ansierr().ansi();
+    let actual_plain = text::strip_ansi(actual);
+    if expected_plain != actual_plain {
+        panic!("styled output differs without ANSI codes:\n{}", Diff(ansi, expected_plain, &actual_plain));
+    }
+    if expected_ansi != actual {
+        panic!("styled output differs in ANSI codes:\n{}", Diff(ansi, expected_ansi, actual));
+    }
+}
+
+/// Shows `expected` and `actual` with escapes made visible, highlighting
+/// where they start to differ, using the given default style.
+struct Diff<'a>(Ansi, &'a str, &'a str);
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (ansi, expected, actual) = (self.0, self.1, self.2);
+        let common = expected.char_indices()
+            .zip(actual.chars())
+            .find(|((_, e), a)| e != a)
+            .map_or(expected.len().min(actual.len()), |((i, _), _)| i);
+        styled_write!(f, ansi, "expected: \"{}{}\"\n",
+            expected[..common].escape_debug(), styled_format_args!(Green, "{}", expected[common..].escape_debug()))?;
+        styled_write!(f, ansi, "  actual: \"{}{}\"",
+            actual[..common].escape_debug(), styled_format_args!(Red, "{}", actual[common..].escape_debug()))
+    }
+}
+
+/// The line that [`marked()`] prints before the output to be checked.
+#[cfg(feature="test-util")]
+pub const START_MARKER: &str = "[test_start]";
+/// The line that [`marked()`] prints after the output to be checked.
+#[cfg(feature="test-util")]
+pub const END_MARKER: &str = "[test_end]";
+
+/// Runs `f` between a [`START_MARKER`] line and an [`END_MARKER`] line, printed to both
+/// `stdout` and `stderr`, so that its output can be found by [`marked_lines()`] among
+/// any other output, e.g. that of the test harness.
+///
+/// *Note: only available with `feature=test-util`*
+#[cfg(feature="test-util")]
+pub fn marked<R>(f: impl FnOnce() -> R) -> R {
+    println!("{}", START_MARKER);
+    eprintln!("{}", START_MARKER);
+    let result = f();
+    println!("{}", END_MARKER);
+    eprintln!("{}", END_MARKER);
+    result
+}
+
+/// An iterator over the lines of some output that are between the first [`START_MARKER`]
+/// line and the next [`END_MARKER`] line (see [`marked()`]).
+///
+/// Created by [`marked_lines()`].
+#[cfg(feature="test-util")]
+#[derive(Clone, Debug)]
+pub struct MarkedLines<'a> {
+    lines: Option<str::Lines<'a>>,
+}
+
+#[cfg(feature="test-util")]
+impl<'a> Iterator for MarkedLines<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let line = self.lines.as_mut()?.next().filter(|line| *line != END_MARKER);
+        if line.is_none() {
+            self.lines = None;
+        }
+        line
+    }
+}
+
+/// Gets the lines of `output` that are between the first [`START_MARKER`] line and the next
+/// [`END_MARKER`] line, i.e. the output of [`marked()`]. If there is no start marker,
+/// there are no lines. The start marker may follow other output on its line, e.g. the
+/// test harness's `"test name ... "`.
+///
+/// *Note: only available with `feature=test-util`*
+///
+/// ```
+/// use ansiconst::testing::marked_lines;
+///
+/// let output = "running 1 test\n[test_start]\n\x1B[31mred\x1B[39m\n[test_end]\nok\n";
+/// assert_eq!(marked_lines(output).collect::<Vec<_>>(), ["\x1B[31mred\x1B[39m"]);
+/// ```
+#[cfg(feature="test-util")]
+pub fn marked_lines(output: &str) -> MarkedLines<'_> {
+    let mut lines = output.lines();
+    // Note: the marker may follow other output on the same line, e.g. "test name ... "
+    let is_started = lines.any(|line| line.ends_with(START_MARKER));
+    MarkedLines { lines: is_started.then_some(lines) }
+}
+
+/// The captured output of a subprocess, see [`run()`].
+#[cfg(feature="test-util")]
+#[derive(Clone, Debug)]
+pub struct Output {
+    stdout: String,
+    stderr: String,
+    status: ExitStatus,
+}
+
+#[cfg(feature="test-util")]
+impl Output {
+    /// Gets the subprocess's `stdout`, including ANSI codes.
+    #[inline]
+    pub fn stdout(&self) -> &str { &self.stdout }
+    /// Gets the subprocess's `stderr`, including ANSI codes.
+    #[inline]
+    pub fn stderr(&self) -> &str { &self.stderr }
+    /// Gets the subprocess's `stdout`, without ANSI codes (see [`text::strip_ansi()`]).
+    #[inline]
+    pub fn plain_stdout(&self) -> Cow<'_, str> { text::strip_ansi(&self.stdout) }
+    /// Gets the subprocess's `stderr`, without ANSI codes (see [`text::strip_ansi()`]).
+    #[inline]
+    pub fn plain_stderr(&self) -> Cow<'_, str> { text::strip_ansi(&self.stderr) }
+    /// Gets the subprocess's exit status.
+    #[inline]
+    pub fn status(&self) -> ExitStatus { self.status }
+}
+
+/// Runs `cmd` to completion, capturing its `stdout` and `stderr`.
+///
+/// Since the captured streams are not terminals, styles would normally be disabled
+/// (see [`AnsiPreference`](crate::io::AnsiPreference)), so the `FORCE_COLOR` env variable
+/// is set and `NO_COLOR` is removed, i.e. the subprocess writes styles as if to a terminal.
+/// Output that is not valid UTF-8 is converted lossily.
+///
+/// *Note: only available with `feature=test-util`*
+#[cfg(feature="test-util")]
+pub fn run(mut cmd: Command) -> io::Result<Output> {
+    let output = cmd.env("FORCE_COLOR", "1").env_remove("NO_COLOR").output()?;
+    Ok(Output {
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        status: output.status,
+    })
+}
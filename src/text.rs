@@ -0,0 +1,913 @@
+//! Inspect and transform text containing ANSI escape sequences.
+//!
+//! The functions in this module work on any text, i.e. not only text written by this crate.
+//! They recognize the following escape sequences:
+//!
+//! - *CSI* sequences, e.g. `"\x1B[1;31m"`, which include SGR (colour/effect) sequences.
+//! - *OSC* sequences, e.g. `"\x1B]0;title\x07"`, terminated by `BEL` or `ESC \`.
+//! - *DCS*, *SOS*, *PM* and *APC* strings, e.g. `"\x1BPq#0\x1B\\"`, likewise terminated.
+//! - Any other two-byte escape sequence, e.g. `"\x1Bc"`.
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{*, text, Colour::Red};
+//!
+//! let styled = styled_format!(Red, "Hello {}", "world");
+//!
+//! assert_eq!(styled, "\x1B[31mHello world\x1B[39m");
+//! assert_eq!(text::strip_ansi(&styled), "Hello world");
+//! ```
+
+use crate::{Ansi, Attrs, Colour, Effect, Styled, StyledSpans};
+use std::borrow::Cow;
+use std::fmt;
+
+const ESC: char = '\x1B';
+
+/// A part of some text, as produced by [`segments()`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) enum Segment<'a> {
+    /// Text without any escape sequences.
+    Text(&'a str),
+    /// A single escape sequence, including its leading `ESC`.
+    Escape(&'a str),
+}
+
+/// Splits `s` into [`Segment`]s of text and escape sequences.
+pub(crate) fn segments(s: &str) -> Segments<'_> { Segments { rest: s } }
+
+pub(crate) struct Segments<'a> { rest: &'a str }
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let len = if self.rest.starts_with(ESC) {
+            escape_len(self.rest)
+        } else {
+            self.rest.find(ESC).unwrap_or(self.rest.len())
+        };
+        let (segment, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Some(if segment.starts_with(ESC) { Segment::Escape(segment) } else { Segment::Text(segment) })
+    }
+}
+
+/// Gets the length in bytes of the escape sequence at the start of `s`, which
+/// must start with `ESC`. An unterminated sequence extends to the end of `s`.
+fn escape_len(s: &str) -> usize { escape_end(s.as_bytes()).unwrap_or(s.len()) }
+
+/// Gets the length in bytes of the escape sequence at the start of `bytes`, which
+/// must start with `ESC`, or `None` if it is unterminated, i.e. incomplete.
+pub(crate) fn escape_end(bytes: &[u8]) -> Option<usize> {
+    match bytes.get(1) {
+        // CSI: parameter and intermediate bytes, then a final byte in 0x40..=0x7E
+        Some(b'[') => bytes[2..].iter()
+            .position(|b| (0x40..=0x7E).contains(b))
+            .map(|i| i + 3),
+        // OSC, DCS, SOS, PM, APC: terminated by BEL or ST (i.e. ESC \)
+        Some(b']' | b'P' | b'X' | b'^' | b'_') => bytes[2..].iter().enumerate()
+            .find(|(i, b)| **b == 0x07 || (**b == 0x1B && bytes.get(i + 3) == Some(&b'\\')))
+            .map(|(i, b)| if *b == 0x07 { i + 3 } else { i + 4 }),
+        // Other: ESC plus a single char
+        Some(b)    => Some(1 + utf8_len(*b)).filter(|len| *len <= bytes.len()),
+        None       => None,
+    }
+}
+
+/// Gets the length in bytes of the UTF-8 char that starts with the given byte.
+pub(crate) const fn utf8_len(first: u8) -> usize {
+    match first {
+        0xF0..=0xFF => 4,
+        0xE0..=0xEF => 3,
+        0xC0..=0xDF => 2,
+        _           => 1,
+    }
+}
+
+/// Removes all ANSI escape sequences from `s`.
+///
+/// Returns `s` unchanged, i.e. without allocating, if it contains no escape sequences.
+/// See [`io::StripWriter`](crate::io::StripWriter) to remove escape sequences from streamed output.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::text::strip_ansi;
+///
+/// assert_eq!(strip_ansi("\x1B[1;31mBold red\x1B[22;39m plain"), "Bold red plain");
+/// assert_eq!(strip_ansi("\x1B]0;Title\x07Text"),               "Text");
+/// assert_eq!(strip_ansi("No escapes"),                         "No escapes");
+/// ```
+pub fn strip_ansi(s: &str) -> Cow<'_, str> {
+    if !s.contains(ESC) {
+        return Cow::Borrowed(s);
+    }
+    let mut stripped = String::with_capacity(s.len());
+    for segment in segments(s) {
+        if let Segment::Text(text) = segment {
+            stripped.push_str(text);
+        }
+    }
+    Cow::Owned(stripped)
+}
+
+/// Gets the visible width of `s` in columns, i.e. its number of chars, excluding
+/// escape sequences.
+///
+/// *Note:* every char is assumed to be one column wide, i.e. wide chars (e.g. CJK)
+/// and zero-width chars (e.g. combining accents) are not accounted for.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, text, Colour::Red};
+///
+/// assert_eq!(text::visible_width(&styled_format!(Red, "héllo")), 5);
+/// ```
+pub fn visible_width(s: &str) -> usize {
+    segments(s).map(|segment| match segment {
+        Segment::Text(text) => text.chars().count(),
+        Segment::Escape(_)  => 0,
+    }).sum()
+}
+
+/// A `Writer` that calls a function for each visible char written to it, i.e. skipping
+/// escape sequences (as recognized by [`segments()`]), even if they are split across writes.
+pub(crate) struct VisibleCharsWriter<F: FnMut(char)> { f: F, state: EscapeState }
+
+/// How far [`VisibleCharsWriter`] is through an escape sequence.
+#[derive(Clone, Copy)]
+enum EscapeState {
+    Text,
+    /// After `ESC`
+    Escape,
+    /// Within a CSI sequence
+    Csi,
+    /// Within an OSC, DCS, SOS, PM or APC string
+    String,
+    /// After `ESC` within a string, i.e. possibly its terminating `ESC \`
+    StringEscape,
+}
+
+impl<F: FnMut(char)> VisibleCharsWriter<F> {
+    #[inline]
+    pub(crate) fn new(f: F) -> Self { Self { f, state: EscapeState::Text } }
+}
+
+impl<F: FnMut(char)> fmt::Write for VisibleCharsWriter<F> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        use EscapeState::*;
+        for c in s.chars() {
+            self.state = match (self.state, c) {
+                (Text, ESC)                           => Escape,
+                (Text, c)                             => { (self.f)(c); Text },
+                (Escape, '[')                         => Csi,
+                (Escape, ']' | 'P' | 'X' | '^' | '_') => String,
+                (Escape, _)                           => Text,
+                (Csi, '\x40'..='\x7E')                => Text,
+                (Csi, _)                              => Csi,
+                (String, '\x07')                      => Text,
+                (String | StringEscape, ESC)          => StringEscape,
+                (String, _)                           => String,
+                (StringEscape, '\\')                  => Text,
+                (StringEscape, _)                     => String,
+            };
+        }
+        Ok(())
+    }
+}
+
+/// Converts `s` into the arguments for styling it in a browser's console, i.e. a format
+/// string with a `%c` directive wherever the style changes, and the CSS for each directive
+/// (see [`Ansi::to_css()`]).
+///
+/// SGR sequences are converted, other escape sequences are removed, and `%` is escaped
+/// as `%%`. Since the console resets the style after each message, a style active at
+/// the end of `s` needs no reset.
+///
+/// The arguments are intended to be passed to e.g. `console.log()`, via a crate such
+/// as `web-sys`, so that code written for terminals is styled sensibly in a browser.
+///
+/// *Note: only available with `feature=web`*
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, text, Colour::Red, Effect::Bold};
+///
+/// let (format, css) = text::console_args(&styled_format!(Bold, "100% {}", styled!(Red, "done")));
+///
+/// assert_eq!(format, "%c100%% %cdone");
+/// assert_eq!(css, ["font-weight: bold", "color: #cd0000; font-weight: bold"]);
+/// // E.g. with web-sys:
+/// //   let args = js_sys::Array::of1(&format.into());
+/// //   css.into_iter().for_each(|css| { args.push(&css.into()); });
+/// //   web_sys::console::log(&args);
+/// ```
+#[cfg(feature="web")]
+pub fn console_args(s: &str) -> (String, Vec<String>) {
+    let (mut format, mut css) = (String::with_capacity(s.len()), Vec::new());
+    let (mut ansi, mut written) = (Ansi::unspecified(), Ansi::unspecified());
+    for segment in segments(s) {
+        match segment {
+            Segment::Text(text) => {
+                if ansi != written {
+                    format.push_str("%c");
+                    css.push(ansi.to_css());
+                    written = ansi;
+                }
+                format.push_str(&text.replace('%', "%%"));
+            },
+            Segment::Escape(escape) => if let Some(sgr) = parse_sgr(escape) {
+                ansi = sgr.apply(ansi);
+            },
+        }
+    }
+    (format, css)
+}
+
+/// Splits `s` into spans of text, each paired with the style that is active for it
+/// according to the SGR sequences before it, e.g. for rendering styled text in a GUI.
+///
+/// Consecutive text with the same style forms a single span, other escape sequences are
+/// removed, and text without any style is paired with [`Ansi::unspecified()`].
+///
+/// See also [`Styled::to_spans()`].
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, text, Colour::Red, Effect::Bold};
+///
+/// assert_eq!(text::spans("plain \x1B[1mbold\x1B[31m red\x1B[22;39m"), [
+///     ("plain ".to_string(), Ansi::unspecified()),
+///     ("bold".to_string(),   ansi!(Bold)),
+///     (" red".to_string(),   ansi!(Bold, Red)),
+/// ]);
+/// ```
+pub fn spans(s: &str) -> Vec<(String, Ansi)> {
+    let mut spans: Vec<(String, Ansi)> = Vec::new();
+    let mut ansi = Ansi::unspecified();
+    for segment in segments(s) {
+        match segment {
+            Segment::Text(text) => match spans.last_mut() {
+                Some((last, last_ansi)) if *last_ansi == ansi => last.push_str(text),
+                _ => spans.push((text.to_string(), ansi)),
+            },
+            Segment::Escape(escape) => if let Some(sgr) = parse_sgr(escape) {
+                ansi = sgr.apply(ansi);
+            },
+        }
+    }
+    spans
+}
+
+/// Applies a text transform, e.g. [`str::to_uppercase()`], to the visible text of a string
+/// containing ANSI escape sequences, leaving the escape sequences, and so the styles of
+/// the text, unchanged, e.g. to post-process styled output without re-styling it.
+///
+/// `f` is called once for each run of text between escape sequences, so a transform that
+/// depends on its surroundings, e.g. replacing a word, does not match across them.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, text, Colour::Red, Effect::Bold};
+///
+/// let heading = styled_format!(Bold, "Error in {}", styled!(Red, "main.rs"));
+///
+/// assert_eq!(text::map_text(&heading, str::to_uppercase), styled_format!(Bold, "ERROR IN {}", styled!(Red, "MAIN.RS")));
+/// assert_eq!(text::map_text(&heading, |s| s.replace("rs", "c")), styled_format!(Bold, "Error in {}", styled!(Red, "main.c")));
+/// ```
+pub fn map_text(s: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut mapped = String::with_capacity(s.len());
+    for segment in segments(s) {
+        match segment {
+            Segment::Text(text)     => mapped.push_str(&f(text)),
+            Segment::Escape(escape) => mapped.push_str(escape),
+        }
+    }
+    mapped
+}
+
+/// Styles each match of `pattern` in `text`, e.g. to highlight the results of a search.
+///
+/// Unlike the other functions in this module, `text` should be plain text, i.e. without
+/// escape sequences. The matches are non-overlapping, and an empty pattern matches nothing.
+/// Like any [`StyledSpans`], the result combines with any enclosing style, and further
+/// spans may be added, e.g. with [`StyledSpans::highlight()`] for another pattern.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, text, Colour::Yellow, Effect::{Bold, Reverse}};
+///
+/// let found = text::highlight("one two one", "one", ansi!(Reverse));
+///
+/// assert_eq!(found.to_string(), "\x1B[7mone\x1B[27m two \x1B[7mone\x1B[27m");
+/// assert_eq!(
+///     styled_format!(Bold, "{}", found.highlight("two", ansi!(Yellow))),
+///     "\x1B[1m\x1B[7mone\x1B[27m \x1B[33mtwo\x1B[39m \x1B[7mone\x1B[27m\x1B[22m",
+/// );
+/// ```
+pub fn highlight<'a>(text: &'a str, pattern: &str, ansi: Ansi) -> StyledSpans<'a> {
+    StyledSpans::new(text).highlight(pattern, ansi)
+}
+
+/// How [`sanitize()`] treats unsafe escape sequences and control characters.
+///
+/// See [`Sanitize::apply()`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Sanitize {
+    /// Removes them.
+    Strip,
+    /// Replaces them with their escaped form, e.g. `"\x1B[2J"` becomes `"\\u{1b}[2J"`,
+    /// so that they are visible but harmless.
+    Escape,
+    /// Replaces *all* escape sequences, including SGR sequences, and control characters
+    /// with visible symbols, e.g. `"\x1B[1;31m"` becomes `"␛[1;31m"`, so that the exact
+    /// output can be inspected, e.g. when debugging or teaching, without a hex dump.
+    ///
+    /// See [`reveal()`] and [`Reveal`].
+    Reveal,
+}
+
+impl Sanitize {
+    /// Makes `s` safe to print, e.g. if it contains untrusted input such as filenames or
+    /// network data, while preserving its SGR (colour/effect) escape sequences.
+    ///
+    /// Other escape sequences (e.g. cursor movement, OSC, DCS) and control characters
+    /// other than `\n` and `\t` (e.g. `\r`, backspace, and C1 controls) can spoof or
+    /// corrupt the terminal, and so are stripped or escaped, according to `self`.
+    /// [`Sanitize::Reveal`] instead makes *all* of them visible, including SGR sequences.
+    ///
+    /// Returns `s` unchanged, i.e. without allocating, if it is already safe.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::text::Sanitize;
+    ///
+    /// let untrusted = "\x1B[31mred\x1B[39m\x1B]0;pwned\x07\x1B[2Jfile\r";
+    ///
+    /// assert_eq!(Sanitize::Strip.apply(untrusted),  "\x1B[31mred\x1B[39mfile");
+    /// assert_eq!(Sanitize::Escape.apply(untrusted), "\x1B[31mred\x1B[39m\\u{1b}]0;pwned\\u{7}\\u{1b}[2Jfile\\r");
+    /// assert_eq!(Sanitize::Reveal.apply(untrusted), "␛[31mred␛[39m␛]0;pwned␇␛[2Jfile␍");
+    /// ```
+    pub fn apply(self, s: &str) -> Cow<'_, str> {
+        let is_safe = segments(s).all(|segment| match segment {
+            Segment::Escape(escape) => self != Sanitize::Reveal && parse_sgr(escape).is_some(),
+            Segment::Text(text)     => !text.chars().any(is_unsafe),
+        });
+        if is_safe {
+            return Cow::Borrowed(s);
+        }
+        let mut sanitized = String::with_capacity(s.len());
+        if self == Sanitize::Reveal {
+            s.chars().for_each(|c| if is_unsafe(c) { push_revealed(&mut sanitized, c) } else { sanitized.push(c) });
+            return Cow::Owned(sanitized);
+        }
+        for segment in segments(s) {
+            match segment {
+                Segment::Escape(escape) if parse_sgr(escape).is_some() => sanitized.push_str(escape),
+                Segment::Escape(escape) => if self == Sanitize::Escape {
+                    sanitized.extend(escape.chars().flat_map(char::escape_debug));
+                },
+                Segment::Text(text) => for c in text.chars() {
+                    match (is_unsafe(c), self) {
+                        (false, _)                => sanitized.push(c),
+                        (true,  Sanitize::Escape) => sanitized.extend(c.escape_debug()),
+                        (true,  _)                => {},
+                    }
+                },
+            }
+        }
+        Cow::Owned(sanitized)
+    }
+}
+
+/// Determines whether `c` is a control character that is unsafe to print as is,
+/// i.e. including `ESC`, which may start an unsafe escape sequence.
+fn is_unsafe(c: char) -> bool { c.is_control() && c != '\n' && c != '\t' }
+
+/// Pushes the visible form of an unsafe control character, i.e. its Unicode "control
+/// picture" (e.g. `␛` for `ESC`), or its escaped form for C1 controls, which have none.
+fn push_revealed(s: &mut String, c: char) {
+    match c {
+        '\0'..='\x1F' => s.push(char::from_u32(0x2400 + c as u32).unwrap_or(c)),
+        '\x7F'        => s.push('\u{2421}'),
+        _             => s.extend(c.escape_debug()),
+    }
+}
+
+/// Removes all escape sequences except SGR (colour/effect) sequences, and all control
+/// characters except `\n` and `\t`, from `s`, so that it is safe to print.
+///
+/// Equivalent to [`Sanitize::Strip.apply(s)`](Sanitize::apply()). To sanitize all output
+/// written to a `Writer`, see [`set_sanitize()`](crate::io::AnsiWrite::set_sanitize()).
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::text::sanitize;
+///
+/// assert_eq!(sanitize("\x1B[1mok\x1B[22m\x1B[1A\x1B[2K\x08"), "\x1B[1mok\x1B[22m");
+/// assert_eq!(sanitize("\x1BP+q544e\x1B\\plain"),                "plain");
+/// ```
+pub fn sanitize(s: &str) -> Cow<'_, str> { Sanitize::Strip.apply(s) }
+
+/// Replaces all escape sequences and control characters except `\n` and `\t` in `s`
+/// with visible symbols, e.g. `"\x1B[1;31m"` becomes `"␛[1;31m"`.
+///
+/// Equivalent to [`Sanitize::Reveal.apply(s)`](Sanitize::apply()). To reveal all output
+/// written to a `Writer`, see [`set_sanitize()`](crate::io::AnsiWrite::set_sanitize()),
+/// and to reveal a single value, see [`Reveal`].
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, text::reveal, Colour::Red};
+///
+/// assert_eq!(reveal(&styled_format!(Red, "red")), "␛[31mred␛[39m");
+/// assert_eq!(reveal("bell\x07\r\n"),               "bell␇␍\n");
+/// ```
+pub fn reveal(s: &str) -> Cow<'_, str> { Sanitize::Reveal.apply(s) }
+
+/// A [`Display`](fmt::Display) adapter that [reveals](reveal()) the escape sequences
+/// and control characters written by its target, e.g. `"␛[1;31m"`, so that they can be
+/// inspected, rather than interpreted by the terminal.
+///
+/// Since the revealed output has no escape sequences, it is styled by any parent style
+/// as plain text.
+///
+/// *Note:* the target is formatted into a temporary `String`, and its formatting
+/// options (e.g. width) are ignored.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, text::Reveal, Colour::Red, Effect::Bold};
+///
+/// let bold   = styled!(Bold, "bold");
+/// let styled = styled!(Red, &bold);
+///
+/// assert_eq!(Reveal::new(&styled).to_string(), "␛[31m␛[1mbold␛[22m␛[39m");
+/// paintln!("Writes: {}", Reveal::new(&styled));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Reveal<T: fmt::Display>(T);
+
+impl<T: fmt::Display> Reveal<T> {
+    /// Creates an instance that wraps the given target.
+    #[inline]
+    pub const fn new(target: T) -> Self { Self(target) }
+    /// Gets the target.
+    #[inline]
+    pub const fn target(&self) -> &T { &self.0 }
+}
+
+impl<T: fmt::Display> fmt::Display for Reveal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&reveal(&self.0.to_string()))
+    }
+}
+
+/// The marker that replaces the lines removed by [`head()`] and [`tail()`].
+///
+/// The marker is written on a line of its own, in its own style, which is not
+/// affected by the style of the surrounding text.
+#[derive(Clone, Copy, Debug)]
+pub struct Elision {
+    ansi:  Ansi,
+    write: fn(usize, &mut fmt::Formatter<'_>) -> fmt::Result,
+}
+
+impl Elision {
+    /// The default marker, e.g. `"… 42 more lines"` in [`Faint`](Effect::Faint).
+    pub const DEFAULT: Elision = Elision::new(Effect::Faint.ansi(), |lines, f| {
+        write!(f, "… {} more line{}", lines, if lines == 1 { "" } else { "s" })
+    });
+
+    /// Creates a new instance with the given style, which writes the
+    /// marker given the number of lines removed.
+    #[inline]
+    pub const fn new(ansi: Ansi, write: fn(usize, &mut fmt::Formatter<'_>) -> fmt::Result) -> Self {
+        Self { ansi, write }
+    }
+
+    /// Gets the style.
+    #[inline]
+    pub const fn ansi(&self) -> Ansi { self.ansi }
+
+    /// Keeps only the first `lines` lines of `s`, replacing the rest with this marker.
+    ///
+    /// The style active at the end of the kept lines is reset, so that escape
+    /// sequences remain balanced. Returns `s` unchanged, i.e. without allocating,
+    /// if it has no more than `lines` lines.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, text::Elision};
+    ///
+    /// const MORE: Elision = Elision::new(Ansi::unspecified(), |n, f| write!(f, "[+{}]", n));
+    ///
+    /// let log = "\x1B[31mone\ntwo\nthree\x1B[39m\n";
+    /// assert_eq!(MORE.head(log, 1), "\x1B[31mone\x1B[39m\n[+2]\n");
+    /// ```
+    pub fn head(self, s: &str, lines: usize) -> Cow<'_, str> {
+        let ends = line_ends(s);
+        if ends.len() <= lines {
+            return Cow::Borrowed(s);
+        }
+        let cut = if lines == 0 { 0 } else { ends[lines - 1] };
+        // Keep the newline after the reset, so that the reset is on the same line
+        let kept = s[..cut].strip_suffix('\n').unwrap_or(&s[..cut]);
+        let active = style_at_end(kept);
+        let newline = if s.ends_with('\n') { "\n" } else { "" };
+        let sep = if lines == 0 { "" } else { "\n" };
+        Cow::Owned(format!("{}{}{}{}{}", kept, active.transition(Ansi::unspecified()), sep, self.marker(ends.len() - lines), newline))
+    }
+
+    /// Keeps only the last `lines` lines of `s`, replacing the rest with this marker.
+    ///
+    /// The style active at the start of the kept lines is restored, so that they
+    /// look the same as before. Returns `s` unchanged, i.e. without allocating,
+    /// if it has no more than `lines` lines.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use ansiconst::{*, text::Elision};
+    ///
+    /// const MORE: Elision = Elision::new(Ansi::unspecified(), |n, f| write!(f, "[+{}]", n));
+    ///
+    /// let log = "\x1B[31mone\ntwo\nthree\x1B[39m\n";
+    /// assert_eq!(MORE.tail(log, 1), "[+2]\n\x1B[31mthree\x1B[39m\n");
+    /// ```
+    pub fn tail(self, s: &str, lines: usize) -> Cow<'_, str> {
+        let ends = line_ends(s);
+        if ends.len() <= lines {
+            return Cow::Borrowed(s);
+        }
+        let marker = self.marker(ends.len() - lines);
+        if lines == 0 {
+            return Cow::Owned(format!("{}{}", marker, if s.ends_with('\n') { "\n" } else { "" }));
+        }
+        let start = ends[ends.len() - lines - 1];
+        let active = style_at_end(&s[..start]);
+        Cow::Owned(format!("{}\n{}{}", marker, Ansi::unspecified().transition(active), &s[start..]))
+    }
+
+    fn marker(self, lines: usize) -> Styled<ElisionText> {
+        Styled::new(self.ansi, ElisionText(lines, self.write))
+    }
+}
+
+impl Default for Elision {
+    fn default() -> Self { Self::DEFAULT }
+}
+
+struct ElisionText(usize, fn(usize, &mut fmt::Formatter<'_>) -> fmt::Result);
+
+impl fmt::Display for ElisionText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { (self.1)(self.0, f) }
+}
+
+/// Gets the byte offset of the end of each line of `s`, i.e. after its `\n`, if any.
+fn line_ends(s: &str) -> Vec<usize> {
+    let mut ends: Vec<usize> = s.match_indices('\n').map(|(i, _)| i + 1).collect();
+    if ends.last().map_or(0, |end| *end) < s.len() {
+        ends.push(s.len());
+    }
+    ends
+}
+
+/// Gets the style active at the end of `s`, according to its SGR sequences.
+pub(crate) fn style_at_end(s: &str) -> Ansi {
+    segments(s).fold(Ansi::unspecified(), |ansi, segment| match segment {
+        Segment::Escape(escape) => parse_sgr(escape).map_or(ansi, |sgr| sgr.apply(ansi)),
+        Segment::Text(_)        => ansi,
+    })
+}
+
+/// Keeps only the first `lines` lines of `s`, keeping escape sequences balanced and
+/// replacing the rest with the [default](Elision::DEFAULT) marker. See [`Elision::head()`].
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, text, Colour::Red};
+///
+/// let log = styled_format!(Red, "one\ntwo\nthree\nfour");
+///
+/// assert_eq!(text::head(&log, 2), "\x1B[31mone\ntwo\x1B[39m\n\x1B[2m… 2 more lines\x1B[22m");
+/// assert_eq!(text::tail(&log, 1), "\x1B[2m… 3 more lines\x1B[22m\n\x1B[31mfour\x1B[39m");
+/// ```
+pub fn head(s: &str, lines: usize) -> Cow<'_, str> { Elision::DEFAULT.head(s, lines) }
+
+/// Keeps only the last `lines` lines of `s`, keeping escape sequences balanced and
+/// replacing the rest with the [default](Elision::DEFAULT) marker. See [`Elision::tail()`].
+pub fn tail(s: &str, lines: usize) -> Cow<'_, str> { Elision::DEFAULT.tail(s, lines) }
+
+/// The changes made by an SGR escape sequence: the attributes it sets, and the
+/// attributes it resets (i.e. returns to their default).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) struct Sgr {
+    pub(crate) set:   Ansi,
+    pub(crate) reset: Attrs,
+}
+
+impl Sgr {
+    /// Applies these changes to `ansi`, such that reset attributes become unspecified.
+    pub(crate) const fn apply(&self, ansi: Ansi) -> Ansi {
+        ansi.filter(Attrs::all().difference(self.reset)).add(self.set)
+    }
+}
+
+/// Parses an escape sequence as an SGR sequence, e.g. `"\x1B[1;31m"`.
+///
+/// Parameters may have `:`-separated sub-parameters, e.g. `"\x1B[4:3m"` (curly underline),
+/// which is any underline other than `4:0`, i.e. no underline, and `"\x1B[38:2::255:0:0m"`.
+///
+/// Returns `None` if `escape` is not an SGR sequence. Unrecognized parameters are ignored.
+pub(crate) fn parse_sgr(escape: &str) -> Option<Sgr> {
+    parse_sgr_params(escape.strip_prefix("\x1B[")?.strip_suffix('m')?)
+}
+
+/// Parses the parameters of an SGR sequence, i.e. the part between `"\x1B["` and `"m"`,
+/// as for [`parse_sgr()`].
+pub(crate) fn parse_sgr_params(params: &str) -> Option<Sgr> {
+    if !params.bytes().all(|b| b.is_ascii_digit() || b == b';' || b == b':') {
+        return None;
+    }
+    let mut sgr = Sgr { set: Ansi::unspecified(), reset: Attrs::empty() };
+    let mut params = params.split(';');
+    while let Some(param) = params.next() {
+        let (set, reset) = if param.contains(':') {
+            match sub_params(param) {
+                Some(sub) => sub,
+                None      => continue,
+            }
+        } else {
+            let param = param_num(param);
+            match param {
+                0         => (Ansi::unspecified(), Attrs::all()),
+                1..=9     => match sgr_effect(param) {
+                    Some(effect) => (effect.ansi(), Attrs::empty()),
+                    None         => continue,
+                },
+                22        => (Ansi::unspecified(), Attrs::Bold.union(Attrs::Faint)),
+                23..=29   => match sgr_effect(param - 20) {
+                    Some(effect) => (Ansi::unspecified(), effect.ansi().attrs()),
+                    None         => continue,
+                },
+                30..=37   => (COLOURS[param as usize - 30].fg(), Attrs::empty()),
+                40..=47   => (COLOURS[param as usize - 40].bg(), Attrs::empty()),
+                90..=97   => (COLOURS[param as usize - 82].fg(), Attrs::empty()),
+                100..=107 => (COLOURS[param as usize - 92].bg(), Attrs::empty()),
+                38        => match extended_colour(&mut params.by_ref().map(param_num)) {
+                    Some(colour) => (colour.fg(), Attrs::empty()),
+                    None         => continue,
+                },
+                48        => match extended_colour(&mut params.by_ref().map(param_num)) {
+                    Some(colour) => (colour.bg(), Attrs::empty()),
+                    None         => continue,
+                },
+                39        => (Ansi::unspecified(), Attrs::Foreground),
+                49        => (Ansi::unspecified(), Attrs::Background),
+                _         => continue,
+            }
+        };
+        // Later parameters take precedence over earlier ones
+        sgr.set   = sgr.set.filter(Attrs::all().difference(reset).difference(set.attrs())).add(set);
+        sgr.reset = sgr.reset.difference(set.attrs()).union(reset);
+    }
+    Some(sgr)
+}
+
+/// The 16 named colours, in order of their SGR codes.
+pub(crate) const COLOURS: [Colour; 16] = [
+    Colour::Black, Colour::Red, Colour::Green, Colour::Yellow,
+    Colour::Blue, Colour::Purple, Colour::Cyan, Colour::White,
+    Colour::BrightBlack, Colour::BrightRed, Colour::BrightGreen, Colour::BrightYellow,
+    Colour::BrightBlue, Colour::BrightPurple, Colour::BrightCyan, Colour::BrightWhite,
+];
+
+fn sgr_effect(param: u8) -> Option<Effect> {
+    match param {
+        1 => Some(Effect::Bold),
+        2 => Some(Effect::Faint),
+        3 => Some(Effect::Italic),
+        4 => Some(Effect::Underline),
+        5 => Some(Effect::Blink),
+        7 => Some(Effect::Reverse),
+        8 => Some(Effect::Hidden),
+        9 => Some(Effect::Strike),
+        _ => None,
+    }
+}
+
+/// Parses an SGR parameter, where an empty parameter is `0`, and an invalid one is ignored.
+fn param_num(param: &str) -> u8 {
+    if param.is_empty() { 0 } else { param.parse().unwrap_or(u8::MAX) }
+}
+
+/// Parses a parameter with `:`-separated sub-parameters, i.e. an underline style, e.g.
+/// `4:3`, or an extended colour, e.g. `38:5:n`, `38:2:r:g:b` or `38:2:id:r:g:b`, where
+/// `id` is an (ignored) colour space id.
+fn sub_params(param: &str) -> Option<(Ansi, Attrs)> {
+    let mut subs = param.split(':').map(param_num);
+    let (code, first) = (subs.next()?, subs.next()?);
+    match (code, first) {
+        (4, 0) => Some((Ansi::unspecified(), Attrs::Underline)),
+        (4, _) => Some((Effect::Underline.ansi(), Attrs::empty())),
+        (38 | 48, _) => {
+            let rest: Vec<u8> = subs.collect();
+            let rest = match (first, rest.as_slice()) {
+                (2, [_, r, g, b]) => vec![*r, *g, *b],
+                _                 => rest,
+            };
+            let colour = extended_colour(&mut [first].into_iter().chain(rest))?;
+            Some((if code == 38 { colour.fg() } else { colour.bg() }, Attrs::empty()))
+        },
+        _ => None,
+    }
+}
+
+/// Parses the parameters following `38` or `48`, i.e. `5;n` or `2;r;g;b`.
+///
+/// Returns `None` for colours that are not supported by the enabled features.
+#[allow(unused_variables)]
+fn extended_colour(params: &mut impl Iterator<Item = u8>) -> Option<Colour> {
+    match params.next()? {
+        5 => {
+            let num = params.next()?;
+            #[cfg(feature="ansi256")]
+            return Some(Colour::Ansi256(num));
+            #[cfg(not(feature="ansi256"))]
+            return COLOURS.get(num as usize).copied();
+        },
+        2 => {
+            let (r, g, b) = (params.next()?, params.next()?, params.next()?);
+            #[cfg(feature="rgb")]
+            return Some(Colour::Rgb(r, g, b));
+            #[cfg(not(feature="rgb"))]
+            return None;
+        },
+        _ => None,
+    }
+}
+
+/// A [`Display`](fmt::Display) adapter for values that write their own ANSI styles,
+/// e.g. values styled by other crates, so that they participate in this crate's nesting.
+///
+/// When formatted, the SGR escape sequences written by the target are converted into
+/// [`Styled`] text, and so are combined with any parent style. In particular:
+///
+/// - Attributes [protected](Ansi::protect_attrs()) by a parent are not overridden, e.g.
+///   no ANSI codes are written at all inside [`Ansi::no_ansi()`].
+/// - Resets written by the target (e.g. `"\x1B[0m"`) restore the parent style, instead
+///   of clearing it.
+///
+/// Escape sequences other than SGR sequences (e.g. hyperlinks) are written unchanged.
+///
+/// *Note:* the target is formatted into a temporary `String`, and its formatting
+/// options (e.g. width) are ignored.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, text::Foreign, Colour::Red};
+///
+/// // E.g. styled by another crate, which resets all attributes at the end
+/// let other = "\x1B[1mBold\x1B[0m";
+///
+/// assert_eq!(
+///     styled_format!(Red, "Red {} Red", Foreign::new(other)),
+///     "\x1B[31mRed \x1B[1mBold\x1B[22m Red\x1B[39m",
+/// );
+/// assert_eq!(
+///     styled_format!(Ansi::no_ansi(), "Plain {} Plain", Foreign::new(other)),
+///     "Plain Bold Plain",
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Foreign<T: fmt::Display>(T);
+
+impl<T: fmt::Display> Foreign<T> {
+    /// Creates an instance that wraps the given target.
+    #[inline]
+    pub const fn new(target: T) -> Self { Self(target) }
+    /// Gets the target.
+    #[inline]
+    pub const fn target(&self) -> &T { &self.0 }
+}
+
+impl<T: fmt::Display> fmt::Display for Foreign<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rendered = self.0.to_string();
+        let mut ansi = Ansi::unspecified();
+        for segment in segments(&rendered) {
+            match segment {
+                Segment::Text(text)     => write!(f, "{}", Styled::new(ansi, text))?,
+                Segment::Escape(escape) => match parse_sgr(escape) {
+                    Some(sgr) => ansi = sgr.apply(ansi),
+                    None      => f.write_str(escape)?,
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A [`fmt::Write`] adapter that tracks the style that is active in the text written
+/// through it, according to its SGR sequences, e.g. for templating engines that insert
+/// their own content into styled output and then resume its style.
+///
+/// All text is written to the inner `Writer` unchanged, including all escape sequences.
+/// An SGR sequence that is split across several writes takes effect once complete.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, text::StyleTracker, Colour::{Blue, Red}};
+/// use std::fmt::Write;
+///
+/// let mut tracker = StyleTracker::new(String::new());
+/// write!(tracker, "{}", styled!(Red, "Red")).unwrap();
+/// assert_eq!(tracker.active(), Ansi::unspecified());
+///
+/// // E.g. a template that was rendered in parts, ending mid-style
+/// write!(tracker, "{}Blue ", Blue.ansi()).unwrap();
+/// assert_eq!(tracker.active(), ansi!(Blue));
+///
+/// // Insert unstyled content, then resume the active style
+/// let active = tracker.active();
+/// write!(tracker, "{}inserted{}", active.not(), active).unwrap();
+/// write!(tracker, "Blue{}", active.not()).unwrap();
+///
+/// assert_eq!(
+///     tracker.into_inner(),
+///     "\x1B[31mRed\x1B[39m\x1B[34mBlue \x1B[39minserted\x1B[34mBlue\x1B[39m",
+/// );
+/// ```
+#[derive(Debug)]
+pub struct StyleTracker<W: fmt::Write> {
+    inner:   W,
+    active:  Ansi,
+    pending: String,
+}
+
+impl<W: fmt::Write> StyleTracker<W> {
+    /// Creates a new instance that writes to the given `Writer`, with no active style.
+    #[inline]
+    pub fn new(inner: W) -> Self { Self { inner, active: Ansi::unspecified(), pending: String::new() } }
+    /// Gets the style that is active at the end of the text written so far, i.e. the
+    /// combined effect of all its SGR sequences.
+    #[inline]
+    pub fn active(&self) -> Ansi { self.active }
+    /// Gets a reference to the inner `Writer`.
+    #[inline]
+    pub fn get_ref(&self) -> &W { &self.inner }
+    /// Unwraps this `StyleTracker`, returning the inner `Writer`.
+    #[inline]
+    pub fn into_inner(self) -> W { self.inner }
+}
+
+impl<W: fmt::Write> fmt::Write for StyleTracker<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_str(s)?;
+        if self.pending.is_empty() && !s.contains(ESC) {
+            return Ok(());
+        }
+        let joined;
+        let mut rest = s;
+        if !self.pending.is_empty() {
+            self.pending.push_str(s);
+            joined = std::mem::take(&mut self.pending);
+            rest = &joined;
+        }
+        while let Some(start) = rest.find(ESC) {
+            rest = &rest[start..];
+            match escape_end(rest.as_bytes()) {
+                Some(len) => {
+                    if let Some(sgr) = parse_sgr(&rest[..len]) {
+                        self.active = sgr.apply(self.active);
+                    }
+                    rest = &rest[len..];
+                },
+                None      => {
+                    self.pending.push_str(rest);
+                    break;
+                },
+            }
+        }
+        Ok(())
+    }
+}
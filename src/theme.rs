@@ -0,0 +1,212 @@
+//! Define a theme of semantic styles, e.g. for headings and errors, that can be swapped
+//! at runtime without touching the code that uses them.
+//!
+//! Each [`Slot`] of a theme is a semantic style, which can be used anywhere an [`Ansi`]
+//! can, e.g. `paintln!(Slot::Heading, "Title")`. Its style is looked up in the process-wide
+//! theme each time it is used, so [`set_theme()`] changes the style of all output written
+//! afterwards. Until a theme is set, the [`DEFAULT`] theme applies.
+//!
+//! The slots are [`NamedStyle`]s, e.g. `"ansiconst::heading"`, so the process-wide theme is
+//! kept in the [`registry`](mod@crate::registry), and individual slots may also be re-themed
+//! with [`registry::set_style()`](crate::registry::set_style()). The [`Success`](Slot::Success),
+//! [`Warning`](Slot::Warning) and [`Error`](Slot::Error) slots are the styles of the
+//! [`message`](mod@crate::message) labels, e.g. of [`paint_error!`](crate::paint_error).
+//!
+//! An application offering several themes, e.g. `"dark"` and `"light"`, can let the user
+//! choose one at startup with [`set_theme_from_env()`], e.g. using a `MYAPP_THEME=light`
+//! env variable, falling back to the theme named in the application's config, if any,
+//! and then to the [`DEFAULT`] theme.
+//!
+//! ### Examples
+//!
+//! ```
+//! use ansiconst::{*, theme::{self, Slot, Theme}, Colour::{Blue, Purple}, Effect::Bold};
+//!
+//! fn heading(title: &str) -> String {
+//!     styled_format!(Slot::Heading, "{}", title)
+//! }
+//!
+//! assert_eq!(heading("Title"), "\x1B[1;4mTitle\x1B[22;24m");
+//!
+//! const MY_THEME: Theme = theme::DEFAULT
+//!     .with(Slot::Heading, ansi!(Purple, Bold))
+//!     .with(Slot::Error,   ansi!(Blue));
+//!
+//! theme::set_theme(&MY_THEME);
+//! assert_eq!(heading("Title"), "\x1B[1;35mTitle\x1B[22;39m");
+//! assert_eq!(theme::current_theme(), MY_THEME);
+//!
+//! theme::reset_theme();
+//! assert_eq!(heading("Title"), "\x1B[1;4mTitle\x1B[22;24m");
+//! ```
+
+use crate::{ansi, registry::{self, NamedStyle}, Ansi};
+use crate::Colour::{Cyan, Green, Red, Yellow};
+use crate::Effect::{Bold, Faint, Italic, Underline};
+use std::env;
+
+/// A semantic style of a [`Theme`].
+///
+/// See the [module-level documentation](crate::theme).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Slot {
+    /// The style of headings
+    Heading,
+    /// The style of subheadings
+    Subheading,
+    /// The style of emphasized text
+    Emphasis,
+    /// The style of secondary text, e.g. hints
+    Muted,
+    /// The style of informational messages
+    Info,
+    /// The style of success messages
+    Success,
+    /// The style of warning messages
+    Warning,
+    /// The style of error messages
+    Error,
+}
+
+impl Slot {
+    /// Gets all slots, in declaration order.
+    #[inline]
+    pub const fn all() -> [Slot; 8] {
+        [
+            Self::Heading, Self::Subheading, Self::Emphasis, Self::Muted,
+            Self::Info,    Self::Success,    Self::Warning,  Self::Error,
+        ]
+    }
+
+    /// Gets the [`NamedStyle`] of this slot, whose default style is that of the [`DEFAULT`] theme.
+    #[inline]
+    pub const fn style(&self) -> NamedStyle { STYLES[*self as usize] }
+
+    /// Gets the style of this slot in the process-wide theme. Used by the `styled_*!` macros.
+    #[inline]
+    pub fn ansi(&self) -> Ansi { self.style().ansi() }
+}
+
+/// A mapping from each [`Slot`] to a style.
+///
+/// With `feature=serde`, a theme is (de)serialized as a struct with a field per slot,
+/// e.g. `heading`, whose value is an [`Ansi::spec()`] string. Missing fields have the
+/// style of the [`DEFAULT`] theme, e.g. when loading a theme from a config file.
+///
+/// See the [module-level documentation](crate::theme).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Theme { styles: [Ansi; 8] }
+
+impl Theme {
+    /// Creates a new instance with the styles of the [`DEFAULT`] theme.
+    #[inline]
+    pub const fn new() -> Self { DEFAULT }
+
+    /// Gets the style of the given slot.
+    #[inline]
+    pub const fn get(&self, slot: Slot) -> Ansi { self.styles[slot as usize] }
+
+    /// Sets the style of the given slot.
+    #[inline]
+    pub const fn with(self, slot: Slot, ansi: Ansi) -> Self {
+        let mut styles = self.styles;
+        styles[slot as usize] = ansi;
+        Self { styles }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self { Self::new() }
+}
+
+/// The default theme.
+pub const DEFAULT: Theme = Theme {
+    styles: [
+        ansi!(Bold, Underline),
+        ansi!(Bold),
+        ansi!(Italic),
+        ansi!(Faint),
+        ansi!(Cyan),
+        ansi!(Green, Bold),
+        ansi!(Yellow, Bold),
+        ansi!(Red, Bold),
+    ],
+};
+
+/// The styles of the slots, e.g. for [`registry::register()`](crate::registry::register()).
+pub const STYLES: &[NamedStyle] = &[
+    NamedStyle::new("ansiconst::heading",    DEFAULT.styles[0]),
+    NamedStyle::new("ansiconst::subheading", DEFAULT.styles[1]),
+    NamedStyle::new("ansiconst::emphasis",   DEFAULT.styles[2]),
+    NamedStyle::new("ansiconst::muted",      DEFAULT.styles[3]),
+    NamedStyle::new("ansiconst::info",       DEFAULT.styles[4]),
+    NamedStyle::new("ansiconst::success",    DEFAULT.styles[5]),
+    NamedStyle::new("ansiconst::warning",    DEFAULT.styles[6]),
+    NamedStyle::new("ansiconst::error",      DEFAULT.styles[7]),
+];
+
+/// Sets the process-wide theme, i.e. the style of every [`Slot`].
+pub fn set_theme(theme: &Theme) {
+    for slot in Slot::all() {
+        registry::set_style(slot.style().name(), theme.get(slot));
+    }
+}
+
+/// Gets the process-wide theme.
+pub fn current_theme() -> Theme {
+    Theme { styles: Slot::all().map(|slot| slot.ansi()) }
+}
+
+/// Restores the process-wide theme to the [`DEFAULT`] theme.
+pub fn reset_theme() {
+    for slot in Slot::all() {
+        registry::unset_style(slot.style().name());
+    }
+}
+
+/// Finds the theme with the given name, ignoring ASCII case, amongst `themes`.
+pub fn find_theme(themes: &[(&str, Theme)], name: &str) -> Option<Theme> {
+    themes.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, theme)| *theme)
+}
+
+/// Selects a theme from `themes` by name, trying in turn:
+///
+/// 1. The value of the env variable `var`, e.g. `"MYAPP_THEME"`.
+/// 2. The name `config`, e.g. as read from the application's config file.
+/// 3. The [`DEFAULT`] theme.
+///
+/// Names are matched ignoring ASCII case, and a name that is unset, empty or not
+/// amongst `themes` falls back to the next.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, theme::{self, Slot, Theme}, Colour::{Black, White}, Effect::Bold};
+///
+/// const THEMES: &[(&str, Theme)] = &[
+///     ("dark",  theme::DEFAULT.with(Slot::Heading, ansi!(White, Bold))),
+///     ("light", theme::DEFAULT.with(Slot::Heading, ansi!(Black, Bold))),
+/// ];
+///
+/// std::env::set_var("MYAPP_THEME", "Light");
+/// assert_eq!(theme::select_theme(THEMES, "MYAPP_THEME", Some("dark")), THEMES[1].1);
+///
+/// std::env::set_var("MYAPP_THEME", "unknown");
+/// assert_eq!(theme::select_theme(THEMES, "MYAPP_THEME", Some("dark")), THEMES[0].1);
+/// assert_eq!(theme::select_theme(THEMES, "MYAPP_THEME", None),         theme::DEFAULT);
+/// ```
+pub fn select_theme(themes: &[(&str, Theme)], var: &str, config: Option<&str>) -> Theme {
+    let from_env = env::var(var).ok().and_then(|name| find_theme(themes, &name));
+    from_env
+        .or_else(|| config.and_then(|name| find_theme(themes, name)))
+        .unwrap_or(DEFAULT)
+}
+
+/// Sets the process-wide theme to the one chosen by [`select_theme()`], returning it.
+///
+/// Typically called once at startup, so that all [`Slot`]s are looked up in the chosen theme.
+pub fn set_theme_from_env(themes: &[(&str, Theme)], var: &str, config: Option<&str>) -> Theme {
+    let theme = select_theme(themes, var, config);
+    set_theme(&theme);
+    theme
+}
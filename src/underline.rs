@@ -0,0 +1,114 @@
+use crate::state::{STYLES, UNDERLINE};
+use crate::{Attrs, Effect};
+use std::fmt;
+
+/// The extended underline styles, i.e. SGR `4:1` to `4:5`, as supported by many modern
+/// terminals, e.g. curly underlines for highlighting errors.
+///
+/// Use [`wrap()`](Self::wrap()) to apply a style to a target. Unlike an [`Effect`], an
+/// `UnderlineStyle` is not part of an [`Ansi`](crate::Ansi), which keeps `Ansi` instances
+/// small. Instead, while the target is formatted, the *plain* [`Effect::Underline`] is
+/// active, so nested styles combine and transition as normal, and an enclosing underline
+/// style is restored after the target, and after any nested style that resets underlining.
+///
+/// Underline styles respect [protection](crate::Ansi::protect_attrs()): nothing is written
+/// while [`Attrs::Underline`] is protected, e.g. inside [`Ansi::no_ansi()`](crate::Ansi::no_ansi()).
+///
+/// Terminals that do not support underline styles usually show a plain underline.
+///
+/// ### Examples
+///
+/// ```
+/// use ansiconst::{*, Effect::{NotUnderline, Underline}, UnderlineStyle::{Curly, Double}};
+///
+/// assert_eq!(
+///     styled_format!(Underline, "a {} d", Curly.wrap(format_args!("b {} c", Double.wrap("x")))),
+///     "\x1B[4ma \x1B[4:3mb \x1B[4:2mx\x1B[4:3m c\x1B[4m d\x1B[24m",
+/// );
+/// assert_eq!(
+///     Curly.wrap(styled!(NotUnderline, "not")).to_string(),
+///     "\x1B[4:3m\x1B[24mnot\x1B[4m\x1B[4:3m\x1B[24m",
+/// );
+/// assert_eq!(
+///     styled_format!(Ansi::no_ansi(), "{}", Curly.wrap("plain")),
+///     "plain",
+/// );
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum UnderlineStyle {
+    /// Underline with SGR attribute code `4:1`
+    Single,
+    /// Underline with SGR attribute code `4:2`
+    Double,
+    /// Underline with SGR attribute code `4:3`
+    Curly,
+    /// Underline with SGR attribute code `4:4`
+    Dotted,
+    /// Underline with SGR attribute code `4:5`
+    Dashed,
+}
+
+impl UnderlineStyle {
+    /// Gets the SGR sub-parameter of this style, i.e. the `n` in `4:n`.
+    #[inline]
+    pub const fn code(&self) -> u8 {
+        match self {
+            Self::Single => 1,
+            Self::Double => 2,
+            Self::Curly  => 3,
+            Self::Dotted => 4,
+            Self::Dashed => 5,
+        }
+    }
+    /// Applies this style to the given target.
+    #[inline]
+    pub const fn wrap<T: fmt::Display>(&self, target: T) -> Underlined<T> { Underlined { style: *self, target } }
+}
+
+/// Associates a [`Display`](fmt::Display) *target* with an [`UnderlineStyle`].
+///
+/// Created by [`UnderlineStyle::wrap()`].
+#[derive(Clone, Copy, Debug)]
+pub struct Underlined<T: fmt::Display> {
+    style:  UnderlineStyle,
+    target: T,
+}
+
+impl<T: fmt::Display> Underlined<T> {
+    /// Gets the [`UnderlineStyle`].
+    #[inline]
+    pub const fn style(&self) -> UnderlineStyle { self.style }
+    /// Gets the target.
+    #[inline]
+    pub const fn target(&self) -> &T { &self.target }
+}
+
+impl<T: fmt::Display> fmt::Display for Underlined<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (old_ansi, old_out) = STYLES.get();
+        if old_out.protected_attrs().contains(Attrs::Underline) {
+            return self.target.fmt(f);
+        }
+        let parent = UNDERLINE.replace(Some(self.style));
+        STYLES.set((old_ansi.add(Effect::Underline.ansi()), old_out.add(Effect::Underline.ansi())));
+        let result = write!(f, "\x1B[4:{}m", self.style.code()).and_then(|_| self.target.fmt(f));
+        STYLES.set((old_ansi, old_out));
+        UNDERLINE.set(parent);
+        result?;
+        match parent {
+            Some(style) => write!(f, "\x1B[4:{}m", style.code()),
+            None if old_out.effect().has_effect(Effect::Underline) => f.write_str("\x1B[4m"),
+            None        => f.write_str("\x1B[24m"),
+        }
+    }
+}
+
+/// Writes the enclosing [`UnderlineStyle`], if any, after a transition between nested
+/// styles that (re)enabled the plain [`Effect::Underline`].
+#[inline]
+pub(crate) fn restore(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match UNDERLINE.get() {
+        Some(style) => write!(f, "\x1B[4:{}m", style.code()),
+        None        => Ok(()),
+    }
+}
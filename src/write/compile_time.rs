@@ -103,6 +103,8 @@ impl Buffer<[u8;25]> {
     }
 }
 
+// Note: the buffer fits the codes of any style, i.e. at most 18: 8 effects (each set or
+// reset, with 22 shared by bold and faint) and 2 RGB colours of 5 codes each.
 struct ConstWriter { buf: Buffer<[u8;25]> }
 
 impl ConstWriter {
@@ -103,7 +103,7 @@ pub(crate) struct Formatter<'a,'f> where 'f: 'a {
 impl<'a,'f> Formatter<'a,'f> where 'f: 'a {
     #[inline]
     pub(crate) fn fmt_ansi(f: &'a mut fmt::Formatter<'f>, ansi: Ansi) -> fmt::Result {
-        if !ansi.is_unspecified() {
+        if !ansi.renders_nothing() {
             let mut w = Self::new(f);
             write_ansi(&mut w, ansi)?;
             w.write_terminator()?;
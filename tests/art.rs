@@ -0,0 +1,64 @@
+mod common;
+use common::check_fmt;
+
+use ansiconst::{*, art::{decode_cp437, AnsiArt}, Colour::*};
+
+#[test]
+fn test_decode_cp437() {
+    check_fmt("░▒▓█ ☺é ⌂\x1B[31m\r\n", decode_cp437(b"\xB0\xB1\xB2\xDB \x01\x82 \x7F\x1B[31m\r\n"));
+}
+
+#[test]
+fn test_wrapping() {
+    // Rows wrap at the given number of columns, but a full row followed by a newline does not
+    let art = AnsiArt::from_text("abcdefg\r\nhij\r\nk", 3);
+    check_fmt("abc\ndef\ng\nhij\nk\n", art.to_string());
+    assert_eq!((art.width(), art.height()), (3, 5));
+}
+
+#[test]
+fn test_cursor() {
+    // Forward, up, back
+    check_fmt("ad b\ncd\n", AnsiArt::from_text("a\x1B[2Cb\r\nc\x1B[Ad\x1B[B\x1B[Dd", 80).to_string());
+    // Absolute position, save/restore
+    check_fmt("\n x\ny\n", AnsiArt::from_text("\x1B[s\x1B[2;2Hx\x1B[u\x1B[3Hy", 80).to_string());
+    // Clear screen, clear to end of line
+    check_fmt("b\n", AnsiArt::from_text("aaa\x1B[2J\x1B[1;1Hbcd\x1B[2D\x1B[K", 80).to_string());
+}
+
+#[test]
+fn test_styles() {
+    let art = AnsiArt::from_cp437(b"\x1B[1;31mA\x1B[44mB\x1B[0m   \x1B[42m \x1B[0m  ");
+    // Trailing blanks are trimmed, unless they have a background colour
+    check_fmt("\x1B[1;31mA\x1B[44mB\x1B[22;39;49m   \x1B[42m \x1B[49m\n", art.to_string());
+    // Nested inside parent styles
+    check_fmt("\x1B[33m\x1B[1;31mA\x1B[44mB\x1B[22;33;49m   \x1B[42m \x1B[49m\n\x1B[39m", styled_format!(Yellow, "{}", art));
+    check_fmt("AB    \n", styled_format!(Ansi::no_ansi(), "{}", art));
+    // Palette remapping
+    let mut palette = AnsiArt::DOS_PALETTE;
+    palette[4] = BrightBlue;
+    check_fmt("\x1B[1;31mA\x1B[104mB\x1B[22;39;49m   \x1B[42m \x1B[49m\n", art.palette(palette).to_string());
+}
+
+#[test]
+fn test_max_width() {
+    let art = AnsiArt::from_text("abcdef\nab\n\x1B[41m    ", 80).max_width(3);
+    check_fmt("abc\nab\n\x1B[41m   \x1B[49m\n", art.to_string());
+    assert_eq!(art.width(), 6);
+}
+
+#[test]
+fn test_sauce() {
+    let mut bytes = b"abcdef\x1A".to_vec();
+    let mut sauce = [0u8; 128];
+    sauce[..7].copy_from_slice(b"SAUCE00");
+    sauce[94] = 1;
+    sauce[95] = 1;
+    sauce[96] = 4;
+    bytes.extend_from_slice(&sauce);
+    check_fmt("abcd\nef\n", AnsiArt::from_cp437(&bytes).to_string());
+
+    // Without an EOF marker, the SAUCE record is still excluded
+    bytes.remove(6);
+    check_fmt("abcd\nef\n", AnsiArt::from_cp437(&bytes).to_string());
+}
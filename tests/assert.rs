@@ -0,0 +1,20 @@
+use ansiconst::{*, Colour::{Blue, Red}};
+
+#[test]
+fn test_assert_styled_eq() {
+    assert_styled_eq!("Red", "\x1B[31mRed\x1B[39m", styled!(Red, "Red"));
+    assert_styled_eq!(String::from("Red"), "\x1B[31mRed\x1B[39m", styled_format!(Red, "Red"));
+    assert_styled_eq!("Plain", "Plain", "Plain");
+}
+
+#[test]
+#[should_panic(expected = "styled output differs without ANSI codes")]
+fn test_assert_styled_eq_plain_differs() {
+    assert_styled_eq!("Red", "\x1B[31mRed\x1B[39m", styled!(Red, "Rod"));
+}
+
+#[test]
+#[should_panic(expected = "styled output differs in ANSI codes")]
+fn test_assert_styled_eq_ansi_differs() {
+    assert_styled_eq!("Red", "\x1B[31mRed\x1B[39m", styled!(Blue, "Red"));
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use ansiconst::{*, io::{AnsiWrite, AnsiWriter}, map, Colour::{Blue, Green, Red}, Effect::{Bold, Italic}};
+use common::{check_fmt, Sink};
+use std::io::Write;
+
+const FRAGMENTS: [(Ansi, &str); 4] = [
+    (ansi!(Red),         "a"),
+    (ansi!(Red, Bold),   "b"),
+    (Ansi::unspecified(), "c"),
+    (ansi!(Italic),      "d"),
+];
+
+#[test]
+fn test_batch() {
+    let batch = StyledBatch::new(&FRAGMENTS);
+    assert_eq!(batch.fragments().len(), 4);
+    check_fmt("\x1B[31ma\x1B[1mb\x1B[22;39mc\x1B[3md\x1B[23m", batch.to_string());
+    // Same text, fewer codes, than formatting each fragment in turn
+    let naive: String = FRAGMENTS.iter().map(|(ansi, text)| Styled::new(*ansi, *text).to_string()).collect();
+    assert_eq!(text::strip_ansi(&naive), text::strip_ansi(&batch.to_string()));
+    assert!(naive.len() > batch.to_string().len());
+}
+
+#[test]
+fn test_batch_nested() {
+    let batch = StyledBatch::new(&FRAGMENTS);
+    check_fmt(
+        "\x1B[34m<\x1B[31ma\x1B[1mb\x1B[22;34mc\x1B[3md\x1B[23m>\x1B[39m",
+        styled_format!(Blue, "<{}>", batch),
+    );
+    check_fmt(
+        "\x1B[32m<abcd>\x1B[39m",
+        styled_format!(Green.only(), "<{}>", batch),
+    );
+    check_fmt("<abcd>", styled_format!(Ansi::no_ansi(), "<{}>", batch));
+}
+
+#[test]
+fn test_batch_writer_options() {
+    let mut writer = AnsiWriter::new(Sink::default(), Ansi::unspecified());
+    writer.set_style_map(Some(map::bold_as_bright));
+    write!(writer, "{}", StyledBatch::new(&FRAGMENTS)).unwrap();
+    writer.set_style_map(None);
+    writer.set_style_budget(Some(10));
+    write!(writer, "|{}", StyledBatch::new(&FRAGMENTS)).unwrap();
+    assert_eq!(
+        writer.get_ref().output(),
+        "\x1B[31ma\x1B[91mb\x1B[39mc\x1B[3md\x1B[23m|\x1B[31ma\x1B[39mbcd",
+    );
+}
+
+#[test]
+fn test_spans() {
+    check_fmt("", StyledSpans::new("").to_string());
+    check_fmt("abc", StyledSpans::new("abc").to_string());
+    let spans = StyledSpans::new("héllo wörld")
+        .span(0..6, ansi!(Red))
+        .span(3..6, ansi!(Bold))
+        .span(6..7, ansi!(Red))
+        .span(8..13, ansi!(Italic));
+    assert_eq!(spans.text(), "héllo wörld");
+    assert_eq!(spans.spans().len(), 4);
+    // Overlapping spans combine, and adjacent spans with the same style need no codes
+    check_fmt("\x1B[31mhé\x1B[1mllo\x1B[22m \x1B[39mw\x1B[3mörld\x1B[23m", spans.to_string());
+    assert_eq!(spans.to_spans(), [
+        ("hé".to_string(), ansi!(Red)),
+        ("llo".to_string(), ansi!(Red, Bold)),
+        (" ".to_string(), ansi!(Red)),
+        ("w".to_string(), Ansi::unspecified()),
+        ("örld".to_string(), ansi!(Italic)),
+    ]);
+
+    // Later spans take precedence, and the parent's style applies outside any span
+    let mut spans = StyledSpans::new("abc");
+    spans.push(0..3, ansi!(Red));
+    spans.push(1..2, ansi!(Green));
+    check_fmt("\x1B[34m<\x1B[31ma\x1B[32mb\x1B[31mc\x1B[34m>\x1B[39m", styled_format!(Blue, "<{}>", spans));
+    check_fmt("<abc>", styled_format!(Ansi::no_ansi(), "<{}>", spans));
+}
+
+#[test]
+#[should_panic]
+fn test_spans_char_boundary() {
+    let _ = StyledSpans::new("é").span(0..1, ansi!(Red));
+}
+
+#[test]
+fn test_highlight() {
+    check_fmt("abc", text::highlight("abc", "", ansi!(Red)).to_string());
+    check_fmt("abc", text::highlight("abc", "x", ansi!(Red)).to_string());
+    // Matches do not overlap
+    let found = text::highlight("aaa", "aa", ansi!(Red));
+    assert_eq!(found.spans(), [(0..2, ansi!(Red))]);
+    check_fmt("\x1B[31maa\x1B[39ma", found.to_string());
+    // Overlapping highlights of different patterns combine
+    check_fmt(
+        "\x1B[34m<\x1B[31mgr\x1B[1mé\x1B[34my\x1B[22;39m \x1B[34m>\x1B[39m",
+        styled_format!(Blue, "<{}>", text::highlight("gréy ", "gré", ansi!(Red)).highlight("éy", ansi!(Bold)).highlight(" ", Colour::Reset.ansi())),
+    );
+}
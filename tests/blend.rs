@@ -0,0 +1,56 @@
+#![cfg(feature="rgb")]
+
+use ansiconst::{*, Colour::*, Effect::*};
+
+#[test]
+fn test_blend_colours() {
+    assert_eq!(Red.blend(Blue, BlendMode::Average),           Rgb(0x67, 0x00, 0x77));
+    assert_eq!(Ansi256(16).blend(White, BlendMode::Multiply), Rgb(0, 0, 0));
+    assert_eq!(BrightWhite.blend(Green, BlendMode::Multiply), Rgb(0x00, 0xcd, 0x00));
+    assert_eq!(Reset.blend(Green, BlendMode::Average),        Green);
+    assert_eq!(Green.blend(Reset, BlendMode::Average),        Reset);
+}
+
+#[test]
+fn test_blend_ansi() {
+    const BASE: Ansi = ansi!(Rgb(100, 100, 100), Italic);
+    assert_eq!(BASE.blend(ansi!(Rgb(200, 0, 100), Bold), BlendMode::Average), ansi!(Rgb(150, 50, 100), Italic, Bold));
+    // Nothing to blend with
+    assert_eq!(BASE.blend(ansi!(Blue.bg()), BlendMode::Average), BASE.add(Blue.bg()));
+    assert_eq!(ansi!(Bold).blend(BASE, BlendMode::Multiply),     ansi!(Bold).add(BASE));
+    assert_eq!(BASE.blend(ansi!(Reset), BlendMode::Average),     ansi!(Reset, Italic));
+    // Protected colours are neither replaced nor blended, unless by a higher priority
+    assert_eq!(BASE.protect().blend(ansi!(Black), BlendMode::Average), BASE.protect());
+    assert_eq!(BASE.protect().blend(ansi!(Black).priority(2), BlendMode::Average), BASE.protect().add(Rgb(50, 50, 50).ansi().priority(2)));
+}
+
+#[test]
+fn test_hsl_hsv() {
+    assert_eq!(Colour::hsl(0, 100, 50),    Rgb(255, 0, 0));
+    assert_eq!(Colour::hsl(210, 50, 40),   Rgb(51, 102, 153));
+    assert_eq!(Colour::hsl(359, 200, 200), Rgb(255, 255, 255));
+    assert_eq!(Colour::hsv(120, 100, 100), Rgb(0, 255, 0));
+    assert_eq!(Colour::hsv(300, 100, 0),   Rgb(0, 0, 0));
+
+    assert_eq!(Rgb(51, 102, 153).to_hsl(), Some((210, 50, 40)));
+    assert_eq!(Rgb(51, 102, 153).to_hsv(), Some((210, 67, 60)));
+    assert_eq!(Rgb(0, 0, 0).to_hsl(),      Some((0, 0, 0)));
+    assert_eq!(Rgb(0, 0, 0).to_hsv(),      Some((0, 0, 0)));
+    assert_eq!(Ansi256(196).to_hsv(),      Some((0, 100, 100)));
+    assert_eq!(Colour::Unspecified.to_hsv(), None);
+    assert_eq!(Rgb(51, 102, 153).get_hsl(), Rgb(51, 102, 153).to_hsl());
+    assert_eq!(Rgb(51, 102, 153).get_hsv(), Rgb(51, 102, 153).to_hsv());
+
+    // Converting to and from HSL/HSV changes each component by at most a little
+    let close = |a: Colour, b: Colour| {
+        let (a, b) = (a.to_rgb().unwrap(), b.to_rgb().unwrap());
+        a.0.abs_diff(b.0) <= 3 && a.1.abs_diff(b.1) <= 3 && a.2.abs_diff(b.2) <= 3
+    };
+    for num in 16..=255 {
+        let colour = Ansi256(num);
+        let (h, s, l) = colour.to_hsl().unwrap();
+        assert!(close(Colour::hsl(h, s, l), colour), "{colour:?}: {:?}", Colour::hsl(h, s, l));
+        let (h, s, v) = colour.to_hsv().unwrap();
+        assert!(close(Colour::hsv(h, s, v), colour), "{colour:?}: {:?}", Colour::hsv(h, s, v));
+    }
+}
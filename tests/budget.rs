@@ -0,0 +1,64 @@
+mod common;
+use common::{check_fmt, Sink};
+
+use ansiconst::{*, io::{AnsiWrite, AnsiWriter}, Colour::{Blue, Red}, Effect::{Bold, Italic}};
+use std::io::Write;
+
+fn budget_writer(budget: Option<usize>) -> AnsiWriter<Sink> {
+    let mut writer = AnsiWriter::new(Sink::default(), Ansi::unspecified());
+    writer.set_style_budget(budget);
+    writer
+}
+
+const MSG: Styled<&str> = styled!(Red, Bold, "Msg");
+
+#[test]
+fn test_within_budget() {
+    // "\x1B[1;31m" + "\x1B[22;39m" = 15 bytes
+    let mut writer = budget_writer(Some(15));
+    assert_eq!(writer.style_budget(), Some(15));
+    write!(writer, "{}", MSG).unwrap();
+    check_fmt("\x1B[1;31mMsg\x1B[22;39m", writer.get_ref().output());
+}
+
+#[test]
+fn test_effects_dropped_first() {
+    // "\x1B[31m" + "\x1B[39m" = 10 bytes
+    let mut writer = budget_writer(Some(14));
+    write!(writer, "{}", MSG).unwrap();
+    check_fmt("\x1B[31mMsg\x1B[39m", writer.get_ref().output());
+}
+
+#[test]
+fn test_colours_dropped_last() {
+    let mut writer = budget_writer(Some(9));
+    write!(writer, "{}", MSG).unwrap();
+    check_fmt("Msg", writer.get_ref().output());
+}
+
+#[test]
+fn test_budget_per_write() {
+    let mut writer = budget_writer(Some(10));
+    write!(writer, "{} {}", styled!(Red, "A"), styled!(Blue, "B")).unwrap();
+    write!(writer, " {}", styled!(Blue, "C")).unwrap();
+    check_fmt("\x1B[31mA\x1B[39m B \x1B[34mC\x1B[39m", writer.get_ref().output());
+}
+
+#[test]
+fn test_budget_nested() {
+    // Nested styles are degraded too: "\x1B[3m" + "\x1B[23m" = 9 bytes
+    let mut writer = budget_writer(Some(24));
+    write!(writer, "{}", styled_format_args!(Red, Bold, "A {} A", styled!(Italic, "B"))).unwrap();
+    check_fmt("\x1B[1;31mA \x1B[3mB\x1B[23m A\x1B[22;39m", writer.get_ref().output());
+
+    let mut writer = budget_writer(Some(25));
+    write!(writer, "{}", styled_format_args!(Red, Bold, "A {} A", styled!(Italic, Blue, "B"))).unwrap();
+    check_fmt("\x1B[1;31mA \x1B[34mB\x1B[31m A\x1B[22;39m", writer.get_ref().output());
+}
+
+#[test]
+fn test_no_budget() {
+    let mut writer = budget_writer(None);
+    write!(writer, "{}", MSG).unwrap();
+    check_fmt("\x1B[1;31mMsg\x1B[22;39m", writer.get_ref().output());
+}
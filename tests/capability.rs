@@ -0,0 +1,41 @@
+use ansiconst::io::{self, ColourSupport};
+
+use std::env;
+
+// Note: a single test, since the env variables are shared by all threads
+#[test]
+fn test_capability_report() {
+    for var in ["FORCE_COLOR", "NO_COLOR", "TERM", "COLORTERM", "COLUMNS"] {
+        env::remove_var(var);
+    }
+    assert_eq!(io::colour_support(), ColourSupport::Ansi16);
+    env::set_var("TERM", "dumb");
+    assert_eq!(io::colour_support(), ColourSupport::Monochrome);
+    env::set_var("TERM", "xterm-256color");
+    assert_eq!(io::colour_support(), ColourSupport::Ansi256);
+    env::set_var("COLORTERM", "truecolor");
+    assert_eq!(io::colour_support(), ColourSupport::Rgb);
+    assert!(ColourSupport::Monochrome < ColourSupport::Ansi16 && ColourSupport::Ansi256 < ColourSupport::Rgb);
+
+    env::remove_var("COLORTERM");
+    env::set_var("NO_COLOR", "1");
+    env::set_var("COLUMNS", "120");
+    let report = io::capability_report();
+    assert_eq!(report.env()[1], ("NO_COLOR", Some("1".to_string())));
+    assert_eq!(report.env()[3], ("COLORTERM", None));
+    assert_eq!(report.colour_support(), ColourSupport::Ansi256);
+    assert_eq!(report.terminal_width(), 120);
+    assert!(report.stdout().is_ansi_banned() && !report.stdout().is_ansi_preferred());
+
+    let text = report.to_string();
+    println!("{}", text);
+    assert!(text.starts_with("Environment:\n  FORCE_COLOR (unset)\n  NO_COLOR    = \"1\"\n  TERM        = \"xterm-256color\"\n"));
+    assert!(text.contains("\nColours:        256 colours (TERM contains 256color)\n"));
+    assert!(text.contains("\nTerminal width: 120\n"));
+    assert!(text.ends_with("\nstderr:         disabled (NO_COLOR is set)\n"));
+
+    env::set_var("FORCE_COLOR", "1");
+    let report = io::capability_report();
+    assert!(report.stdout().is_ansi_forced() && report.stdout().is_ansi_preferred());
+    assert!(report.to_string().contains("\nstdout:         enabled (FORCE_COLOR is set)"));
+}
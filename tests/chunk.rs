@@ -0,0 +1,48 @@
+mod common;
+
+use ansiconst::{*, io::{AnsiWriter, ChunkedWriter}, Colour::Red, Effect::Bold};
+use common::Sink;
+use std::io::{self, Write};
+
+/// Records each write, i.e. each chunk, separately.
+#[derive(Default)]
+struct Chunks(Vec<String>);
+
+impl Write for Chunks {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.push(String::from_utf8(buf.to_vec()).unwrap());
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+#[test]
+fn test_chunked_writer() {
+    let mut writer = ChunkedWriter::new(Chunks::default(), 6);
+    write!(writer, "ab{}cd", styled!(Red, Bold, "é")).unwrap();
+    // The last chunk is buffered until flushed
+    assert_eq!(writer.get_ref().0, ["ab", "\x1B[1;31m", "é", "\x1B[22;39m"]);
+    assert_eq!(writer.buffer(), b"cd");
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().0, ["ab", "\x1B[1;31m", "é", "\x1B[22;39m", "cd"]);
+    assert_eq!(writer.max_chunk(), 6);
+
+    // Incomplete escape sequences and chars are held back when flushed
+    let mut writer = ChunkedWriter::new(Chunks::default(), 4);
+    writer.write_all(b"abc\x1B[3").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().0, ["abc"]);
+    writer.write_all(b"1m\xC3").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().0, ["abc", "\x1B[31m"]);
+    writer.write_all(b"\xA9\x1B]0;").unwrap();
+    assert_eq!(writer.into_inner().unwrap().0.concat().as_bytes(), b"abc\x1B[31m\xC3\xA9\x1B]0;");
+}
+
+#[test]
+fn test_chunked_styled() {
+    let mut writer = AnsiWriter::default(ChunkedWriter::new(Sink::default(), 3));
+    writeln!(writer, "{}", styled!(Red, "Hello")).unwrap();
+    let sink = writer.into_inner().into_inner().unwrap();
+    assert_eq!(sink.output(), "\x1B[31mHello\x1B[39m\n");
+}
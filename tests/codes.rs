@@ -0,0 +1,77 @@
+mod common;
+use common::check_fmt;
+
+use ansiconst::{codes, Ansi, Colour, Effect};
+
+#[test]
+fn test_effect_codes() {
+    for code in codes::EFFECTS {
+        check_fmt(&format!("\x1B[{}m", code.set()),   code.effect().to_string());
+        check_fmt(&format!("\x1B[{}m", code.reset()), code.not_effect().to_string());
+        assert_eq!(Ansi::from_spec(code.name()), Ok(code.effect().ansi()));
+    }
+}
+
+#[test]
+fn test_colour_codes() {
+    for code in codes::COLOURS {
+        check_fmt(&format!("\x1B[{}m", code.fg()), code.colour().fg().to_string());
+        check_fmt(&format!("\x1B[{}m", code.bg()), code.colour().bg().to_string());
+        assert_eq!(Ansi::from_spec(&format!("fg={}", code.name())), Ok(code.colour().fg()));
+    }
+    check_fmt(&format!("\x1B[{}m", codes::FG_RESET), Colour::Reset.fg().to_string());
+    check_fmt(&format!("\x1B[{}m", codes::BG_RESET), Colour::Reset.bg().to_string());
+    check_fmt(&format!("\x1B[{}m", codes::RESET),    Ansi::reset().to_string());
+}
+
+#[test]
+#[cfg(feature="rgb")]
+fn test_extended_codes() {
+    use codes::{BG_EXTENDED, EXTENDED_ANSI256, EXTENDED_RGB, FG_EXTENDED};
+    check_fmt(&format!("\x1B[{};{};7m", FG_EXTENDED, EXTENDED_ANSI256), Colour::Ansi256(7).fg().to_string());
+    check_fmt(&format!("\x1B[{};{};1;2;3m", BG_EXTENDED, EXTENDED_RGB), Colour::Rgb(1, 2, 3).bg().to_string());
+}
+
+#[test]
+fn test_json() {
+    let json = codes::to_json();
+    assert!(json.starts_with(r#"{"reset":0,"fg_reset":39,"bg_reset":49,"fg_extended":38,"bg_extended":48,"#));
+    assert!(json.contains(r#""effects":[{"name":"bold","set":1,"reset":22},{"name":"faint","set":2,"reset":22},"#));
+    assert!(json.ends_with(r#"{"name":"bright_white","fg":97,"bg":107}]}"#));
+}
+
+#[test]
+fn test_names() {
+    let colours: Vec<Colour> = codes::COLOURS.iter().map(|code| code.colour()).collect();
+    assert_eq!(Colour::all16().as_slice(), colours);
+    for code in codes::COLOURS {
+        assert_eq!(code.colour().name(), Some(code.name()));
+    }
+    for code in codes::EFFECTS {
+        assert_eq!(code.effect().name(), Some(code.name()));
+    }
+    for effect in Effect::all() {
+        let name = effect.name().unwrap();
+        assert_eq!(Ansi::from_spec(name), Ok(effect.ansi()));
+    }
+    assert_eq!(Colour::Unspecified.name(), None);
+    assert_eq!(Effect::Unspecified.name(), None);
+}
+
+#[test]
+fn test_by_name() {
+    for colour in Colour::all16().into_iter().chain([Colour::Reset]) {
+        let name = colour.name().unwrap();
+        assert_eq!(Colour::by_name(name), Some(colour));
+        assert_eq!(Colour::by_name(&name.to_uppercase()), Some(colour));
+    }
+    for effect in Effect::all() {
+        let name = effect.name().unwrap();
+        assert_eq!(Effect::by_name(name), Some(effect));
+        assert_eq!(Effect::by_name(&name.to_uppercase()), Some(effect));
+    }
+    assert_eq!(Colour::by_name("pink"), None);
+    assert_eq!(Colour::by_name(""),     None);
+    assert_eq!(Effect::by_name("shiny"), None);
+    assert_eq!(Effect::by_name("bright_red"), None);
+}
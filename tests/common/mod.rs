@@ -33,3 +33,20 @@ impl<'a> TestLines<'a> {
         None
     }
 }
+
+/// An in-memory `Writer` that prefers ANSI styles, for testing `AnsiWrite` implementations.
+#[derive(Default)]
+pub struct Sink(pub Vec<u8>);
+
+impl Sink {
+    pub fn output(&self) -> &str { str::from_utf8(&self.0).unwrap() }
+}
+
+impl std::io::Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> { self.0.write(buf) }
+    fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+}
+
+impl ansiconst::io::AnsiPreference for Sink {
+    fn is_ansi_preferred(&self) -> bool { true }
+}
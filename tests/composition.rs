@@ -0,0 +1,40 @@
+mod common;
+use common::{check_fmt, Sink};
+
+use ansiconst::{*, io::{AnsiWrite, AnsiWriter, Composition}, Colour::{Blue, Red}, Effect::{Bold, Italic}};
+use std::io::Write;
+
+fn write_with(ansi: Ansi, composition: Composition) -> String {
+    let mut writer = AnsiWriter::new(Sink::default(), ansi);
+    writer.set_composition(composition);
+    assert_eq!(writer.composition(), composition);
+    write!(writer, "Plain {} {}", styled!(Bold, "bold"), styled_format_args!(Red, "red {}", styled!(Italic, "italic"))).unwrap();
+    writer.into_inner().output().to_string()
+}
+
+#[test]
+fn test_composition() {
+    assert_eq!(AnsiWriter::new(Sink::default(), Ansi::unspecified()).composition(), Composition::Outer);
+    check_fmt(
+        "\x1B[34mPlain \x1B[1mbold\x1B[22m \x1B[31mred \x1B[3mitalic\x1B[23m\x1B[34m\x1B[39m",
+        write_with(ansi!(Blue), Composition::Outer)
+    );
+    check_fmt(
+        "\x1B[34mPlain \x1B[1;39mbold\x1B[22;34m \x1B[31mred \x1B[3mitalic\x1B[23m\x1B[34m\x1B[39m",
+        write_with(ansi!(Blue), Composition::Fallback)
+    );
+    // Protected attributes of the default style are never changed
+    check_fmt(
+        "\x1B[34mPlain \x1B[1mbold\x1B[22m red \x1B[3mitalic\x1B[23m\x1B[39m",
+        write_with(Blue.protect(), Composition::Fallback)
+    );
+    check_fmt(
+        "Plain bold red italic",
+        write_with(Ansi::no_ansi(), Composition::Fallback)
+    );
+    // A fallback effect is removed from nested styles
+    check_fmt(
+        "\x1B[3mPlain \x1B[23;1mbold\x1B[22;3m \x1B[23;31mred \x1B[3mitalic\x1B[23m\x1B[3;39m\x1B[23m",
+        write_with(ansi!(Italic), Composition::Fallback)
+    );
+}
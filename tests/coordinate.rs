@@ -0,0 +1,14 @@
+use ansiconst::io::{self, ansierr, ansiout};
+
+#[test]
+fn test_coordinated() {
+    assert!(!io::is_coordinated());
+    io::set_coordinated(true);
+    assert!(io::is_coordinated());
+    // Not terminals, so no resets are written, but writes must still succeed
+    write!(ansiout(), "\x1B[31m").unwrap();
+    writeln!(ansierr(), "Not red").unwrap();
+    writeln!(ansiout(), "\x1B[39m").unwrap();
+    io::set_coordinated(false);
+    assert!(!io::is_coordinated());
+}
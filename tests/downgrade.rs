@@ -0,0 +1,101 @@
+use ansiconst::{*, io::ColourSupport, Colour::*, Effect::*};
+
+#[test]
+fn test_downgrade_named() {
+    const STYLE: Ansi = ansi!(Red, Blue.bg(), Bold).protect();
+    for level in [ColourSupport::Ansi16, ColourSupport::Ansi256, ColourSupport::Rgb] {
+        assert_eq!(STYLE.downgraded(level), STYLE);
+    }
+    assert_eq!(STYLE.downgraded(ColourSupport::Monochrome).attrs(), Attrs::Bold);
+    assert_eq!(STYLE.downgraded(ColourSupport::Monochrome).protected_attrs(), STYLE.protected_attrs());
+    assert_eq!(Reset.downgraded(ColourSupport::Monochrome), Colour::Unspecified);
+    assert_eq!(Reset.downgraded(ColourSupport::Ansi16), Reset);
+}
+
+#[cfg(feature="ansi256")]
+#[test]
+fn test_downgrade_ansi256() {
+    assert_eq!(Ansi256(9).downgraded(ColourSupport::Ansi16),   BrightRed);
+    assert_eq!(Ansi256(196).downgraded(ColourSupport::Ansi16), BrightRed);
+    assert_eq!(Ansi256(88).downgraded(ColourSupport::Ansi16),  Red);
+    assert_eq!(Ansi256(244).downgraded(ColourSupport::Ansi16), BrightBlack);
+    assert_eq!(Ansi256(244).downgraded(ColourSupport::Ansi256), Ansi256(244));
+}
+
+#[cfg(feature="rgb")]
+#[test]
+fn test_downgrade_rgb() {
+    const STYLE:    Ansi = ansi!(Rgb(205, 0, 0), Rgb(0, 0, 0).bg(), Underline);
+    const STYLE_16: Ansi = STYLE.downgraded(ColourSupport::Ansi16);
+    assert_eq!(STYLE_16, ansi!(Red, Black.bg(), Underline));
+    assert_eq!(STYLE.downgraded(ColourSupport::Ansi256), ansi!(Ansi256(160), Ansi256(16).bg(), Underline));
+    assert_eq!(STYLE.downgraded(ColourSupport::Rgb), STYLE);
+
+    assert_eq!(Rgb(255, 255, 255).downgraded(ColourSupport::Ansi256), Ansi256(231));
+    assert_eq!(Rgb(128, 128, 128).downgraded(ColourSupport::Ansi256), Ansi256(244));
+    assert_eq!(Rgb(95, 135, 175).downgraded(ColourSupport::Ansi256),  Ansi256(67));
+    assert_eq!(Rgb(250, 250, 250).downgraded(ColourSupport::Ansi16),  BrightWhite);
+}
+
+#[test]
+#[cfg(feature="rgb")]
+fn test_quantize() {
+    use ansiconst::Colour::{Ansi256, Blue, BrightGreen, Rgb};
+
+    // Perceptual distance: a difference in green counts more than one in blue
+    assert_eq!(Rgb(0, 95, 135).to_256(), Ansi256(24));
+    assert_eq!(Rgb(0, 255, 10).to_16(),  BrightGreen);
+    assert_eq!(Rgb(0, 0, 200).to_16(),   Blue);
+    for num in 16..=255 {
+        assert_eq!(Ansi256(num).to_rgb().map(|(r, g, b)| Rgb(r, g, b).to_256()), Some(Ansi256(num)));
+    }
+    assert_eq!(Blue.to_16(), Blue);
+}
+
+#[cfg(feature="ansi256")]
+#[test]
+fn test_cube_navigation() {
+    // Lighter and darker shades, within the cube and the greyscale ramp
+    assert_eq!(Ansi256(196).lighter(), Some(Ansi256(203)));
+    assert_eq!(Ansi256(203).darker(),  Some(Ansi256(160)));
+    assert_eq!(Ansi256(59).lighter(),  Some(Ansi256(102)));
+    assert_eq!(Ansi256(232).lighter(), Some(Ansi256(233)));
+    assert_eq!(Ansi256(255).darker(),  Some(Ansi256(254)));
+
+    // No further shades at the extremes, nor outside the cube and ramp
+    assert_eq!(Ansi256(231).lighter(), None);
+    assert_eq!(Ansi256(255).lighter(), None);
+    assert_eq!(Ansi256(16).darker(),   None);
+    assert_eq!(Ansi256(232).darker(),  None);
+    assert_eq!(Ansi256(9).lighter(),   None);
+    assert_eq!(Red.darker(),           None);
+    assert_eq!(Colour::Reset.lighter(), None);
+
+    // Lightening and darkening every cube colour stays within the cube
+    for num in 16..=231 {
+        for shade in [Ansi256(num).lighter(), Ansi256(num).darker()].into_iter().flatten() {
+            assert!(matches!(shade, Ansi256(16..=231)), "{num}: {shade:?}");
+        }
+    }
+
+    // Hues rotate around the ring, keeping the lightness and saturation
+    assert_eq!(Ansi256(196).shift_hue(1),  Some(Ansi256(202)));
+    assert_eq!(Ansi256(196).shift_hue(10), Some(Ansi256(46)));
+    assert_eq!(Ansi256(196).shift_hue(20), Some(Ansi256(21)));
+    assert_eq!(Ansi256(196).shift_hue(-1), Some(Ansi256(197)));
+    assert_eq!(Ansi256(138).shift_hue(3),  Some(Ansi256(109)));
+    assert_eq!(Ansi256(102).shift_hue(1),  None);
+    assert_eq!(Ansi256(240).shift_hue(1),  None);
+    assert_eq!(Red.shift_hue(1),           None);
+
+    // A full rotation, in either direction, returns to the same colour
+    for num in 16..=231 {
+        if let Some(Ansi256(shifted)) = Ansi256(num).shift_hue(1) {
+            assert_ne!(shifted, num);
+            let ring = (1..).find(|&steps| Ansi256(num).shift_hue(steps) == Some(Ansi256(num))).unwrap();
+            assert_eq!(ring % 6, 0, "{num}");
+            assert_eq!(Ansi256(num).shift_hue(-ring), Some(Ansi256(num)));
+            assert!(Ansi256(num).shift_hue(i32::MIN).is_some());
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#![cfg(feature="rgb")]
+
+mod common;
+
+use ansiconst::{*, io::{AnsiWrite, AnsiWriter}, map, Colour::{Red, Rgb}, DynamicColour::*};
+use common::Sink;
+use std::io::Write;
+
+#[test]
+fn test_dynamic_colours() {
+    assert_eq!([Foreground, Background, Cursor, Highlight, HighlightText].map(|dynamic| dynamic.code()), [10, 11, 12, 17, 19]);
+    assert_eq!(Highlight.attrs(), Attrs::Background);
+    assert_eq!(HighlightText.wrap(Red, "x").dynamic(), HighlightText);
+    assert_eq!(Highlight.wrap(Rgb(1, 2, 3), "x").to_string(), "\x1B]17;rgb:01/02/03\x1B\\x\x1B]117\x1B\\");
+    assert_eq!(Cursor.wrap(Colour::Unspecified, "x").to_string(), "x");
+    assert_eq!(Cursor.wrap(Colour::Reset, "x").to_string(), "\x1B]112\x1B\\x\x1B]112\x1B\\");
+}
+
+#[test]
+fn test_dynamic_nesting() {
+    // Enclosing colours of the same region are restored, and other regions are independent
+    assert_eq!(
+        Cursor.wrap(Rgb(1, 1, 1), format_args!("a{}b", Background.wrap(Rgb(2, 2, 2), Cursor.wrap(Colour::Reset, "c")))).to_string(),
+        "\x1B]12;rgb:01/01/01\x1B\\a\x1B]11;rgb:02/02/02\x1B\\\x1B]112\x1B\\c\x1B]12;rgb:01/01/01\x1B\\\x1B]111\x1B\\b\x1B]112\x1B\\",
+    );
+    // Styled text combines as normal
+    assert_eq!(
+        styled_format!(Red, "{}", Cursor.wrap(Rgb(0, 0, 0), styled!(Effect::Bold, "a"))),
+        "\x1B[31m\x1B]12;rgb:00/00/00\x1B\\\x1B[1ma\x1B[22m\x1B]112\x1B\\\x1B[39m",
+    );
+}
+
+#[test]
+fn test_dynamic_protected() {
+    assert_eq!(styled_format!(Ansi::no_ansi(), "{}", Cursor.wrap(Red, "a")), "a");
+    assert_eq!(styled_format!(Red.ansi().protect(), "{}", Cursor.wrap(Red, "a")), "\x1B[31ma\x1B[39m");
+    assert_eq!(
+        styled_format!(Red.ansi().protect(), "{}", Background.wrap(Rgb(1, 2, 3), "a")),
+        "\x1B[31m\x1B]11;rgb:01/02/03\x1B\\a\x1B]111\x1B\\\x1B[39m",
+    );
+}
+
+#[test]
+fn test_dynamic_writer() {
+    let mut writer = AnsiWriter::new(Sink::default(), Ansi::unspecified());
+    writer.set_style_map(Some(map::no_backgrounds));
+    write!(writer, "{}{}", Background.wrap(Red, "a"), Cursor.wrap(Red, "b")).unwrap();
+    writer.no_ansi();
+    write!(writer, "{}", Cursor.wrap(Red, "c")).unwrap();
+    assert_eq!(writer.get_ref().output(), "a\x1B]12;rgb:cd/00/00\x1B\\b\x1B]112\x1B\\c");
+}
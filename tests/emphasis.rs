@@ -0,0 +1,45 @@
+mod common;
+
+use ansiconst::{*, io::{AnsiWrite, AnsiWriter, Emphasis}, text::Sanitize, Colour::{Red, Yellow}};
+use common::Sink;
+use std::time::Duration;
+
+const ALERT: Emphasis = Emphasis::new(ansi!(Red), ansi!(Yellow), Duration::from_millis(10));
+
+#[test]
+fn test_style_at() {
+    assert_eq!(ALERT.style_at(Duration::ZERO),              ansi!(Red));
+    assert_eq!(ALERT.style_at(Duration::from_millis(15)),   ansi!(Yellow));
+    assert_eq!(ALERT.style_at(Duration::from_millis(20)),   ansi!(Red));
+    let never = Emphasis::new(ansi!(Red), ansi!(Yellow), Duration::ZERO);
+    assert_eq!(never.style_at(Duration::from_secs(1)),      ansi!(Red));
+}
+
+#[test]
+fn test_rewrite() {
+    let mut writer = AnsiWriter::new(Sink::default(), Ansi::unspecified());
+    // Control sequences are not sanitized
+    writer.set_sanitize(Some(Sanitize::Strip));
+    ALERT.rewrite(&mut writer, "Alert", Duration::ZERO).unwrap();
+    ALERT.rewrite(&mut writer, "Alert", Duration::from_millis(10)).unwrap();
+    assert_eq!(
+        writer.get_ref().output(),
+        "\r\x1B[31mAlert\x1B[39m\x1B[K\r\x1B[33mAlert\x1B[39m\x1B[K",
+    );
+}
+
+#[test]
+fn test_cycle() {
+    let mut writer = AnsiWriter::new(Sink::default(), Ansi::unspecified());
+    ALERT.cycle(&mut writer, "Alert", Duration::from_millis(25)).unwrap();
+    let output = writer.get_ref().output();
+    assert!(output.contains("\x1B[33mAlert"));
+    assert!(output.ends_with("\r\x1B[31mAlert\x1B[39m\x1B[K"));
+}
+
+#[test]
+fn test_cycle_no_ansi() {
+    let mut writer = AnsiWriter::new(Sink::default(), Ansi::no_ansi());
+    ALERT.cycle(&mut writer, "Alert", Duration::from_millis(25)).unwrap();
+    assert_eq!(writer.get_ref().output(), "Alert");
+}
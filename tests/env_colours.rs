@@ -0,0 +1,82 @@
+use ansiconst::*;
+use ansiconst::env_colours::{self, EnvColours};
+use ansiconst::theme::{self, Slot};
+use ansiconst::Colour::*;
+use ansiconst::Effect::*;
+
+#[test]
+fn test_parse() {
+    let colours = EnvColours::parse("di=01;34:ln=01;36::*.rs=00;33:ex=01;32;41:error=:di=4").unwrap();
+    assert_eq!(colours.len(), 5);
+    assert_eq!(colours.get("di"),    Some(ansi!(Underline)));
+    assert_eq!(colours.get("ln"),    Some(ansi!(Bold, Cyan)));
+    assert_eq!(colours.get("*.rs"),  Some(Ansi::reset().add(ansi!(Yellow))));
+    assert_eq!(colours.get("ex"),    Some(ansi!(Bold, Green, Red.bg())));
+    assert_eq!(colours.get("error"), Some(Ansi::unspecified()));
+    assert_eq!(colours.get("fi"),    None);
+
+    let keys: Vec<&str> = colours.iter().map(|(key, _)| key).collect();
+    assert_eq!(keys, ["di", "ln", "*.rs", "ex", "error"]);
+
+    assert!(EnvColours::parse("").unwrap().is_empty());
+    assert_eq!(EnvColours::parse("di=01;34:ln=target").unwrap_err().token(), "ln=target");
+    assert_eq!(EnvColours::parse("di").unwrap_err().token(),                  "di");
+    assert_eq!(EnvColours::parse("=01").unwrap_err().token(),                 "=01");
+}
+
+#[test]
+fn test_from_env() {
+    std::env::set_var("ANSICONST_TEST_COLORS", "error=01;31:ln=target:warning=1;35");
+    let colours = EnvColours::from_env("ANSICONST_TEST_COLORS");
+    assert_eq!(colours.len(), 2);
+    assert_eq!(colours.get("error"),   Some(ansi!(Bold, Red)));
+    assert_eq!(colours.get("warning"), Some(ansi!(Bold, Purple)));
+
+    assert!(EnvColours::from_env("ANSICONST_TEST_UNSET_COLORS").is_empty());
+}
+
+#[test]
+fn test_to_theme() {
+    let colours = EnvColours::parse("error=01;31:note=01;36:quote=2").unwrap();
+    let theme = colours.to_theme(theme::DEFAULT, env_colours::GCC_SLOTS);
+    assert_eq!(theme.get(Slot::Error),   ansi!(Bold, Red));
+    assert_eq!(theme.get(Slot::Info),    ansi!(Bold, Cyan));
+    assert_eq!(theme.get(Slot::Muted),   ansi!(Faint));
+    assert_eq!(theme.get(Slot::Warning), theme::DEFAULT.get(Slot::Warning));
+    assert_eq!(theme.get(Slot::Heading), theme::DEFAULT.get(Slot::Heading));
+}
+
+#[test]
+fn test_style_strings() {
+    let colours = EnvColours::parse("error=bold red:warning=yellow on_blue:info=01;36:muted=").unwrap();
+    assert_eq!(colours.get("error"),   Some(ansi!(Bold, Red)));
+    assert_eq!(colours.get("warning"), Some(ansi!(Yellow, Blue.bg())));
+    assert_eq!(colours.get("info"),    Some(ansi!(Bold, Cyan)));
+    assert_eq!(colours.get("muted"),   Some(Ansi::unspecified()));
+    assert_eq!(EnvColours::parse("error=bold pink").unwrap_err().token(), "error=bold pink");
+
+    let theme = colours.to_theme(theme::DEFAULT, env_colours::THEME_SLOTS);
+    assert_eq!(theme.get(Slot::Error),   ansi!(Bold, Red));
+    assert_eq!(theme.get(Slot::Warning), ansi!(Yellow, Blue.bg()));
+    assert_eq!(theme.get(Slot::Muted),   Ansi::unspecified());
+    assert_eq!(theme.get(Slot::Heading), theme::DEFAULT.get(Slot::Heading));
+}
+
+#[test]
+fn test_file_style() {
+    use env_colours::FileKind;
+
+    let colours = EnvColours::parse("no=2:fi=0:di=01;34:ln=01;36:ex=01;32:*.gz=31:*.tar.gz=01;31:*README=33").unwrap();
+    assert_eq!(colours.file_style("a.gz",     FileKind::Regular),    Some(ansi!(Red)));
+    assert_eq!(colours.file_style("a.TAR.GZ", FileKind::Regular),    Some(ansi!(Bold, Red)));
+    assert_eq!(colours.file_style("README",   FileKind::Regular),    Some(ansi!(Yellow)));
+    assert_eq!(colours.file_style("a.txt",    FileKind::Regular),    Some(Ansi::reset()));
+    assert_eq!(colours.file_style("run.gz",   FileKind::Executable), Some(ansi!(Bold, Green)));
+    assert_eq!(colours.file_style("src.gz",   FileKind::Directory),  Some(ansi!(Bold, Blue)));
+    assert_eq!(colours.file_style("pipe",     FileKind::Fifo),       Some(ansi!(Faint)));
+    assert_eq!(EnvColours::new().file_style("a.gz", FileKind::Regular), None);
+
+    let dir = std::env::temp_dir();
+    assert_eq!(FileKind::from_metadata(&std::fs::symlink_metadata(&dir).unwrap()), FileKind::Directory);
+    assert_eq!(FileKind::Socket.key(), "so");
+}
@@ -0,0 +1,15 @@
+use ansiconst::{*, Colour::*, Effect::*};
+
+#[test]
+fn test_escalate() {
+    assert_eq!(Ansi::unspecified().escalated(1), ansi!(Bold));
+    assert_eq!(Ansi::unspecified().escalated(3), ansi!(BrightRed, Bold, Underline));
+    assert_eq!(ansi!(Blue, Italic).escalated(3), ansi!(BrightBlue, Bold, Italic, Underline));
+    assert_eq!(ansi!(BrightBlue).escalated(3),   ansi!(BrightRed, Bold, Underline));
+    assert_eq!(ansi!(Blue).escalated(4),         ansi!(BrightRed, Bold, Underline));
+    assert_eq!(ansi!(Blue).escalated(u32::MAX),  ansi!(Blue).escalated(4));
+
+    // Protected attributes are not changed
+    const PROTECTED: Ansi = ansi!(Green.protect(), NotBold.ansi().protect());
+    assert_eq!(PROTECTED.escalated(4), PROTECTED.add(Underline.ansi()));
+}
@@ -0,0 +1,53 @@
+use ansiconst::*;
+use ansiconst::Colour::*;
+use ansiconst::Effect::*;
+
+#[test]
+fn test_explain() {
+    let explanation = ansi!(Red, Bold, Underline).explain(ansi!(Blue, Italic).only());
+    assert_eq!(explanation.result(),     ansi!(Blue, Italic).only());
+    assert_eq!(explanation.inherited(),  Attrs::empty());
+    assert_eq!(explanation.overridden(), Attrs::Foreground);
+    assert_eq!(explanation.added(),      Attrs::Italic);
+    assert_eq!(explanation.blocked(),    Attrs::empty());
+    assert_eq!(explanation.suppressed(), Attrs::Bold | Attrs::Underline);
+
+    let explanation = ansi!(Red, Bold).only().explain(ansi!(Blue, Italic).only());
+    assert_eq!(explanation.result(),     ansi!(Red, Bold).only());
+    assert_eq!(explanation.inherited(),  Attrs::Bold);
+    assert_eq!(explanation.blocked(),    Attrs::Foreground | Attrs::Italic);
+    assert_eq!(explanation.suppressed(), Attrs::empty());
+
+    let explanation = ansi!(Bold).explain(ansi!(NotBold, Faint));
+    assert_eq!(explanation.overridden(), Attrs::Bold);
+    assert_eq!(explanation.added(),      Attrs::Faint);
+    assert_eq!(
+        explanation.to_string(),
+        "bold: overridden (parent: bold, child: not_bold, result: )\nfaint: added (child: faint)\n"
+    );
+
+    assert_eq!(Ansi::unspecified().explain(Ansi::unspecified()).to_string(), "");
+}
+
+#[test]
+fn test_explain_priority() {
+    let explanation = ansi!(Blue).protect().explain(ansi!(Red).priority(2));
+    assert_eq!(explanation.result(),     ansi!(Red).priority(2));
+    assert_eq!(explanation.overridden(), Attrs::Foreground);
+    assert_eq!(explanation.blocked(),    Attrs::empty());
+    assert_eq!(
+        explanation.to_string(),
+        "fg: overridden (parent: fg=blue,!fg, child: fg=red,!fg=2, result: fg=red,!fg=2)\n"
+    );
+
+    let explanation = ansi!(Blue).priority(3).explain(ansi!(Red).priority(2));
+    assert_eq!(explanation.result(),     ansi!(Blue).priority(3));
+    assert_eq!(explanation.inherited(),  Attrs::empty());
+    assert_eq!(explanation.blocked(),    Attrs::Foreground);
+
+    // The child protects bold without setting it, at a higher priority than the parent
+    let explanation = ansi!(Bold, Red).protect().explain(Ansi::unspecified().priority_attrs(Attrs::Bold, 2));
+    assert_eq!(explanation.result().attrs(), Attrs::Foreground);
+    assert_eq!(explanation.suppressed(),     Attrs::Bold);
+    assert_eq!(explanation.inherited(),      Attrs::Foreground);
+}
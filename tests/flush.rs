@@ -0,0 +1,34 @@
+use ansiconst::{*, io::{AnsiPreference, AnsiWrite, AnsiWriter, FlushPolicy}, Colour::Red};
+use std::io::{self, Write};
+
+/// Records the length of its output each time it is flushed.
+#[derive(Default)]
+struct FlushSink { output: Vec<u8>, flushed_at: Vec<usize> }
+
+impl Write for FlushSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.output.write(buf) }
+    fn flush(&mut self) -> io::Result<()> { self.flushed_at.push(self.output.len()); Ok(()) }
+}
+
+impl AnsiPreference for FlushSink {
+    fn is_ansi_preferred(&self) -> bool { true }
+}
+
+fn flushed_at(policy: FlushPolicy) -> Vec<usize> {
+    let mut writer = AnsiWriter::new(FlushSink::default(), Ansi::unspecified());
+    writer.set_flush_policy(policy);
+    assert_eq!(writer.flush_policy(), policy);
+    write!(writer, "{}", styled!(Red, "ab")).unwrap();
+    writeln!(writer, "c").unwrap();
+    write!(writer, "d\ne").unwrap();
+    writer.write_all(b"f\n").unwrap();
+    writer.into_inner().flushed_at
+}
+
+#[test]
+fn test_flush_policy() {
+    assert_eq!(flushed_at(FlushPolicy::default()), []);
+    assert_eq!(flushed_at(FlushPolicy::Manual),    []);
+    assert_eq!(flushed_at(FlushPolicy::Line),      [14, 17]);
+    assert_eq!(flushed_at(FlushPolicy::Always),    [12, 14, 17]);
+}
@@ -0,0 +1,34 @@
+use ansiconst::{*, io::{AnsiFmtWriter, AnsiWrite, LinePrefix}, map, Colour::{Blue, Red}, Effect::Bold};
+
+#[test]
+fn test_fmt_writer() {
+    use std::fmt::Write;
+    let mut writer = AnsiFmtWriter::new(String::new(), Ansi::unspecified());
+    write!(writer, "{}", styled!(Red, "a")).unwrap();
+    // Unaffected by the default style
+    writer.write_str("\x1B[1mb\x1B[22m").unwrap();
+    writer.set_ansi(ansi!(Blue.only()));
+    write!(writer, "{}", styled!(Red, Bold, "c")).unwrap();
+    writer.set_ansi(Ansi::unspecified());
+    writer.set_style_map(Some(map::bold_as_bright));
+    write!(writer, "{}", styled!(Red, Bold, "d")).unwrap();
+    assert_eq!(
+        writer.get_ref(),
+        "\x1B[31ma\x1B[39m\x1B[1mb\x1B[22m\x1B[34mc\x1B[39m\x1B[91md\x1B[39m",
+    );
+    writer.get_mut().clear();
+    writer.auto_ansi();
+    assert!(writer.ansi().is_no_ansi() || std::env::var_os("FORCE_COLOR").is_some());
+}
+
+#[test]
+fn test_fmt_writer_io() {
+    use std::io::Write;
+    let mut writer = AnsiFmtWriter::new(String::new(), Ansi::no_ansi());
+    writer.set_line_prefix(Some(LinePrefix::new(ansi!(Blue), |f| f.write_str("> "))));
+    writeln!(writer, "{}", styled!(Red, "a")).unwrap();
+    write!(writer, "{}", styled!(Red, "b")).unwrap();
+    assert_eq!(writer.write(b"c").unwrap(), 1);
+    assert!(writer.write(b"\xFF").is_err());
+    assert_eq!(writer.into_inner(), "> a\n> bc");
+}
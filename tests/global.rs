@@ -0,0 +1,90 @@
+#![cfg(feature="global-writer")]
+
+use ansiconst::{*, io::{self, AnsiWrite, FlushPolicy, LinePrefix}, Colour::{Blue, Red}};
+use std::io::Write as _;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CAPTURED: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+static FLUSHES:  AtomicUsize    = AtomicUsize::new(0);
+
+struct Capture;
+
+impl std::io::Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        CAPTURED.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        FLUSHES.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_global_writer() {
+    assert!(!io::has_global_writer());
+    assert!(io::set_global_writer(Box::new(Capture)).is_ok());
+    assert!(io::set_global_writer(Box::new(Capture)).is_err());
+    assert!(io::has_global_writer());
+    io::ansiout().all_ansi();
+
+    let n = 2;
+    paint!(Red, "styled ");
+    paint!("unstyled {n} ");
+    paintln!("{}", n => Red);
+    paintln!();
+    try_paintln!(Red, "try").unwrap();
+    paint_success!("done");
+    io::ansiout().write_all(b"raw\n").unwrap();
+    io::ansiout().flush().unwrap();
+
+    // Output written to a lock is buffered until flushed or dropped
+    let len = CAPTURED.lock().unwrap().len();
+    let mut out = io::ansiout().lock();
+    styled_writeln!(out, Red, "locked").unwrap();
+    out.write_all(b"raw\n").unwrap();
+    assert_eq!(CAPTURED.lock().unwrap().len(), len);
+    out.flush().unwrap();
+    assert_eq!(&CAPTURED.lock().unwrap()[len..], b"\x1B[31mlocked\x1B[39m\nraw\n");
+    writeln!(out, "dropped").unwrap();
+    drop(out);
+
+    let captured = String::from_utf8(CAPTURED.lock().unwrap().clone()).unwrap();
+    assert!(captured.starts_with("\x1B[31mstyled \x1B[39munstyled 2 \x1B[31m2\x1B[39m\n\n\x1B[31mtry\x1B[39m\n"), "{:?}", captured);
+    assert!(captured.ends_with("done\nraw\n\x1B[31mlocked\x1B[39m\nraw\ndropped\n"), "{:?}", captured);
+
+    // Unstyled output is written via ansiout() too, e.g. with its line prefix
+    let len = CAPTURED.lock().unwrap().len();
+    io::ansiout().set_line_prefix(Some(LinePrefix::new(ansi!(Blue), |f| f.write_str("[app] "))));
+    paintln!("Started");
+    paint!("{n}\n");
+    io::ansiout().set_line_prefix(None);
+    assert_eq!(&CAPTURED.lock().unwrap()[len..], b"\x1B[34m[app] \x1B[39mStarted\n\x1B[34m[app] \x1B[39m2\n");
+
+    // Unstyled output is flushed according to the flush policy
+    let flushes = FLUSHES.load(Ordering::Relaxed);
+    paint!("unflushed");
+    assert_eq!(FLUSHES.load(Ordering::Relaxed), flushes);
+    io::ansiout().set_flush_policy(FlushPolicy::Always);
+    paint!("flushed");
+    assert_eq!(FLUSHES.load(Ordering::Relaxed), flushes + 1);
+    io::ansiout().set_flush_policy(FlushPolicy::Line);
+    paint!("unflushed");
+    paintln!();
+    assert_eq!(FLUSHES.load(Ordering::Relaxed), flushes + 2);
+    io::ansiout().set_flush_policy(FlushPolicy::Manual);
+
+    // Plain output bypasses the defaults, but still records the state of the line
+    let len = CAPTURED.lock().unwrap().len();
+    io::ansiout().set_ansi(Ansi::unspecified());
+    paint!("partial");
+    io::ansiout().set_line_prefix(Some(LinePrefix::new(ansi!(Blue), |f| f.write_str("[app] "))));
+    paintln!(" line");
+    paintln!("next");
+    io::ansiout().set_line_prefix(None);
+    paintln!("plain");
+    paint!("\x1B[31mraw\x1B[39m\n");
+    assert_eq!(&CAPTURED.lock().unwrap()[len..], b"partial line\n\x1B[34m[app] \x1B[39mnext\nplain\n\x1B[31mraw\x1B[39m\n");
+    io::ansiout().all_ansi();
+}
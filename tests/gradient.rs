@@ -0,0 +1,42 @@
+#![cfg(feature="rgb")]
+
+use ansiconst::{*, gradient::Gradient, Colour::*, Effect::*};
+
+#[test]
+fn test_gradient() {
+    const STOPS: &[Colour] = &[Rgb(0, 0, 0), Rgb(100, 200, 50)];
+
+    assert_eq!(Gradient::new(STOPS, "").to_string(), "");
+    assert_eq!(Gradient::new(STOPS, "é").to_string(), "\x1B[38;2;0;0;0mé\x1B[39m");
+    assert_eq!(
+        Gradient::new(STOPS, "abc").to_string(),
+        "\x1B[38;2;0;0;0ma\x1B[38;2;50;100;25mb\x1B[38;2;100;200;50mc\x1B[39m"
+    );
+    // Decreasing components, and named colours as stops
+    assert_eq!(Gradient::new(&[BrightWhite, Black], "ab").to_string(), "\x1B[38;2;255;255;255ma\x1B[38;2;0;0;0mb\x1B[39m");
+    // Stops without RGB components are ignored
+    assert_eq!(Gradient::new(&[Colour::Unspecified, Rgb(1, 2, 3), Colour::Reset], "ab").to_string(), "\x1B[38;2;1;2;3mab\x1B[39m");
+    assert_eq!(Gradient::new(&[Colour::Unspecified], "ab").to_string(), "ab");
+}
+
+#[test]
+fn test_gradient_stops() {
+    let gradient = Gradient::new(&[Rgb(0, 0, 0), Rgb(255, 0, 0), Rgb(255, 255, 0), Rgb(0, 0, 0)], "");
+    let colours: Vec<Colour> = (0..7).map(|i| gradient.colour_at(i, 7)).collect();
+    assert_eq!(colours, [
+        Rgb(0, 0, 0), Rgb(128, 0, 0), Rgb(255, 0, 0), Rgb(255, 128, 0),
+        Rgb(255, 255, 0), Rgb(128, 128, 0), Rgb(0, 0, 0),
+    ]);
+    assert_eq!(gradient.colour_at(99, 7), Rgb(0, 0, 0));
+}
+
+#[test]
+fn test_gradient_nested() {
+    let gradient = Gradient::new(&[Rgb(255, 0, 0), Rgb(0, 0, 255)], "ab");
+    assert_eq!(
+        styled_format!(Bold, "<{}>", gradient),
+        "\x1B[1m<\x1B[38;2;255;0;0ma\x1B[38;2;0;0;255mb\x1B[39m>\x1B[22m"
+    );
+    // A protected parent foreground overrides the gradient
+    assert_eq!(styled_format!(Green.protect(), "{}", gradient), "\x1B[32mab\x1B[39m");
+}
@@ -0,0 +1,106 @@
+#![cfg(any(feature="crossterm", feature="ratatui", feature="termion", feature="web"))]
+
+#[allow(unused_imports)]
+use ansiconst::{*, Colour::*, Effect::*};
+
+#[cfg(feature="crossterm")]
+#[test]
+fn test_crossterm() {
+    use crossterm::style::{Attribute, Attributes, Color, ContentStyle};
+
+    assert_eq!(Color::from(Red),                 Color::DarkRed);
+    assert_eq!(Color::from(BrightRed),           Color::Red);
+    assert_eq!(Color::from(Colour::Unspecified), Color::Reset);
+    assert_eq!(Colour::Unspecified.to_crossterm(), None);
+    assert_eq!(Colour::from(Color::Grey),        White);
+    #[cfg(feature="ansi256")]
+    assert_eq!(Colour::from(Color::AnsiValue(9)), Ansi256(9));
+    #[cfg(not(feature="ansi256"))]
+    assert_eq!(Colour::from(Color::AnsiValue(9)), BrightRed);
+
+    assert_eq!(Attributes::from(Bold),                Attribute::Bold.into());
+    assert_eq!(Attributes::from(Effect::Unspecified), Attributes::none());
+    assert_eq!(Effect::from(Attribute::Framed),       Effect::Unspecified);
+
+    const STYLE: ContentStyle = ansi!(Blue.bg(), Italic, Faint.not()).to_crossterm();
+    assert_eq!(STYLE.foreground_color, None);
+    assert_eq!(STYLE.background_color, Some(Color::DarkBlue));
+    assert!(STYLE.attributes.has(Attribute::Italic));
+    assert!(STYLE.attributes.has(Attribute::NormalIntensity));
+    assert_eq!(Ansi::from(STYLE), ansi!(Blue.bg(), Italic, Bold.not()));
+
+    for colour in codes::COLOURS.iter().map(codes::ColourCode::colour) {
+        assert_eq!(Colour::from(Color::from(colour)), colour);
+    }
+    for effect in codes::EFFECTS.iter().map(codes::EffectCode::effect) {
+        assert_eq!(Ansi::from(ContentStyle::from(effect.ansi())), effect.ansi());
+    }
+}
+
+#[cfg(feature="ratatui")]
+#[test]
+fn test_ratatui() {
+    use ratatui::style::{Color, Modifier, Style};
+
+    assert_eq!(Color::from(Red),                 Color::Red);
+    assert_eq!(Color::from(BrightRed),           Color::LightRed);
+    assert_eq!(Color::from(White),               Color::Gray);
+    assert_eq!(Colour::Unspecified.to_ratatui(), None);
+    assert_eq!(Colour::from(Color::DarkGray),    BrightBlack);
+    #[cfg(feature="ansi256")]
+    assert_eq!(Colour::from(Color::Indexed(9)),  Ansi256(9));
+    #[cfg(not(feature="ansi256"))]
+    assert_eq!(Colour::from(Color::Indexed(9)),  BrightRed);
+
+    const STYLE: Style = ansi!(Cyan, Green.bg(), Underline, Bold.not()).to_ratatui();
+    assert_eq!(STYLE, Style::new().fg(Color::Cyan).bg(Color::Green)
+        .add_modifier(Modifier::UNDERLINED).remove_modifier(Modifier::BOLD));
+    assert_eq!(Ansi::from(STYLE), ansi!(Cyan, Green.bg(), Underline, Bold.not()));
+    assert_eq!(Ansi::from(Style::new().add_modifier(Modifier::RAPID_BLINK)), Blink.ansi());
+
+    for colour in codes::COLOURS.iter().map(codes::ColourCode::colour) {
+        assert_eq!(Colour::from(Color::from(colour)), colour);
+    }
+    for effect in codes::EFFECTS.iter().map(codes::EffectCode::effect) {
+        assert_eq!(Ansi::from(Style::from(effect.ansi())), effect.ansi());
+        assert_eq!(Ansi::from(Style::from(effect.not().ansi())), effect.not().ansi());
+    }
+}
+
+#[cfg(feature="termion")]
+#[test]
+fn test_termion() {
+    use termion::{color, style};
+
+    assert_eq!(color::Fg(Red).to_string(),                "\x1B[31m");
+    assert_eq!(color::Bg(Colour::Reset).to_string(),      "\x1B[49m");
+    assert_eq!(color::Fg(Colour::Unspecified).to_string(), "");
+    assert_eq!(Colour::from(color::Magenta),              Purple);
+    assert_eq!(Colour::from(color::LightBlack),           BrightBlack);
+    assert_eq!(Effect::from(style::Invert),               Reverse);
+    assert_eq!(Effect::from(style::NoCrossedOut),         Strike.not());
+}
+
+#[cfg(feature="web")]
+#[test]
+fn test_web() {
+    assert_eq!(Red.to_css().as_deref(),                 Some("#cd0000"));
+    assert_eq!(BrightBlue.to_css().as_deref(),          Some("#5c5cff"));
+    assert_eq!(Colour::Reset.to_css(),                  None);
+    #[cfg(feature="ansi256")]
+    assert_eq!(Ansi256(196).to_css().as_deref(),        Some("#ff0000"));
+    #[cfg(feature="ansi256")]
+    assert_eq!(Ansi256(244).to_css().as_deref(),        Some("#808080"));
+    #[cfg(feature="rgb")]
+    assert_eq!(Rgb(1, 2, 255).to_css().as_deref(),      Some("#0102ff"));
+
+    assert_eq!(ansi!(Faint, Italic, Strike, Underline).to_css(),
+        "font-weight: lighter; font-style: italic; text-decoration: underline line-through");
+    assert_eq!(ansi!(Red, Reverse).to_css(), "background-color: #cd0000");
+    assert_eq!(ansi!(Red, Hidden).to_css(),  "color: transparent");
+    assert_eq!(ansi!(Bold.not()).to_css(),   "");
+
+    let (format, css) = text::console_args("plain \x1B[32mgreen\x1B]0;title\x07\x1B[39m plain\x1B[1m");
+    assert_eq!(format, "plain %cgreen%c plain");
+    assert_eq!(css, ["color: #00cd00", ""]);
+}
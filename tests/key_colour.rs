@@ -0,0 +1,19 @@
+use ansiconst::{*, key_colour::{self, KeyColours}, Colour::{Black, Blue, BrightBlack, BrightWhite, Green, Red, White}};
+
+#[test]
+fn test_key_colour() {
+    // The hash is stable, so these never change
+    const COLOURS: KeyColours = KeyColours::new(&[ansi!(Red), ansi!(Green), ansi!(Blue)]);
+    let got: Vec<Ansi> = ["a", "b", "c", "main", ""].iter().map(|key| COLOURS.get(key)).collect();
+    assert_eq!(got, [ansi!(Green), ansi!(Green), ansi!(Red), ansi!(Blue), ansi!(Blue)]);
+
+    assert_eq!(KeyColours::new(&[]).get("a"), Ansi::unspecified());
+    assert_eq!(KeyColours::default(), key_colour::DEFAULT);
+
+    for low_contrast in [Black, White, BrightBlack, BrightWhite, Blue] {
+        assert!(!key_colour::DEFAULT.colours().contains(&low_contrast.ansi()));
+    }
+    let distinct = (0..100).map(|n| key_colour::get(&format!("thread-{}", n)))
+        .fold(Vec::new(), |mut seen, ansi| { if !seen.contains(&ansi) { seen.push(ansi) }; seen });
+    assert_eq!(distinct.len(), key_colour::DEFAULT.colours().len());
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use ansiconst::{*, io::{AnsiWrite, AnsiWriter}, map, Colour::{Blue, Red, BrightRed}, Effect::Bold};
+use common::Sink;
+use std::io::Write;
+
+#[test]
+fn test_last_style() {
+    let mut writer = AnsiWriter::new(Sink::default(), Ansi::unspecified());
+    assert_eq!(writer.last_style(), Ansi::unspecified());
+
+    write!(writer, "{}", styled!(Red, "a")).unwrap();
+    assert_eq!(writer.last_style(), ansi!(Red));
+
+    // Unstyled writes leave the last style unchanged
+    writeln!(writer, "b").unwrap();
+    assert_eq!(writer.last_style(), ansi!(Red));
+
+    // The emitted style, i.e. after any style map
+    writer.set_style_map(Some(map::bold_as_bright));
+    write!(writer, "{}", styled!(Red, Bold, "c")).unwrap();
+    assert_eq!(writer.last_style(), ansi!(BrightRed));
+
+    // The style of the last styled text
+    writer.set_style_map(None);
+    write!(writer, "{}", styled_format_args!(Blue, "d{}", styled!(Bold, "e"))).unwrap();
+    assert_eq!(writer.last_style(), ansi!(Blue, Bold));
+}
+
+#[test]
+fn test_last_style_no_ansi() {
+    let mut writer = AnsiWriter::new(Sink::default(), Ansi::no_ansi());
+    write!(writer, "{}", styled!(Red, "a")).unwrap();
+    assert_eq!(writer.last_style(), Ansi::unspecified());
+}
@@ -0,0 +1,71 @@
+mod common;
+use common::check_fmt;
+
+use ansiconst::{*, layout::Columns, Colour::Red};
+
+const ITEMS: [&str; 7] = ["a", "bb", "ccc", "dddd", "e", "ff", "g"];
+
+#[test]
+fn test_columns() {
+    // All in one row
+    check_fmt("a  bb  ccc  dddd  e  ff  g\n", Columns::new(&ITEMS).width(80).to_string());
+    // Column-major order, columns sized by their widest item
+    check_fmt("a     e\nbb    ff\nccc   g\ndddd\n", Columns::new(&ITEMS).width(10).to_string());
+    check_fmt("a  ccc  e  g\nbb dddd ff\n", Columns::new(&ITEMS).width(12).gap(1).to_string());
+    // One per row, including items wider than the width
+    check_fmt("a\nbb\nccc\ndddd\ne\nff\ng\n", Columns::new(&ITEMS).width(3).to_string());
+    check_fmt("", Columns::<&str>::new(&[]).to_string());
+}
+
+#[test]
+fn test_columns_styled() {
+    let items = [styled!(Red, "red"), styled!(Ansi::unspecified(), "x")];
+    check_fmt("\x1B[31mred\x1B[39m\nx\n", Columns::new(&items).width(4).to_string());
+    check_fmt("\x1B[31mred\x1B[39m  x\n", Columns::new(&items).width(6).to_string());
+    // Unbalanced styles are reset
+    check_fmt("\x1B[1ma\x1B[22m  b\n", Columns::new(&["\x1B[1ma", "b"]).width(10).to_string());
+}
+
+#[test]
+fn test_repeat() {
+    check_fmt("\x1B[2m───\x1B[22m", styled!(Effect::Faint, "─").repeat(3).to_string());
+    check_fmt("\x1B[31m1212\x1B[39m", layout::styled_repeat(ansi!(Colour::Red), 12, 2).to_string());
+    check_fmt("\x1B[31m\x1B[39m", styled!(Colour::Red, "─").repeat(0).to_string());
+    check_fmt(
+        "\x1B[1m[\x1B[32m===\x1B[39m]\x1B[22m",
+        styled_format!(Effect::Bold, "[{}]", styled!(Colour::Green, "=").repeat(3))
+    );
+    assert_eq!(layout::Repeat::new("ab", 2).to_string(), "abab");
+}
+
+#[test]
+fn test_rule_and_section() {
+    check_fmt("\x1B[2m────\x1B[22m", layout::hr(ansi!(Effect::Faint)).width(4).to_string());
+    check_fmt("\x1B[31m====\x1B[39m", layout::hr(ansi!(Red)).width(4).pattern('=').to_string());
+    check_fmt("\x1B[31m── Title ──\x1B[39m", layout::section("Title", ansi!(Red)).width(11).to_string());
+    check_fmt("\x1B[31m= Title =\x1B[39m", layout::section("Title", ansi!(Red)).width(9).pattern('=').centred().to_string());
+    check_fmt("\x1B[31m─ Title ──\x1B[39m", layout::section("Title", ansi!(Red)).width(10).centred().to_string());
+    // Too wide for any rule characters
+    check_fmt("\x1B[31mTitle\x1B[39m", layout::section("Title", ansi!(Red)).width(6).to_string());
+    check_fmt(
+        "\x1B[31m── \x1B[1mTitle\x1B[22m ──\x1B[39m",
+        layout::section(styled!(Effect::Bold, "Title"), ansi!(Red)).width(11).to_string()
+    );
+}
+
+#[test]
+fn test_aligned() {
+    let red = styled!(Red, "red");
+    check_fmt("\x1B[31mred\x1B[39m  ", layout::left(&red, 5).to_string());
+    check_fmt(" \x1B[31mred\x1B[39m ", layout::centre(&red, 5).to_string());
+    check_fmt("  \x1B[31mred\x1B[39m", layout::right(&red, 5).to_string());
+    check_fmt("-\x1B[31mred\x1B[39m--", layout::centre(&red, 6).fill('-').to_string());
+    // Too wide for any padding
+    check_fmt("\x1B[31mred\x1B[39m", layout::right(&red, 2).to_string());
+    // Nested in other styles, with padding outside of the target's style
+    check_fmt(
+        "\x1B[1m|\x1B[31mred\x1B[39m  |\x1B[22m",
+        styled_format!(Effect::Bold, "|{}|", layout::left(&red, 5))
+    );
+    assert_eq!(layout::right("x", 3).alignment(), layout::Alignment::Right);
+}
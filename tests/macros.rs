@@ -42,3 +42,106 @@ fn test_macros() {
         format!("Plain {} Plain", styled_format_args!(Effect::Faint, "Faint {} Faint", styled_format_args!(Effect::Bold, "Bold")))
     );
 }
+
+#[test]
+#[cfg(feature="rgb")]
+fn test_rgb() {
+    const BRAND: Colour = rgb!("#FF8800");
+    const SHORT: Colour = rgb!("#f80");
+    assert_eq!(BRAND, Colour::Rgb(255, 136, 0));
+    assert_eq!(SHORT, BRAND);
+    assert_eq!(macros::rgb!("#000000"), Colour::Rgb(0, 0, 0));
+    check_fmt(
+        "\x1B[48;2;255;136;0mBrand\x1B[49m",
+        styled_format!(BRAND.bg(), "Brand")
+    );
+    assert_eq!(Colour::from_hex("#ff88"), None);
+    assert_eq!(Colour::from_hex("#ff88zz"), None);
+    assert_eq!(Colour::from_hex("#ff8800 "), None);
+
+    const HEX: Colour = Colour::hex(0xff8800);
+    assert_eq!(HEX, BRAND);
+    assert_eq!(Colour::hex(0x000000), Colour::Rgb(0, 0, 0));
+    assert_eq!(Colour::hex(0x12abcdef), Colour::Rgb(0xab, 0xcd, 0xef));
+
+    // The largest possible style fits ansi_code!'s buffer
+    use Effect::*;
+    const LARGEST: &str = ansi_code!(Bold, Faint, Italic, Underline, Blink, Reverse, Hidden, Strike,
+        Colour::Rgb(255, 255, 255), Colour::Rgb(255, 255, 255).bg());
+    assert_eq!(LARGEST, "\x1B[1;2;3;4;5;7;8;9;38;2;255;255;255;48;2;255;255;255m");
+    const RESETS: &str = ansi_code!(NotBold, NotFaint, NotItalic, NotUnderline, NotBlink, NotReverse, NotHidden, NotStrike);
+    assert_eq!(RESETS, "\x1B[22;23;24;25;27;28;29m");
+}
+
+mod theme {
+    use ansiconst::{*, Colour::{Green, Red}, Effect::{Bold, Underline}};
+
+    palette! {
+        heading: Green + Bold + Underline,
+        error:   Red.bg() + Effect::Bold.only()
+    }
+}
+
+#[test]
+fn test_palette() {
+    use theme::Palette;
+
+    const PALETTE: Palette = Palette::DEFAULT;
+    assert_eq!(PALETTE.heading, ansi!(Colour::Green, Effect::Bold, Effect::Underline));
+    assert_eq!(PALETTE.error, ansi!(Colour::Red.bg(), Effect::Bold.only()));
+    assert_eq!(Palette::default(), PALETTE);
+    assert_eq!(PALETTE.styles().map(|(name, _)| name), ["heading", "error"]);
+    check_fmt(
+        "\x1B[1;4;32mHeading\x1B[22;24;39m",
+        styled_format!(PALETTE.heading, "Heading")
+    );
+}
+
+#[test]
+fn test_inline_args() {
+    use Colour::{Blue, Green, Red};
+    use Effect::{Bold, Italic};
+
+    let pet = String::from("cat");
+    let age = 5;
+    check_fmt(
+        "My \x1B[32mcat\x1B[39m is \x1B[1;31m5\x1B[22;39m years old",
+        styled_format!("My {} is {} years old", pet => Green, age => Red + Bold)
+    );
+    check_fmt(
+        "\x1B[3mMy \x1B[34mcat\x1B[39m is 5, not \x1B[1;41m6\x1B[22;49m\x1B[23m",
+        styled_format!(Italic, "My {} is {age}, not {wrong}", pet => Blue, wrong = age + 1 => Red.bg() + Bold,)
+    );
+    // Arguments are borrowed, as by format!
+    assert_eq!(pet, "cat");
+
+    let mut output = String::new();
+    styled_writeln!(&mut output, "{:>3}|{}", age => Green, pet).unwrap();
+    styled_write!(&mut output, "{}", age => Bold).unwrap();
+    assert_eq!(output, "\x1B[32m  5\x1B[39m|cat\n\x1B[1m5\x1B[22m");
+    assert_eq!(styled_format!("Plain"), "Plain");
+    paintln!("Printed {}", age => Green);
+
+    // Implicitly captured arguments
+    let mut output = String::new();
+    styled_writeln!(&mut output, "{pet}").unwrap();
+    styled_writeln!(&mut output, "{pet} is {}", age => Green).unwrap();
+    styled_write!(&mut output, "{age}").unwrap();
+    assert_eq!(output, "cat\ncat is \x1B[32m5\x1B[39m\n5");
+    try_paintln!("Printed {pet}").unwrap();
+    try_epaintln!("Printed {pet}").unwrap();
+    try_paintln!("Printed {pet} is {}", age => Green).unwrap();
+}
+
+#[test]
+fn test_styled_debug() {
+    use Colour::{Blue, Red};
+    use Effect::Bold;
+
+    const NONE: Styled<AsDebug<Option<u8>>> = styled_debug!(Red, Bold, None);
+    check_fmt("\x1B[1;31mNone\x1B[22;39m", NONE.to_string());
+    check_fmt("\x1B[31m\"a\\tb\"\x1B[39m", styled_debug!(Red, "a\tb").to_string());
+    check_fmt("\x1B[34m[\n    1,\n    2,\n]\x1B[39m", format!("{:#}", styled_debug!(Blue, [1, 2])));
+    check_fmt("\x1B[34m[1, \x1B[31m2\x1B[34m]\x1B[39m", styled_format!(Blue, "[1, {}]", styled_debug!(Red, 2)));
+    check_fmt("\x1B[31m0xff\x1B[39m", styled!(Red, format_args!("{:#x}", 255)).to_string());
+}
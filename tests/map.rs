@@ -0,0 +1,77 @@
+mod common;
+use common::{check_fmt, Sink};
+
+use ansiconst::{*, io::{self, AnsiWrite, AnsiWriter}, Colour::{Blue, BrightBlue, BrightRed, Red}, Effect::{Bold, Italic}};
+use std::io::Write;
+
+fn writer(map: Option<map::StyleMap>) -> AnsiWriter<Sink> {
+    let mut writer = AnsiWriter::new(Sink::default(), Ansi::unspecified());
+    writer.set_style_map(map);
+    writer
+}
+
+#[test]
+fn test_no_style_map() {
+    let mut writer = writer(None);
+    assert!(writer.style_map().is_none());
+    write!(writer, "{}", styled!(BrightRed, "Bright")).unwrap();
+    check_fmt("\x1B[91mBright\x1B[39m", writer.get_ref().output());
+}
+
+#[test]
+fn test_bright_as_bold() {
+    let mut writer = writer(Some(map::bright_as_bold));
+    write!(writer, "{} {}", styled!(BrightRed, "Bright"), styled!(Red, "Normal")).unwrap();
+    check_fmt("\x1B[1;31mBright\x1B[22;39m \x1B[31mNormal\x1B[39m", writer.get_ref().output());
+}
+
+#[test]
+fn test_bold_as_bright() {
+    let mut writer = writer(Some(map::bold_as_bright));
+    write!(writer, "{} {}", styled!(Red, Bold, "Bold"), styled!(Bold, "Plain")).unwrap();
+    check_fmt("\x1B[91mBold\x1B[39m \x1B[1mPlain\x1B[22m", writer.get_ref().output());
+}
+
+#[test]
+fn test_nested_style_map() {
+    let mut writer = writer(Some(map::bright_as_bold));
+    write!(writer, "{}", styled_format_args!(BrightBlue, "A {} C", styled!(Italic, Blue, "B"))).unwrap();
+    check_fmt("\x1B[1;34mA \x1B[22;3mB\x1B[23;1m C\x1B[22;39m", writer.get_ref().output());
+}
+
+#[test]
+fn test_style_map_with_default_ansi() {
+    let mut writer = writer(Some(map::bright_as_bold));
+    writer.set_ansi(Ansi::no_ansi());
+    write!(writer, "{}", styled!(BrightRed, "Plain")).unwrap();
+    check_fmt("Plain", writer.get_ref().output());
+}
+
+#[test]
+fn test_style_map_not_leaked() {
+    let mut writer = writer(Some(map::bright_as_bold));
+    write!(writer, "{}", styled!(BrightRed, "Mapped")).unwrap();
+    check_fmt("\x1B[91mUnmapped\x1B[39m", styled_format!(BrightRed, "Unmapped"));
+}
+
+#[test]
+fn test_no_backgrounds() {
+    let mut writer = writer(Some(map::no_backgrounds));
+    write!(writer, "{}", styled_format_args!(Red, Blue.bg(), "A {} C", styled!(Bold, BrightBlue.bg(), "B"))).unwrap();
+    check_fmt("\x1B[31mA \x1B[1mB\x1B[22m C\x1B[39m", writer.get_ref().output());
+}
+
+#[test]
+fn test_no_backgrounds_from_env() {
+    std::env::set_var("ANSICONST_NO_BG", "1");
+    assert_eq!(map::from_env().map(|map| map(Blue.bg())), Some(Ansi::unspecified()));
+    // Selected by default, when first used
+    assert_eq!(io::ansiout().style_map().map(|map| map(Blue.bg())), Some(Ansi::unspecified()));
+    io::ansiout().set_style_map(None);
+    assert!(io::ansiout().style_map().is_none());
+
+    std::env::set_var("ANSICONST_NO_BG", "");
+    assert!(map::from_env().is_none());
+    std::env::remove_var("ANSICONST_NO_BG");
+    assert!(map::from_env().is_none());
+}
@@ -0,0 +1,43 @@
+mod common;
+use common::check_fmt;
+
+use ansiconst::{*, message::{self, Label}, registry::{self, NamedStyle}, Colour::{Blue, Purple}};
+
+// Stands in for a logging crate, e.g. `log`, whose macros must not clash with
+// ansiconst's message macros when both are glob-imported
+mod log {
+    macro_rules! error { ($($arg:tt)*) => { format!($($arg)*) } }
+    pub(crate) use error;
+}
+use log::*;
+
+const NOTE: Label = Label::new(NamedStyle::new("test::note", ansi!(Blue)), "note:");
+
+#[test]
+fn test_labels() {
+    check_fmt("\x1B[1;32msuccess:\x1B[22;39m", message::SUCCESS.to_string());
+    check_fmt("\x1B[1;33mwarning:\x1B[22;39m", message::WARNING.to_string());
+    check_fmt("\x1B[34mnote:\x1B[39m",         NOTE.to_string());
+    check_fmt("note:",                         styled_format!(Ansi::no_ansi(), "{}", NOTE));
+    assert_eq!(NOTE.text(), "note:");
+    assert_eq!(message::STYLES.len(), 3);
+}
+
+#[test]
+fn test_label_rethemed() {
+    registry::set_style(message::ERROR.style().name(), ansi!(Purple));
+    check_fmt("\x1B[35merror:\x1B[39m", message::ERROR.to_string());
+    registry::unset_style(message::ERROR.style().name());
+    check_fmt("\x1B[1;31merror:\x1B[22;39m", message::ERROR.to_string());
+}
+
+#[test]
+fn test_macros() {
+    paint_success!("{} passed", 3);
+    paint_warning!("slow");
+    paint_error!("{}", "expected failure");
+    paint_message!(NOTE, "a {}", "note");
+    epaint_message!(NOTE, "an error note");
+    macros::paint_success!("namespaced");
+    assert_eq!(error!("{}", 1), "1");
+}
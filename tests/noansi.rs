@@ -25,9 +25,15 @@ fn test_output_noansi() {
     check_line(stdout_lines.next().unwrap(), "\x1B[1;31mansi\x1B[22;39m");
     check_line(stdout_lines.next().unwrap(), "\x1B[1;31mall ansi\x1B[22;39m");
     check_line(stdout_lines.next().unwrap(), "no ansi");
+    check_line(stdout_lines.next().unwrap(), "try no ansi");
+    check_line(stdout_lines.next().unwrap(), "inline no ansi");
+    check_line(stdout_lines.next().unwrap(), "inline no ansi");
     check_line(stderr_lines.next().unwrap(), "\x1B[1;31mansi\x1B[22;39m");
     check_line(stderr_lines.next().unwrap(), "\x1B[1;31mall ansi\x1B[22;39m");
     check_line(stderr_lines.next().unwrap(), "no ansi");
+    check_line(stderr_lines.next().unwrap(), "try no ansi");
+    check_line(stderr_lines.next().unwrap(), "inline no ansi");
+    check_line(stderr_lines.next().unwrap(), "inline no ansi");
 }
 
 #[test]
@@ -42,6 +48,10 @@ fn test_noansi() {
     io::ansiout().no_ansi();
     assert!(io::ansiout().is_no_ansi());
     paintln!(Effect::Bold, Colour::Red, "no ansi");
+    try_paintln!(Effect::Bold, Colour::Red, "try no ansi").unwrap();
+    paintln!("inline {}", "no ansi" => Colour::Red);
+    paint!("inline {}", "no ansi" => Colour::Red);
+    paintln!();
     println!("[test_end]");
 
     // Stderr
@@ -53,5 +63,9 @@ fn test_noansi() {
     io::ansierr().no_ansi();
     assert!(io::ansierr().is_no_ansi());
     epaintln!(Effect::Bold, Colour::Red, "no ansi");
+    try_epaintln!(Effect::Bold, Colour::Red, "try no ansi").unwrap();
+    epaintln!("inline {}", "no ansi" => Colour::Red);
+    epaint!("inline {}", "no ansi" => Colour::Red);
+    epaintln!();
     eprintln!("[test_end]");
 }
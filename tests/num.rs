@@ -0,0 +1,59 @@
+mod common;
+use common::check_fmt;
+
+use ansiconst::{*, num::grouped, Colour::Green, Effect::Bold};
+
+#[test]
+fn test_grouped_integers() {
+    check_fmt("0",                          grouped(0u8).to_string());
+    check_fmt("999",                        grouped(999).to_string());
+    check_fmt("1,000",                      grouped(1000).to_string());
+    check_fmt("-100,000",                   grouped(-100000).to_string());
+    check_fmt("18,446,744,073,709,551,615", grouped(u64::MAX).to_string());
+    check_fmt("-128",                       grouped(i8::MIN).to_string());
+    check_fmt("1 000 000",                  grouped(1000000).separator(' ').to_string());
+}
+
+#[test]
+fn test_grouped_floats() {
+    check_fmt("1,234.5",   grouped(1234.5).to_string());
+    check_fmt("-1,234.50", format!("{:.2}", grouped(-1234.5)));
+    check_fmt("1,235",     format!("{:.0}", grouped(1234.6f32)));
+    check_fmt("0.001",     grouped(0.001).to_string());
+    check_fmt("inf",       grouped(f64::INFINITY).to_string());
+    check_fmt("NaN",       grouped(f64::NAN).to_string());
+}
+
+#[test]
+fn test_grouped_padding() {
+    check_fmt("[    1,000]", format!("[{:9}]",    grouped(1000)));
+    check_fmt("[1,000    ]", format!("[{:<9}]",   grouped(1000)));
+    check_fmt("[**1,000**]", format!("[{:*^9}]",  grouped(1000)));
+    check_fmt("[ 1,000.00]", format!("[{:9.2}]",  grouped(1000.0)));
+    check_fmt("[1,000]",     format!("[{:3}]",    grouped(1000)));
+}
+
+#[test]
+fn test_styled_num() {
+    check_fmt(
+        "\x1B[1;32m   12,345\x1B[22;39m|\x1B[1;32m      678\x1B[22;39m",
+        format!("{:9}|{:9}", styled!(Green, Bold, grouped(12345)), styled!(Green, Bold, grouped(678))),
+    );
+    check_fmt("\x1B[32mTotal: 1,024.0\x1B[39m", styled_format!(Green, "Total: {:.1}", grouped(1024.0)));
+}
+
+#[test]
+fn test_badge() {
+    use ansiconst::{num::{badge, Threshold}, Colour::{Red, Yellow}};
+
+    const THRESHOLDS: [Threshold; 2] = [
+        Threshold::below(0.5, ansi!(Green), "ok"),
+        Threshold::below(0.8, ansi!(Yellow), "warn"),
+    ];
+    check_fmt("\x1B[32m0% ok\x1B[39m",    badge(0.0, &THRESHOLDS).to_string());
+    check_fmt("\x1B[32m50% ok\x1B[39m",   badge(0.499, &THRESHOLDS).to_string());
+    check_fmt("\x1B[33m79% warn\x1B[39m", badge(0.794, &THRESHOLDS).to_string());
+    check_fmt("80%",                      badge(0.8, &THRESHOLDS).to_string());
+    check_fmt("NaN%",                     badge(f64::NAN, &THRESHOLDS).to_string());
+    check_fmt("\x1B[31m150%\x1B[39m",     badge(1.5, &[Threshold::otherwise(ansi!(Red), "")]).to_string());
+}
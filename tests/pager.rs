@@ -0,0 +1,37 @@
+#![cfg(unix)]
+
+use ansiconst::{*, io::{ansiout, AnsiWrite, Pager}, Colour::Red};
+use std::{env, fs};
+
+#[test]
+fn test_pager() {
+    ansiout().all_ansi();
+    let dir = env::temp_dir();
+
+    // Below the threshold, the pager is not spawned
+    let unpaged = dir.join(format!("ansiconst-unpaged-{}", std::process::id()));
+    let command = format!("tee {}", unpaged.display()).leak();
+    let paging = Pager::new().terminal_only(false).threshold(2).command(command).start();
+    assert!(paging.is_active());
+    paintln!(Red, "One");
+    paintln!(Red, "Two");
+    paging.finish().unwrap();
+    assert!(!unpaged.exists());
+
+    // Above the threshold, all output is piped to the pager
+    let paged = dir.join(format!("ansiconst-paged-{}", std::process::id()));
+    let command = format!("tee {}", paged.display()).leak();
+    let paging = Pager::new().terminal_only(false).threshold(2).command(command).start();
+    assert!(!Pager::new().terminal_only(false).start().is_active());
+    paintln!(Red, "One");
+    paintln!(Red, "Two");
+    paintln!("Three");
+    paint!("Four");
+    paintln!(Red, "Five");
+    paging.finish().unwrap();
+    assert_eq!(
+        fs::read_to_string(&paged).unwrap(),
+        "\x1B[31mOne\x1B[39m\n\x1B[31mTwo\x1B[39m\nThree\nFour\x1B[31mFive\x1B[39m\n",
+    );
+    fs::remove_file(paged).unwrap();
+}
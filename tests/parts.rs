@@ -0,0 +1,56 @@
+use ansiconst::*;
+use ansiconst::Colour::*;
+use ansiconst::Effect::*;
+
+#[test]
+fn test_parts() {
+    let parts = ansi!(Red, Blue.bg(), Bold, NotItalic).parts();
+    assert_eq!(parts.effects_set,   Attrs::Bold);
+    assert_eq!(parts.effects_reset, Attrs::Italic);
+    assert_eq!(parts.fg,            Red);
+    assert_eq!(parts.bg,            Blue);
+    assert_eq!(parts.protected,     Attrs::empty());
+    assert_eq!(parts.priorities,    [0; 10]);
+
+    let parts = ansi!(Bold.ansi().priority(3), Red.ansi().protect()).parts();
+    assert_eq!(parts.protected,  Attrs::Bold | Attrs::Foreground);
+    assert_eq!(parts.priorities, [3, 0, 0, 0, 0, 0, 0, 0, 1, 0]);
+
+    let parts = Ansi::reset().parts();
+    assert_eq!(parts.effects_set,   Attrs::empty());
+    assert_eq!(parts.effects_reset, Attrs::effects());
+    assert_eq!((parts.fg, parts.bg), (Colour::Reset, Colour::Reset));
+}
+
+#[test]
+fn test_parts_round_trip() {
+    let styles = [
+        Ansi::unspecified(),
+        Ansi::no_ansi(),
+        Ansi::reset(),
+        ansi!(Red, Blue.bg(), Bold, NotItalic, Underline),
+        ansi!(Red.ansi().priority(2), Bold.only(), Green.bg().priority(3)),
+        ansi!(Bold, Italic).priority(2).priority_attrs(Attrs::Italic, 3),
+        ansi!(NotBold, NotFaint).protect(),
+    ];
+    for style in styles {
+        assert_eq!(Ansi::from(style.parts()), style, "{:?}", style);
+        assert_eq!(AnsiParts::from(style), style.parts());
+    }
+}
+
+#[test]
+fn test_parts_to_ansi() {
+    let parts = AnsiParts {
+        effects_set:   Attrs::Bold | Attrs::Foreground,
+        effects_reset: Attrs::Bold | Attrs::Italic,
+        fg:            Colour::Unspecified,
+        bg:            Red,
+        protected:     Attrs::Background,
+        priorities:    [2, 2, 2, 2, 2, 2, 2, 2, 2, 9],
+    };
+    assert_eq!(parts.to_ansi(), ansi!(Bold, NotItalic, Red.bg().priority(3)));
+
+    let parts = AnsiParts { priorities: [0; 10], ..parts };
+    assert_eq!(parts.to_ansi(), ansi!(Bold, NotItalic, Red.bg().protect()));
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use ansiconst::{*, io::{AnsiWrite, AnsiWriter, LinePrefix}, Colour::{Blue, Red}, Effect::Bold};
+use common::Sink;
+use std::io::Write;
+
+const PREFIX: LinePrefix = LinePrefix::new(ansi!(Blue), |f| f.write_str("> "));
+
+fn prefixed_writer(ansi: Ansi) -> AnsiWriter<Sink> {
+    let mut writer = AnsiWriter::new(Sink::default(), ansi);
+    writer.set_line_prefix(Some(PREFIX));
+    assert!(writer.line_prefix().is_some());
+    writer
+}
+
+#[test]
+fn test_line_prefix() {
+    let mut writer = prefixed_writer(Ansi::unspecified());
+    writeln!(writer, "a").unwrap();
+    write!(writer, "b").unwrap();
+    writeln!(writer, "c\n\nd").unwrap();
+    assert_eq!(
+        writer.get_ref().output(),
+        "\x1B[34m> \x1B[39ma\n\x1B[34m> \x1B[39mbc\n\x1B[34m> \x1B[39m\n\x1B[34m> \x1B[39md\n",
+    );
+}
+
+#[test]
+fn test_line_prefix_restores_style() {
+    let mut writer = prefixed_writer(Ansi::unspecified());
+    write!(writer, "{}", styled!(Red, Bold, "a\nb")).unwrap();
+    assert_eq!(
+        writer.get_ref().output(),
+        "\x1B[34m> \x1B[39m\x1B[1;31ma\n\x1B[22;34m> \x1B[1;31mb\x1B[22;39m",
+    );
+}
+
+#[test]
+fn test_line_prefix_deferred() {
+    // Not written before a reset that ends a write, only before the next visible char
+    let mut writer = prefixed_writer(Ansi::unspecified());
+    write!(writer, "{}", styled!(Red, "a\n")).unwrap();
+    assert_eq!(writer.get_ref().output(), "\x1B[34m> \x1B[39m\x1B[31ma\n\x1B[39m");
+    writeln!(writer, "b").unwrap();
+    assert_eq!(writer.get_ref().output(), "\x1B[34m> \x1B[39m\x1B[31ma\n\x1B[39m\x1B[34m> \x1B[39mb\n");
+}
+
+#[test]
+fn test_line_prefix_no_ansi() {
+    let mut writer = prefixed_writer(Ansi::no_ansi());
+    writeln!(writer, "{}", styled!(Red, "a\nb")).unwrap();
+    assert_eq!(writer.get_ref().output(), "> a\n> b\n");
+}
+
+#[test]
+fn test_line_prefix_none() {
+    let mut writer = prefixed_writer(Ansi::unspecified());
+    writer.set_line_prefix(None);
+    writeln!(writer, "a\nb").unwrap();
+    assert_eq!(writer.get_ref().output(), "a\nb\n");
+}
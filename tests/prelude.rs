@@ -0,0 +1,23 @@
+use ansiconst::prelude::*;
+use ansiconst::Colour::Red;
+
+// Stands in for a logging crate, e.g. `log`, whose macros would clash with
+// ansiconst's message macros if they shared a name
+mod log {
+    macro_rules! error { ($($arg:tt)*) => { format!($($arg)*) } }
+    pub(crate) use error;
+}
+use log::*;
+
+#[test]
+fn test_prelude() {
+    assert_eq!(error!("{}", 1), "1");
+    assert_eq!(styled_format!(Red, "{}", 1).to_string(), "\x1B[31m1\x1B[39m");
+    assert_eq!(ansi!(Red, Effect::Bold), Red.ansi().add(Effect::Bold.ansi()));
+
+    let mut output = String::new();
+    styled_write!(&mut output, Red, "Oops").unwrap();
+    assert_eq!(output, "\x1B[31mOops\x1B[39m");
+
+    ansiconst::macros::paint_error!("still reachable via ansiconst::macros");
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use ansiconst::{*, io::{AnsiWriter, Prompt, PromptStyle}, Colour::{Blue, Red}};
+use common::Sink;
+use std::io;
+
+const STYLE: PromptStyle = PromptStyle::new(ansi!(Blue), Ansi::unspecified(), ansi!(Red));
+
+fn writer(ansi: Ansi) -> AnsiWriter<Sink> { AnsiWriter::new(Sink::default(), ansi) }
+
+#[test]
+fn test_confirm() {
+    let mut output = writer(Ansi::unspecified());
+    let answer = Prompt::new("Save?").style(STYLE).confirm_with(&mut &b"maybe\nYes\n"[..], &mut output);
+    assert!(answer.unwrap());
+    assert_eq!(
+        output.get_ref().output(),
+        "\x1B[34mSave?\x1B[39m [y/n] \x1B[31mPlease answer y or n\x1B[39m\n\x1B[34mSave?\x1B[39m [y/n] ",
+    );
+}
+
+#[test]
+fn test_confirm_default() {
+    let mut output = writer(Ansi::no_ansi());
+    let answer = Prompt::new("Save?").default("n").confirm_with(&mut &b"\n"[..], &mut output);
+    assert!(!answer.unwrap());
+    assert_eq!(output.get_ref().output(), "Save? [y/N] ");
+
+    let answer = Prompt::new("Save?").default("y").confirm_with(&mut &b""[..], &mut writer(Ansi::no_ansi()));
+    assert!(answer.unwrap());
+}
+
+#[test]
+fn test_input() {
+    let mut output = writer(Ansi::unspecified());
+    let answer = Prompt::new("Name?").default("bob").input_with(&mut &b"  alice  \r\n"[..], &mut output);
+    assert_eq!(answer.unwrap(), "alice");
+    assert_eq!(output.get_ref().output(), "\x1B[1mName?\x1B[22m \x1B[36m(bob)\x1B[39m ");
+
+    let answer = Prompt::new("Name?").default("bob").input_with(&mut &b"\n"[..], &mut writer(Ansi::no_ansi()));
+    assert_eq!(answer.unwrap(), "bob");
+}
+
+#[test]
+fn test_parse() {
+    let mut output = writer(Ansi::no_ansi());
+    let answer: io::Result<u8> = Prompt::new("Age?").parse_with(&mut &b"old\n42\n"[..], &mut output);
+    assert_eq!(answer.unwrap(), 42);
+    assert_eq!(output.get_ref().output(), "Age? Invalid input \"old\": invalid digit found in string\nAge? ");
+}
+
+#[test]
+fn test_eof() {
+    let mut output = writer(Ansi::no_ansi());
+    let answer = Prompt::new("Save?").confirm_with(&mut &b"x\n"[..], &mut output);
+    assert_eq!(answer.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(output.get_ref().output(), "Save? [y/n] Please answer y or n\nSave? [y/n] \n");
+}
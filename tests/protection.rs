@@ -22,3 +22,82 @@ fn test_protection() {
         ).to_string()
     );
 }
+
+#[test]
+fn test_priority() {
+    const APP:  Ansi = ansi!(Colour::Blue, Effect::Italic).protect();
+    const USER: Ansi = ansi!(Colour::Red).priority(2);
+    const A11Y: Ansi = ansi!(Colour::Yellow, Effect::Underline).priority(3);
+
+    check_fmt(
+        "\x1B[3;34mApp \x1B[31muser\x1B[34m app\x1B[23;39m",
+        styled_format_args!(APP, "App {} app", styled_format_args!(USER, "user")).to_string()
+    );
+    check_fmt(
+        "\x1B[4;33mA11y \x1B[3maccessible\x1B[23m a11y\x1B[24;39m",
+        styled_format_args!(A11Y, "A11y {} a11y",
+            styled_format_args!(USER, "{}", styled_format_args!(APP, "accessible"))
+        ).to_string()
+    );
+
+    assert_eq!(Effect::Bold.ansi().protect().priority_of(Attrs::Bold), 1);
+    assert_eq!(USER.priority_of(Attrs::Foreground), 2);
+    assert_eq!(USER.priority_of(Attrs::Background), 0);
+    assert_eq!(A11Y.priority_of(Attrs::all()), 3);
+    assert_eq!(ansi!(Colour::Red).priority(7), ansi!(Colour::Red).priority(3));
+    assert_eq!(USER.priority(0), ansi!(Colour::Red));
+    assert_eq!(USER.priority(1), ansi!(Colour::Red).protect());
+    assert_eq!(USER.unprotect().protect(), ansi!(Colour::Red).protect());
+    assert_eq!(APP.add(USER), ansi!(Colour::Red, Effect::Italic).protect().priority_attrs(Attrs::Foreground, 2));
+
+    // Levels are per attribute, e.g. raising one effect's level leaves the others' unchanged
+    const THEME: Ansi = ansi!(Effect::Bold, Effect::Underline).protect().priority_attrs(Attrs::Underline, 3);
+    assert_eq!(THEME.priority_of(Attrs::Bold), 1);
+    assert_eq!(THEME.priority_of(Attrs::Underline), 3);
+    assert_eq!(THEME.priority_of(Attrs::Italic), 0);
+    assert_eq!(
+        THEME.add(ansi!(Effect::NotBold, Effect::NotUnderline).priority(2)),
+        ansi!(Effect::NotBold, Effect::Underline).priority(2).priority_attrs(Attrs::Underline, 3)
+    );
+}
+
+#[test]
+fn test_renders_nothing() {
+    // Only the protection differs, so no codes are written for the middle style
+    check_fmt(
+        "\x1B[31mRed \x1B[1mstill red\x1B[22m\x1B[39m",
+        styled_format_args!(Colour::Red, "Red {}",
+            styled_format_args!(Colour::Red.protect(), "{}",
+                styled_format_args!(Effect::Bold, Colour::Blue, "still red")
+            )
+        ).to_string()
+    );
+    check_fmt(
+        "\x1B[31mRed \x1B[32mgreen\x1B[31m\x1B[39m",
+        styled_format_args!(Colour::Red, "Red {}",
+            styled_format_args!(Colour::Red.protect(), "{}",
+                styled_format_args!(Colour::Green.ansi().priority(2), "green")
+            )
+        ).to_string()
+    );
+    assert!(Ansi::unspecified().renders_nothing());
+    assert!(Colour::Red.only().transition(Colour::Red.ansi()).renders_nothing());
+    assert!(!Colour::Red.only().renders_nothing());
+}
+
+#[test]
+fn test_subset_policy() {
+    use ansiconst::{Colour::{Blue, Red}, Effect::{Bold, Italic, NotBold}};
+
+    const POLICY: Ansi = ansi!(Bold, Red);
+    const _: () = assert!(ansi!(Blue).is_subset_of(&POLICY));
+
+    assert!(ansi!(NotBold, Blue).is_subset_of(&POLICY));
+    assert!(!ansi!(Italic).is_subset_of(&POLICY));
+    assert!(!Blue.bg().is_subset_of(&POLICY));
+    assert!(!Ansi::reset().is_subset_of(&POLICY));
+    assert!(Ansi::reset().is_subset_of(&Ansi::reset()));
+    // Protection does not specify attributes
+    assert!(Ansi::no_ansi().is_subset_of(&Ansi::unspecified()));
+    assert!(!Bold.ansi().is_subset_of(&Ansi::no_ansi()));
+}
@@ -0,0 +1,79 @@
+mod common;
+use common::check_fmt;
+
+use ansiconst::{*, io::{AnsiFmtWriter, AnsiWrite}, Colour::{Blue, Red}, Effect::{Bold, Underline}, UnderlineStyle::Curly};
+
+const OVERLINE: RawSgr = RawSgr::new("53", "55");
+const CURLY:    RawSgr = RawSgr::new("4:3", "4:0");
+
+#[test]
+fn test_raw_sgr() {
+    check_fmt("\x1B[53mA\x1B[55m", OVERLINE.wrap("A").to_string());
+    check_fmt("\x1B[5:1mA", RawSgr::new("5:1", "").wrap("A").to_string());
+    assert_eq!((OVERLINE.set(), OVERLINE.reset()), ("53", "55"));
+}
+
+#[test]
+fn test_raw_sgr_nested() {
+    check_fmt(
+        "\x1B[31mA \x1B[53m\x1B[1mB\x1B[22m\x1B[55m C\x1B[39m",
+        styled_format!(Red, "A {} C", OVERLINE.wrap(styled!(Bold, "B"))),
+    );
+    check_fmt(
+        "\x1B[53m\x1B[34mB\x1B[39m\x1B[55m",
+        OVERLINE.wrap(styled!(Blue, "B")).to_string(),
+    );
+    // The enclosing raw style is restored after a nested reset
+    check_fmt(
+        "\x1B[53mA \x1B[4:3mB\x1B[4:0m\x1B[53m C\x1B[55m",
+        OVERLINE.wrap(format_args!("A {} C", CURLY.wrap("B"))).to_string(),
+    );
+    // As are the attributes of the enclosing style that a nested reset resets
+    check_fmt(
+        "\x1B[4mA \x1B[4:3mB\x1B[4:0m\x1B[4m C\x1B[24m",
+        styled_format!(Underline, "A {} C", CURLY.wrap("B")),
+    );
+    check_fmt(
+        "\x1B[1;31mA \x1B[53mB\x1B[0m\x1B[1;31m C\x1B[22;39m",
+        styled_format!(Bold, Red, "A {} C", RawSgr::new("53", "0").wrap("B")),
+    );
+}
+
+#[test]
+fn test_raw_sgr_protected() {
+    check_fmt("A B C",                  styled_format!(Ansi::no_ansi(), "A {} C", OVERLINE.wrap("B")));
+    check_fmt("\x1B[31mA B C\x1B[39m", styled_format!(Red.only(), "A {} C", OVERLINE.wrap("B")));
+    check_fmt(
+        "\x1B[31mA \x1B[53mB\x1B[55m C\x1B[39m",
+        styled_format!(Red.protect(), "A {} C", OVERLINE.wrap("B")),
+    );
+    // Only raw parameters that set or reset a protected attribute are suppressed
+    check_fmt(
+        "\x1B[4mA B C\x1B[24m",
+        styled_format!(ansi!(Underline).protect(), "A {} C", CURLY.wrap("B")),
+    );
+    check_fmt(
+        "\x1B[31mA B C\x1B[39m",
+        styled_format!(Red.protect(), "A {} C", RawSgr::new("53;34", "55;39").wrap("B")),
+    );
+    check_fmt(
+        "\x1B[4mA \x1B[53mB\x1B[55m C\x1B[24m",
+        styled_format!(ansi!(Underline).protect(), "A {} C", OVERLINE.wrap("B")),
+    );
+}
+
+#[test]
+fn test_raw_sgr_protected_as_written() {
+    // Like an `Underlined`, the protection of the enclosing style as written applies,
+    // e.g. after a style map
+    let mut writer = AnsiFmtWriter::new(String::new(), Ansi::unspecified());
+    writer.set_style_map(Some(|ansi: Ansi| ansi.unprotect()));
+    write!(writer, "{}", styled!(ansi!(Underline).only(), format_args!("{}{}", CURLY.wrap("a"), Curly.wrap("b")))).unwrap();
+    assert_eq!(writer.get_ref(), "\x1B[4m\x1B[4:3ma\x1B[4:0m\x1B[4m\x1B[4:3mb\x1B[4m\x1B[24m");
+}
+
+#[test]
+#[should_panic(expected = "SGR parameters may only contain")]
+fn test_raw_sgr_invalid() {
+    let _ = RawSgr::new("4m\x1B[31", "");
+}
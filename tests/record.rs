@@ -0,0 +1,51 @@
+mod common;
+
+use ansiconst::{*, io::{AnsiWriter, Chunk, Recorder, Recording}, Colour::Green};
+use common::Sink;
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_recorder() {
+    let mut writer = AnsiWriter::default(Recorder::new(Sink::default()));
+    write!(writer, "{}", styled!(Green, "one")).unwrap();
+    thread::sleep(Duration::from_millis(20));
+    writeln!(writer, "two").unwrap();
+
+    let (sink, recording) = writer.into_inner().into_parts();
+    assert_eq!(sink.output(), "\x1B[32mone\x1B[39mtwo\n");
+    let chunks = recording.chunks();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].data(), b"\x1B[32mone\x1B[39m");
+    assert_eq!(chunks[1].data(), b"two\n");
+    assert!(chunks[1].time() >= Duration::from_millis(20));
+    assert_eq!(recording.duration(), chunks[1].time());
+}
+
+#[test]
+fn test_replay() {
+    let recording = Recording::new(vec![
+        Chunk::new(Duration::ZERO,              "a"),
+        Chunk::new(Duration::from_millis(40),   "b"),
+    ]);
+    let mut output = Vec::new();
+    let start = std::time::Instant::now();
+    recording.replay(&mut output, 2.0).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    assert_eq!(output, b"ab");
+
+    let mut output = Vec::new();
+    recording.replay(&mut output, 0.0).unwrap();
+    assert_eq!(output, b"ab");
+}
+
+#[test]
+fn test_to_cast() {
+    let recording = Recording::new(vec![Chunk::new(Duration::from_micros(1_250), "\"q\"\\\x07é")]);
+    assert_eq!(
+        recording.to_cast(100, 30),
+        "{\"version\":2,\"width\":100,\"height\":30}\n[0.001250,\"o\",\"\\\"q\\\"\\\\\\u0007é\"]\n",
+    );
+    assert_eq!(Recording::default().to_cast(80, 24), "{\"version\":2,\"width\":80,\"height\":24}\n");
+}
@@ -0,0 +1,31 @@
+mod common;
+use common::check_fmt;
+
+use ansiconst::{*, registry::{self, NamedStyle}, Colour::{Blue, Red}, Effect::Bold};
+
+// Note: the registry is global, so each test uses its own style names
+
+#[test]
+fn test_register() {
+    const A: NamedStyle = NamedStyle::new("test_register::a", ansi!(Red));
+    const B: NamedStyle = NamedStyle::new("test_register::b", ansi!(Bold));
+    registry::register(&[A, B]);
+    registry::register(&[B, NamedStyle::new("test_register::a", ansi!(Blue))]);
+    let styles: Vec<NamedStyle> = registry::styles().into_iter()
+        .filter(|style| style.name().starts_with("test_register::"))
+        .collect();
+    assert_eq!(styles, [A, B]);
+}
+
+#[test]
+fn test_set_style() {
+    const A: NamedStyle = NamedStyle::new("test_set_style::a", ansi!(Red));
+    check_fmt("\x1B[31mA\x1B[39m", styled_format!(A.ansi(), "A"));
+    registry::set_style(A.name(), ansi!(Blue, Bold));
+    assert_eq!(A.default_ansi(), ansi!(Red));
+    check_fmt("\x1B[1;34mA\x1B[22;39m", styled_format!(A.ansi(), "A"));
+    registry::set_style(A.name(), ansi!(Bold));
+    check_fmt("\x1B[1mA\x1B[22m", styled_format!(A.ansi(), "A"));
+    registry::unset_style(A.name());
+    check_fmt("\x1B[31mA\x1B[39m", styled_format!(A.ansi(), "A"));
+}
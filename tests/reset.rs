@@ -0,0 +1,46 @@
+use ansiconst::{*, io, Colour::{Blue, Red}, Effect::{Bold, Italic}};
+use std::fmt;
+use std::panic;
+
+struct Panics;
+
+impl fmt::Display for Panics {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result { panic!("panicked while styled") }
+}
+
+#[test]
+fn test_reset_hook() {
+    let reset = io::install_reset_hook();
+    let _again = io::install_reset_hook();
+
+    // The installed hook must chain to the default hook, and not itself panic
+    let result = panic::catch_unwind(|| paint!(Red, "{}", Panics));
+    assert!(result.is_err());
+
+    paintln!(Red, "Still works");
+    io::reset_terminal();
+    drop(reset);
+}
+
+#[test]
+fn test_partial_reset() {
+    const COLOURS: Ansi = Ansi::reset_colors();
+    const EFFECTS: Ansi = Ansi::reset_effects();
+
+    assert_eq!(COLOURS.attrs(), Attrs::colours());
+    assert_eq!(EFFECTS.attrs(), Attrs::effects());
+    assert_eq!(COLOURS.add(EFFECTS), Ansi::reset());
+    assert_eq!(COLOURS.to_string(), "\x1B[39;49m");
+    assert_eq!(EFFECTS.to_string(), "\x1B[22;23;24;25;27;28;29m");
+    assert_eq!(ansi!(Red, Bold).add(COLOURS), ansi!(Bold, Colour::Reset, Colour::Reset.bg()));
+    assert_eq!(ansi!(Blue, Italic).add(EFFECTS).colours().collect::<Vec<_>>(), [(Attrs::Foreground, Blue)]);
+    assert_eq!(styled!(Red, Bold, styled!(COLOURS, "x")).to_string(), "\x1B[1;31m\x1B[39;49mx\x1B[31m\x1B[22;39m");
+}
+
+#[cfg(feature="ctrlc")]
+#[test]
+fn test_ctrlc_reset() {
+    io::install_ctrlc_reset().unwrap();
+    // Only one handler may be installed
+    assert!(io::install_ctrlc_reset().is_err());
+}
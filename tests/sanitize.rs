@@ -0,0 +1,58 @@
+mod common;
+
+use ansiconst::{*, io::{AnsiWrite, AnsiWriter, LinePrefix}, text::Sanitize, Colour::{Blue, Red}};
+use common::Sink;
+use std::io::Write;
+
+const UNTRUSTED: &str = "a\x1B[2J\x1B]0;title\x1B\\b\x07\x1B[1mc";
+
+fn sanitizing_writer(mode: Sanitize) -> AnsiWriter<Sink> {
+    let mut writer = AnsiWriter::new(Sink::default(), Ansi::unspecified());
+    writer.set_sanitize(Some(mode));
+    assert_eq!(writer.sanitize(), Some(mode));
+    writer
+}
+
+#[test]
+fn test_sanitize_strip() {
+    let mut writer = sanitizing_writer(Sanitize::Strip);
+    write!(writer, "{}", styled!(Red, UNTRUSTED)).unwrap();
+    assert_eq!(writer.get_ref().output(), "\x1B[31mab\x1B[1mc\x1B[39m");
+}
+
+#[test]
+fn test_sanitize_escape() {
+    let mut writer = sanitizing_writer(Sanitize::Escape);
+    writeln!(writer, "{}", UNTRUSTED).unwrap();
+    assert_eq!(writer.get_ref().output(), "a\\u{1b}[2J\\u{1b}]0;title\\u{1b}\\\\b\\u{7}\x1B[1mc\n");
+}
+
+#[test]
+fn test_sanitize_reveal() {
+    let mut writer = sanitizing_writer(Sanitize::Reveal);
+    write!(writer, "{}", styled!(Red, UNTRUSTED)).unwrap();
+    assert_eq!(writer.get_ref().output(), "␛[31ma␛[2J␛]0;title␛\\b␇␛[1mc␛[39m");
+}
+
+#[test]
+fn test_sanitize_with_prefix() {
+    let mut writer = sanitizing_writer(Sanitize::Strip);
+    writer.set_line_prefix(Some(LinePrefix::new(ansi!(Blue), |f| f.write_str("> "))));
+    writeln!(writer, "{}", styled!(Red, "x\r\ny\x1B[A")).unwrap();
+    assert_eq!(writer.get_ref().output(), "\x1B[34m> \x1B[39m\x1B[31mx\n\x1B[34m> \x1B[31my\x1B[39m\n");
+}
+
+#[test]
+fn test_sanitize_disabled() {
+    let mut writer = sanitizing_writer(Sanitize::Strip);
+    writer.set_sanitize(None);
+    write!(writer, "{}", UNTRUSTED).unwrap();
+    assert_eq!(writer.get_ref().output(), UNTRUSTED);
+}
+
+#[test]
+fn test_sanitize_underline_style() {
+    let mut writer = sanitizing_writer(Sanitize::Strip);
+    write!(writer, "{}", UnderlineStyle::Curly.wrap(styled!(Red, "x"))).unwrap();
+    assert_eq!(writer.get_ref().output(), "\x1B[4:3m\x1B[31mx\x1B[39m\x1B[24m");
+}
@@ -0,0 +1,27 @@
+mod common;
+
+use ansiconst::{*, io::{self, AnsiWrite, AnsiWriter}, Colour::{Blue, Red}};
+use common::Sink;
+use std::io::Write;
+
+#[test]
+fn test_scoped_ansi() {
+    let writer = AnsiWriter::new(Sink::default(), ansi!(Blue.only()));
+    let mut guard = writer.scoped_ansi(Ansi::no_ansi());
+    assert_eq!(guard.previous(), ansi!(Blue.only()));
+    write!(guard, "{} ", styled!(Red, "a")).unwrap();
+    guard.set_ansi(Ansi::unspecified());
+    write!(guard, "{}", styled!(Red, "b")).unwrap();
+    assert_eq!(guard.get_ref().output(), "a \x1B[31mb\x1B[39m");
+    drop(guard);
+
+    // The guard consumed the writer, so check restoring with ansierr() instead
+    io::ansierr().set_ansi(ansi!(Blue.only()));
+    {
+        let err = io::ansierr().scoped_ansi(Ansi::no_ansi());
+        assert!(err.is_no_ansi());
+        assert!(io::ansierr().is_no_ansi());
+    }
+    assert_eq!(io::ansierr().ansi(), ansi!(Blue.only()));
+    io::ansierr().auto_ansi();
+}
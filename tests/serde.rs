@@ -0,0 +1,73 @@
+#![cfg(feature="serde")]
+
+use ansiconst::{*, theme::{self, Slot, Theme}, Colour::{Blue, BrightRed, Red}, Effect::{Bold, NotBold}};
+use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens, Token};
+
+#[test]
+fn test_serde_ansi() {
+    assert_tokens(&ansi!(Bold, Red, Blue.bg()), &[Token::Str("bold,fg=red,bg=blue")]);
+    assert_tokens(&Red.ansi().protect(), &[Token::Str("fg=red,!fg")]);
+    assert_tokens(&Ansi::unspecified(), &[Token::Str("")]);
+    assert_de_tokens(&ansi!(Bold, Red, Blue.bg()), &[Token::Str("bold red on_blue")]);
+    assert_de_tokens_error::<Ansi>(&[Token::Str("bold on_pink")], "invalid ANSI style token: `on_pink`");
+}
+
+#[test]
+fn test_serde_colour_effect() {
+    assert_tokens(&BrightRed, &[Token::Str("bright_red")]);
+    assert_tokens(&Colour::Unspecified, &[Token::Str("")]);
+    assert_de_tokens(&BrightRed, &[Token::Str("Bright_Red")]);
+    assert_tokens(&Bold, &[Token::Str("bold")]);
+    assert_tokens(&NotBold, &[Token::Str("not_bold")]);
+    assert_tokens(&Effect::Unspecified, &[Token::Str("")]);
+    assert_de_tokens_error::<Colour>(
+        &[Token::Str("pink")],
+        "invalid value: string \"pink\", expected a colour name, e.g. \"bright_red\"",
+    );
+    assert_de_tokens_error::<Effect>(
+        &[Token::Str("shiny")],
+        "invalid value: string \"shiny\", expected an effect name, e.g. \"bold\"",
+    );
+}
+
+#[test]
+fn test_serde_styled() {
+    assert_ser_tokens(&styled!(Red, String::from("Hello")), &[
+        Token::Struct { name: "Styled", len: 2 },
+        Token::Str("style"),
+        Token::Str("fg=red"),
+        Token::Str("text"),
+        Token::Str("Hello"),
+        Token::StructEnd,
+    ]);
+    assert_de_tokens_error::<Styled<String>>(&[
+        Token::Struct { name: "Styled", len: 2 },
+        Token::Str("style"),
+        Token::Str("fg=pink"),
+    ], "invalid ANSI style token: `fg=pink`");
+}
+
+#[test]
+fn test_serde_theme() {
+    const THEME: Theme = theme::DEFAULT.with(Slot::Error, ansi!(Blue));
+    assert_tokens(&THEME, &[
+        Token::Struct { name: "Theme", len: 8 },
+        Token::Str("heading"),    Token::Str("bold,underline"),
+        Token::Str("subheading"), Token::Str("bold"),
+        Token::Str("emphasis"),   Token::Str("italic"),
+        Token::Str("muted"),      Token::Str("faint"),
+        Token::Str("info"),       Token::Str("fg=cyan"),
+        Token::Str("success"),    Token::Str("bold,fg=green"),
+        Token::Str("warning"),    Token::Str("bold,fg=yellow"),
+        Token::Str("error"),      Token::Str("fg=blue"),
+        Token::StructEnd,
+    ]);
+
+    // Missing slots have their default style
+    assert_de_tokens(&THEME, &[
+        Token::Struct { name: "Theme", len: 1 },
+        Token::Str("error"),
+        Token::Str("fg=blue"),
+        Token::StructEnd,
+    ]);
+}
@@ -32,9 +32,9 @@ fn run_test(feature: Option<&'static str>, max_effect_size: usize, max_colour_si
 
 #[test]
 fn test_output_sizes() {
-    run_test(None, 1, 1, 6);
-    run_test(Some("ansi256"), 1, 2, 8);
-    run_test(Some("rgb"), 1, 4, 12);
+    run_test(None, 1, 1, 8);
+    run_test(Some("ansi256"), 1, 2, 12);
+    run_test(Some("rgb"), 1, 4, 16);
 }
 
 #[test]
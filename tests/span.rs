@@ -0,0 +1,33 @@
+mod common;
+
+use common::Sink;
+use ansiconst::{*, io::{AnsiWriter, SpanRecorder}, Colour::{Blue, Red}, Effect::{Bold, Italic}};
+use std::io::Write;
+
+#[test]
+fn test_span_recorder() {
+    let styled = styled_format!(Bold, "a{}b\x1B]0;title\x07c{}", styled!(Red, "é"), styled!(Italic.only(), "d"));
+    let expect: Vec<(String, Ansi)> = text::spans(&styled);
+    let text = text::strip_ansi(&styled).into_owned();
+
+    // Split escape sequences across writes
+    let mut recorder = SpanRecorder::new(Vec::new());
+    for byte in styled.as_bytes() {
+        recorder.write_all(&[*byte]).unwrap();
+    }
+    assert_eq!(recorder.text_len(), text.len());
+    assert_eq!(recorder.active(), Ansi::unspecified());
+    let (output, spans) = recorder.into_parts();
+    assert_eq!(output, styled.as_bytes());
+    let got: Vec<(String, Ansi)> = spans.into_iter().map(|(range, ansi)| (text[range].to_string(), ansi)).collect();
+    assert_eq!(got, expect);
+}
+
+#[test]
+fn test_span_recorder_writer() {
+    let mut writer = AnsiWriter::new(SpanRecorder::new(Sink::default()), ansi!(Blue));
+    write!(writer, "a{}", styled!(Red, "b")).unwrap();
+    write!(writer, "\x1B[1mc").unwrap();
+    assert_eq!(writer.get_ref().spans(), [(0..1, ansi!(Blue)), (1..2, ansi!(Red)), (2..3, ansi!(Blue, Bold))]);
+    assert_eq!(writer.get_ref().active(), ansi!(Bold));
+}
@@ -0,0 +1,112 @@
+use ansiconst::*;
+use ansiconst::Colour::*;
+use ansiconst::Effect::*;
+
+fn check_spec(ansi: Ansi, expect: &str) {
+    let got = ansi.spec().to_string();
+    println!("{: <8}{}\n{: <8}{}", "Expect:", expect, "Got:", got);
+    assert_eq!(expect, got);
+    assert_eq!(Ansi::from_spec(&got), Ok(ansi));
+}
+
+#[test]
+fn test_spec() {
+    check_spec(Ansi::unspecified(),                           "");
+    check_spec(Ansi::reset(),                                 "reset");
+    check_spec(Ansi::no_ansi(),                               "!all");
+    check_spec(Ansi::reset().only(),                          "reset,!all");
+    check_spec(ansi!(Bold, NotFaint, Italic),                 "bold,italic,not_faint");
+    check_spec(ansi!(Red, BrightBlue.bg()),                   "fg=red,bg=bright_blue");
+    check_spec(ansi!(Reset, Reset.bg()),                      "fg=reset,bg=reset");
+    check_spec(ansi!(Underline, Green).protect(),             "underline,fg=green,!underline,!fg");
+    check_spec(ansi!(Strike).protect_attrs(Attrs::colours()), "strike,!fg,!bg");
+    check_spec(ansi!(Red).priority(3),                        "fg=red,!fg=3");
+    check_spec(ansi!(Bold, Red).priority(2).priority_attrs(Attrs::Background, 3), "bold,fg=red,!bold=2,!fg=2,!bg=3");
+    check_spec(Ansi::no_ansi().priority_attrs(Attrs::all(), 2),  "!all=2");
+    check_spec(Ansi::no_ansi().priority_attrs(Attrs::Foreground, 3), "!bold,!faint,!italic,!underline,!blink,!reverse,!hidden,!strike,!fg=3,!bg");
+    check_spec(ansi!(Bold, Underline).protect().priority_attrs(Attrs::Underline, 3), "bold,underline,!bold,!underline=3");
+    #[cfg(feature="ansi256")]
+    check_spec(ansi!(Ansi256(128), Ansi256(7).bg()),          "fg=128,bg=7");
+    #[cfg(feature="rgb")]
+    check_spec(ansi!(Rgb(255, 136, 0)),                       "fg=#ff8800");
+}
+
+#[test]
+fn test_spec_parse() {
+    assert_eq!(Ansi::from_spec(" BOLD , Fg = Red "), Ok(ansi!(Bold, Red)));
+    assert_eq!(Ansi::from_spec(",,bold,,"),         Ok(ansi!(Bold)));
+    assert_eq!(Ansi::from_spec("!fg,fg=red"),       Ok(Red.protect()));
+    assert_eq!(Ansi::from_spec("!fg = 2,fg=red"),   Ok(Red.ansi().priority(2)));
+    assert_eq!(Ansi::from_spec("fg=red,!fg=9"),     Ok(Red.ansi().priority(3)));
+    assert_eq!(Ansi::from_spec("!all=2,!italic=3"), Ok(Ansi::no_ansi().priority_attrs(Attrs::all(), 2).priority_attrs(Attrs::Italic, 3)));
+    assert_eq!(Ansi::from_spec("!all=2,!italic=3").unwrap().priority_of(Attrs::Bold), 2);
+    for invalid in ["boldly", "fg", "fg=", "bg=pink", "xy=red", "!fgx", "fg=#12345", "fg=#gg0000", "!fg=0", "!fg=x", "!x=2"] {
+        assert_eq!(Ansi::from_spec(invalid).unwrap_err().token(), invalid);
+    }
+    #[cfg(not(feature="ansi256"))]
+    assert!(Ansi::from_spec("fg=128").is_err());
+    // Hex colours are parsed as for a style string, e.g. including the short form
+    #[cfg(feature="rgb")]
+    assert_eq!(Ansi::from_spec("fg=#F80,bg=#0000ff"), Ok(ansi!(Rgb(255, 136, 0), Rgb(0, 0, 255).bg())));
+}
+
+#[test]
+fn test_parse_sgr() {
+    for ansi in [
+        ansi!(Bold, Underline, Red),
+        ansi!(NotItalic, NotStrike, Colour::Reset, Blue.bg()),
+        ansi!(BrightCyan, BrightBlack.bg()),
+        Ansi::reset(),
+        Ansi::reset_colors(),
+        Ansi::reset_effects(),
+    ] {
+        assert_eq!(Ansi::parse_sgr(&ansi.to_string()), Ok(ansi));
+    }
+    assert_eq!(Ansi::parse_sgr("\x1B[1m\x1B[22m"),  Ok(ansi!(NotBold, NotFaint)));
+    assert_eq!(Ansi::parse_sgr("\x1B[31m\x1B[m"),   Ok(Ansi::reset()));
+    assert_eq!(Ansi::parse_sgr("\x1B[0;4m"),        Ok(Ansi::reset().filter(Attrs::all().difference(Attrs::Underline)).add(Underline.ansi())));
+    assert_eq!(Ansi::parse_sgr("\x1B[6;31m"),       Ok(Red.ansi()));
+    assert_eq!(Ansi::parse_sgr("\x1B[2J").unwrap_err().token(), "\x1B[2J");
+    assert_eq!(Ansi::parse_sgr("\x1B[1m \x1B[4m").unwrap_err().token(), " ");
+}
+
+#[test]
+fn test_style_str() {
+    assert_eq!(Ansi::from_style_str("bold underline bright_red on_black"), Ok(ansi!(Bold, Underline, BrightRed, Black.bg())));
+    assert_eq!(Ansi::from_style_str("\tITALIC\n not_bold  On_Cyan "),        Ok(ansi!(Italic, NotBold, Cyan.bg())));
+    assert_eq!(Ansi::from_style_str("red blue"),                             Ok(ansi!(Blue)));
+    assert_eq!(Ansi::from_style_str("reset on_reset"),                       Ok(Ansi::reset()));
+    assert_eq!(Ansi::from_style_str("reset bold"),                           Ok(Ansi::reset().add(ansi!(Bold))));
+    assert_eq!(Ansi::from_style_str(" "),                                    Ok(Ansi::unspecified()));
+    for token in ["pink", "on_", "on_bold", "fg=red", "bold,red", "on__red"] {
+        assert_eq!(Ansi::from_style_str(&format!("bold {} red", token)).unwrap_err().token(), token);
+    }
+
+    const STYLE: Ansi = ansi_style!("bold on_green");
+    assert_eq!(STYLE, ansi!(Bold, Green.bg()));
+}
+
+#[test]
+fn test_style_str_on() {
+    assert_eq!(Ansi::from_style_str("bold on blue"),     Ok(ansi!(Bold, Blue.bg())));
+    assert_eq!(Ansi::from_style_str("ON\tRed  italic"), Ok(ansi!(Red.bg(), Italic)));
+    assert_eq!(Ansi::from_style_str("red on on_blue").unwrap_err().token(), "on_blue");
+    assert_eq!(Ansi::from_style_str("on bold").unwrap_err().token(),        "bold");
+    assert_eq!(Ansi::from_style_str("bold on ").unwrap_err().token(),       "on");
+
+    const STYLE: Ansi = ansi!("italic green on black");
+    assert_eq!(STYLE, ansi!(Italic, Green, Black.bg()));
+}
+
+#[test]
+#[cfg(feature="rgb")]
+fn test_style_str_extended() {
+    assert_eq!(Ansi::from_style_str("196 on_232"),         Ok(ansi!(Ansi256(196), Ansi256(232).bg())));
+    assert_eq!(Ansi::from_style_str("#ff8800 on_#0af"),     Ok(ansi!(Rgb(255, 136, 0), Rgb(0, 170, 255).bg())));
+    assert_eq!(Ansi::from_style_str("256").unwrap_err().token(),     "256");
+    assert_eq!(Ansi::from_style_str("#ff880g").unwrap_err().token(), "#ff880g");
+
+    const STYLE: Ansi = ansi_style!("bold #ff8800");
+    assert_eq!(STYLE, ansi!(Bold, Rgb(255, 136, 0)));
+    assert_eq!(ansi!("bold italic #ff8800 on blue"), ansi!(Bold, Italic, Rgb(255, 136, 0), Blue.bg()));
+}
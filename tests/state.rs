@@ -0,0 +1,36 @@
+#![cfg(feature="custom-state")]
+
+mod common;
+use common::*;
+
+use ansiconst::{*, state::{self, NestingState, StateStorage}, Colour::{Blue, Red}, Effect::Bold};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingStorage {
+    state: Mutex<NestingState>,
+    sets:  AtomicUsize,
+}
+
+impl StateStorage for CountingStorage {
+    fn get(&self) -> NestingState { *self.state.lock().unwrap() }
+    fn set(&self, state: NestingState) {
+        self.sets.fetch_add(1, Ordering::Relaxed);
+        *self.state.lock().unwrap() = state;
+    }
+}
+
+static STORAGE: CountingStorage = CountingStorage { state: Mutex::new(NestingState::new()), sets: AtomicUsize::new(0) };
+
+#[test]
+fn test_custom_state() {
+    assert!(state::set_storage(&STORAGE).is_ok());
+    assert!(state::set_storage(&STORAGE).is_err());
+
+    check_fmt(
+        "\x1B[1mBold \x1B[31mRed \x1B[34mBlue\x1B[31m Red\x1B[39m Bold\x1B[22m",
+        styled_format!(Bold, "Bold {} Bold", styled_format_args!(Red, "Red {} Red", styled!(Blue, "Blue"))),
+    );
+    assert!(STORAGE.sets.load(Ordering::Relaxed) > 0);
+    assert_eq!(format!("{:?}", STORAGE.get()), format!("{:?}", NestingState::new()));
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use ansiconst::{*, io::{AnsiWriter, StatusLine}, Colour::Green};
+use common::Sink;
+use std::time::Duration;
+
+fn ms(millis: u64) -> Duration { Duration::from_millis(millis) }
+
+#[test]
+fn test_status_rate_limit() {
+    let mut status = StatusLine::new(Sink::default(), ms(100));
+    assert_eq!(status.interval(), ms(100));
+    for i in 0..=25 {
+        // One update every 10ms, i.e. 10 updates per rendered update
+        status.update_at(i, ms(i as u64 * 10)).unwrap();
+    }
+    assert_eq!(status.pending(), Some(&25));
+    assert_eq!(status.get_ref().output(), "\r0\x1B[K\r10\x1B[K\r20\x1B[K");
+    let sink = status.finish().unwrap();
+    assert_eq!(sink.output(), "\r0\x1B[K\r10\x1B[K\r20\x1B[K\r25\x1B[K\n");
+}
+
+#[test]
+fn test_status_final_rendered_once() {
+    let mut status = StatusLine::new(Sink::default(), ms(100));
+    status.update_at("a", ms(0)).unwrap();
+    status.update_at("b", ms(100)).unwrap();
+    assert_eq!(status.pending(), None);
+    // The clock going backwards renders immediately
+    status.update_at("c", ms(50)).unwrap();
+    assert_eq!(status.finish().unwrap().output(), "\ra\x1B[K\rb\x1B[K\rc\x1B[K\n");
+}
+
+#[test]
+fn test_status_styled() {
+    let mut status = StatusLine::new(AnsiWriter::new(Sink::default(), Ansi::unspecified()), ms(100));
+    status.update(styled!(Green, "50%"));
+    status.update(styled!(Green, "100%"));
+    let writer = status.finish().unwrap();
+    assert!(writer.get_ref().output().ends_with("\r\x1B[32m100%\x1B[39m\x1B[K\n"), "{:?}", writer.get_ref().output());
+}
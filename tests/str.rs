@@ -22,3 +22,25 @@ fn test_str() {
     assert_eq!("\x1B[48;2;33;66;99m", ansi_code!(Colour::Rgb(33,66,99).bg()));
     assert_eq!("\x1B[1;31m", ansi_code!(Colour::Red, Effect::Bold));
 }
+
+#[test]
+fn test_str_fallback() {
+    assert_eq!(("\x1B[1;31m", "\x1B[1;31m"), ansi_code_fallback!(Colour::Red, Effect::Bold));
+    #[cfg(feature="ansi256")]
+    assert_eq!(("\x1B[48;5;21m", "\x1B[44m"), ansi_code_fallback!(Colour::Ansi256(21).bg()));
+    #[cfg(feature="rgb")]
+    assert_eq!(("\x1B[38;2;0;200;0m", "\x1B[32m"), ansi_code_fallback!(Colour::Rgb(0,200,0)));
+}
+
+#[test]
+fn test_str_concat() {
+    use ansiconst::{Colour::Red, Effect::Italic};
+
+    const RED:    &str = ansi_code!(Red);
+    const ITALIC: &str = ansi_code!(Italic);
+    const PROMPT: &str = const_styled_concat!(RED, "> ", ITALIC, "ünïcode", ansi_code!(Ansi::reset()),);
+
+    assert_eq!(PROMPT, "\x1B[31m> \x1B[3münïcode\x1B[0m");
+    assert_eq!(const_styled_concat!(), "");
+    assert_eq!(const_styled_concat!("a", "b"), "ab");
+}
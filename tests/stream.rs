@@ -0,0 +1,37 @@
+use ansiconst::{*, io::{self, AnsiWrite}, Colour::{Blue, Cyan, Green, Purple, Red, Yellow}};
+use std::thread;
+
+#[test]
+fn test_stream_lock() {
+    // The same thread may hold several instances at once, which share the default style
+    let mut out = io::ansiout();
+    out.set_ansi(ansi!(Red.only()));
+    assert_eq!(io::ansiout().ansi(), ansi!(Red.only()));
+    paintln!("[test_stream_lock] nested write while locked");
+    writeln!(out, "[test_stream_lock] locked write").unwrap();
+    out.auto_ansi();
+    drop(out);
+
+    // Other threads see the same default style, once the lock is released
+    thread::spawn(|| io::ansierr().no_ansi()).join().unwrap();
+    assert!(io::ansierr().is_no_ansi());
+    io::ansierr().auto_ansi();
+}
+
+#[test]
+fn test_stream_threads() {
+    let colours = [Red, Green, Yellow, Blue, Purple, Cyan].map(|colour| colour.only().ansi());
+    let threads: Vec<_> = colours.iter().map(|ansi| {
+        let ansi = *ansi;
+        thread::spawn(move || for _ in 0..100 {
+            // Each instance holds the lock, so no other thread may change the style meanwhile
+            let mut out = io::ansiout();
+            out.set_ansi(ansi);
+            thread::yield_now();
+            assert_eq!(out.ansi(), ansi);
+        })
+    }).collect();
+    for thread in threads { thread.join().unwrap(); }
+    assert!(colours.contains(&io::ansiout().ansi()));
+    io::ansiout().auto_ansi();
+}
@@ -0,0 +1,29 @@
+use ansiconst::{*, io::{AnsiWrite, AnsiWriter, StripWriter}, Colour::Red, Effect::Bold};
+use std::io::Write;
+
+#[test]
+fn test_strip_writer() {
+    let mut writer = StripWriter::new(Vec::new());
+    write!(writer, "a{}b", styled!(Red, Bold, "é")).unwrap();
+    assert_eq!(writer.get_ref(), "aéb".as_bytes());
+
+    // Escape sequences split across writes are held back until complete
+    for part in ["c\x1B", "[3", "8;5;1mdé", "\x1B]8;;https://example.com\x1B", "\\e\x1B"] {
+        writer.write_all(part.as_bytes()).unwrap();
+    }
+    assert_eq!(writer.get_ref(), "aébcdée".as_bytes());
+    assert_eq!(writer.buffer(), b"\x1B");
+    writer.write_all(b"cf").unwrap();
+    assert_eq!(writer.get_ref(), "aébcdéef".as_bytes());
+    assert!(writer.buffer().is_empty());
+
+    // Incomplete escape sequences are discarded
+    writer.write_all(b"g\x1B[1").unwrap();
+    assert_eq!(writer.into_inner().unwrap(), "aébcdéefg".as_bytes());
+
+    // Avoids writing styles when wrapped in an AnsiWriter
+    let mut writer = AnsiWriter::default(StripWriter::new(Vec::new()));
+    assert_eq!(writer.ansi(), Ansi::no_ansi());
+    write!(writer, "{}", styled!(Red, "red")).unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"red");
+}
@@ -0,0 +1,40 @@
+#![cfg(feature="test-util")]
+
+use ansiconst::{*, testing, Colour::Red, Effect::Bold};
+
+use std::env;
+use std::process::Command;
+
+#[test]
+fn test_run_marked() {
+    let mut cmd = Command::new(env::current_exe().unwrap());
+    cmd.args(["test_marked", "--exact", "--nocapture", "--include-ignored"]);
+    let output = testing::run(cmd).unwrap();
+    assert!(output.status().success());
+
+    let stdout: Vec<_> = testing::marked_lines(output.stdout()).collect();
+    assert_eq!(stdout, ["\x1B[1;31mstdout\x1B[22;39m", "plain"]);
+    let stderr: Vec<_> = testing::marked_lines(output.stderr()).collect();
+    assert_eq!(stderr, ["\x1B[31mstderr\x1B[39m"]);
+    assert!(output.plain_stdout().contains("\nstdout\nplain\n"));
+    assert!(output.plain_stderr().contains("\nstderr\n"));
+}
+
+#[test]
+#[ignore = "used by run test"]
+fn test_marked() {
+    let result = testing::marked(|| {
+        paintln!(Red, Bold, "stdout");
+        println!("plain");
+        epaintln!(Red, "stderr");
+        1
+    });
+    assert_eq!(result, 1);
+}
+
+#[test]
+fn test_marked_lines() {
+    assert_eq!(testing::marked_lines("a\nb\n").count(), 0);
+    assert_eq!(testing::marked_lines("a\n[test_start]\nb\nc").collect::<Vec<_>>(), ["b", "c"]);
+    assert_eq!(testing::marked_lines("[test_start]\n[test_end]\nb\n").count(), 0);
+}
@@ -0,0 +1,213 @@
+mod common;
+use common::check_fmt;
+
+use ansiconst::text::strip_ansi;
+use std::borrow::Cow;
+
+#[test]
+fn test_strip_ansi() {
+    check_fmt("",            strip_ansi(""));
+    check_fmt("Bold",        strip_ansi("\x1B[1mBold\x1B[22m"));
+    check_fmt("Title",       strip_ansi("\x1B]0;window\x1B\\Title"));
+    check_fmt("ab",          strip_ansi("a\x1B[2Jb"));
+    check_fmt("reset",       strip_ansi("\x1Bcreset"));
+    check_fmt("unfinished",  strip_ansi("unfinished\x1B[1;3"));
+    check_fmt("unfinished",  strip_ansi("unfinished\x1B]0;abc"));
+    check_fmt("lone",        strip_ansi("lone\x1B"));
+    check_fmt("ünïcödé",     strip_ansi("\x1B[31mü\x1B[39mnï\x1B[4mcödé"));
+    assert!(matches!(strip_ansi("borrowed"), Cow::Borrowed("borrowed")));
+}
+
+#[test]
+fn test_foreign() {
+    use ansiconst::{*, text::Foreign, Colour::Red, Effect::Italic};
+
+    // Plain target
+    check_fmt("\x1B[31mA B C\x1B[39m", styled_format!(Red, "A {} C", Foreign::new("B")));
+    // Target sets and resets individual attributes
+    check_fmt(
+        "\x1B[31mA \x1B[1;34mB\x1B[22;31m C\x1B[39m",
+        styled_format!(Red, "A {} C", Foreign::new("\x1B[1;34mB\x1B[22;39m")),
+    );
+    // Target resets everything, parent is restored
+    check_fmt(
+        "\x1B[3mA \x1B[32mB\x1B[39m C\x1B[23m",
+        styled_format!(Italic, "A {} C", Foreign::new("\x1B[32mB\x1B[0m")),
+    );
+    // Protected parent attributes are not overridden
+    check_fmt(
+        "\x1B[31mA B\x1B[4m C\x1B[24m D\x1B[39m",
+        styled_format!(Red.protect(), "A {} D", Foreign::new("\x1B[34mB\x1B[4m C\x1B[m")),
+    );
+    // No ANSI at all
+    check_fmt("A B C", styled_format!(Ansi::no_ansi(), "A {} C", Foreign::new("\x1B[1;4;92mB\x1B[0m")));
+    // Non-SGR escapes are kept
+    check_fmt(
+        "\x1B]8;;http://x\x1B\\\x1B[32mlink\x1B[39m\x1B]8;;\x1B\\",
+        Foreign::new("\x1B]8;;http://x\x1B\\\x1B[32mlink\x1B[39m\x1B]8;;\x1B\\").to_string(),
+    );
+    // Later parameters win, unknown parameters are ignored
+    check_fmt("\x1B[34mB\x1B[39m", Foreign::new("\x1B[31;6;34;255mB").to_string());
+}
+
+#[test]
+fn test_sanitize() {
+    use ansiconst::text::{sanitize, Sanitize};
+
+    check_fmt("",                          sanitize(""));
+    check_fmt("\x1B[1;31mok\x1B[m",        sanitize("\x1B[1;31mok\x1B[m"));
+    check_fmt("ab",                        sanitize("a\x1B[5;5Hb"));
+    check_fmt("title",                     sanitize("\x1B]8;;http://x\x07title\x1B]8;;\x1B\\"));
+    check_fmt("dcs",                       sanitize("\x1BPq#0;2;0;0;0\x1B\\dcs"));
+    check_fmt("apc",                       sanitize("\x1B_hidden\x1B\\apc"));
+    check_fmt("a\tb\nc",                   sanitize("a\tb\r\n\x08c\u{9B}"));
+    check_fmt("reset",                     sanitize("\x1Bcreset"));
+    check_fmt("unfinished",                sanitize("unfinished\x1B[?25"));
+    check_fmt("\\u{1b}[Hx\\r",             Sanitize::Escape.apply("\x1B[Hx\r"));
+    check_fmt("\x1B[4mu\\u{9b}",           Sanitize::Escape.apply("\x1B[4mu\u{9B}"));
+    assert!(matches!(sanitize("safe\x1B[1m"), Cow::Borrowed("safe\x1B[1m")));
+}
+
+#[test]
+fn test_reveal() {
+    use ansiconst::text::{reveal, Reveal, Sanitize};
+
+    check_fmt("",                          reveal(""));
+    check_fmt("␛[1;31mok␛[m",              reveal("\x1B[1;31mok\x1B[m"));
+    check_fmt("a\tb␍\n␈c\\u{9b}␡",         reveal("a\tb\r\n\x08c\u{9B}\x7F"));
+    check_fmt("␛]0;t␇",                    Sanitize::Reveal.apply("\x1B]0;t\x07"));
+    assert_eq!("␛[31mred␛[39m", Reveal::new(ansiconst::styled!(ansiconst::Colour::Red, "red")).to_string());
+    assert!(matches!(reveal("plain"), Cow::Borrowed("plain")));
+}
+
+#[test]
+fn test_head_tail() {
+    use ansiconst::{*, text::{self, Elision}, Effect::Bold};
+
+    const MORE: Elision = Elision::new(Ansi::unspecified(), |n, f| write!(f, "[{}]", n));
+
+    // Nothing elided
+    assert!(matches!(text::head("a\nb\n", 2), Cow::Borrowed("a\nb\n")));
+    assert!(matches!(text::tail("a\nb", 5),   Cow::Borrowed("a\nb")));
+    // Plain text, with and without a trailing newline
+    check_fmt("a\n[2]\n",  MORE.head("a\nb\nc\n", 1));
+    check_fmt("a\n[2]",    MORE.head("a\nb\nc", 1));
+    check_fmt("[2]\nc\n",  MORE.tail("a\nb\nc\n", 1));
+    check_fmt("[3]\n",     MORE.head("a\nb\nc\n", 0));
+    check_fmt("[3]",       MORE.tail("a\nb\nc", 0));
+    // Styles are balanced at the cut, and restored after it
+    check_fmt("\x1B[1ma\x1B[31mb\x1B[22;39m\n[1]", MORE.head("\x1B[1ma\x1B[31mb\nc\x1B[0m", 1));
+    check_fmt("[1]\n\x1B[1;31mc\x1B[0m",           MORE.tail("\x1B[1ma\x1B[31mb\nc\x1B[0m", 1));
+    check_fmt("[1]\nb\x1B[1m",                     MORE.tail("a\x1B[1m\x1B[m\nb\x1B[1m", 1));
+    // Default marker
+    check_fmt("a\n\x1B[2m… 1 more line\x1B[22m",   text::head("a\nb", 1));
+    check_fmt(&format!("{}", styled!(Bold, "x")),  text::tail(&styled_format!(Bold, "x"), 1));
+}
+
+#[test]
+fn test_visible_width() {
+    use ansiconst::text::visible_width;
+
+    assert_eq!(visible_width(""),                              0);
+    assert_eq!(visible_width("\x1B[1;31mBold\x1B[22;39m red"), 8);
+    assert_eq!(visible_width("\x1B]0;title\x07ünï"),          3);
+}
+
+#[test]
+fn test_visible_chars() {
+    use ansiconst::{*, text, Colour::{Blue, Red}, Effect::Bold};
+    use std::fmt;
+
+    // Writes its escape sequences split across several writes
+    struct Split;
+    impl fmt::Display for Split {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            for part in ["a\x1B", "[1", ";31mb\x1B]0;ti", "tle\x1B", "\\c\x1B", "]8;;\x07d\x1B", "7é"] {
+                f.write_str(part)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn visible<T: fmt::Display>(styled: Styled<T>) -> String {
+        let mut visible = String::new();
+        styled.for_each_visible_char(|c| visible.push(c));
+        visible
+    }
+    assert_eq!(visible(styled_format_args!(Blue, "a {} c", styled!(Red, Bold, "b"))), "a b c");
+    assert_eq!(visible(styled!(Bold, Split)), "abcdé");
+    assert_eq!(styled!(Red, Split).visible_width(), 5);
+    assert_eq!(styled!(Red, "").visible_width(), 0);
+
+    let nested = styled!(Blue, styled!(Red, Bold, "héllo"));
+    assert_eq!(nested.visible_width(), text::visible_width(&nested.to_string()));
+
+    // Iterating, including across several chunks of chars
+    assert_eq!(styled!(Bold, Split).visible_chars().collect::<String>(), "abcdé");
+    assert_eq!(styled!(Red, "").visible_chars().next(), None);
+    for n in [31, 32, 33, 64, 100] {
+        let long = "é".repeat(n - 1) + "x";
+        let styled = styled!(Blue, styled!(Red, Bold, &long));
+        assert_eq!(styled.visible_chars().collect::<String>(), long);
+        assert_eq!(styled.visible_chars().count(), styled.visible_width());
+    }
+    let ab = styled!(Red, "ab");
+    let mut chars = ab.visible_chars();
+    assert_eq!((chars.next(), chars.next(), chars.next(), chars.next()), (Some('a'), Some('b'), None, None));
+    // Formatting state is left intact, even if iteration stops part-way
+    let _ = styled!(Red, "a".repeat(40)).visible_chars().nth(35);
+    assert_eq!(styled!(Red, "b").to_string(), "\x1B[31mb\x1B[39m");
+}
+
+#[test]
+fn test_spans() {
+    use ansiconst::{*, text, Colour::{Blue, Red}, Effect::Bold};
+
+    assert_eq!(text::spans(""), []);
+    assert_eq!(text::spans("\x1B[1m\x1B]0;title\x07\x1B[22m"), []);
+    assert_eq!(text::spans("a\x1B[1m\x1B[22mb\x1B[31m"), [("ab".to_string(), Ansi::unspecified())]);
+    assert_eq!(
+        styled_format_args!(Bold, "a{}c", styled!(Red.bg(), "b")).to_spans(),
+        [("a".to_string(), ansi!(Bold)), ("b".to_string(), ansi!(Bold, Red.bg())), ("c".to_string(), ansi!(Bold))]
+    );
+    assert_eq!(
+        StyledBatch::new(&[(ansi!(Blue), "a"), (ansi!(Red), ""), (ansi!(Blue), "b"), (Ansi::unspecified(), "c")]).to_spans(),
+        [("ab".to_string(), ansi!(Blue)), ("c".to_string(), Ansi::unspecified())]
+    );
+}
+
+#[test]
+fn test_map_text() {
+    use ansiconst::{*, text, Colour::Red, Effect::Bold};
+
+    assert_eq!(text::map_text("", str::to_uppercase), "");
+    assert_eq!(text::map_text("\x1B[1mbold\x1B]0;title\x07\x1B[22m plain", str::to_uppercase), "\x1B[1mBOLD\x1B]0;title\x07\x1B[22m PLAIN");
+
+    let styled = styled_format!(Red, "a{}c", styled!(Bold, "b"));
+    let mapped = text::map_text(&styled, |s| s.repeat(2));
+    assert_eq!(mapped, styled_format!(Red, "aa{}cc", styled!(Bold, "bb")));
+    assert_eq!(text::spans(&mapped).len(), text::spans(&styled).len());
+
+    let mut calls = 0;
+    text::map_text(&styled, |s| { calls += 1; s.to_string() });
+    assert_eq!(calls, 3);
+}
+
+#[test]
+fn test_style_tracker() {
+    use ansiconst::{*, text::StyleTracker, Colour::Red, Effect::{Bold, Italic}};
+    use std::fmt::Write;
+
+    let mut tracker = StyleTracker::new(String::new());
+    for part in ["a\x1B[1", ";31mb", "\x1B]0;ti", "tle\x07c\x1B", "[3m"] {
+        tracker.write_str(part).unwrap();
+    }
+    assert_eq!(tracker.active(), ansi!(Red, Bold, Italic));
+    tracker.write_str("d\x1B[22;39").unwrap();
+    assert_eq!(tracker.active(), ansi!(Red, Bold, Italic));
+    tracker.write_str("m").unwrap();
+    assert_eq!(tracker.active(), ansi!(Italic));
+    tracker.write_str("\x1B[0me").unwrap();
+    assert_eq!(tracker.active(), Ansi::unspecified());
+    assert_eq!(tracker.get_ref(), "a\x1B[1;31mb\x1B]0;title\x07c\x1B[3md\x1B[22;39m\x1B[0me");
+}
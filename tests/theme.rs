@@ -0,0 +1,58 @@
+use ansiconst::{*, message, registry, theme::{self, Slot, Theme}, Colour::{Blue, Purple}, Effect::Italic};
+
+const THEMES: &[(&str, Theme)] = &[
+    ("purple", theme::DEFAULT.with(Slot::Heading, ansi!(Purple))),
+    ("blue",   theme::DEFAULT.with(Slot::Heading, ansi!(Blue))),
+];
+
+#[test]
+fn test_theme() {
+    const THEME: Theme = Theme::new().with(Slot::Heading, ansi!(Purple)).with(Slot::Error, ansi!(Blue, Italic));
+
+    assert_eq!(Theme::default(), theme::DEFAULT);
+    assert_eq!(THEME.get(Slot::Heading), ansi!(Purple));
+    assert_eq!(THEME.get(Slot::Muted), theme::DEFAULT.get(Slot::Muted));
+    assert_eq!(Slot::all().map(|slot| slot.style()), theme::STYLES);
+    assert_eq!(message::ERROR.style(), Slot::Error.style());
+
+    assert_eq!(theme::current_theme(), theme::DEFAULT);
+    assert_eq!(styled_format!(Slot::Heading, "h"), "\x1B[1;4mh\x1B[22;24m");
+
+    theme::set_theme(&THEME);
+    assert_eq!(theme::current_theme(), THEME);
+    assert_eq!(styled_format!(Slot::Heading, "h"), "\x1B[35mh\x1B[39m");
+    assert_eq!(message::ERROR.to_string(), "\x1B[3;34merror:\x1B[23;39m");
+
+    registry::set_style(Slot::Muted.style().name(), ansi!(Blue));
+    assert_eq!(theme::current_theme(), THEME.with(Slot::Muted, ansi!(Blue)));
+
+    theme::reset_theme();
+    assert_eq!(theme::current_theme(), theme::DEFAULT);
+
+    std::env::set_var("ANSICONST_TEST_THEME", "blue");
+    assert_eq!(theme::set_theme_from_env(THEMES, "ANSICONST_TEST_THEME", Some("purple")), THEMES[1].1);
+    assert_eq!(styled_format!(Slot::Heading, "h"), "\x1B[34mh\x1B[39m");
+
+    theme::reset_theme();
+    assert_eq!(theme::current_theme(), theme::DEFAULT);
+}
+
+#[test]
+fn test_select_theme() {
+    assert_eq!(theme::find_theme(THEMES, "BLUE"),  Some(THEMES[1].1));
+    assert_eq!(theme::find_theme(THEMES, "green"), None);
+
+    let select = |env: Option<&str>, config| {
+        match env {
+            Some(name) => std::env::set_var("ANSICONST_SELECT_THEME", name),
+            None       => std::env::remove_var("ANSICONST_SELECT_THEME"),
+        }
+        theme::select_theme(THEMES, "ANSICONST_SELECT_THEME", config)
+    };
+    assert_eq!(select(Some("Purple"), Some("blue")), THEMES[0].1);
+    assert_eq!(select(Some("green"),  Some("blue")), THEMES[1].1);
+    assert_eq!(select(Some(""),       Some("blue")), THEMES[1].1);
+    assert_eq!(select(None,           Some("blue")), THEMES[1].1);
+    assert_eq!(select(None,           Some("red")),  theme::DEFAULT);
+    assert_eq!(select(None,           None),         theme::DEFAULT);
+}
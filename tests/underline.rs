@@ -0,0 +1,78 @@
+use ansiconst::{*, Colour::Red, Effect::{NotUnderline, Underline}, UnderlineStyle::{Curly, Dashed, Dotted, Double, Single}};
+
+#[test]
+fn test_underline_styles() {
+    assert_eq!([Single, Double, Curly, Dotted, Dashed].map(|style| style.code()), [1, 2, 3, 4, 5]);
+    assert_eq!(Dotted.wrap("x").style(), Dotted);
+    assert_eq!(Dashed.wrap("x").to_string(), "\x1B[4:5mx\x1B[24m");
+}
+
+#[test]
+fn test_underline_nesting() {
+    // Nested styles combine with the plain underline, and do not end it
+    assert_eq!(
+        Curly.wrap(styled!(Red, "red")).to_string(),
+        "\x1B[4:3m\x1B[31mred\x1B[39m\x1B[24m",
+    );
+    // Enclosing underline styles are restored
+    assert_eq!(
+        styled_format!(Underline, "a{}b", Curly.wrap(format_args!("c{}d", Double.wrap("e")))),
+        "\x1B[4ma\x1B[4:3mc\x1B[4:2me\x1B[4:3md\x1B[4mb\x1B[24m",
+    );
+    assert_eq!(
+        Curly.wrap(format_args!("a{}b", styled!(NotUnderline, "c"))).to_string(),
+        "\x1B[4:3ma\x1B[24mc\x1B[4m\x1B[4:3mb\x1B[24m",
+    );
+    const BATCH: StyledBatch = StyledBatch::new(&[
+        (ansi!(NotUnderline), "a"),
+        (ansi!(Red),          "b"),
+    ]);
+    assert_eq!(
+        Curly.wrap(BATCH).to_string(),
+        "\x1B[4:3m\x1B[24ma\x1B[4;31m\x1B[4:3mb\x1B[39m\x1B[24m",
+    );
+}
+
+#[test]
+fn test_underline_protected() {
+    assert_eq!(styled_format!(Ansi::no_ansi(), "{}", Curly.wrap("a")), "a");
+    assert_eq!(styled_format!(NotUnderline.only(), "{}", Curly.wrap("a")), "\x1B[24ma");
+    assert_eq!(styled_format!(Red.ansi().protect(), "{}", Curly.wrap("a")), "\x1B[31m\x1B[4:3ma\x1B[24m\x1B[39m");
+}
+
+#[test]
+fn test_underline_parsed() {
+    use ansiconst::text::{self, Foreign, StyleTracker};
+    use std::fmt::Write;
+
+    // Underline styles are SGR sequences, so are kept when sanitizing
+    let curly = Curly.wrap(styled!(Red, "x")).to_string();
+    assert_eq!(text::sanitize(&curly), curly);
+    let raw = RawSgr::new("4:3", "4:0").wrap("x").to_string();
+    assert_eq!(text::sanitize(&raw), raw);
+
+    assert_eq!(Ansi::parse_sgr("\x1B[4:3m"), Ok(ansi!(Underline)));
+    assert_eq!(Ansi::parse_sgr("\x1B[4:0m"), Ansi::parse_sgr("\x1B[24m"));
+    assert_eq!(Ansi::parse_sgr("\x1B[1;4:2;31m"), Ansi::parse_sgr("\x1B[1;4;31m"));
+    assert_eq!(Ansi::parse_sgr("\x1B[38:5:1m"), Ansi::parse_sgr("\x1B[38;5;1m"));
+    #[cfg(feature="rgb")]
+    assert_eq!(Ansi::parse_sgr("\x1B[38:2::255:0:0;48:2:0:0:255m"), Ok(ansi!(Colour::Rgb(255, 0, 0), Colour::Rgb(0, 0, 255).bg())));
+
+    assert_eq!(text::spans(&curly), [("x".to_string(), ansi!(Underline, Red))]);
+    assert_eq!(
+        text::spans(&raw),
+        [("x".to_string(), ansi!(Underline))],
+    );
+
+    let mut tracker = StyleTracker::new(String::new());
+    tracker.write_str("\x1B[4:").unwrap();
+    tracker.write_str("3m").unwrap();
+    assert_eq!(tracker.active(), ansi!(Underline));
+    tracker.write_str("\x1B[4:0m").unwrap();
+    assert_eq!(tracker.active(), Ansi::unspecified());
+
+    assert_eq!(
+        styled_format!(Red, "a {} c", Foreign::new(&curly)),
+        "\x1B[31ma \x1B[4mx\x1B[24m c\x1B[39m",
+    );
+}
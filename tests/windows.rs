@@ -0,0 +1,12 @@
+#![cfg(feature="windows")]
+
+use ansiconst::io;
+
+#[test]
+fn test_enable_windows_ansi() {
+    // Succeeds on other platforms, or when not running on a console, and may be repeated
+    if !cfg!(windows) {
+        assert!(io::enable_windows_ansi().is_ok());
+        assert!(io::enable_windows_ansi().is_ok());
+    }
+}
@@ -0,0 +1,27 @@
+mod common;
+use common::check_fmt;
+
+// Note: neither std::fmt::Write nor std::io::Write is imported here
+use ansiconst::{styled_write, styled_writeln, Colour::Red, Effect::Bold};
+
+#[test]
+fn test_write_fmt() {
+    let mut output = String::new();
+    styled_write!(&mut output, "Plain ").unwrap();
+    styled_write!(&mut output, Red, Bold, "{}", "Red").unwrap();
+    styled_writeln!(&mut output, " {}", "Plain").unwrap();
+    styled_writeln!(&mut output, Bold, "Bold").unwrap();
+    styled_writeln!(&mut output).unwrap();
+    check_fmt("Plain \x1B[1;31mRed\x1B[22;39m Plain\n\x1B[1mBold\x1B[22m\n\n", output);
+}
+
+#[test]
+fn test_write_io() {
+    let mut output: Vec<u8> = Vec::new();
+    styled_write!(&mut output, "Plain ").unwrap();
+    styled_write!(&mut output, Red, Bold, "{}", "Red").unwrap();
+    styled_writeln!(&mut output, " {}", "Plain").unwrap();
+    styled_writeln!(&mut output, Bold, "Bold").unwrap();
+    styled_writeln!(&mut output).unwrap();
+    check_fmt("Plain \x1B[1;31mRed\x1B[22;39m Plain\n\x1B[1mBold\x1B[22m\n\n", String::from_utf8(output).unwrap());
+}